@@ -2,10 +2,22 @@ use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
 pub fn parse_to_json_string(text: &str) -> String {
-    let screenplay = jumpcut::parse(text);
+    let mut screenplay = jumpcut::parse(text);
+    screenplay.assign_element_ids();
     screenplay.to_json_string()
 }
 
+#[wasm_bindgen]
+pub fn parse_to_outline_json_string(text: &str, with_pages: bool) -> String {
+    let screenplay = jumpcut::parse(text);
+    let outline = if with_pages {
+        screenplay.outline_with_pages()
+    } else {
+        screenplay.outline()
+    };
+    outline.to_json_string()
+}
+
 #[wasm_bindgen]
 pub fn parse_to_fountain_string(text: &str) -> String {
     let screenplay = jumpcut::parse(text);
@@ -1,9 +1,11 @@
 #[cfg(feature = "cli")]
-use clap::{Parser, ValueEnum};
+use clap::{Parser, Subcommand, ValueEnum};
 #[cfg(feature = "cli")]
 use jumpcut::ElementText;
 #[cfg(feature = "cli")]
-use jumpcut::{parse, parse_fdx};
+use jumpcut::include::FileSystemResolver;
+#[cfg(feature = "cli")]
+use jumpcut::{parse, parse_fdx, parse_with_includes};
 #[cfg(feature = "cli")]
 use serde_json;
 #[cfg(feature = "cli")]
@@ -18,10 +20,15 @@ use std::path::{Path, PathBuf};
 #[command(
     name = "JumpCut",
     about = "A tool for converting Fountain and Final Draft screenplay documents into Fountain, FDX, HTML, JSON, text, and optional PDF formats.",
-    version
+    version,
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
 )]
 #[cfg(feature = "cli")]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Formats (Fountain, FDX, HTML, JSON, text, PDF)
     #[arg(short, long)]
     format: Option<String>,
@@ -54,8 +61,50 @@ struct Args {
     #[arg(long)]
     no_title_page: bool,
 
+    /// Show boneyard content in HTML and PDF renders
+    #[arg(long)]
+    draft: bool,
+
+    /// Mark where notes sit in unpaginated HTML output
+    #[arg(long)]
+    render_notes: bool,
+
+    /// Give scene headings their length in eighths of a page in JSON and HTML output
+    #[arg(long)]
+    scene_lengths: bool,
+
+    /// Mark what changed since an earlier draft as a new revision
+    #[arg(long, value_name = "FILE")]
+    revised_from: Option<PathBuf>,
+
+    /// Color of the new revision, such as Blue or Pink, or a #RRGGBB color
+    #[arg(
+        long,
+        value_name = "COLOR",
+        default_value = "Blue",
+        requires = "revised_from"
+    )]
+    revision_color: String,
+
+    /// Name of the new revision. Defaults to "<color> Revision"
+    #[arg(long, value_name = "NAME", requires = "revised_from")]
+    revision_name: Option<String>,
+
+    /// Date of the new revision, shown in the page header
+    #[arg(long, value_name = "DATE", requires = "revised_from")]
+    revision_date: Option<String>,
+
+    /// Paginate against a saved page lock, labeling new pages 12A, 12B and so on
+    #[arg(long, value_name = "FILE")]
+    locked_pages: Option<PathBuf>,
+
+    /// Lock the screenplay's pages and save the page lock to FILE
+    #[arg(long, value_name = "FILE")]
+    lock_pages: Option<PathBuf>,
+
     /// Input file, pass a dash ("-") to receive stdin
-    input: PathBuf,
+    #[arg(required = true)]
+    input: Option<PathBuf>,
 
     /// Output file in the legacy positional form.
     #[arg(conflicts_with_all = ["output_flag", "write"])]
@@ -74,6 +123,241 @@ struct Args {
     metadata: Option<PathBuf>,
 }
 
+#[cfg(feature = "cli")]
+impl Args {
+    fn input(&self) -> &Path {
+        self.input
+            .as_deref()
+            .expect("clap requires an input file without a subcommand")
+    }
+}
+
+#[cfg(feature = "cli")]
+#[derive(Subcommand)]
+enum Command {
+    /// Compare two drafts and report the changed elements and metadata
+    Diff(DiffArgs),
+    /// Merge two Fountain drafts of a common base, marking conflicts
+    Merge(MergeArgs),
+    /// Number the scenes of a Fountain file, rewriting it in place
+    Renumber(RenumberArgs),
+    /// Rename a character in a Fountain file, rewriting it in place
+    Rename(RenameArgs),
+    /// Report page, scene, character and act statistics for a draft
+    Stats(StatsArgs),
+    /// Estimate the runtime of a draft per scene, per act and in total
+    Runtime(RuntimeArgs),
+    /// Write a breakdown sheet for each scene, with its cast and tagged elements
+    Breakdown(BreakdownArgs),
+    /// Write a stripboard with a strip for each scene, colored by setting and time of day
+    Stripboard(StripboardArgs),
+    /// Print sides: the pages of a character's scenes or numbered scenes, as PDF
+    #[cfg(feature = "pdf")]
+    Sides(SidesArgs),
+}
+
+#[cfg(feature = "cli")]
+#[derive(clap::Args)]
+struct DiffArgs {
+    /// The earlier draft
+    old: PathBuf,
+
+    /// The later draft
+    new: PathBuf,
+
+    /// Report format
+    #[arg(short, long, value_enum, default_value_t = ReportFormat::Text)]
+    format: ReportFormat,
+
+    /// Output file.
+    #[arg(short = 'o', long = "output", value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
+#[cfg(feature = "cli")]
+#[derive(clap::Args)]
+struct MergeArgs {
+    /// The common ancestor draft
+    base: PathBuf,
+
+    /// Our draft
+    ours: PathBuf,
+
+    /// Their draft
+    theirs: PathBuf,
+
+    /// Output file. As a git merge driver, pass %A to write over our draft.
+    #[arg(short = 'o', long = "output", value_name = "FILE")]
+    output: Option<PathBuf>,
+
+    /// Name shown after the <<<<<<< conflict markers
+    #[arg(long, value_name = "NAME", default_value = "ours")]
+    ours_label: String,
+
+    /// Name shown after the >>>>>>> conflict markers
+    #[arg(long, value_name = "NAME", default_value = "theirs")]
+    theirs_label: String,
+}
+
+#[cfg(feature = "cli")]
+#[derive(clap::Args)]
+struct RenumberArgs {
+    /// Fountain file, pass a dash ("-") to receive stdin
+    input: PathBuf,
+
+    /// Keep existing numbers, numbering inserted scenes 12A, 12B and marking cut scenes OMITTED
+    #[arg(long)]
+    locked: bool,
+
+    /// Output file. Defaults to the input file, or stdout for stdin.
+    #[arg(short = 'o', long = "output", value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
+#[cfg(feature = "cli")]
+#[derive(clap::Args)]
+struct RenameArgs {
+    /// Fountain file, pass a dash ("-") to receive stdin
+    input: PathBuf,
+
+    /// The character's current name
+    from: String,
+
+    /// The character's new name
+    to: String,
+
+    /// Also rename the character where the name appears in action, dialogue and parentheticals
+    #[arg(long)]
+    in_text: bool,
+
+    /// Output file. Defaults to the input file, or stdout for stdin.
+    #[arg(short = 'o', long = "output", value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
+#[cfg(feature = "cli")]
+#[derive(clap::Args)]
+struct StatsArgs {
+    /// Input file, pass a dash ("-") to receive stdin
+    input: PathBuf,
+
+    /// Report format
+    #[arg(short, long, value_enum, default_value_t = ReportFormat::Text)]
+    format: ReportFormat,
+
+    /// Output file.
+    #[arg(short = 'o', long = "output", value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
+#[cfg(feature = "cli")]
+#[derive(clap::Args)]
+struct RuntimeArgs {
+    /// Input file, pass a dash ("-") to receive stdin
+    input: PathBuf,
+
+    /// Report format
+    #[arg(short, long, value_enum, default_value_t = ReportFormat::Text)]
+    format: ReportFormat,
+
+    /// Words of dialogue spoken per minute. Defaults to 140, or 190 for multicam
+    #[arg(long, value_name = "WORDS")]
+    words_per_minute: Option<f64>,
+
+    /// Lines of action played out per second. Defaults to 0.7, or 1.5 for multicam
+    #[arg(long, value_name = "LINES")]
+    action_lines_per_second: Option<f64>,
+
+    /// Seconds each transition adds. Defaults to 2, or 1 for multicam
+    #[arg(long, value_name = "SECONDS")]
+    transition_seconds: Option<f64>,
+
+    /// Output file.
+    #[arg(short = 'o', long = "output", value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
+#[cfg(feature = "cli")]
+#[derive(clap::Args)]
+struct BreakdownArgs {
+    /// Input file, pass a dash ("-") to receive stdin
+    input: PathBuf,
+
+    /// Breakdown format
+    #[arg(short, long, value_enum, default_value_t = BreakdownFormat::Json)]
+    format: BreakdownFormat,
+
+    /// Output file.
+    #[arg(short = 'o', long = "output", value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
+#[cfg(feature = "cli")]
+#[derive(clap::Args)]
+struct StripboardArgs {
+    /// Input file, pass a dash ("-") to receive stdin
+    input: PathBuf,
+
+    /// Stripboard format
+    #[arg(short, long, value_enum, default_value_t = StripboardFormat::Csv)]
+    format: StripboardFormat,
+
+    /// Output file.
+    #[arg(short = 'o', long = "output", value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
+#[cfg(all(feature = "cli", feature = "pdf"))]
+#[derive(clap::Args)]
+#[command(group = clap::ArgGroup::new("selection").required(true).multiple(true))]
+struct SidesArgs {
+    /// Input file, pass a dash ("-") to receive stdin
+    input: PathBuf,
+
+    /// Include the scenes this character speaks in. Repeat for more characters
+    #[arg(short, long = "character", value_name = "NAME", group = "selection")]
+    characters: Vec<String>,
+
+    /// Include these scenes, by scene number, such as 12,14A
+    #[arg(
+        short,
+        long,
+        value_name = "NUMBERS",
+        value_delimiter = ',',
+        group = "selection"
+    )]
+    scenes: Vec<String>,
+
+    /// Output file.
+    #[arg(short = 'o', long = "output", value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
+#[cfg(feature = "cli")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum ReportFormat {
+    Text,
+    Json,
+}
+
+#[cfg(feature = "cli")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum BreakdownFormat {
+    Json,
+    Csv,
+    #[cfg(feature = "pdf")]
+    Pdf,
+}
+
+#[cfg(feature = "cli")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum StripboardFormat {
+    Csv,
+    #[cfg(feature = "html")]
+    Html,
+    Json,
+}
+
 #[cfg(feature = "cli")]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
 enum RenderProfile {
@@ -91,23 +375,54 @@ enum InputFormat {
 #[cfg(feature = "cli")]
 fn main() {
     let opt = Args::parse();
+    if let Some(command) = &opt.command {
+        let result = match command {
+            Command::Diff(args) => run_diff(args).map(|()| 0),
+            Command::Merge(args) => run_merge(args),
+            Command::Renumber(args) => run_renumber(args).map(|()| 0),
+            Command::Rename(args) => run_rename(args).map(|()| 0),
+            Command::Stats(args) => run_stats(args).map(|()| 0),
+            Command::Runtime(args) => run_runtime(args).map(|()| 0),
+            Command::Breakdown(args) => run_breakdown(args).map(|()| 0),
+            Command::Stripboard(args) => run_stripboard(args).map(|()| 0),
+            #[cfg(feature = "pdf")]
+            Command::Sides(args) => run_sides(args).map(|()| 0),
+        };
+        match result {
+            Ok(0) => return,
+            Ok(code) => std::process::exit(code),
+            Err(error) => {
+                eprintln!("Error: {error}");
+                std::process::exit(2);
+            }
+        }
+    }
+
     let metadata = read_cli_metadata(&opt).unwrap_or_else(|error| {
         eprintln!("{error}");
         std::process::exit(1);
     });
-    let content = read_cli_input(&opt.input).unwrap_or_else(|error| {
+    let content = read_cli_input(opt.input()).unwrap_or_else(|error| {
         eprintln!("{error}");
         std::process::exit(1);
     });
 
-    let mut screenplay = parse_cli_input(&opt.input, &content, metadata).unwrap_or_else(|error| {
+    let mut screenplay = parse_cli_input(opt.input(), &content, metadata).unwrap_or_else(|error| {
         eprintln!("Error: {error}");
         std::process::exit(1);
     });
     apply_cli_render_overrides(&mut screenplay, &opt);
+    if let Err(error) = apply_cli_revision(&mut screenplay, &opt) {
+        eprintln!("Error: {error}");
+        std::process::exit(1);
+    }
+    if let Err(error) = apply_cli_page_lock(&mut screenplay, &opt) {
+        eprintln!("Error: {error}");
+        std::process::exit(1);
+    }
     let explicit_output = opt.output_flag.as_ref().or(opt.output.as_ref());
     let format = infer_format(opt.format.as_deref(), explicit_output);
-    let output_path = resolve_output_path(&opt.input, explicit_output, opt.write, &format)
+    let output_path = resolve_output_path(opt.input(), explicit_output, opt.write, &format)
         .unwrap_or_else(|error| {
             eprintln!("Error: {error}");
             std::process::exit(2);
@@ -140,8 +455,26 @@ fn main() {
         std::process::exit(2);
     }
 
+    if format != "html" && format != "json" && opt.scene_lengths {
+        eprintln!("Error: --scene-lengths is only supported with --format html or --format json.");
+        std::process::exit(2);
+    }
+
+    if format == "json" {
+        screenplay.assign_element_ids();
+    }
+
     let output_bytes = match format.as_str() {
         "fountain" => screenplay.to_fountain().into_bytes(),
+        "json" if opt.scene_lengths => {
+            match serde_json::to_string_pretty(&screenplay.to_json_value_with_scene_lengths()) {
+                Ok(json) => json.into_bytes(),
+                Err(e) => {
+                    eprintln!("{}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
         "json" => match serde_json::to_string_pretty(&screenplay) {
             Ok(json) => json.into_bytes(),
             Err(e) => {
@@ -159,6 +492,9 @@ fn main() {
                 render_title_page: !opt.no_title_page,
                 embed_courier_prime: opt.embed_courier_prime,
                 embedded_courier_prime_css: None,
+                draft: opt.draft,
+                render_notes: opt.render_notes,
+                scene_lengths: opt.scene_lengths,
             })
             .into_bytes(),
         "text" => screenplay
@@ -172,22 +508,255 @@ fn main() {
         "pdf" => screenplay.to_pdf_with_options(jumpcut::rendering::pdf::PdfRenderOptions {
             render_continueds: !opt.no_continueds,
             render_title_page: !opt.no_title_page,
+            draft: opt.draft,
         }),
         _ => b"nothing".to_vec(),
     };
 
+    write_cli_output(output_path.as_deref(), &output_bytes);
+}
+
+#[cfg(feature = "cli")]
+fn write_cli_output(output_path: Option<&Path>, output_bytes: &[u8]) {
     match output_path {
         Some(outfile) => fs::write(outfile, output_bytes).expect("Unable to write file."),
         None => {
             let stdout = io::stdout();
             let mut handle = io::BufWriter::new(stdout);
             handle
-                .write_all(&output_bytes)
+                .write_all(output_bytes)
                 .expect("Unable to write to buffer.");
         }
     }
 }
 
+#[cfg(feature = "cli")]
+fn run_diff(args: &DiffArgs) -> Result<(), String> {
+    let old = read_cli_screenplay(&args.old)?;
+    let new = read_cli_screenplay(&args.new)?;
+    let result = jumpcut::diff::diff(&old, &new);
+    let output = match args.format {
+        ReportFormat::Text => result.to_report(),
+        ReportFormat::Json => {
+            serde_json::to_string_pretty(&result).map_err(|error| error.to_string())?
+        }
+    };
+    write_cli_output(args.output.as_deref(), output.as_bytes());
+    Ok(())
+}
+
+/// Writes the merged draft and exits with 1 if it has conflicts, as git
+/// expects of a merge driver.
+#[cfg(feature = "cli")]
+fn run_merge(args: &MergeArgs) -> Result<i32, String> {
+    let base = read_cli_input(&args.base)?;
+    let ours = read_cli_input(&args.ours)?;
+    let theirs = read_cli_input(&args.theirs)?;
+    let labels = jumpcut::merge::MergeLabels {
+        ours: args.ours_label.clone(),
+        theirs: args.theirs_label.clone(),
+    };
+    let result = jumpcut::merge::merge_fountain_with_labels(&base, &ours, &theirs, &labels);
+    write_cli_output(args.output.as_deref(), result.fountain.as_bytes());
+    if !result.is_clean() {
+        eprintln!("{} merge conflict(s)", result.conflicts);
+    }
+    Ok(if result.is_clean() { 0 } else { 1 })
+}
+
+#[cfg(feature = "cli")]
+fn run_renumber(args: &RenumberArgs) -> Result<(), String> {
+    let source = read_cli_input(&args.input)?;
+    let mut tree = jumpcut::syntax::SyntaxTree::parse(&source);
+    let numbering = if args.locked {
+        jumpcut::SceneNumbering::Locked
+    } else {
+        jumpcut::SceneNumbering::Fresh
+    };
+    tree.number_scenes(numbering)
+        .map_err(|error| error.to_string())?;
+    let output = match &args.output {
+        Some(output) => Some(output.as_path()),
+        None if args.input.to_str() == Some("-") => None,
+        None => Some(args.input.as_path()),
+    };
+    write_cli_output(output, tree.to_fountain().as_bytes());
+    Ok(())
+}
+
+#[cfg(feature = "cli")]
+fn run_rename(args: &RenameArgs) -> Result<(), String> {
+    let source = read_cli_input(&args.input)?;
+    let mut tree = jumpcut::syntax::SyntaxTree::parse(&source);
+    let options = jumpcut::RenameOptions {
+        in_text: args.in_text,
+    };
+    let renamed = tree
+        .rename_character_with_options(&args.from, &args.to, options)
+        .map_err(|error| error.to_string())?;
+    if renamed == 0 {
+        return Err(format!("no character named {} was found", args.from));
+    }
+    // The rewritten file may go to stdout, so the changes are reported on stderr.
+    for change in tree.changed_lines() {
+        eprintln!("line {}: {} -> {}", change.line, change.before, change.after);
+    }
+    let output = match &args.output {
+        Some(output) => Some(output.as_path()),
+        None if args.input.to_str() == Some("-") => None,
+        None => Some(args.input.as_path()),
+    };
+    write_cli_output(output, tree.to_fountain().as_bytes());
+    Ok(())
+}
+
+#[cfg(feature = "cli")]
+fn run_stats(args: &StatsArgs) -> Result<(), String> {
+    let statistics = read_cli_screenplay(&args.input)?.statistics();
+    let output = match args.format {
+        ReportFormat::Text => statistics.to_report(),
+        ReportFormat::Json => {
+            serde_json::to_string_pretty(&statistics).map_err(|error| error.to_string())?
+        }
+    };
+    write_cli_output(args.output.as_deref(), output.as_bytes());
+    Ok(())
+}
+
+#[cfg(feature = "cli")]
+fn run_runtime(args: &RuntimeArgs) -> Result<(), String> {
+    let screenplay = read_cli_screenplay(&args.input)?;
+    let mut options = jumpcut::runtime::RuntimeOptions::for_screenplay(&screenplay);
+    for (value, rate) in [
+        (args.words_per_minute, &mut options.dialogue_words_per_minute),
+        (
+            args.action_lines_per_second,
+            &mut options.action_lines_per_second,
+        ),
+    ] {
+        match value {
+            Some(value) if value > 0.0 => *rate = value,
+            Some(_) => return Err("rates must be greater than zero".to_string()),
+            None => {}
+        }
+    }
+    match args.transition_seconds {
+        Some(seconds) if seconds < 0.0 => {
+            return Err("--transition-seconds cannot be negative".to_string());
+        }
+        Some(seconds) => options.transition_seconds = seconds,
+        None => {}
+    }
+    let estimate = screenplay.runtime_with_options(options);
+    let output = match args.format {
+        ReportFormat::Text => estimate.to_report(),
+        ReportFormat::Json => {
+            serde_json::to_string_pretty(&estimate).map_err(|error| error.to_string())?
+        }
+    };
+    write_cli_output(args.output.as_deref(), output.as_bytes());
+    Ok(())
+}
+
+#[cfg(feature = "cli")]
+fn run_breakdown(args: &BreakdownArgs) -> Result<(), String> {
+    let breakdown = read_cli_screenplay(&args.input)?.breakdown();
+    let output = match args.format {
+        BreakdownFormat::Json => serde_json::to_string_pretty(&breakdown)
+            .map_err(|error| error.to_string())?
+            .into_bytes(),
+        BreakdownFormat::Csv => breakdown.to_csv().into_bytes(),
+        #[cfg(feature = "pdf")]
+        BreakdownFormat::Pdf => breakdown.to_pdf(),
+    };
+    write_cli_output(args.output.as_deref(), &output);
+    Ok(())
+}
+
+#[cfg(feature = "cli")]
+fn run_stripboard(args: &StripboardArgs) -> Result<(), String> {
+    let stripboard = read_cli_screenplay(&args.input)?.stripboard();
+    let output = match args.format {
+        StripboardFormat::Csv => stripboard.to_csv(),
+        #[cfg(feature = "html")]
+        StripboardFormat::Html => stripboard.to_html(),
+        StripboardFormat::Json => {
+            serde_json::to_string_pretty(&stripboard).map_err(|error| error.to_string())?
+        }
+    };
+    write_cli_output(args.output.as_deref(), output.as_bytes());
+    Ok(())
+}
+
+#[cfg(all(feature = "cli", feature = "pdf"))]
+fn run_sides(args: &SidesArgs) -> Result<(), String> {
+    let screenplay = read_cli_screenplay(&args.input)?;
+    let selection = jumpcut::sides::SidesSelection {
+        characters: args.characters.clone(),
+        scene_numbers: args.scenes.clone(),
+    };
+    if screenplay.sides(&selection).is_empty() {
+        return Err("no scenes match the given characters or scene numbers".to_string());
+    }
+    let pdf = screenplay.to_sides_pdf(
+        &selection,
+        jumpcut::rendering::pdf::PdfRenderOptions {
+            render_title_page: false,
+            ..Default::default()
+        },
+    );
+    write_cli_output(args.output.as_deref(), &pdf);
+    Ok(())
+}
+
+#[cfg(feature = "cli")]
+fn read_cli_screenplay(input: &Path) -> Result<jumpcut::Screenplay, String> {
+    let content = read_cli_input(input)?;
+    parse_cli_input(input, &content, jumpcut::Metadata::new())
+}
+
+#[cfg(feature = "cli")]
+fn apply_cli_revision(screenplay: &mut jumpcut::Screenplay, opt: &Args) -> Result<(), String> {
+    let Some(previous) = &opt.revised_from else {
+        return Ok(());
+    };
+    let previous = read_cli_screenplay(previous)?;
+    let mut previous_ids = previous.clone();
+    previous_ids.assign_element_ids();
+    screenplay.carry_element_ids(&previous_ids);
+    let id = previous
+        .next_revision_id()
+        .max(screenplay.next_revision_id());
+    let name = opt
+        .revision_name
+        .clone()
+        .unwrap_or_else(|| format!("{} Revision", opt.revision_color));
+    let mut revision = jumpcut::RevisionSet::new(id, name, opt.revision_color.clone());
+    revision.date = opt.revision_date.clone();
+    screenplay.mark_revisions(&previous, revision);
+    Ok(())
+}
+
+/// Applies `--locked-pages`, then locks the pages for `--lock-pages`, so a
+/// locked draft can be locked again with its inserted pages.
+#[cfg(feature = "cli")]
+fn apply_cli_page_lock(screenplay: &mut jumpcut::Screenplay, opt: &Args) -> Result<(), String> {
+    if let Some(path) = &opt.locked_pages {
+        let json = fs::read_to_string(path)
+            .map_err(|error| format!("Could not read file '{}': {error}", path.display()))?;
+        let lock = jumpcut::PageLock::from_json_str(&json)
+            .map_err(|error| format!("Invalid page lock '{}': {error}", path.display()))?;
+        screenplay.assign_element_ids();
+        screenplay.page_lock = Some(lock);
+    }
+    if let Some(path) = &opt.lock_pages {
+        let lock = screenplay.lock_pages();
+        fs::write(path, lock.to_json_string())
+            .map_err(|error| format!("Could not write file '{}': {error}", path.display()))?;
+    }
+    Ok(())
+}
+
 #[cfg(feature = "cli")]
 fn infer_format(format_opt: Option<&str>, output_opt: Option<&PathBuf>) -> String {
     match format_opt {
@@ -304,7 +873,7 @@ fn read_cli_metadata(opt: &Args) -> Result<jumpcut::Metadata, String> {
     let Some(metadata_arg_path) = &opt.metadata else {
         return Ok(jumpcut::Metadata::new());
     };
-    let actual_metadata_file_path = resolve_metadata_path(&opt.input, metadata_arg_path);
+    let actual_metadata_file_path = resolve_metadata_path(opt.input(), metadata_arg_path);
     let metadata_content = fs::read_to_string(&actual_metadata_file_path).map_err(|error| {
         format!(
             "Error reading metadata file '{}': {}",
@@ -355,7 +924,20 @@ fn parse_cli_input(
 ) -> Result<jumpcut::Screenplay, String> {
     let input_format = infer_input_format(input, content);
     let mut screenplay = match input_format {
-        InputFormat::Fountain => parse(content),
+        InputFormat::Fountain => {
+            // Includes in text piped to stdin are relative to the working
+            // directory.
+            let resolver = if input.to_str() == Some("-") {
+                let base_dir = std::env::current_dir()
+                    .map_err(|error| format!("Could not read the working directory: {error}"))?;
+                FileSystemResolver::new(base_dir)
+            } else {
+                FileSystemResolver::default()
+            };
+            parse_with_includes(content, &input.to_string_lossy(), &resolver)
+                .map_err(|error| error.to_string())?
+                .0
+        }
         InputFormat::Fdx => parse_fdx(content).map_err(|error| error.to_string())?,
     };
 
@@ -390,8 +972,8 @@ mod tests {
     use super::pdf_output_enabled;
     use super::{
         apply_render_profile_override, infer_format, infer_input_format, looks_like_fdx,
-        parse_cli_input, resolve_metadata_path, resolve_output_path, Args, InputFormat,
-        RenderProfile,
+        parse_cli_input, resolve_metadata_path, resolve_output_path, Args, BreakdownFormat,
+        Command, InputFormat, RenderProfile, ReportFormat, StripboardFormat,
     };
     use clap::Parser;
     use jumpcut::{ElementText, Metadata};
@@ -442,6 +1024,157 @@ mod tests {
         assert!(parsed.is_ok());
     }
 
+    #[test]
+    fn cli_accepts_diff_subcommand() {
+        let args =
+            Args::try_parse_from(["jumpcut", "diff", "old.fountain", "new.fdx", "-f", "json"])
+                .unwrap();
+
+        let Some(Command::Diff(diff)) = args.command else {
+            panic!("expected the diff subcommand");
+        };
+        assert_eq!(diff.old, PathBuf::from("old.fountain"));
+        assert_eq!(diff.new, PathBuf::from("new.fdx"));
+        assert_eq!(diff.format, ReportFormat::Json);
+        assert_eq!(args.input, None);
+    }
+
+    #[test]
+    fn cli_accepts_merge_subcommand_as_a_git_merge_driver() {
+        let args = Args::try_parse_from([
+            "jumpcut",
+            "merge",
+            "%O",
+            "%A",
+            "%B",
+            "-o",
+            "%A",
+            "--theirs-label",
+            "feature",
+        ])
+        .unwrap();
+
+        let Some(Command::Merge(merge)) = args.command else {
+            panic!("expected the merge subcommand");
+        };
+        assert_eq!(merge.base, PathBuf::from("%O"));
+        assert_eq!(merge.output, Some(PathBuf::from("%A")));
+        assert_eq!(merge.ours_label, "ours");
+        assert_eq!(merge.theirs_label, "feature");
+    }
+
+    #[test]
+    fn cli_accepts_stats_subcommand() {
+        let args = Args::try_parse_from(["jumpcut", "stats", "script.fdx", "-f", "json"]).unwrap();
+
+        let Some(Command::Stats(stats)) = args.command else {
+            panic!("expected the stats subcommand");
+        };
+        assert_eq!(stats.input, PathBuf::from("script.fdx"));
+        assert_eq!(stats.format, ReportFormat::Json);
+        assert_eq!(stats.output, None);
+    }
+
+    #[test]
+    fn cli_accepts_runtime_subcommand() {
+        let args = Args::try_parse_from([
+            "jumpcut",
+            "runtime",
+            "script.fountain",
+            "--words-per-minute",
+            "160",
+            "-f",
+            "json",
+        ])
+        .unwrap();
+
+        let Some(Command::Runtime(runtime)) = args.command else {
+            panic!("expected the runtime subcommand");
+        };
+        assert_eq!(runtime.input, PathBuf::from("script.fountain"));
+        assert_eq!(runtime.format, ReportFormat::Json);
+        assert_eq!(runtime.words_per_minute, Some(160.0));
+        assert_eq!(runtime.action_lines_per_second, None);
+        assert_eq!(runtime.transition_seconds, None);
+    }
+
+    #[test]
+    fn cli_accepts_breakdown_subcommand() {
+        let args =
+            Args::try_parse_from(["jumpcut", "breakdown", "script.fountain", "-f", "csv"]).unwrap();
+
+        let Some(Command::Breakdown(breakdown)) = args.command else {
+            panic!("expected the breakdown subcommand");
+        };
+        assert_eq!(breakdown.input, PathBuf::from("script.fountain"));
+        assert_eq!(breakdown.format, BreakdownFormat::Csv);
+        assert_eq!(breakdown.output, None);
+    }
+
+    #[test]
+    fn cli_accepts_rename_subcommand() {
+        let args = Args::try_parse_from([
+            "jumpcut",
+            "rename",
+            "script.fountain",
+            "Bob",
+            "ROBERT",
+            "--in-text",
+        ])
+        .unwrap();
+
+        let Some(Command::Rename(rename)) = args.command else {
+            panic!("expected the rename subcommand");
+        };
+        assert_eq!(rename.input, PathBuf::from("script.fountain"));
+        assert_eq!((rename.from.as_str(), rename.to.as_str()), ("Bob", "ROBERT"));
+        assert!(rename.in_text);
+        assert_eq!(rename.output, None);
+    }
+
+    #[test]
+    fn cli_accepts_stripboard_subcommand() {
+        let args = Args::try_parse_from(["jumpcut", "stripboard", "script.fountain"]).unwrap();
+
+        let Some(Command::Stripboard(stripboard)) = args.command else {
+            panic!("expected the stripboard subcommand");
+        };
+        assert_eq!(stripboard.input, PathBuf::from("script.fountain"));
+        assert_eq!(stripboard.format, StripboardFormat::Csv);
+        assert_eq!(stripboard.output, None);
+    }
+
+    #[cfg(feature = "pdf")]
+    #[test]
+    fn cli_accepts_sides_subcommand() {
+        let args = Args::try_parse_from([
+            "jumpcut",
+            "sides",
+            "script.fountain",
+            "--character",
+            "Alice",
+            "--scenes",
+            "12,14A",
+            "-o",
+            "sides.pdf",
+        ])
+        .unwrap();
+
+        let Some(Command::Sides(sides)) = args.command else {
+            panic!("expected the sides subcommand");
+        };
+        assert_eq!(sides.characters, ["Alice"]);
+        assert_eq!(sides.scenes, ["12", "14A"]);
+        assert_eq!(sides.output, Some(PathBuf::from("sides.pdf")));
+        assert!(Args::try_parse_from(["jumpcut", "sides", "script.fountain"]).is_err());
+    }
+
+    #[test]
+    fn cli_still_requires_an_input_without_a_subcommand() {
+        assert!(Args::try_parse_from(["jumpcut"]).is_err());
+        assert!(Args::try_parse_from(["jumpcut", "-f", "json"]).is_err());
+    }
+
     #[test]
     fn cli_rejects_removed_final_draft_render_profile_name() {
         let parsed = Args::try_parse_from([
@@ -581,7 +1314,7 @@ mod tests {
 
         assert_eq!(format, "fdx");
         assert_eq!(
-            resolve_output_path(&args.input, explicit_output, args.write, &format).unwrap(),
+            resolve_output_path(args.input(), explicit_output, args.write, &format).unwrap(),
             Some(PathBuf::from("big fish.fdx"))
         );
     }
@@ -596,7 +1329,7 @@ mod tests {
 
         assert_eq!(format, "pdf");
         assert_eq!(
-            resolve_output_path(&args.input, explicit_output, args.write, &format).unwrap(),
+            resolve_output_path(args.input(), explicit_output, args.write, &format).unwrap(),
             Some(PathBuf::from("big fish.pdf"))
         );
     }
@@ -610,7 +1343,7 @@ mod tests {
 
         assert_eq!(format, "fountain");
         assert_eq!(
-            resolve_output_path(&args.input, explicit_output, args.write, &format).unwrap(),
+            resolve_output_path(args.input(), explicit_output, args.write, &format).unwrap(),
             Some(PathBuf::from("big fish.fountain"))
         );
     }
@@ -622,7 +1355,7 @@ mod tests {
         let format = infer_format(args.format.as_deref(), explicit_output);
 
         let error =
-            resolve_output_path(&args.input, explicit_output, args.write, &format).unwrap_err();
+            resolve_output_path(args.input(), explicit_output, args.write, &format).unwrap_err();
         assert_eq!(
             error,
             "cannot auto-derive an output path when input is stdin"
@@ -636,7 +1369,7 @@ mod tests {
         let format = infer_format(args.format.as_deref(), explicit_output);
 
         let error =
-            resolve_output_path(&args.input, explicit_output, args.write, &format).unwrap_err();
+            resolve_output_path(args.input(), explicit_output, args.write, &format).unwrap_err();
         assert_eq!(
             error,
             "auto-derived output path matches the input path; specify --format or --output"
@@ -696,7 +1429,7 @@ mod tests {
         assert_eq!(requested_output, Some(&PathBuf::from("output.pdf")));
         assert_eq!(format, "pdf");
         assert_eq!(
-            resolve_output_path(&args.input, requested_output, args.write, &format).unwrap(),
+            resolve_output_path(args.input(), requested_output, args.write, &format).unwrap(),
             Some(PathBuf::from("output.pdf"))
         );
     }
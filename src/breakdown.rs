@@ -0,0 +1,345 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::pagination::Eighths;
+use crate::pagination::scene_lengths::ScenePages;
+use crate::{BreakdownCategory, DayNight, Element, SceneHeadingParts, Screenplay};
+
+/// A breakdown sheet for every scene, as built by [`Screenplay::breakdown`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct Breakdown {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub sheets: Vec<BreakdownSheet>,
+}
+
+/// What a scene needs to be shot: its cast and the production elements
+/// tagged in it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct BreakdownSheet {
+    /// The scene's position in the screenplay, counting from 1.
+    pub index: usize,
+    pub scene_number: Option<String>,
+    pub heading: String,
+    pub heading_parts: SceneHeadingParts,
+    /// Whether the scene plays by day or by night. Scenes that are
+    /// `CONTINUOUS` or `LATER` take it from the scene before them.
+    pub day_night: Option<DayNight>,
+    pub eighths: Eighths,
+    /// The printed number of the page the scene starts on.
+    pub first_page: String,
+    /// The printed number of the page the scene ends on.
+    pub last_page: String,
+    /// The characters who speak in the scene, in order of their first
+    /// speech, followed by anyone else tagged as cast.
+    pub cast: Vec<String>,
+    /// The other tagged elements by category, each listed once in the order
+    /// it was first tagged.
+    pub elements: BTreeMap<BreakdownCategory, Vec<String>>,
+}
+
+impl Breakdown {
+    /// The sheets as CSV, one row per scene with a column for each category.
+    /// Cast and elements in a category are separated by `; `.
+    pub fn to_csv(&self) -> String {
+        let mut headers = vec![
+            "Index",
+            "Scene Number",
+            "Heading",
+            "Setting",
+            "Location",
+            "Time of Day",
+            "Day/Night",
+            "Eighths",
+            "Length",
+            "First Page",
+            "Last Page",
+        ];
+        headers.extend(
+            BreakdownCategory::ALL
+                .into_iter()
+                .map(BreakdownCategory::label),
+        );
+        let mut out = String::new();
+        write_csv_row(&mut out, headers.into_iter().map(str::to_string));
+        for sheet in &self.sheets {
+            let parts = &sheet.heading_parts;
+            let mut row = vec![
+                sheet.index.to_string(),
+                sheet.scene_number.clone().unwrap_or_default(),
+                sheet.heading.clone(),
+                parts.prefix.clone().unwrap_or_default(),
+                parts.locations.join(" - "),
+                parts.time_of_day.clone().unwrap_or_default(),
+                day_night_label(sheet.day_night).to_string(),
+                sheet.eighths.0.to_string(),
+                sheet.eighths.to_string(),
+                sheet.first_page.clone(),
+                sheet.last_page.clone(),
+            ];
+            row.extend(
+                BreakdownCategory::ALL
+                    .into_iter()
+                    .map(|category| sheet.category(category).join("; ")),
+            );
+            write_csv_row(&mut out, row);
+        }
+        out
+    }
+
+    /// Prints the sheets, one scene to a page.
+    #[cfg(feature = "pdf")]
+    pub fn to_pdf(&self) -> Vec<u8> {
+        crate::rendering::pdf::render_with_options(
+            &self.to_sheets_screenplay(),
+            crate::rendering::pdf::PdfRenderOptions {
+                render_title_page: false,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Lays the sheets out as centered titles and action, so that the PDF
+    /// renderer can print them.
+    #[cfg(feature = "pdf")]
+    fn to_sheets_screenplay(&self) -> Screenplay {
+        use crate::{Attributes, ElementText, Metadata, TextRun};
+
+        let run = |content: &str, styles: &[&str]| TextRun {
+            content: content.to_string(),
+            text_style: styles.iter().map(|style| style.to_string()).collect(),
+            revision: None,
+        };
+        let centered = Attributes {
+            centered: true,
+            ..Attributes::default()
+        };
+
+        let mut elements = Vec::new();
+        for sheet in &self.sheets {
+            elements.push(Element::Action(
+                ElementText::Styled(vec![run("BREAKDOWN SHEET", &["Bold"])]),
+                Attributes {
+                    starts_new_page: !elements.is_empty(),
+                    ..centered.clone()
+                },
+            ));
+            if let Some(title) = &self.title {
+                elements.push(Element::Action(title.as_str().into(), centered.clone()));
+            }
+
+            let scene = match &sheet.scene_number {
+                Some(number) => format!("Scene {number}"),
+                None => format!("Scene {} (unnumbered)", sheet.index),
+            };
+            let pages = if sheet.first_page == sheet.last_page {
+                format!("Script page: {}", sheet.first_page)
+            } else {
+                format!("Script pages: {}-{}", sheet.first_page, sheet.last_page)
+            };
+            elements.push(Element::Action(
+                ElementText::Styled(vec![run(&sheet.heading, &["Bold"])]),
+                Attributes::default(),
+            ));
+            let parts = &sheet.heading_parts;
+            let mut details = vec![scene, format!("Length: {} pages", sheet.eighths), pages];
+            if let Some(prefix) = &parts.prefix {
+                details.push(format!("Setting: {prefix}"));
+            }
+            if !parts.locations.is_empty() {
+                details.push(format!("Location: {}", parts.locations.join(" - ")));
+            }
+            if let Some(time_of_day) = &parts.time_of_day {
+                details.push(format!("Time of day: {time_of_day}"));
+            }
+            if sheet.day_night.is_some() {
+                details.push(format!("Day/Night: {}", day_night_label(sheet.day_night)));
+            }
+            elements.push(Element::Action(
+                details.join("\n").into(),
+                Attributes::default(),
+            ));
+
+            for category in BreakdownCategory::ALL {
+                let names = sheet.category(category);
+                if names.is_empty() {
+                    continue;
+                }
+                elements.push(Element::Action(
+                    ElementText::Styled(vec![
+                        run(&category.label().to_uppercase(), &["Bold", "Underline"]),
+                        run(&format!("\n{}", names.join("\n")), &[]),
+                    ]),
+                    Attributes::default(),
+                ));
+            }
+        }
+
+        let mut metadata = Metadata::new();
+        if let Some(title) = &self.title {
+            metadata.insert(
+                "title".to_string(),
+                vec![format!("{title} Breakdown").into()],
+            );
+        }
+        Screenplay {
+            metadata,
+            imported_layout: None,
+            imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
+            elements,
+        }
+    }
+}
+
+impl BreakdownSheet {
+    /// The names listed under `category`, with the scene's cast under
+    /// [`BreakdownCategory::Cast`].
+    pub fn category(&self, category: BreakdownCategory) -> &[String] {
+        match category {
+            BreakdownCategory::Cast => &self.cast,
+            category => self
+                .elements
+                .get(&category)
+                .map(Vec::as_slice)
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl Screenplay {
+    /// Builds a breakdown sheet for each scene from the characters who speak
+    /// in it and the production elements tagged in it, such as
+    /// `[[prop: revolver]]` in Fountain or Final Draft's tags. Scenes are
+    /// measured on the pages the PDF renderer prints.
+    pub fn breakdown(&self) -> Breakdown {
+        let scene_pages = ScenePages::new(self);
+        let mut breakdown = Breakdown {
+            title: self.metadata.get("title").map(|lines| {
+                lines
+                    .iter()
+                    .map(|line| line.plain_text())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            }),
+            sheets: Vec::new(),
+        };
+        let mut previous_day_night = None;
+        for element in &self.elements {
+            if let Element::SceneHeading(text, attributes) = element {
+                let heading = text.plain_text();
+                let heading_parts = SceneHeadingParts::parse(&heading);
+                let day_night = match heading_parts.day_night() {
+                    None if heading_parts.continues_previous_time() => previous_day_night,
+                    day_night => day_night,
+                };
+                previous_day_night = day_night;
+                let index = breakdown.sheets.len();
+                let (eighths, first_page, last_page) = scene_pages
+                    .scenes
+                    .get(index)
+                    .map(|scene| {
+                        (
+                            scene_pages.eighths(scene),
+                            scene_pages.pages[scene.first_page].clone(),
+                            scene_pages.pages[scene.last_page].clone(),
+                        )
+                    })
+                    .unwrap_or_default();
+                breakdown.sheets.push(BreakdownSheet {
+                    index: index + 1,
+                    scene_number: attributes.scene_number.clone(),
+                    heading,
+                    heading_parts,
+                    day_night,
+                    eighths,
+                    first_page,
+                    last_page,
+                    cast: Vec::new(),
+                    elements: BTreeMap::new(),
+                });
+            }
+            if let Some(sheet) = breakdown.sheets.last_mut() {
+                add_to_sheet(sheet, element);
+            }
+        }
+        for sheet in &mut breakdown.sheets {
+            finish_cast(sheet);
+        }
+        breakdown
+    }
+}
+
+/// Adds the speaking characters and tags of `element` to the sheet.
+fn add_to_sheet(sheet: &mut BreakdownSheet, element: &Element) {
+    match element {
+        Element::DialogueBlock(block) | Element::DualDialogueBlock(block) => {
+            for element in block {
+                add_to_sheet(sheet, element);
+            }
+            return;
+        }
+        Element::Character(_, _) => {
+            for cue in element.character_cues() {
+                push_unique(&mut sheet.cast, cue.name);
+            }
+        }
+        _ => {}
+    }
+    // Tagged cast wait under their category until `finish_cast` adds them.
+    for tag in element
+        .attributes()
+        .into_iter()
+        .flat_map(|attributes| &attributes.tags)
+    {
+        push_unique(
+            sheet.elements.entry(tag.category).or_default(),
+            tag.name.clone(),
+        );
+    }
+}
+
+/// Adds the tagged cast after the characters who speak.
+fn finish_cast(sheet: &mut BreakdownSheet) {
+    for name in sheet
+        .elements
+        .remove(&BreakdownCategory::Cast)
+        .unwrap_or_default()
+    {
+        push_unique(&mut sheet.cast, name.to_uppercase());
+    }
+}
+
+/// Adds `name` unless it is already listed, ignoring case.
+fn push_unique(names: &mut Vec<String>, name: String) {
+    if !names
+        .iter()
+        .any(|listed| listed.eq_ignore_ascii_case(&name))
+    {
+        names.push(name);
+    }
+}
+
+pub(crate) fn day_night_label(day_night: Option<DayNight>) -> &'static str {
+    match day_night {
+        Some(DayNight::Day) => "Day",
+        Some(DayNight::Night) => "Night",
+        None => "",
+    }
+}
+
+pub(crate) fn write_csv_row(out: &mut String, fields: impl IntoIterator<Item = String>) {
+    let fields = fields
+        .into_iter()
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field
+            }
+        })
+        .collect::<Vec<_>>();
+    out.push_str(&fields.join(","));
+    out.push('\n');
+}
@@ -11,6 +11,7 @@ use crate::pagination::{
     Fragment, LayoutGeometry, LineRange, NormalizedElement, NormalizedScreenplay, PageBreakFixture,
     PageBreakFixtureSourceRefs, PaginatedScreenplay, PaginationConfig, SemanticUnit,
 };
+use crate::pagination::continueds::continued_character_cue_text;
 use crate::parse;
 
 pub fn write_big_fish_public_slice_json(debug_dir: &Path) {
@@ -2021,17 +2022,6 @@ fn render_dialogue_continuation_prefix(
         .collect()
 }
 
-fn continued_character_cue_text(text: &str) -> String {
-    let trimmed = text.trim_end();
-    let upper = trimmed.to_ascii_uppercase();
-
-    if upper.ends_with("(CONT'D)") || upper.ends_with("(CONT’D)") {
-        trimmed.to_string()
-    } else {
-        format!("{trimmed} (CONT'D)")
-    }
-}
-
 fn dialogue_part_render_text(
     _dialogue: &crate::pagination::DialogueUnit,
     dialogue_part: &crate::pagination::DialoguePart,
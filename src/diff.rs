@@ -0,0 +1,760 @@
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Write;
+
+use crate::{Element, ElementText, Screenplay};
+
+/// How an element changed between two drafts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum ChangeKind {
+    Inserted,
+    Deleted,
+    Moved,
+    Modified,
+}
+
+/// A run of words in the text of a changed element. Each run keeps the
+/// whitespace that followed it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "op", content = "text")]
+pub enum WordChange {
+    Equal(String),
+    Inserted(String),
+    Deleted(String),
+}
+
+/// One changed element. Paths are indexes into `Screenplay::elements`,
+/// followed by indexes into dialogue blocks for the elements inside them.
+///
+/// `words` is only filled in for modified elements, and for moved elements
+/// whose text also changed. A modified element whose words are all equal had
+/// its formatting changed. `attributes` lists the changed attributes of
+/// modified and moved elements, and is empty when only the text changed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ElementChange {
+    pub kind: ChangeKind,
+    pub element_type: String,
+    pub old_path: Option<Vec<usize>>,
+    pub new_path: Option<Vec<usize>>,
+    pub old_text: Option<String>,
+    pub new_text: Option<String>,
+    /// The scene heading the element sits under, in the new draft unless the
+    /// element was deleted.
+    pub scene: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub words: Vec<WordChange>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub attributes: Vec<AttributeChange>,
+}
+
+/// An attribute of an element that changes how it prints: its
+/// `scene_number`, or the `centered` and `starts_new_page` flags, which are
+/// `"true"` when set and missing otherwise.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct AttributeChange {
+    pub name: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+/// A metadata key that was added, removed or changed. Values are the plain
+/// text of each line.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct MetadataChange {
+    pub key: String,
+    pub old: Option<Vec<String>>,
+    pub new: Option<Vec<String>>,
+}
+
+/// The differences between two drafts of a screenplay, as built by [`diff`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct ScreenplayDiff {
+    pub metadata: Vec<MetadataChange>,
+    pub elements: Vec<ElementChange>,
+}
+
+/// Compares two drafts paragraph by paragraph.
+///
+/// Elements are matched by id when both drafts have ids, and by type and
+/// text otherwise. Unmatched elements left in the same place, elements of the
+/// same type with mostly the same words, and dialogue blocks with the same
+/// speaker are reported as modified, as are matched elements whose scene
+/// number, centering or page break changed.
+/// Matched dialogue blocks are compared line by line, so a changed line of
+/// dialogue is reported on its own.
+pub fn diff(old: &Screenplay, new: &Screenplay) -> ScreenplayDiff {
+    let mut differ = Differ {
+        old_scenes: scene_headings(&old.elements),
+        new_scenes: scene_headings(&new.elements),
+        changes: Vec::new(),
+    };
+    differ.diff_sequence(&old.elements, &new.elements, &[], &[]);
+
+    ScreenplayDiff {
+        metadata: diff_metadata(old, new),
+        elements: differ.changes,
+    }
+}
+
+impl ScreenplayDiff {
+    pub fn is_empty(&self) -> bool {
+        self.metadata.is_empty() && self.elements.is_empty()
+    }
+
+    pub fn to_json_string(&self) -> String {
+        serde_json::to_string(self)
+            .expect("Should be impossible for this JSON serialization to fail.")
+    }
+
+    pub fn to_json_value(&self) -> serde_json::Value {
+        serde_json::to_value(self)
+            .expect("Should be impossible for this JSON serialization to fail.")
+    }
+
+    /// A plain-text report of the changes. Element paths are counted from 1,
+    /// and changed words are marked `[-deleted-]` and `{+inserted+}`.
+    pub fn to_report(&self) -> String {
+        if self.is_empty() {
+            return "No differences.\n".to_string();
+        }
+
+        let mut out = String::new();
+        if !self.metadata.is_empty() {
+            out.push_str("Metadata\n");
+            for change in &self.metadata {
+                let (marker, detail) = match (&change.old, &change.new) {
+                    (None, Some(new)) => ('+', quote_values(new)),
+                    (Some(old), None) => ('-', quote_values(old)),
+                    (Some(old), Some(new)) => (
+                        '~',
+                        format!("{} -> {}", quote_values(old), quote_values(new)),
+                    ),
+                    (None, None) => continue,
+                };
+                writeln!(out, "  {marker} {}: {detail}", change.key).unwrap();
+            }
+        }
+
+        if !self.elements.is_empty() {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str("Elements\n");
+            for change in &self.elements {
+                report_element_change(&mut out, change);
+            }
+        }
+        out
+    }
+}
+
+fn report_element_change(out: &mut String, change: &ElementChange) {
+    let (marker, location) = match (change.kind, &change.old_path, &change.new_path) {
+        (ChangeKind::Inserted, _, Some(new)) => ('+', format_path(new)),
+        (ChangeKind::Deleted, Some(old), _) => ('-', format_path(old)),
+        (ChangeKind::Moved, Some(old), Some(new)) => {
+            ('>', format!("{} -> {}", format_path(old), format_path(new)))
+        }
+        (_, _, Some(new)) => ('~', format_path(new)),
+        (_, _, None) => ('~', String::new()),
+    };
+    write!(out, "  {marker} {} {location}", change.element_type).unwrap();
+    if let Some(scene) = &change.scene {
+        write!(out, " ({scene})").unwrap();
+    }
+    out.push('\n');
+
+    let body = if change.words.is_empty() {
+        change
+            .new_text
+            .clone()
+            .or_else(|| change.old_text.clone())
+            .unwrap_or_default()
+    } else {
+        mark_words(&change.words)
+    };
+    for line in body.lines() {
+        writeln!(out, "      {line}").unwrap();
+    }
+    for attribute in &change.attributes {
+        let value = |value: &Option<String>| {
+            value
+                .as_ref()
+                .map_or("none".to_string(), |value| format!("{value:?}"))
+        };
+        writeln!(
+            out,
+            "      {}: {} -> {}",
+            attribute.name,
+            value(&attribute.old),
+            value(&attribute.new)
+        )
+        .unwrap();
+    }
+}
+
+fn format_path(path: &[usize]) -> String {
+    let parts: Vec<String> = path.iter().map(|index| (index + 1).to_string()).collect();
+    format!("#{}", parts.join("."))
+}
+
+fn quote_values(values: &[String]) -> String {
+    let quoted: Vec<String> = values.iter().map(|value| format!("{value:?}")).collect();
+    quoted.join(", ")
+}
+
+fn mark_words(words: &[WordChange]) -> String {
+    let mut out = String::new();
+    for word in words {
+        let (open, text, close) = match word {
+            WordChange::Equal(text) => ("", text, ""),
+            WordChange::Inserted(text) => ("{+", text, "+}"),
+            WordChange::Deleted(text) => ("[-", text, "-]"),
+        };
+        let trimmed = text.trim_end();
+        write!(out, "{open}{trimmed}{close}{}", &text[trimmed.len()..]).unwrap();
+    }
+    out
+}
+
+fn diff_metadata(old: &Screenplay, new: &Screenplay) -> Vec<MetadataChange> {
+    let values = |screenplay: &Screenplay, key: &str| {
+        screenplay.metadata.get(key).map(|values| {
+            values
+                .iter()
+                .map(ElementText::plain_text)
+                .collect::<Vec<_>>()
+        })
+    };
+    let keys: BTreeSet<&String> = old.metadata.keys().chain(new.metadata.keys()).collect();
+    keys.into_iter()
+        .filter_map(|key| {
+            let old = values(old, key);
+            let new = values(new, key);
+            (old != new).then(|| MetadataChange {
+                key: key.clone(),
+                old,
+                new,
+            })
+        })
+        .collect()
+}
+
+/// The text of the scene heading each top-level element sits under.
+fn scene_headings(elements: &[Element]) -> Vec<Option<String>> {
+    let mut current = None;
+    elements
+        .iter()
+        .map(|element| {
+            if let Element::SceneHeading(text, _) = element {
+                current = Some(text.plain_text());
+            }
+            current.clone()
+        })
+        .collect()
+}
+
+/// How two runs of elements line up.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Step {
+    /// The same element in both drafts.
+    Same(usize, usize),
+    /// Different but similar elements in the same place.
+    Similar(usize, usize),
+    Deleted(usize),
+    Inserted(usize),
+    Moved(usize, usize),
+}
+
+struct Differ {
+    old_scenes: Vec<Option<String>>,
+    new_scenes: Vec<Option<String>>,
+    changes: Vec<ElementChange>,
+}
+
+impl Differ {
+    fn diff_sequence(
+        &mut self,
+        old: &[Element],
+        new: &[Element],
+        old_prefix: &[usize],
+        new_prefix: &[usize],
+    ) {
+        let old_path = |index: usize| [old_prefix, &[index]].concat();
+        let new_path = |index: usize| [new_prefix, &[index]].concat();
+
+        for step in align(old, new) {
+            match step {
+                Step::Same(i, j) | Step::Similar(i, j) => {
+                    self.compare(&old[i], &new[j], old_path(i), new_path(j), false);
+                }
+                Step::Moved(i, j) => {
+                    self.compare(&old[i], &new[j], old_path(i), new_path(j), true);
+                }
+                Step::Deleted(i) => {
+                    self.push(
+                        ChangeKind::Deleted,
+                        &old[i],
+                        Some(old_path(i)),
+                        None,
+                        Vec::new(),
+                        Vec::new(),
+                    );
+                }
+                Step::Inserted(j) => {
+                    self.push(
+                        ChangeKind::Inserted,
+                        &new[j],
+                        None,
+                        Some(new_path(j)),
+                        Vec::new(),
+                        Vec::new(),
+                    );
+                }
+            }
+        }
+    }
+
+    fn compare(
+        &mut self,
+        old: &Element,
+        new: &Element,
+        old_path: Vec<usize>,
+        new_path: Vec<usize>,
+        moved: bool,
+    ) {
+        let words = match (old.text(), new.text()) {
+            (Some(old_text), Some(new_text)) if old_text != new_text => {
+                diff_words(&old_text.plain_text(), &new_text.plain_text())
+            }
+            _ => Vec::new(),
+        };
+        let attributes = diff_attributes(old, new);
+        if moved {
+            self.push(
+                ChangeKind::Moved,
+                new,
+                Some(old_path.clone()),
+                Some(new_path.clone()),
+                words,
+                attributes,
+            );
+        } else if !words.is_empty() || !attributes.is_empty() {
+            self.push(
+                ChangeKind::Modified,
+                new,
+                Some(old_path.clone()),
+                Some(new_path.clone()),
+                words,
+                attributes,
+            );
+        }
+
+        if let (
+            Element::DialogueBlock(old_block) | Element::DualDialogueBlock(old_block),
+            Element::DialogueBlock(new_block) | Element::DualDialogueBlock(new_block),
+        ) = (old, new)
+        {
+            self.diff_sequence(old_block, new_block, &old_path, &new_path);
+        }
+    }
+
+    fn push(
+        &mut self,
+        kind: ChangeKind,
+        element: &Element,
+        old_path: Option<Vec<usize>>,
+        new_path: Option<Vec<usize>>,
+        words: Vec<WordChange>,
+        attributes: Vec<AttributeChange>,
+    ) {
+        let scene = match (kind, &old_path, &new_path) {
+            (ChangeKind::Deleted, Some(path), _) => self.old_scenes[path[0]].clone(),
+            (_, _, Some(path)) => self.new_scenes[path[0]].clone(),
+            _ => None,
+        };
+        let text = |words: &[WordChange], keep: fn(&WordChange) -> Option<&String>| {
+            words
+                .iter()
+                .filter_map(keep)
+                .map(String::as_str)
+                .collect::<String>()
+        };
+        let (old_text, new_text) = match kind {
+            ChangeKind::Inserted => (None, Some(element_text(element))),
+            ChangeKind::Deleted => (Some(element_text(element)), None),
+            _ if words.is_empty() => (Some(element_text(element)), Some(element_text(element))),
+            _ => (
+                Some(text(&words, |word| match word {
+                    WordChange::Equal(text) | WordChange::Deleted(text) => Some(text),
+                    WordChange::Inserted(_) => None,
+                })),
+                Some(text(&words, |word| match word {
+                    WordChange::Equal(text) | WordChange::Inserted(text) => Some(text),
+                    WordChange::Deleted(_) => None,
+                })),
+            ),
+        };
+
+        self.changes.push(ElementChange {
+            kind,
+            element_type: element.name().to_string(),
+            old_path,
+            new_path,
+            old_text,
+            new_text,
+            scene,
+            words,
+            attributes,
+        });
+    }
+}
+
+/// The changes to the scene number, centering and page break of an element.
+fn diff_attributes(old: &Element, new: &Element) -> Vec<AttributeChange> {
+    let (Some(old), Some(new)) = (old.attributes(), new.attributes()) else {
+        return Vec::new();
+    };
+    let flag = |set: bool| set.then(|| "true".to_string());
+    [
+        (
+            "scene_number",
+            old.scene_number.clone(),
+            new.scene_number.clone(),
+        ),
+        ("centered", flag(old.centered), flag(new.centered)),
+        (
+            "starts_new_page",
+            flag(old.starts_new_page),
+            flag(new.starts_new_page),
+        ),
+    ]
+    .into_iter()
+    .filter(|(_, old, new)| old != new)
+    .map(|(name, old, new)| AttributeChange {
+        name: name.to_string(),
+        old,
+        new,
+    })
+    .collect()
+}
+
+/// The text of an element, with one line per element of a block.
+fn element_text(element: &Element) -> String {
+    match element {
+        Element::DialogueBlock(block) | Element::DualDialogueBlock(block) => block
+            .iter()
+            .map(element_text)
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => element
+            .text()
+            .map(ElementText::plain_text)
+            .unwrap_or_default(),
+    }
+}
+
+/// The type and text of an element, ignoring case and spacing.
+fn content_key(element: &Element) -> String {
+    let text = element_text(element).to_lowercase();
+    let words: Vec<&str> = text.split_whitespace().collect();
+    format!("{}:{}", element.name(), words.join(" "))
+}
+
+fn leaf_ids(element: &Element) -> Vec<&str> {
+    match element {
+        Element::DialogueBlock(block) | Element::DualDialogueBlock(block) => {
+            block.iter().flat_map(leaf_ids).collect()
+        }
+        _ => element.id().into_iter().collect(),
+    }
+}
+
+/// Whether two elements are the same paragraph: by id when both have one,
+/// otherwise by type and text. Blocks are the same when they share an id or
+/// their text.
+fn is_same(old: &Element, new: &Element, old_key: &str, new_key: &str) -> bool {
+    if old.name() != new.name() {
+        return false;
+    }
+    match (old, new) {
+        (
+            Element::DialogueBlock(_) | Element::DualDialogueBlock(_),
+            Element::DialogueBlock(_) | Element::DualDialogueBlock(_),
+        ) => {
+            let new_ids = leaf_ids(new);
+            old_key == new_key || leaf_ids(old).iter().any(|id| new_ids.contains(id))
+        }
+        _ => match (old.id(), new.id()) {
+            (Some(old_id), Some(new_id)) => old_id == new_id,
+            _ => old_key == new_key,
+        },
+    }
+}
+
+/// Whether two elements of the same type are close enough to be one edited
+/// paragraph.
+fn is_similar(old: &Element, new: &Element) -> bool {
+    if old.name() != new.name() {
+        return false;
+    }
+    if let (Element::DialogueBlock(_), Element::DialogueBlock(_)) = (old, new) {
+        let speaker = |element: &Element| {
+            element
+                .character_cues()
+                .first()
+                .map(|cue| cue.name.to_lowercase())
+        };
+        if speaker(old).is_some() && speaker(old) == speaker(new) {
+            return true;
+        }
+    }
+    word_similarity(&element_text(old), &element_text(new)) >= 0.5
+}
+
+/// The share of words two texts have in common, from 0 to 1.
+fn word_similarity(old: &str, new: &str) -> f32 {
+    let mut counts: HashMap<String, i32> = HashMap::new();
+    let mut total = 0;
+    for word in old.split_whitespace() {
+        *counts.entry(word.to_lowercase()).or_default() += 1;
+        total += 1;
+    }
+    let mut shared = 0;
+    for word in new.split_whitespace() {
+        total += 1;
+        if let Some(count) = counts.get_mut(&word.to_lowercase())
+            && *count > 0
+        {
+            *count -= 1;
+            shared += 1;
+        }
+    }
+    if total == 0 {
+        1.0
+    } else {
+        2.0 * shared as f32 / total as f32
+    }
+}
+
+pub(crate) fn align(old: &[Element], new: &[Element]) -> Vec<Step> {
+    let old_keys: Vec<String> = old.iter().map(content_key).collect();
+    let new_keys: Vec<String> = new.iter().map(content_key).collect();
+    let same = |i: usize, j: usize| is_same(&old[i], &new[j], &old_keys[i], &new_keys[j]);
+
+    let mut steps = Vec::new();
+    let mut gap_old = Vec::new();
+    let mut gap_new = Vec::new();
+    for (i, j) in longest_common_subsequence(old.len(), new.len(), same) {
+        match (i, j) {
+            (Some(i), Some(j)) => {
+                pair_similar(old, new, &mut gap_old, &mut gap_new, &mut steps);
+                steps.push(Step::Same(i, j));
+            }
+            (Some(i), None) => gap_old.push(i),
+            (None, Some(j)) => gap_new.push(j),
+            (None, None) => {}
+        }
+    }
+    pair_similar(old, new, &mut gap_old, &mut gap_new, &mut steps);
+
+    // An element deleted in one place and inserted in another was moved.
+    let mut moved_to: HashMap<usize, usize> = HashMap::new();
+    for step in &steps {
+        if let Step::Inserted(j) = *step
+            && let Some(i) = steps.iter().find_map(|candidate| match *candidate {
+                Step::Deleted(i) if !moved_to.contains_key(&i) && same(i, j) => Some(i),
+                _ => None,
+            })
+        {
+            moved_to.insert(i, j);
+        }
+    }
+    steps
+        .into_iter()
+        .filter_map(|step| match step {
+            Step::Deleted(i) if moved_to.contains_key(&i) => None,
+            Step::Inserted(j) => match moved_to.iter().find(|(_, to)| **to == j) {
+                Some((&i, _)) => Some(Step::Moved(i, j)),
+                None => Some(step),
+            },
+            _ => Some(step),
+        })
+        .collect()
+}
+
+/// Pairs up similar elements between two runs that sit between the same
+/// matched elements, keeping their order. Runs with the same types in the
+/// same order are paired one for one, as paragraphs edited in place.
+/// Unpaired elements are deleted or inserted.
+fn pair_similar(
+    old: &[Element],
+    new: &[Element],
+    gap_old: &mut Vec<usize>,
+    gap_new: &mut Vec<usize>,
+    steps: &mut Vec<Step>,
+) {
+    let edited_in_place = gap_old.len() == gap_new.len()
+        && gap_old
+            .iter()
+            .zip(gap_new.iter())
+            .all(|(&i, &j)| old[i].name() == new[j].name());
+    if edited_in_place {
+        steps.extend(
+            gap_old
+                .iter()
+                .zip(gap_new.iter())
+                .map(|(&i, &j)| Step::Similar(i, j)),
+        );
+        gap_old.clear();
+        gap_new.clear();
+        return;
+    }
+
+    let mut next_new = 0;
+    for &i in gap_old.iter() {
+        match (next_new..gap_new.len()).find(|&k| is_similar(&old[i], &new[gap_new[k]])) {
+            Some(k) => {
+                steps.extend(gap_new[next_new..k].iter().map(|&j| Step::Inserted(j)));
+                steps.push(Step::Similar(i, gap_new[k]));
+                next_new = k + 1;
+            }
+            None => steps.push(Step::Deleted(i)),
+        }
+    }
+    steps.extend(gap_new[next_new..].iter().map(|&j| Step::Inserted(j)));
+    gap_old.clear();
+    gap_new.clear();
+}
+
+/// Lines up two sequences of lengths `old_len` and `new_len`, returning
+/// index pairs in order: both set for a match, one set for an element only
+/// in that sequence.
+fn longest_common_subsequence(
+    old_len: usize,
+    new_len: usize,
+    same: impl Fn(usize, usize) -> bool,
+) -> Vec<(Option<usize>, Option<usize>)> {
+    let mut start = 0;
+    while start < old_len && start < new_len && same(start, start) {
+        start += 1;
+    }
+    let mut old_end = old_len;
+    let mut new_end = new_len;
+    while old_end > start && new_end > start && same(old_end - 1, new_end - 1) {
+        old_end -= 1;
+        new_end -= 1;
+    }
+
+    let rows = old_end - start;
+    let columns = new_end - start;
+    let mut lengths = vec![0u32; (rows + 1) * (columns + 1)];
+    let at = |i: usize, j: usize| i * (columns + 1) + j;
+    for i in (0..rows).rev() {
+        for j in (0..columns).rev() {
+            lengths[at(i, j)] = if same(start + i, start + j) {
+                lengths[at(i + 1, j + 1)] + 1
+            } else {
+                lengths[at(i + 1, j)].max(lengths[at(i, j + 1)])
+            };
+        }
+    }
+
+    let mut pairs: Vec<_> = (0..start).map(|index| (Some(index), Some(index))).collect();
+    let (mut i, mut j) = (0, 0);
+    while i < rows || j < columns {
+        if i < rows && j < columns && same(start + i, start + j) {
+            pairs.push((Some(start + i), Some(start + j)));
+            i += 1;
+            j += 1;
+        } else if j == columns || (i < rows && lengths[at(i + 1, j)] >= lengths[at(i, j + 1)]) {
+            pairs.push((Some(start + i), None));
+            i += 1;
+        } else {
+            pairs.push((None, Some(start + j)));
+            j += 1;
+        }
+    }
+    pairs.extend(
+        (old_end..old_len)
+            .zip(new_end..new_len)
+            .map(|(i, j)| (Some(i), Some(j))),
+    );
+    pairs
+}
+
+/// Splits text into words, each with the whitespace after it.
+fn words_of(text: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    let mut start = 0;
+    let mut in_space = false;
+    for (index, character) in text.char_indices() {
+        if character.is_whitespace() {
+            in_space = true;
+        } else if in_space {
+            words.push(&text[start..index]);
+            start = index;
+            in_space = false;
+        }
+    }
+    if start < text.len() {
+        words.push(&text[start..]);
+    }
+    words
+}
+
+/// The words `old` and `new` have in common, as `diff_words` matches them:
+/// the character offset of each in `old` and in `new`, and its length without
+/// the whitespace after it.
+pub(crate) fn matched_words(old: &str, new: &str) -> Vec<(usize, usize, usize)> {
+    let old_words = words_of(old);
+    let new_words = words_of(new);
+    let starts = |words: &[&str]| {
+        let mut offset = 0;
+        words
+            .iter()
+            .map(|word| {
+                let start = offset;
+                offset += word.chars().count();
+                start
+            })
+            .collect::<Vec<_>>()
+    };
+    let (old_starts, new_starts) = (starts(&old_words), starts(&new_words));
+    let same = |i: usize, j: usize| old_words[i].trim_end() == new_words[j].trim_end();
+
+    longest_common_subsequence(old_words.len(), new_words.len(), same)
+        .into_iter()
+        .filter_map(|pair| match pair {
+            (Some(i), Some(j)) => Some((
+                old_starts[i],
+                new_starts[j],
+                new_words[j].trim_end().chars().count(),
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+pub(crate) fn diff_words(old: &str, new: &str) -> Vec<WordChange> {
+    let old_words = words_of(old);
+    let new_words = words_of(new);
+    let same = |i: usize, j: usize| old_words[i].trim_end() == new_words[j].trim_end();
+
+    let mut changes: Vec<WordChange> = Vec::new();
+    for pair in longest_common_subsequence(old_words.len(), new_words.len(), same) {
+        let change = match pair {
+            (Some(_), Some(j)) => WordChange::Equal(new_words[j].to_string()),
+            (Some(i), None) => WordChange::Deleted(old_words[i].to_string()),
+            (None, Some(j)) => WordChange::Inserted(new_words[j].to_string()),
+            (None, None) => continue,
+        };
+        match (changes.last_mut(), change) {
+            (Some(WordChange::Equal(last)), WordChange::Equal(text))
+            | (Some(WordChange::Inserted(last)), WordChange::Inserted(text))
+            | (Some(WordChange::Deleted(last)), WordChange::Deleted(text)) => last.push_str(&text),
+            (_, change) => changes.push(change),
+        }
+    }
+    changes
+}
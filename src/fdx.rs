@@ -9,14 +9,27 @@ use crate::pagination::{
     ScreenplayElementStyle, ScreenplayLayoutProfile,
 };
 use crate::{
-    blank_attributes, Element, ElementText, ImportedAlignment, ImportedDialogueContinueds,
-    ImportedElementKind, ImportedElementStyle, ImportedLayoutOverrides, ImportedMoresAndContinueds,
-    ImportedPageLayoutOverrides, ImportedSceneContinueds, ImportedTitlePage,
+    blank_attributes, BreakdownCategory, BreakdownTag, Element, ElementText, ImportedAlignment,
+    ImportedDialogueContinueds, ImportedElementKind, ImportedElementStyle, ImportedLayoutOverrides,
+    ImportedMoresAndContinueds, ImportedPageLayoutOverrides, ImportedSceneContinueds,
+    ImportedTitlePage,
     ImportedTitlePageAlignment, ImportedTitlePagePage, ImportedTitlePageParagraph,
     ImportedTitlePageHeaderFooter, ImportedTitlePageTabStop, ImportedTitlePageTabStopKind,
-    Metadata, Screenplay, TextRun,
+    Metadata, Note, RevisionSet, Screenplay, TextRun,
 };
 
+/// The paragraph attribute holding an element's id, which Final Draft itself
+/// does not use.
+pub(crate) const ELEMENT_ID_ATTR: &[u8] = b"JumpCutID";
+
+/// The revision set attribute holding its date, which Final Draft itself
+/// does not use.
+pub(crate) const REVISION_DATE_ATTR: &[u8] = b"JumpCutDate";
+
+/// The script note attribute marking boneyard that sits inside its
+/// paragraph rather than ahead of it.
+pub(crate) const INLINE_BONEYARD_ATTR: &[u8] = b"JumpCutInline";
+
 #[derive(Debug)]
 pub struct FdxParseError(String);
 
@@ -34,7 +47,8 @@ pub fn parse_fdx(xml: &str) -> Result<Screenplay, FdxParseError> {
     let (title_page_metadata, imported_title_page) = extract_title_page_data(xml)?;
     metadata.extend(title_page_metadata);
 
-    let blocks = parse_blocks(xml)?;
+    let tag_data = parse_tag_data(xml)?;
+    let blocks = parse_blocks(xml, &tag_data)?;
 
     let mut used_paragraph_types = HashSet::new();
     for block in &blocks {
@@ -49,6 +63,8 @@ pub fn parse_fdx(xml: &str) -> Result<Screenplay, FdxParseError> {
         metadata: metadata.clone(),
         imported_layout: imported_layout.clone(),
         imported_title_page: None,
+        revisions: Vec::new(),
+        page_lock: None,
         elements: Vec::new(),
     });
     let elements = group_dialogue_blocks(
@@ -62,6 +78,8 @@ pub fn parse_fdx(xml: &str) -> Result<Screenplay, FdxParseError> {
         metadata,
         imported_layout,
         imported_title_page,
+        revisions: imported_settings.revisions,
+        page_lock: None,
         elements,
     };
     screenplay.apply_structural_act_break_policy();
@@ -76,19 +94,72 @@ struct FdxParagraph {
     space_before_points: Option<f32>,
     starts_new_page: bool,
     number: Option<String>,
+    id: Option<String>,
+    revision: Option<u32>,
     text: ElementText,
+    notes: Vec<Note>,
+    tags: Vec<BreakdownTag>,
+    boneyard: Vec<Note>,
+}
+
+/// The attributes of a `<Paragraph>` tag, read when the paragraph starts and
+/// kept until its text has been read.
+#[derive(Debug, Default)]
+struct FdxParagraphAttributes {
+    paragraph_type: String,
+    alignment: Option<String>,
+    right_indent: Option<f32>,
+    space_before_points: Option<f32>,
+    starts_new_page: bool,
+    number: Option<String>,
+    id: Option<String>,
+}
+
+impl FdxParagraphAttributes {
+    fn read(reader: &Reader<&[u8]>, event: &BytesStart<'_>) -> Result<Self, FdxParseError> {
+        Ok(FdxParagraphAttributes {
+            paragraph_type: required_attr(reader, event, b"Type")?.unwrap_or_default(),
+            alignment: optional_attr(reader, event, b"Alignment")?,
+            right_indent: parse_attr_f32(reader, event, b"RightIndent")?,
+            space_before_points: parse_attr_f32(reader, event, b"SpaceBefore")?,
+            starts_new_page: optional_attr(reader, event, b"StartsNewPage")?.as_deref()
+                == Some("Yes"),
+            number: optional_attr(reader, event, b"Number")?,
+            id: optional_attr(reader, event, ELEMENT_ID_ATTR)?,
+        })
+    }
+
+    /// An empty paragraph with these attributes.
+    fn into_paragraph(self) -> FdxParagraph {
+        FdxParagraph {
+            paragraph_type: self.paragraph_type,
+            alignment: self.alignment,
+            right_indent: self.right_indent,
+            space_before_points: self.space_before_points,
+            starts_new_page: self.starts_new_page,
+            number: self.number,
+            id: self.id,
+            revision: None,
+            text: ElementText::Plain(String::new()),
+            notes: Vec::new(),
+            tags: Vec::new(),
+            boneyard: Vec::new(),
+        }
+    }
 }
 
 #[derive(Debug)]
 enum FdxBlock {
-    Paragraph(FdxParagraph),
+    Paragraph(Box<FdxParagraph>),
     DualDialogue(Vec<FdxParagraph>),
+    Boneyard(String),
 }
 
 #[derive(Debug)]
 struct TextChunk {
     content: String,
     styles: HashSet<String>,
+    revision: Option<u32>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -101,6 +172,7 @@ struct ImportedFdxSettings {
     footer_margin: Option<f32>,
     mores_and_continueds: ImportedMoresAndContinueds,
     paragraph_styles: BTreeMap<String, ImportedParagraphStyle>,
+    revisions: Vec<RevisionSet>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -129,7 +201,10 @@ struct FdxTitlePageParagraph {
     adornment_styles: HashSet<String>,
 }
 
-fn parse_blocks(xml: &str) -> Result<Vec<FdxBlock>, FdxParseError> {
+fn parse_blocks(
+    xml: &str,
+    tag_data: &BTreeMap<String, Vec<BreakdownTag>>,
+) -> Result<Vec<FdxBlock>, FdxParseError> {
     let mut reader = Reader::from_str(xml);
     reader.config_mut().trim_text(false);
 
@@ -139,19 +214,91 @@ fn parse_blocks(xml: &str) -> Result<Vec<FdxBlock>, FdxParseError> {
     let mut in_text = false;
     let mut in_dual_dialogue = false;
 
-    let mut paragraph_type = None;
-    let mut paragraph_alignment = None;
-    let mut paragraph_right_indent = None;
-    let mut paragraph_space_before_points = None;
-    let mut paragraph_starts_new_page = false;
-    let mut paragraph_number = None;
+    let mut paragraph_attributes = FdxParagraphAttributes::default();
     let mut text_chunks: Vec<TextChunk> = Vec::new();
     let mut text_styles: HashSet<String> = HashSet::new();
+    let mut text_revision = None;
     let mut blocks = Vec::new();
     let mut dual_dialogue_paragraphs = Vec::new();
+    let mut in_script_note = false;
+    let mut script_note_offset = 0;
+    let mut script_note_inline = false;
+    let mut script_note_lines: Vec<String> = Vec::new();
+    let mut paragraph_notes = Vec::new();
+    let mut paragraph_tags = Vec::new();
+    let mut text_tags = Vec::new();
+    let mut paragraph_boneyard = Vec::new();
 
     loop {
         match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(event)) if in_script_note => match event.name().as_ref() {
+                b"Paragraph" => script_note_lines.push(String::new()),
+                b"Text" => in_text = true,
+                _ => {}
+            },
+            Ok(Event::Empty(event)) if in_script_note && event.name().as_ref() == b"Paragraph" => {
+                script_note_lines.push(String::new());
+            }
+            Ok(Event::Empty(_)) if in_script_note => {}
+            Ok(Event::Text(event)) if in_script_note && in_text => {
+                let decoded = event
+                    .decode()
+                    .map_err(|err| FdxParseError(err.to_string()))?;
+                if let Some(line) = script_note_lines.last_mut() {
+                    line.push_str(
+                        &unescape(&decoded).map_err(|err| FdxParseError(err.to_string()))?,
+                    );
+                }
+            }
+            Ok(Event::GeneralRef(event)) if in_script_note && in_text => {
+                let decoded = event
+                    .decode()
+                    .map_err(|err| FdxParseError(err.to_string()))?;
+                let entity = format!("&{decoded};");
+                if let Some(line) = script_note_lines.last_mut() {
+                    line.push_str(
+                        &unescape(&entity).map_err(|err| FdxParseError(err.to_string()))?,
+                    );
+                }
+            }
+            Ok(Event::End(event)) if in_script_note => match event.name().as_ref() {
+                b"Text" => in_text = false,
+                b"ScriptNote" => {
+                    in_script_note = false;
+                    // Boneyard exported by jumpcut comes back as boneyard; other
+                    // script notes aren't part of the screenplay text.
+                    let note = std::mem::take(&mut script_note_lines).join("\n");
+                    if let Some(inner) = note
+                        .strip_prefix("/*")
+                        .and_then(|note| note.strip_suffix("*/"))
+                    {
+                        if script_note_inline && paragraph_depth > 0 {
+                            paragraph_boneyard.push(Note {
+                                text: inner.to_string(),
+                                offset: script_note_offset,
+                            });
+                        } else {
+                            blocks.push(FdxBlock::Boneyard(inner.to_string()));
+                        }
+                    } else if paragraph_depth > 0 {
+                        paragraph_notes.push(Note {
+                            text: note,
+                            offset: script_note_offset,
+                        });
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::Start(event)) if event.name().as_ref() == b"ScriptNote" => {
+                in_script_note = true;
+                script_note_lines.clear();
+                // Range is `start,length` in characters of the paragraph text.
+                script_note_offset = optional_attr(&reader, &event, b"Range")?
+                    .and_then(|range| range.split(',').next()?.trim().parse().ok())
+                    .unwrap_or(0);
+                script_note_inline =
+                    optional_attr(&reader, &event, INLINE_BONEYARD_ATTR)?.as_deref() == Some("Yes");
+            }
             Ok(Event::Start(event)) => match event.name().as_ref() {
                 b"Content" if paragraph_depth == 0 => {
                     in_content = true;
@@ -159,17 +306,11 @@ fn parse_blocks(xml: &str) -> Result<Vec<FdxBlock>, FdxParseError> {
                 b"Paragraph" if in_content => {
                     paragraph_depth += 1;
                     if is_active_paragraph(paragraph_depth, in_dual_dialogue) {
-                        begin_paragraph(
-                            &reader,
-                            &event,
-                            &mut paragraph_type,
-                            &mut paragraph_alignment,
-                            &mut paragraph_right_indent,
-                            &mut paragraph_space_before_points,
-                            &mut paragraph_starts_new_page,
-                            &mut paragraph_number,
-                            &mut text_chunks,
-                        )?;
+                        paragraph_attributes = FdxParagraphAttributes::read(&reader, &event)?;
+                        text_chunks.clear();
+                        paragraph_notes.clear();
+                        paragraph_tags.clear();
+                        paragraph_boneyard.clear();
                     }
                 }
                 b"DualDialogue" if in_content => {
@@ -179,34 +320,31 @@ fn parse_blocks(xml: &str) -> Result<Vec<FdxBlock>, FdxParseError> {
                 b"Text" if paragraph_depth > 0 => {
                     in_text = true;
                     text_styles = parse_style_names(optional_attr(&reader, &event, b"Style")?);
+                    text_revision = parse_revision_id(&reader, &event, b"RevisionID")?;
+                    text_tags = text_run_tags(&reader, &event, tag_data)?;
                 }
                 _ => {}
             },
             Ok(Event::Empty(event)) => match event.name().as_ref() {
                 b"Paragraph" if in_content => {
-                    let paragraph = FdxParagraph {
-                        paragraph_type: required_attr(&reader, &event, b"Type")?
-                            .unwrap_or_default(),
-                        alignment: optional_attr(&reader, &event, b"Alignment")?,
-                        right_indent: parse_attr_f32(&reader, &event, b"RightIndent")?,
-                        space_before_points: parse_attr_f32(&reader, &event, b"SpaceBefore")?,
-                        starts_new_page: optional_attr(&reader, &event, b"StartsNewPage")?
-                            .as_deref()
-                            == Some("Yes"),
-                        number: optional_attr(&reader, &event, b"Number")?,
-                        text: ElementText::Plain(String::new()),
-                    };
+                    let paragraph = FdxParagraphAttributes::read(&reader, &event)?.into_paragraph();
                     if in_dual_dialogue {
                         dual_dialogue_paragraphs.push(paragraph);
                     } else {
-                        blocks.push(FdxBlock::Paragraph(paragraph));
+                        blocks.push(FdxBlock::Paragraph(Box::new(paragraph)));
                     }
                 }
                 b"Text" if paragraph_depth > 0 => {
                     text_chunks.push(TextChunk {
                         content: String::new(),
                         styles: parse_style_names(optional_attr(&reader, &event, b"Style")?),
+                        revision: parse_revision_id(&reader, &event, b"RevisionID")?,
                     });
+                    add_text_tags(
+                        &mut paragraph_tags,
+                        text_run_tags(&reader, &event, tag_data)?,
+                        &text_chunks,
+                    );
                 }
                 _ => {}
             },
@@ -219,6 +357,7 @@ fn parse_blocks(xml: &str) -> Result<Vec<FdxBlock>, FdxParseError> {
                         .map_err(|err| FdxParseError(err.to_string()))?
                         .into_owned(),
                     styles: text_styles.clone(),
+                    revision: text_revision,
                 });
             }
             Ok(Event::GeneralRef(event)) if in_text => {
@@ -231,29 +370,33 @@ fn parse_blocks(xml: &str) -> Result<Vec<FdxBlock>, FdxParseError> {
                         .map_err(|err| FdxParseError(err.to_string()))?
                         .into_owned(),
                     styles: text_styles.clone(),
+                    revision: text_revision,
                 });
             }
             Ok(Event::End(event)) => match event.name().as_ref() {
                 b"Text" => {
                     in_text = false;
+                    add_text_tags(
+                        &mut paragraph_tags,
+                        std::mem::take(&mut text_tags),
+                        &text_chunks,
+                    );
                 }
                 b"Paragraph" if paragraph_depth > 0 => {
                     if is_active_paragraph(paragraph_depth, in_dual_dialogue) {
                         let paragraph = FdxParagraph {
-                            paragraph_type: paragraph_type.take().unwrap_or_default(),
-                            alignment: paragraph_alignment.take(),
-                            right_indent: paragraph_right_indent.take(),
-                            space_before_points: paragraph_space_before_points.take(),
-                            starts_new_page: paragraph_starts_new_page,
-                            number: paragraph_number.take(),
+                            revision: paragraph_revision(&mut text_chunks),
                             text: collapse_text_chunks(std::mem::take(&mut text_chunks)),
+                            notes: std::mem::take(&mut paragraph_notes),
+                            tags: std::mem::take(&mut paragraph_tags),
+                            boneyard: std::mem::take(&mut paragraph_boneyard),
+                            ..std::mem::take(&mut paragraph_attributes).into_paragraph()
                         };
                         if in_dual_dialogue {
                             dual_dialogue_paragraphs.push(paragraph);
                         } else {
-                            blocks.push(FdxBlock::Paragraph(paragraph));
+                            blocks.push(FdxBlock::Paragraph(Box::new(paragraph)));
                         }
-                        paragraph_starts_new_page = false;
                     }
                     paragraph_depth -= 1;
                 }
@@ -279,26 +422,120 @@ fn parse_blocks(xml: &str) -> Result<Vec<FdxBlock>, FdxParseError> {
     Ok(blocks)
 }
 
-fn begin_paragraph(
+/// The breakdown tags of a text run's `TagNumber`.
+fn text_run_tags(
     reader: &Reader<&[u8]>,
     event: &BytesStart<'_>,
-    paragraph_type: &mut Option<String>,
-    paragraph_alignment: &mut Option<String>,
-    paragraph_right_indent: &mut Option<f32>,
-    paragraph_space_before_points: &mut Option<f32>,
-    paragraph_starts_new_page: &mut bool,
-    paragraph_number: &mut Option<String>,
-    text_chunks: &mut Vec<TextChunk>,
-) -> Result<(), FdxParseError> {
-    *paragraph_type = required_attr(reader, event, b"Type")?;
-    *paragraph_alignment = optional_attr(reader, event, b"Alignment")?;
-    *paragraph_right_indent = parse_attr_f32(reader, event, b"RightIndent")?;
-    *paragraph_space_before_points = parse_attr_f32(reader, event, b"SpaceBefore")?;
-    *paragraph_starts_new_page =
-        optional_attr(reader, event, b"StartsNewPage")?.as_deref() == Some("Yes");
-    *paragraph_number = optional_attr(reader, event, b"Number")?;
-    text_chunks.clear();
-    Ok(())
+    tag_data: &BTreeMap<String, Vec<BreakdownTag>>,
+) -> Result<Vec<BreakdownTag>, FdxParseError> {
+    let Some(number) = optional_attr(reader, event, b"TagNumber")? else {
+        return Ok(Vec::new());
+    };
+    Ok(tag_data.get(number.trim()).cloned().unwrap_or_default())
+}
+
+/// Adds the breakdown tags of the text run that ends `text_chunks` to its
+/// paragraph's, placing each after the run. A tag already in the paragraph
+/// moves after the run, so that a tag spanning several runs ends up after
+/// the last.
+fn add_text_tags(
+    paragraph_tags: &mut Vec<BreakdownTag>,
+    tags: Vec<BreakdownTag>,
+    text_chunks: &[TextChunk],
+) {
+    let offset = text_chunks
+        .iter()
+        .map(|chunk| chunk.content.chars().count())
+        .sum();
+    for tag in tags {
+        paragraph_tags
+            .retain(|existing| existing.category != tag.category || existing.name != tag.name);
+        paragraph_tags.push(tag.at(offset));
+    }
+}
+
+/// Reads Final Draft's `TagData`: tag categories, the tag definitions in
+/// them, and the numbered tags text runs refer to with `TagNumber`, each of
+/// which names one or more definitions. Returns the breakdown tags of each
+/// tag number.
+fn parse_tag_data(xml: &str) -> Result<BTreeMap<String, Vec<BreakdownTag>>, FdxParseError> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    let mut buf = Vec::new();
+    let mut in_tag_data = false;
+    let mut in_def_id = false;
+    let mut categories = BTreeMap::new();
+    let mut definitions = BTreeMap::new();
+    let mut tags: Vec<(String, Vec<String>)> = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(event)) | Ok(Event::Empty(event)) => match event.name().as_ref() {
+                b"TagData" => in_tag_data = true,
+                b"TagCategory" if in_tag_data => {
+                    if let (Some(id), Some(name)) = (
+                        optional_attr(&reader, &event, b"Id")?,
+                        optional_attr(&reader, &event, b"Name")?,
+                    ) {
+                        categories.insert(id, BreakdownCategory::from_final_draft_name(&name));
+                    }
+                }
+                b"TagDefinition" if in_tag_data => {
+                    if let (Some(id), Some(category_id), Some(label)) = (
+                        optional_attr(&reader, &event, b"Id")?,
+                        optional_attr(&reader, &event, b"CatId")?,
+                        optional_attr(&reader, &event, b"Label")?,
+                    ) {
+                        definitions.insert(id, (category_id, label));
+                    }
+                }
+                b"Tag" if in_tag_data => {
+                    let number = optional_attr(&reader, &event, b"Number")?.unwrap_or_default();
+                    tags.push((number.trim().to_string(), Vec::new()));
+                }
+                b"DefId" if in_tag_data => in_def_id = true,
+                _ => {}
+            },
+            Ok(Event::Text(event)) if in_def_id => {
+                let decoded = event
+                    .decode()
+                    .map_err(|err| FdxParseError(err.to_string()))?;
+                if let Some((_, definition_ids)) = tags.last_mut() {
+                    definition_ids.push(decoded.trim().to_string());
+                }
+            }
+            Ok(Event::End(event)) => match event.name().as_ref() {
+                b"TagData" => in_tag_data = false,
+                b"DefId" => in_def_id = false,
+                _ => {}
+            },
+            Ok(Event::Eof) => break,
+            Err(err) => return Err(FdxParseError(err.to_string())),
+            _ => {}
+        }
+
+        buf.clear();
+    }
+
+    Ok(tags
+        .into_iter()
+        .map(|(number, definition_ids)| {
+            let tags = definition_ids
+                .iter()
+                .filter_map(|id| definitions.get(id))
+                .map(|(category_id, label)| {
+                    BreakdownTag::new(
+                        categories
+                            .get(category_id)
+                            .copied()
+                            .unwrap_or(BreakdownCategory::Other),
+                        label.trim(),
+                    )
+                })
+                .collect();
+            (number, tags)
+        })
+        .collect())
 }
 
 fn is_active_paragraph(paragraph_depth: usize, in_dual_dialogue: bool) -> bool {
@@ -307,7 +544,8 @@ fn is_active_paragraph(paragraph_depth: usize, in_dual_dialogue: bool) -> bool {
 
 fn block_to_element(block: FdxBlock, baseline_profile: &ScreenplayLayoutProfile) -> Option<Element> {
     match block {
-        FdxBlock::Paragraph(paragraph) => paragraph_to_element(paragraph, baseline_profile),
+        FdxBlock::Paragraph(paragraph) => paragraph_to_element(*paragraph, baseline_profile),
+        FdxBlock::Boneyard(text) => Some(Element::Boneyard(ElementText::Plain(text))),
         FdxBlock::DualDialogue(paragraphs) => {
             let dialogue_blocks = group_dialogue_blocks(
                 paragraphs
@@ -518,6 +756,7 @@ fn parse_title_page_paragraphs(xml: &str) -> Result<Vec<FdxTitlePageParagraph>,
                     text_chunks.push(TextChunk {
                         content: String::new(),
                         styles,
+                        revision: None,
                     });
                 }
                 b"Tabstop" if in_title_content && paragraph_depth > 0 => {
@@ -536,6 +775,7 @@ fn parse_title_page_paragraphs(xml: &str) -> Result<Vec<FdxTitlePageParagraph>,
                         .map_err(|err| FdxParseError(err.to_string()))?
                         .into_owned(),
                     styles: text_styles.clone(),
+                    revision: None,
                 });
             }
             Ok(Event::GeneralRef(event)) if in_text => {
@@ -548,6 +788,7 @@ fn parse_title_page_paragraphs(xml: &str) -> Result<Vec<FdxTitlePageParagraph>,
                         .map_err(|err| FdxParseError(err.to_string()))?
                         .into_owned(),
                     styles: text_styles.clone(),
+                    revision: None,
                 });
             }
             Ok(Event::End(event)) => match event.name().as_ref() {
@@ -1014,12 +1255,14 @@ fn split_title_page_bottom_columns(
                     left_runs.push(TextRun {
                         content: left,
                         text_style: run.text_style.clone(),
+                        revision: run.revision,
                     });
                 }
                 if !right.is_empty() {
                     right_runs.push(TextRun {
                         content: right,
                         text_style: run.text_style.clone(),
+                        revision: run.revision,
                     });
                 }
             }
@@ -1102,6 +1345,11 @@ fn extract_import_settings(xml: &str) -> Result<ImportedFdxSettings, FdxParseErr
                         &mut settings.mores_and_continueds.scene,
                     )?;
                 }
+                b"Revision" => {
+                    if let Some(revision) = parse_revision_set(&reader, &event)? {
+                        settings.revisions.push(revision);
+                    }
+                }
                 _ => {}
             },
             Ok(Event::Empty(event)) => match event.name().as_ref() {
@@ -1141,6 +1389,11 @@ fn extract_import_settings(xml: &str) -> Result<ImportedFdxSettings, FdxParseErr
                     &event,
                     &mut settings.mores_and_continueds.scene,
                 )?,
+                b"Revision" => {
+                    if let Some(revision) = parse_revision_set(&reader, &event)? {
+                        settings.revisions.push(revision);
+                    }
+                }
                 _ => {}
             },
             Ok(Event::End(event)) => {
@@ -1159,6 +1412,49 @@ fn extract_import_settings(xml: &str) -> Result<ImportedFdxSettings, FdxParseErr
     Ok(settings)
 }
 
+/// Reads a revision set, skipping Final Draft's set 0, which holds the
+/// original, unrevised text.
+fn parse_revision_set(
+    reader: &Reader<&[u8]>,
+    event: &BytesStart<'_>,
+) -> Result<Option<RevisionSet>, FdxParseError> {
+    let Some(id) = parse_revision_id(reader, event, b"ID")? else {
+        return Ok(None);
+    };
+    let name = optional_attr(reader, event, b"Name")?.unwrap_or_default();
+    let color = optional_attr(reader, event, b"Color")?
+        .and_then(|color| parse_fdx_color(&color))
+        .map_or_else(|| "White".to_string(), RevisionSet::color_name);
+    let mut revision = RevisionSet::new(id, name, color);
+    revision.date = optional_attr(reader, event, REVISION_DATE_ATTR)?;
+    Ok(Some(revision))
+}
+
+/// Reads a revision id attribute, where 0 means unrevised.
+fn parse_revision_id(
+    reader: &Reader<&[u8]>,
+    event: &BytesStart<'_>,
+    name: &[u8],
+) -> Result<Option<u32>, FdxParseError> {
+    Ok(optional_attr(reader, event, name)?
+        .and_then(|value| value.trim().parse::<u32>().ok())
+        .filter(|id| *id > 0))
+}
+
+/// Parses a Final Draft color, written as `#RRRRGGGGBBBB` with 16 bits per
+/// channel, or as `#RRGGBB`.
+fn parse_fdx_color(color: &str) -> Option<[u8; 3]> {
+    let hex = color.strip_prefix('#')?;
+    let width = match hex.len() {
+        12 => 4,
+        6 => 2,
+        _ => return None,
+    };
+    let channel =
+        |index: usize| u8::from_str_radix(hex.get(index * width..index * width + 2)?, 16).ok();
+    Some([channel(0)?, channel(1)?, channel(2)?])
+}
+
 fn apply_page_layout_attrs(
     reader: &Reader<&[u8]>,
     event: &BytesStart<'_>,
@@ -1505,14 +1801,17 @@ fn collapse_text_chunks(chunks: Vec<TextChunk>) -> ElementText {
         return ElementText::Plain(String::new());
     }
 
-    if chunks.iter().all(|chunk| chunk.styles.is_empty()) {
+    if chunks
+        .iter()
+        .all(|chunk| chunk.styles.is_empty() && chunk.revision.is_none())
+    {
         return ElementText::Plain(chunks.into_iter().map(|chunk| chunk.content).collect());
     }
 
     let mut runs: Vec<TextRun> = Vec::new();
     for chunk in chunks {
         if let Some(last) = runs.last_mut() {
-            if last.text_style == chunk.styles {
+            if last.text_style == chunk.styles && last.revision == chunk.revision {
                 last.content.push_str(&chunk.content);
                 continue;
             }
@@ -1521,12 +1820,26 @@ fn collapse_text_chunks(chunks: Vec<TextChunk>) -> ElementText {
         runs.push(TextRun {
             content: chunk.content,
             text_style: chunk.styles,
+            revision: chunk.revision,
         });
     }
 
     ElementText::Styled(runs)
 }
 
+/// The revision of a paragraph whose text all carries the same one, which is
+/// then taken off its chunks.
+fn paragraph_revision(chunks: &mut [TextChunk]) -> Option<u32> {
+    let revision = chunks.first()?.revision?;
+    if chunks.iter().any(|chunk| chunk.revision != Some(revision)) {
+        return None;
+    }
+    for chunk in chunks {
+        chunk.revision = None;
+    }
+    Some(revision)
+}
+
 fn paragraph_to_element(
     paragraph: FdxParagraph,
     baseline_profile: &ScreenplayLayoutProfile,
@@ -1547,7 +1860,31 @@ fn paragraph_to_element(
     }
     attributes.starts_new_page = paragraph.starts_new_page;
     attributes.scene_number = paragraph.number;
+    attributes.id = paragraph.id;
+    attributes.revision = paragraph.revision;
+    attributes.tags = paragraph.tags;
     let text_plain = paragraph.text.plain_text();
+    let text_len = text_plain.chars().count();
+    attributes.boneyard = paragraph
+        .boneyard
+        .into_iter()
+        .map(|boneyard| Note {
+            offset: boneyard.offset.min(text_len),
+            ..boneyard
+        })
+        .collect();
+    if !paragraph.notes.is_empty() {
+        attributes.notes = Some(
+            paragraph
+                .notes
+                .into_iter()
+                .map(|note| Note {
+                    offset: note.offset.min(text_len),
+                    ..note
+                })
+                .collect(),
+        );
+    }
     let resolved_type =
         resolved_body_paragraph_type(&paragraph.paragraph_type, attributes.centered, &text_plain)?;
     if let Some(base_style) =
@@ -1649,14 +1986,23 @@ fn group_dialogue_blocks(elements: Vec<Element>) -> Vec<Element> {
             let mut block = vec![elements[index].clone()];
             index += 1;
 
-            while index < elements.len()
-                && matches!(
-                    elements[index],
-                    Element::Parenthetical(_, _) | Element::Dialogue(_, _) | Element::Lyric(_, _)
-                )
-            {
-                block.push(elements[index].clone());
-                index += 1;
+            loop {
+                // Boneyard noted on a line of dialogue goes ahead of the block.
+                let boneyard_run = elements[index..]
+                    .iter()
+                    .take_while(|element| matches!(element, Element::Boneyard(_)))
+                    .count();
+                match elements.get(index + boneyard_run) {
+                    Some(
+                        Element::Parenthetical(_, _) | Element::Dialogue(_, _) | Element::Lyric(_, _),
+                    ) => {
+                        grouped.extend_from_slice(&elements[index..index + boneyard_run]);
+                        index += boneyard_run;
+                        block.push(elements[index].clone());
+                        index += 1;
+                    }
+                    _ => break,
+                }
             }
 
             if block.len() > 1 {
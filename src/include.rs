@@ -0,0 +1,372 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Component, Path, PathBuf};
+
+use crate::Screenplay;
+use crate::parser::parse_with_source_map;
+use crate::source_map::{ElementSpan, SourceMap, SourceSpan};
+
+/// A file handed back by an [`IncludeResolver`]. `name` identifies the file
+/// for cycle detection, for resolving the file's own includes, and in source
+/// spans.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IncludedFile {
+    pub name: String,
+    pub text: String,
+}
+
+/// Finds the files named by `{{include: path}}` lines.
+pub trait IncludeResolver {
+    /// Loads `path` as included from the file named `from`.
+    fn resolve(&self, from: &str, path: &str) -> Result<IncludedFile, IncludeError>;
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IncludeError {
+    /// The resolver could not load `path`, included from `from`.
+    NotFound {
+        from: String,
+        path: String,
+        reason: String,
+    },
+    /// A file includes itself, directly or through other files. The chain
+    /// starts and ends with the same file.
+    Cycle(Vec<String>),
+}
+
+impl fmt::Display for IncludeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IncludeError::NotFound { from, path, reason } => {
+                write!(f, "Could not include '{path}' from '{from}': {reason}")
+            }
+            IncludeError::Cycle(chain) => {
+                write!(f, "Include cycle: {}", chain.join(" -> "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for IncludeError {}
+
+/// Reads included files from disk, relative to the including file. Relative
+/// file names are taken from `base_dir`, which defaults to the working
+/// directory.
+#[derive(Clone, Debug, Default)]
+pub struct FileSystemResolver {
+    pub base_dir: PathBuf,
+}
+
+impl FileSystemResolver {
+    /// A resolver for files named relative to `base_dir`, such as text read
+    /// from stdin that has no file of its own to be relative to.
+    pub fn new(base_dir: impl Into<PathBuf>) -> Self {
+        FileSystemResolver {
+            base_dir: base_dir.into(),
+        }
+    }
+}
+
+impl IncludeResolver for FileSystemResolver {
+    fn resolve(&self, from: &str, path: &str) -> Result<IncludedFile, IncludeError> {
+        let directory = Path::new(from).parent().unwrap_or(Path::new(""));
+        let joined = self.base_dir.join(directory).join(path);
+        let name = normalize_path(&joined).to_string_lossy().into_owned();
+        std::fs::read_to_string(&name)
+            .map(|text| IncludedFile {
+                name: name.clone(),
+                text,
+            })
+            .map_err(|error| IncludeError::NotFound {
+                from: from.to_string(),
+                path: path.to_string(),
+                reason: error.to_string(),
+            })
+    }
+}
+
+/// Serves included files from memory, keyed by `/`-separated paths, for
+/// callers without a file system such as wasm.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryResolver {
+    pub files: HashMap<String, String>,
+}
+
+impl MemoryResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, name: impl Into<String>, text: impl Into<String>) {
+        self.files.insert(name.into(), text.into());
+    }
+}
+
+impl IncludeResolver for MemoryResolver {
+    fn resolve(&self, from: &str, path: &str) -> Result<IncludedFile, IncludeError> {
+        let name = join_include_path(from, path);
+        match self.files.get(&name) {
+            Some(text) => Ok(IncludedFile {
+                name,
+                text: text.clone(),
+            }),
+            None => Err(IncludeError::NotFound {
+                from: from.to_string(),
+                path: path.to_string(),
+                reason: format!("no file named '{name}'"),
+            }),
+        }
+    }
+}
+
+/// Parses `text`, the contents of the file named `name`, after replacing every
+/// `{{include: path}}` line outside boneyard with the file it names. Each
+/// element span records the file the element came from and is relative to
+/// that file.
+pub fn parse_with_includes(
+    text: &str,
+    name: &str,
+    resolver: &dyn IncludeResolver,
+) -> Result<(Screenplay, SourceMap), IncludeError> {
+    let name = join_include_path("", name);
+    let mut expansion = Expansion::default();
+    expansion.files.push(IncludedFile {
+        name: name.clone(),
+        text: text.to_string(),
+    });
+    expansion.expand(0, &mut vec![name], resolver)?;
+
+    let (screenplay, source_map) = parse_with_source_map(&expansion.text);
+    let source_map = SourceMap {
+        metadata: source_map.metadata.and_then(|span| {
+            let (file, span) = expansion.map_span(span);
+            (file == 0).then_some(span)
+        }),
+        elements: source_map
+            .elements
+            .into_iter()
+            .map(|span| expansion.map_element_span(span))
+            .collect(),
+    };
+    Ok((screenplay, source_map))
+}
+
+/// Resolves `path` against the directory of `from` for `/`-separated names,
+/// like those of a [`MemoryResolver`], dropping `.` and folding `..`
+/// components.
+pub fn join_include_path(from: &str, path: &str) -> String {
+    let joined = if path.starts_with('/') {
+        path.to_string()
+    } else {
+        match from.rfind('/') {
+            Some(index) => format!("{}/{path}", &from[..index]),
+            None => path.to_string(),
+        }
+    };
+
+    let mut components: Vec<&str> = Vec::new();
+    for component in joined.split('/') {
+        match component {
+            "." => {}
+            "" if !components.is_empty() => {}
+            ".." if components
+                .last()
+                .is_some_and(|last| !matches!(*last, ".." | "")) =>
+            {
+                components.pop();
+            }
+            _ => components.push(component),
+        }
+    }
+    components.join("/")
+}
+
+/// Drops `.` and folds `..` components of `path` without touching the disk,
+/// so that the same file always gets the same name.
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir
+                if matches!(
+                    normalized.components().next_back(),
+                    Some(Component::Normal(_))
+                ) =>
+            {
+                normalized.pop();
+            }
+            _ => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+fn include_directive(line: &str) -> Option<&str> {
+    let inner = line.trim().strip_prefix("{{")?.strip_suffix("}}")?;
+    let (keyword, path) = inner.split_once(':')?;
+    let path = path.trim();
+    (keyword.trim().eq_ignore_ascii_case("include") && !path.is_empty()).then_some(path)
+}
+
+/// A stretch of the expanded text copied from `file`, starting at `file_start`.
+/// Blank lines added around included files have no source and map to the
+/// position of the include line.
+struct Segment {
+    start: usize,
+    file: usize,
+    file_start: usize,
+    synthetic: bool,
+}
+
+#[derive(Default)]
+struct Expansion {
+    text: String,
+    files: Vec<IncludedFile>,
+    segments: Vec<Segment>,
+}
+
+impl Expansion {
+    fn expand(
+        &mut self,
+        file: usize,
+        stack: &mut Vec<String>,
+        resolver: &dyn IncludeResolver,
+    ) -> Result<(), IncludeError> {
+        let text = self.files[file].text.clone();
+        let mut copied = 0;
+        let mut line_start = 0;
+        let mut in_boneyard = false;
+
+        for line in text.split_inclusive('\n') {
+            let directive = (!in_boneyard).then(|| include_directive(line)).flatten();
+            in_boneyard = boneyard_left_open(line, in_boneyard);
+            let Some(path) = directive else {
+                line_start += line.len();
+                continue;
+            };
+
+            self.copy(file, &text, copied, line_start);
+            let included = resolver.resolve(&self.files[file].name, path)?;
+            if stack.contains(&included.name) {
+                let mut chain = stack.clone();
+                chain.push(included.name);
+                return Err(IncludeError::Cycle(chain));
+            }
+            self.separate_paragraphs(file, line_start, &included.text);
+            stack.push(included.name.clone());
+            self.files.push(included);
+            self.expand(self.files.len() - 1, stack, resolver)?;
+            stack.pop();
+            self.separate_paragraphs(file, line_start, &text[line_start + line.len()..]);
+
+            line_start += line.len();
+            copied = line_start;
+        }
+        self.copy(file, &text, copied, text.len());
+        Ok(())
+    }
+
+    fn copy(&mut self, file: usize, text: &str, start: usize, end: usize) {
+        if start < end {
+            self.segments.push(Segment {
+                start: self.text.len(),
+                file,
+                file_start: start,
+                synthetic: false,
+            });
+            self.text.push_str(&text[start..end]);
+        }
+    }
+
+    /// Adds the line breaks needed for a blank line between the text so far
+    /// and `next`, so that an included file never runs into the paragraphs
+    /// around it.
+    fn separate_paragraphs(&mut self, file: usize, file_start: usize, next: &str) {
+        if self.text.trim_end_matches(' ').is_empty() {
+            return;
+        }
+        let needed: usize = if next.is_empty() || next.starts_with(['\n', '\r']) {
+            1
+        } else {
+            2
+        };
+        let present = self.text.len() - self.text.trim_end_matches('\n').len();
+        if present < needed {
+            self.push_synthetic(file, file_start, &"\n".repeat(needed - present));
+        }
+    }
+
+    fn push_synthetic(&mut self, file: usize, file_start: usize, text: &str) {
+        self.segments.push(Segment {
+            start: self.text.len(),
+            file,
+            file_start,
+            synthetic: true,
+        });
+        self.text.push_str(text);
+    }
+
+    fn segment_at(&self, offset: usize) -> &Segment {
+        let index = self
+            .segments
+            .partition_point(|segment| segment.start <= offset)
+            .saturating_sub(1);
+        &self.segments[index]
+    }
+
+    fn segment_end(&self, segment: &Segment) -> usize {
+        self.segments
+            .iter()
+            .find(|next| next.start > segment.start)
+            .map_or(self.text.len(), |next| next.start)
+    }
+
+    fn file_offset(&self, segment: &Segment, offset: usize) -> usize {
+        if segment.synthetic {
+            segment.file_start
+        } else {
+            segment.file_start + offset.min(self.segment_end(segment)) - segment.start
+        }
+    }
+
+    /// Maps a span of the expanded text into the file its start came from.
+    /// A span running on into another file is cut off where its file ends.
+    fn map_span(&self, span: SourceSpan) -> (usize, SourceSpan) {
+        if self.segments.is_empty() {
+            return (0, span);
+        }
+        let segment = self.segment_at(span.start);
+        let start = self.file_offset(segment, span.start);
+        let end = self.file_offset(segment, span.end).max(start);
+        let text = &self.files[segment.file].text;
+        (segment.file, SourceSpan::from_offsets(text, start, end))
+    }
+
+    fn map_element_span(&self, span: ElementSpan) -> ElementSpan {
+        let (file, mapped) = self.map_span(span.span);
+        ElementSpan {
+            span: mapped,
+            file: Some(self.files[file].name.clone()),
+            children: span
+                .children
+                .into_iter()
+                .map(|child| self.map_element_span(child))
+                .collect(),
+        }
+    }
+}
+
+fn boneyard_left_open(line: &str, mut open: bool) -> bool {
+    let mut rest = line;
+    loop {
+        let marker = if open { "*/" } else { "/*" };
+        match rest.find(marker) {
+            Some(index) => {
+                open = !open;
+                rest = &rest[index + 2..];
+            }
+            None => return open,
+        }
+    }
+}
@@ -1,12 +1,26 @@
+// The JSON schema is one large `json!` literal.
+#![recursion_limit = "256"]
+
+pub mod breakdown;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod diagnostics;
+pub mod diff;
 pub mod fdx;
+pub mod include;
+pub mod merge;
 pub mod model;
 pub mod pagination;
+pub mod parse_diagnostics;
 pub mod parser;
+pub mod runtime;
 pub mod rendering;
+pub mod sides;
+pub mod stats;
+pub mod stripboard;
+pub mod syntax;
 mod text_style_parser;
 
 pub use fdx::parse_fdx;
+pub use include::parse_with_includes;
 pub use model::*;
-pub use parser::parse;
+pub use parser::{parse, parse_with_diagnostics, parse_with_source_map};
@@ -0,0 +1,448 @@
+use std::collections::BTreeSet;
+
+use crate::diff::{Step, align};
+use crate::rendering::fountain::{
+    render_element_with_page_breaks, render_metadata, render_source_with_page_breaks,
+};
+use crate::source_map::SourceMap;
+use crate::{Element, Metadata, Screenplay, parse_with_source_map};
+
+/// The names written after the conflict markers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MergeLabels {
+    pub ours: String,
+    pub theirs: String,
+}
+
+impl Default for MergeLabels {
+    fn default() -> Self {
+        MergeLabels {
+            ours: "ours".to_string(),
+            theirs: "theirs".to_string(),
+        }
+    }
+}
+
+/// The merged draft as Fountain, and how many conflict sections it holds.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MergeResult {
+    pub fountain: String,
+    pub conflicts: usize,
+}
+
+impl MergeResult {
+    pub fn is_clean(&self) -> bool {
+        self.conflicts == 0
+    }
+}
+
+/// Merges the changes `ours` and `theirs` each made to `base`, element by
+/// element. See [`merge_with_labels`].
+pub fn merge(base: &Screenplay, ours: &Screenplay, theirs: &Screenplay) -> MergeResult {
+    merge_with_labels(base, ours, theirs, &MergeLabels::default())
+}
+
+/// Parses three Fountain drafts and merges them. See
+/// [`merge_fountain_with_labels`].
+pub fn merge_fountain(base: &str, ours: &str, theirs: &str) -> MergeResult {
+    merge_fountain_with_labels(base, ours, theirs, &MergeLabels::default())
+}
+
+/// Parses three Fountain drafts and merges them like [`merge_with_labels`],
+/// except that paragraphs taken whole from one draft, in conflict sections
+/// too, are copied from the Fountain they were written as. Only dialogue
+/// blocks merged line by line and a title page merged key by key are
+/// written out again.
+pub fn merge_fountain_with_labels(
+    base: &str,
+    ours: &str,
+    theirs: &str,
+    labels: &MergeLabels,
+) -> MergeResult {
+    let (base_screenplay, base_map) = parse_with_source_map(base);
+    let (our_screenplay, our_map) = parse_with_source_map(ours);
+    let (their_screenplay, their_map) = parse_with_source_map(theirs);
+    merge_drafts(
+        &Draft::with_source(&base_screenplay, base, &base_map),
+        &Draft::with_source(&our_screenplay, ours, &our_map),
+        &Draft::with_source(&their_screenplay, theirs, &their_map),
+        labels,
+    )
+}
+
+/// Merges the changes `ours` and `theirs` each made to `base`.
+///
+/// Elements are lined up with `base` the way [`crate::diff::diff`] does. A
+/// paragraph changed on one side takes that change, and dialogue blocks
+/// changed on both sides are merged line by line. When both sides change the
+/// same paragraph differently, insert different paragraphs in the same
+/// place, or one changes a paragraph the other deleted, the Fountain gets a
+/// conflict section:
+///
+/// ```text
+/// <<<<<<< ours
+/// Bob waits by the door.
+/// =======
+/// Bob waits by the window.
+/// >>>>>>> theirs
+/// ```
+///
+/// Metadata is merged key by key; a conflicting key puts the whole title
+/// page in a conflict section. The whole draft is written out again as
+/// Fountain, so unchanged paragraphs come back in JumpCut's own Fountain
+/// style; [`merge_fountain_with_labels`] keeps them as they were written.
+pub fn merge_with_labels(
+    base: &Screenplay,
+    ours: &Screenplay,
+    theirs: &Screenplay,
+    labels: &MergeLabels,
+) -> MergeResult {
+    merge_drafts(
+        &Draft::new(base),
+        &Draft::new(ours),
+        &Draft::new(theirs),
+        labels,
+    )
+}
+
+/// A draft to merge, with the Fountain of its title page and of each of its
+/// elements when it was parsed from Fountain.
+struct Draft<'a> {
+    screenplay: &'a Screenplay,
+    metadata_source: Option<&'a str>,
+    sources: Vec<&'a str>,
+}
+
+impl<'a> Draft<'a> {
+    fn new(screenplay: &'a Screenplay) -> Draft<'a> {
+        Draft {
+            screenplay,
+            metadata_source: None,
+            sources: Vec::new(),
+        }
+    }
+
+    fn with_source(screenplay: &'a Screenplay, text: &'a str, map: &SourceMap) -> Draft<'a> {
+        Draft {
+            screenplay,
+            metadata_source: map.metadata.map(|span| &text[span.start..span.end]),
+            sources: map
+                .elements
+                .iter()
+                .map(|element| &text[element.span.start..element.span.end])
+                .collect(),
+        }
+    }
+
+    fn side(&self) -> Side<'_> {
+        Side {
+            elements: &self.screenplay.elements,
+            sources: &self.sources,
+        }
+    }
+
+    /// The title page as Fountain, copied from the source when `metadata` is
+    /// this draft's own.
+    fn render_metadata(&self, metadata: &Metadata) -> String {
+        match self.metadata_source {
+            Some(source) if *metadata == self.screenplay.metadata => source.to_string(),
+            _ => render_metadata(metadata),
+        }
+    }
+}
+
+fn merge_drafts(base: &Draft, ours: &Draft, theirs: &Draft, labels: &MergeLabels) -> MergeResult {
+    let mut paragraphs = Vec::new();
+    let mut conflicts = 0;
+
+    let (our_metadata, their_metadata) = (&ours.screenplay.metadata, &theirs.screenplay.metadata);
+    match merge_metadata(&base.screenplay.metadata, our_metadata, their_metadata) {
+        Some(metadata) => {
+            let block = if metadata == *their_metadata {
+                theirs.render_metadata(&metadata)
+            } else {
+                ours.render_metadata(&metadata)
+            };
+            if !block.is_empty() {
+                paragraphs.push(block);
+            }
+        }
+        None => {
+            conflicts += 1;
+            paragraphs.push(conflict_section(
+                labels,
+                &ours.render_metadata(our_metadata),
+                &theirs.render_metadata(their_metadata),
+            ));
+        }
+    }
+
+    for merged in merge_sequence(&base.screenplay.elements, ours.side(), theirs.side()) {
+        match merged {
+            Merged::Element(piece) => paragraphs.extend(piece.render()),
+            Merged::Conflict { ours, theirs } => {
+                conflicts += 1;
+                paragraphs.push(conflict_section(
+                    labels,
+                    &render_pieces(&ours),
+                    &render_pieces(&theirs),
+                ));
+            }
+        }
+    }
+
+    MergeResult {
+        fountain: paragraphs.join("\n\n") + "\n",
+        conflicts,
+    }
+}
+
+fn render_pieces(pieces: &[Piece]) -> String {
+    pieces
+        .iter()
+        .flat_map(Piece::render)
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn conflict_section(labels: &MergeLabels, ours: &str, theirs: &str) -> String {
+    let side = |text: &str| {
+        if text.is_empty() {
+            String::new()
+        } else {
+            format!("{text}\n")
+        }
+    };
+    format!(
+        "<<<<<<< {}\n{}=======\n{}>>>>>>> {}",
+        labels.ours,
+        side(ours),
+        side(theirs),
+        labels.theirs
+    )
+}
+
+/// Merges metadata key by key, or returns `None` if both sides changed a key
+/// differently.
+fn merge_metadata(base: &Metadata, ours: &Metadata, theirs: &Metadata) -> Option<Metadata> {
+    let keys: BTreeSet<&String> = base
+        .keys()
+        .chain(ours.keys())
+        .chain(theirs.keys())
+        .collect();
+    let mut merged = Metadata::new();
+    for key in keys {
+        let value = pick(base.get(key), ours.get(key), theirs.get(key))?;
+        if let Some(value) = value {
+            merged.insert(key.clone(), value.clone());
+        }
+    }
+    Some(merged)
+}
+
+/// The three-way choice between one side's value and the other's: `None`
+/// when both sides changed it differently.
+fn pick<T: PartialEq>(base: T, ours: T, theirs: T) -> Option<T> {
+    match choose(&base, &ours, &theirs)? {
+        Choice::Ours => Some(ours),
+        Choice::Theirs => Some(theirs),
+    }
+}
+
+enum Choice {
+    Ours,
+    Theirs,
+}
+
+/// Which side [`pick`] takes.
+fn choose<T: PartialEq>(base: &T, ours: &T, theirs: &T) -> Option<Choice> {
+    if ours == theirs || theirs == base {
+        Some(Choice::Ours)
+    } else if ours == base {
+        Some(Choice::Theirs)
+    } else {
+        None
+    }
+}
+
+/// The elements of one side of a merge, with the Fountain each was written
+/// as, or no sources when the side was not parsed from Fountain.
+#[derive(Clone, Copy)]
+struct Side<'a> {
+    elements: &'a [Element],
+    sources: &'a [&'a str],
+}
+
+impl<'a> Side<'a> {
+    fn piece(&self, index: usize) -> Piece<'a> {
+        Piece {
+            element: self.elements[index].clone(),
+            source: self.sources.get(index).copied(),
+        }
+    }
+}
+
+/// An element of the merged draft, with the Fountain it was written as when
+/// it was taken whole from a side that has it.
+struct Piece<'a> {
+    element: Element,
+    source: Option<&'a str>,
+}
+
+impl Piece<'_> {
+    fn render(&self) -> Vec<String> {
+        match self.source {
+            Some(source) => render_source_with_page_breaks(&self.element, source),
+            None => render_element_with_page_breaks(&self.element),
+        }
+    }
+}
+
+enum Merged<'a> {
+    Element(Piece<'a>),
+    Conflict {
+        ours: Vec<Piece<'a>>,
+        theirs: Vec<Piece<'a>>,
+    },
+}
+
+/// Where the elements of one side sit against `base`: the side's index for
+/// each base element it kept, and the elements it inserted before each base
+/// element, with the last entry holding those inserted at the end.
+struct SideAlignment {
+    kept: Vec<Option<usize>>,
+    inserted: Vec<Vec<usize>>,
+}
+
+impl SideAlignment {
+    fn new(base: &[Element], side: &[Element]) -> SideAlignment {
+        let mut kept = vec![None; base.len()];
+        let mut base_index = vec![None; side.len()];
+        for step in align(base, side) {
+            if let Step::Same(i, j) | Step::Similar(i, j) = step {
+                kept[i] = Some(j);
+                base_index[j] = Some(i);
+            }
+        }
+
+        let mut inserted = vec![Vec::new(); base.len() + 1];
+        let mut slot = 0;
+        for (j, base_index) in base_index.into_iter().enumerate() {
+            match base_index {
+                Some(i) => slot = i + 1,
+                None => inserted[slot].push(j),
+            }
+        }
+        SideAlignment { kept, inserted }
+    }
+}
+
+fn merge_sequence<'a>(base: &[Element], ours: Side<'a>, theirs: Side<'a>) -> Vec<Merged<'a>> {
+    let our_side = SideAlignment::new(base, ours.elements);
+    let their_side = SideAlignment::new(base, theirs.elements);
+    let pieces = |indexes: &[usize], side: Side<'a>| -> Vec<Piece<'a>> {
+        indexes.iter().map(|&j| side.piece(j)).collect()
+    };
+    let elements = |pieces: &[Piece]| -> Vec<Element> {
+        pieces.iter().map(|piece| piece.element.clone()).collect()
+    };
+
+    let mut merged = Vec::new();
+    for slot in 0..=base.len() {
+        let our_inserts = pieces(&our_side.inserted[slot], ours);
+        let their_inserts = pieces(&their_side.inserted[slot], theirs);
+        match choose(
+            &Vec::new(),
+            &elements(&our_inserts),
+            &elements(&their_inserts),
+        ) {
+            Some(Choice::Ours) => merged.extend(our_inserts.into_iter().map(Merged::Element)),
+            Some(Choice::Theirs) => merged.extend(their_inserts.into_iter().map(Merged::Element)),
+            None => push_conflict(&mut merged, our_inserts, their_inserts),
+        }
+
+        let Some(base_element) = base.get(slot) else {
+            break;
+        };
+        let our_element = our_side.kept[slot].map(|j| ours.piece(j));
+        let their_element = their_side.kept[slot].map(|j| theirs.piece(j));
+        match (our_element, their_element) {
+            (Some(our_element), Some(their_element)) => {
+                match choose(base_element, &our_element.element, &their_element.element) {
+                    Some(Choice::Ours) => merged.push(Merged::Element(our_element)),
+                    Some(Choice::Theirs) => merged.push(Merged::Element(their_element)),
+                    None => match merge_block(
+                        base_element,
+                        &our_element.element,
+                        &their_element.element,
+                    ) {
+                        Some(element) => merged.push(Merged::Element(Piece {
+                            element,
+                            source: None,
+                        })),
+                        None => push_conflict(&mut merged, vec![our_element], vec![their_element]),
+                    },
+                }
+            }
+            (None, Some(kept)) | (Some(kept), None) if kept.element == *base_element => {}
+            (None, Some(their_element)) => {
+                push_conflict(&mut merged, Vec::new(), vec![their_element])
+            }
+            (Some(our_element), None) => push_conflict(&mut merged, vec![our_element], Vec::new()),
+            (None, None) => {}
+        }
+    }
+    merged
+}
+
+/// Adds a conflict, joining it to a conflict right before it.
+fn push_conflict<'a>(merged: &mut Vec<Merged<'a>>, ours: Vec<Piece<'a>>, theirs: Vec<Piece<'a>>) {
+    if let Some(Merged::Conflict {
+        ours: last_ours,
+        theirs: last_theirs,
+    }) = merged.last_mut()
+    {
+        last_ours.extend(ours);
+        last_theirs.extend(theirs);
+    } else {
+        merged.push(Merged::Conflict { ours, theirs });
+    }
+}
+
+/// Merges a dialogue block both sides changed line by line, or returns
+/// `None` if they changed the same line differently or the element is not a
+/// dialogue block.
+fn merge_block(base: &Element, ours: &Element, theirs: &Element) -> Option<Element> {
+    let rebuild: fn(Vec<Element>) -> Element = match (base, ours, theirs) {
+        (Element::DialogueBlock(_), Element::DialogueBlock(_), Element::DialogueBlock(_)) => {
+            Element::DialogueBlock
+        }
+        (
+            Element::DualDialogueBlock(_),
+            Element::DualDialogueBlock(_),
+            Element::DualDialogueBlock(_),
+        ) => Element::DualDialogueBlock,
+        _ => return None,
+    };
+    let (
+        Element::DialogueBlock(base_block) | Element::DualDialogueBlock(base_block),
+        Element::DialogueBlock(our_block) | Element::DualDialogueBlock(our_block),
+        Element::DialogueBlock(their_block) | Element::DualDialogueBlock(their_block),
+    ) = (base, ours, theirs)
+    else {
+        return None;
+    };
+
+    let side = |elements| Side {
+        elements,
+        sources: &[],
+    };
+    merge_sequence(base_block, side(our_block), side(their_block))
+        .into_iter()
+        .map(|merged| match merged {
+            Merged::Element(piece) => Some(piece.element),
+            Merged::Conflict { .. } => None,
+        })
+        .collect::<Option<Vec<_>>>()
+        .map(rebuild)
+}
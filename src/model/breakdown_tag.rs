@@ -0,0 +1,171 @@
+use serde::{Deserialize, Serialize};
+
+/// The kinds of production element a breakdown sorts tags into, in the order
+/// breakdown sheets list them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum BreakdownCategory {
+    Cast,
+    Extras,
+    Stunts,
+    Props,
+    Wardrobe,
+    Makeup,
+    Vehicles,
+    Animals,
+    SetDressing,
+    SpecialEffects,
+    VisualEffects,
+    Sound,
+    Music,
+    Other,
+}
+
+/// A production element tagged in an element's text, such as the revolver
+/// in `[[prop: revolver]]`, and where the tag sat, as a character offset into
+/// the element's plain text.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct BreakdownTag {
+    pub category: BreakdownCategory,
+    pub name: String,
+    pub offset: usize,
+}
+
+// The first key of each category is the one written back to Fountain.
+const NOTE_KEYS: [(BreakdownCategory, &[&str]); 14] = [
+    (BreakdownCategory::Cast, &["cast"]),
+    (
+        BreakdownCategory::Extras,
+        &["extras", "extra", "background"],
+    ),
+    (BreakdownCategory::Stunts, &["stunt", "stunts"]),
+    (BreakdownCategory::Props, &["prop", "props"]),
+    (BreakdownCategory::Wardrobe, &["wardrobe", "costume"]),
+    (BreakdownCategory::Makeup, &["makeup", "hair"]),
+    (BreakdownCategory::Vehicles, &["vehicle", "vehicles"]),
+    (BreakdownCategory::Animals, &["animal", "animals"]),
+    (BreakdownCategory::SetDressing, &["set dressing"]),
+    (
+        BreakdownCategory::SpecialEffects,
+        &["sfx", "special effects"],
+    ),
+    (BreakdownCategory::VisualEffects, &["vfx", "visual effects"]),
+    (BreakdownCategory::Sound, &["sound"]),
+    (BreakdownCategory::Music, &["music"]),
+    (BreakdownCategory::Other, &["other"]),
+];
+
+impl BreakdownCategory {
+    pub const ALL: [BreakdownCategory; 14] = [
+        BreakdownCategory::Cast,
+        BreakdownCategory::Extras,
+        BreakdownCategory::Stunts,
+        BreakdownCategory::Props,
+        BreakdownCategory::Wardrobe,
+        BreakdownCategory::Makeup,
+        BreakdownCategory::Vehicles,
+        BreakdownCategory::Animals,
+        BreakdownCategory::SetDressing,
+        BreakdownCategory::SpecialEffects,
+        BreakdownCategory::VisualEffects,
+        BreakdownCategory::Sound,
+        BreakdownCategory::Music,
+        BreakdownCategory::Other,
+    ];
+
+    /// The category's name on breakdown sheets, such as `Special Effects`.
+    pub fn label(self) -> &'static str {
+        match self {
+            BreakdownCategory::Cast => "Cast",
+            BreakdownCategory::Extras => "Extras",
+            BreakdownCategory::Stunts => "Stunts",
+            BreakdownCategory::Props => "Props",
+            BreakdownCategory::Wardrobe => "Wardrobe",
+            BreakdownCategory::Makeup => "Makeup",
+            BreakdownCategory::Vehicles => "Vehicles",
+            BreakdownCategory::Animals => "Animals",
+            BreakdownCategory::SetDressing => "Set Dressing",
+            BreakdownCategory::SpecialEffects => "Special Effects",
+            BreakdownCategory::VisualEffects => "Visual Effects",
+            BreakdownCategory::Sound => "Sound",
+            BreakdownCategory::Music => "Music",
+            BreakdownCategory::Other => "Other",
+        }
+    }
+
+    /// The category a Fountain note key such as `prop` or `sfx` stands for,
+    /// ignoring case.
+    pub fn from_note_key(key: &str) -> Option<BreakdownCategory> {
+        let key = key.trim().to_lowercase();
+        NOTE_KEYS
+            .iter()
+            .find(|(_, keys)| keys.contains(&key.as_str()))
+            .map(|(category, _)| *category)
+    }
+
+    /// The category of a Final Draft tag category, such as `Cast Members`.
+    /// Categories jumpcut has no counterpart for, like `Greenery`, are
+    /// [`BreakdownCategory::Other`].
+    pub fn from_final_draft_name(name: &str) -> BreakdownCategory {
+        let name = name.trim();
+        if let Some(category) = BreakdownCategory::ALL
+            .into_iter()
+            .find(|category| category.label().eq_ignore_ascii_case(name))
+        {
+            return category;
+        }
+        match name.to_lowercase().as_str() {
+            "cast members" => BreakdownCategory::Cast,
+            "background actors" => BreakdownCategory::Extras,
+            "costumes" => BreakdownCategory::Wardrobe,
+            "makeup/hair" | "hair/makeup" => BreakdownCategory::Makeup,
+            "mechanical effects" => BreakdownCategory::SpecialEffects,
+            "livestock" | "animal handlers" => BreakdownCategory::Animals,
+            "sound effects" => BreakdownCategory::Sound,
+            other => BreakdownCategory::from_note_key(other).unwrap_or(BreakdownCategory::Other),
+        }
+    }
+
+    fn note_key(self) -> &'static str {
+        NOTE_KEYS
+            .iter()
+            .find(|(category, _)| *category == self)
+            .map(|(_, keys)| keys[0])
+            .unwrap_or("other")
+    }
+}
+
+impl BreakdownTag {
+    /// A tag at the start of the element's text.
+    pub fn new(category: BreakdownCategory, name: impl Into<String>) -> Self {
+        BreakdownTag {
+            category,
+            name: name.into(),
+            offset: 0,
+        }
+    }
+
+    /// Reads a note of the form `category: name`, such as `prop: revolver`,
+    /// or returns `None` for any other note. The category is written in
+    /// lower case and the name is only a name, so that notes such as
+    /// `Sound: too loud?` stay notes.
+    pub fn from_note(note: &str) -> Option<BreakdownTag> {
+        let (key, name) = note.split_once(':')?;
+        if key.chars().any(char::is_uppercase) {
+            return None;
+        }
+        let category = BreakdownCategory::from_note_key(key)?;
+        let name = name.trim();
+        (!name.is_empty() && !name.contains([':', '?', '!']))
+            .then(|| BreakdownTag::new(category, name))
+    }
+
+    /// The same tag at `offset`.
+    pub fn at(self, offset: usize) -> Self {
+        BreakdownTag { offset, ..self }
+    }
+
+    /// The note that tags this element in Fountain, such as `prop: revolver`.
+    pub fn to_note(&self) -> String {
+        format!("{}: {}", self.category.note_key(), self.name)
+    }
+}
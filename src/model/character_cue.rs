@@ -0,0 +1,60 @@
+use serde::Serialize;
+
+/// A character cue split into its parts, e.g. `JOHN (V.O.) (CONT'D) ^` into
+/// the name `JOHN`, the extensions `V.O.` and `CONT'D`, and the dual-dialogue
+/// marker.
+///
+/// Extensions keep the spelling used in the cue and are listed in the order
+/// they were written.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct CharacterCue {
+    pub name: String,
+    pub extensions: Vec<String>,
+    pub dual: bool,
+}
+
+impl CharacterCue {
+    /// Splits the text of a character cue. A forced cue's leading `@` is
+    /// ignored, and a trailing `^` sets `dual`.
+    pub fn parse(cue: &str) -> CharacterCue {
+        let mut rest = cue.trim();
+        rest = rest.strip_prefix('@').unwrap_or(rest).trim_start();
+        let dual = rest.ends_with('^');
+        rest = rest.trim_end_matches('^').trim_end();
+
+        let mut extensions = Vec::new();
+        while rest.ends_with(')') {
+            let Some(open_paren) = rest.rfind('(') else {
+                break;
+            };
+            extensions.push(rest[open_paren + 1..rest.len() - 1].trim().to_string());
+            rest = rest[..open_paren].trim_end();
+        }
+        extensions.reverse();
+
+        CharacterCue {
+            name: rest.to_string(),
+            extensions,
+            dual,
+        }
+    }
+
+    /// Whether the cue already carries a `(CONT'D)`, in any of its usual
+    /// spellings.
+    pub fn is_continued(&self) -> bool {
+        self.has_extension(&["CONT'D", "CONT’D", "CONTD", "CONT.", "CONTINUED"])
+    }
+
+    /// Whether the cue is a voice-over.
+    pub fn is_voice_over(&self) -> bool {
+        self.has_extension(&["V.O.", "V.O", "VO"])
+    }
+
+    fn has_extension(&self, spellings: &[&str]) -> bool {
+        self.extensions.iter().any(|extension| {
+            spellings
+                .iter()
+                .any(|spelling| extension.eq_ignore_ascii_case(spelling))
+        })
+    }
+}
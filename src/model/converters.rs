@@ -24,6 +24,30 @@ impl Screenplay {
         }
     }
 
+    /// A copy with boneyard turned into action, for draft renders that show
+    /// cut material on the page.
+    #[cfg(any(feature = "html", feature = "pdf"))]
+    pub(crate) fn with_boneyard_as_action(&self) -> Screenplay {
+        let mut screenplay = self.with_inline_boneyard_shown();
+        for element in &mut screenplay.elements {
+            if let Element::Boneyard(text) = element {
+                *element = Element::Action(
+                    crate::ElementText::Plain(format!("/*{}*/", text.plain_text())),
+                    crate::blank_attributes(),
+                );
+            }
+        }
+        screenplay
+    }
+
+    /// A copy with the boneyard inside each element written into its text.
+    #[cfg(any(feature = "html", feature = "pdf"))]
+    pub(crate) fn with_inline_boneyard_shown(&self) -> Screenplay {
+        let mut screenplay = self.clone();
+        screenplay.elements.iter_mut().for_each(show_inline_boneyard);
+        screenplay
+    }
+
     pub fn to_fountain(&self) -> String {
         crate::rendering::fountain::render(self)
     }
@@ -67,15 +91,85 @@ impl Screenplay {
         crate::rendering::pdf::render_with_options(self, options)
     }
 
-    pub fn to_json_string(self) -> String {
-        serde_json::to_string(&self)
+    /// Prints sides: the pages with the scenes chosen by `selection`,
+    /// numbered as in the full screenplay, with any other scenes on those
+    /// pages crossed out between `START` and `END` rules. See
+    /// [`Screenplay::sides`].
+    #[cfg(feature = "pdf")]
+    pub fn to_sides_pdf(
+        &self,
+        selection: &crate::sides::SidesSelection,
+        options: crate::rendering::pdf::PdfRenderOptions,
+    ) -> Vec<u8> {
+        crate::rendering::pdf::render_sides(self, selection, options)
+    }
+
+    /// Writes the screenplay as JSON. Elements are written with the ids they
+    /// have, so call `assign_element_ids` first to give every element one.
+    pub fn to_json_string(&self) -> String {
+        serde_json::to_string(self)
             .expect("Should be impossible for this JSON serialization to fail.")
     }
 
-    pub fn to_json_value(self) -> serde_json::Value {
-        serde_json::to_value(&self)
+    /// Returns the screenplay as a JSON value, with the element ids it has
+    /// like `to_json_string`.
+    pub fn to_json_value(&self) -> serde_json::Value {
+        serde_json::to_value(self)
             .expect("Should be impossible for this JSON serialization to fail.")
     }
+
+    /// Returns the screenplay as a JSON value like `to_json_value`, with each
+    /// scene heading carrying its `length` from `scene_lengths`.
+    pub fn to_json_value_with_scene_lengths(&self) -> serde_json::Value {
+        let lengths = self.scene_lengths();
+        let mut json = self.to_json_value();
+        let headings = json["elements"]
+            .as_array_mut()
+            .into_iter()
+            .flatten()
+            .filter(|element| element["type"] == "Scene Heading");
+        for (heading, length) in headings.zip(lengths) {
+            heading["length"] = serde_json::json!({
+                "eighths": length.eighths,
+                "text": length.eighths.to_string(),
+                "first_page": length.first_page,
+                "last_page": length.last_page,
+            });
+        }
+        json
+    }
+
+    /// Reads a screenplay back from the JSON that `to_json_string` writes.
+    pub fn from_json_str(json: &str) -> Result<Screenplay, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Reads a screenplay back from the JSON that `to_json_value` returns.
+    pub fn from_json_value(json: serde_json::Value) -> Result<Screenplay, serde_json::Error> {
+        serde_json::from_value(json)
+    }
+}
+
+/// Writes an element's inline boneyard into its text as `/* ... */`, moving
+/// the notes after it along.
+#[cfg(any(feature = "html", feature = "pdf"))]
+fn show_inline_boneyard(element: &mut Element) {
+    if let Element::DialogueBlock(block) | Element::DualDialogueBlock(block) = element {
+        block.iter_mut().for_each(show_inline_boneyard);
+        return;
+    }
+    let Some((text, attributes)) = element.text_and_attributes_mut() else {
+        return;
+    };
+    for boneyard in std::mem::take(&mut attributes.boneyard).iter().rev() {
+        let shown = format!("/*{}*/", boneyard.text);
+        text.insert_str(boneyard.offset, &shown);
+        for note in attributes.notes.iter_mut().flatten() {
+            if note.offset > boneyard.offset {
+                note.offset += shown.chars().count();
+            }
+        }
+    }
 }
 
 // * Tests
@@ -288,7 +382,15 @@ mod tests {
         assert!(
             actual.contains("<FinalDraft DocumentType=\"Script\" Template=\"No\" Version=\"4\">")
         );
-        assert!(actual.contains("<Paragraph Type=\"Scene Heading\" Number=\"1\">"));
+        let heading_id = screenplay
+            .elements
+            .iter()
+            .find(|element| matches!(element, Element::SceneHeading(_, _)))
+            .and_then(Element::id)
+            .expect("scene heading should have been given an id");
+        assert!(actual.contains(&format!(
+            "<Paragraph Type=\"Scene Heading\" Number=\"1\" JumpCutID=\"{heading_id}\">"
+        )));
         assert!(actual.contains("<Text Style=\"Bold\">BOLD</Text>"));
         assert!(actual.contains("<DualDialogue>"));
         assert!(actual.contains("<ElementSettings Type=\"Dialogue\">"));
@@ -327,6 +429,8 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::Lyric(p("I love to sing"), blank_attributes())],
         };
 
@@ -344,13 +448,21 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::Action(p("Body."), blank_attributes())],
         };
 
         let actual = screenplay.to_final_draft();
         let paragraph_tag = body_paragraph_opening_tag_for_text(&actual, "Body.");
 
-        assert_eq!(paragraph_tag, "<Paragraph Type=\"Action\">");
+        assert_eq!(
+            paragraph_tag,
+            format!(
+                "<Paragraph Type=\"Action\" JumpCutID=\"{}\">",
+                screenplay.elements[0].id().unwrap()
+            )
+        );
     }
 
     #[test]
@@ -361,6 +473,8 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::Action(p("Body."), attributes)],
         };
 
@@ -378,6 +492,8 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::Action(p("Body."), attributes)],
         };
 
@@ -397,6 +513,8 @@ mod tests {
             metadata,
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![],
         };
 
@@ -415,6 +533,8 @@ mod tests {
             metadata,
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![],
         };
 
@@ -436,6 +556,8 @@ mod tests {
             metadata,
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![],
         };
 
@@ -463,6 +585,8 @@ mod tests {
             metadata,
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![],
         };
 
@@ -594,6 +718,8 @@ mod tests {
             metadata,
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![],
         };
 
@@ -681,6 +807,8 @@ mod tests {
             metadata,
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![
                 Element::SceneHeading(p("INT.KITCHEN"), scene_attrs),
                 Element::Action(styled, blank_attributes()),
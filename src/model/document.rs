@@ -1,25 +1,113 @@
 use serde::ser::{SerializeMap, Serializer};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::default::Default;
 
+use crate::json_schema::SCHEMA_VERSION;
+use crate::{BreakdownTag, CharacterCue, PageLock, RevisionSet, SceneHeadingParts};
+
 pub type Metadata = HashMap<String, Vec<ElementText>>;
 
-#[derive(Debug, PartialEq, Serialize)]
+/// The metadata keys a document's language is read from, in order of
+/// preference.
+pub(crate) const LANGUAGE_METADATA_KEYS: [&str; 2] = ["lang", "language"];
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct Screenplay {
     pub metadata: Metadata,
     pub imported_layout: Option<ImportedLayoutOverrides>,
     pub imported_title_page: Option<ImportedTitlePage>,
+    pub revisions: Vec<RevisionSet>,
+    /// The locked pagination new drafts are paginated against. See
+    /// [`Screenplay::lock_pages`].
+    pub page_lock: Option<PageLock>,
     pub elements: Vec<Element>,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize)]
+/// The JSON document a screenplay serializes to, tagged with the
+/// [`SCHEMA_VERSION`] of its format.
+#[derive(Serialize)]
+struct SerializeScreenplayHelper<'a> {
+    schema_version: u32,
+    metadata: &'a Metadata,
+    imported_layout: &'a Option<ImportedLayoutOverrides>,
+    imported_title_page: &'a Option<ImportedTitlePage>,
+    #[serde(skip_serializing_if = "<[RevisionSet]>::is_empty")]
+    revisions: &'a [RevisionSet],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    page_lock: &'a Option<PageLock>,
+    elements: &'a [Element],
+}
+
+impl Serialize for Screenplay {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        SerializeScreenplayHelper {
+            schema_version: SCHEMA_VERSION,
+            metadata: &self.metadata,
+            imported_layout: &self.imported_layout,
+            imported_title_page: &self.imported_title_page,
+            revisions: &self.revisions,
+            page_lock: &self.page_lock,
+            elements: &self.elements,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// JSON written before the format was versioned has no `schema_version` and
+/// is read as version 1. Every version so far only added fields, each read as
+/// empty when missing, so older documents need no conversion.
+#[derive(Deserialize)]
+struct DeserializeScreenplayHelper {
+    #[serde(default = "first_schema_version")]
+    schema_version: u32,
+    metadata: Metadata,
+    imported_layout: Option<ImportedLayoutOverrides>,
+    imported_title_page: Option<ImportedTitlePage>,
+    #[serde(default)]
+    revisions: Vec<RevisionSet>,
+    #[serde(default)]
+    page_lock: Option<PageLock>,
+    elements: Vec<Element>,
+}
+
+fn first_schema_version() -> u32 {
+    1
+}
+
+impl<'de> Deserialize<'de> for Screenplay {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let helper = DeserializeScreenplayHelper::deserialize(deserializer)?;
+        if helper.schema_version > SCHEMA_VERSION {
+            return Err(serde::de::Error::custom(format!(
+                "unsupported schema_version {}, expected at most {SCHEMA_VERSION}",
+                helper.schema_version
+            )));
+        }
+        Ok(Screenplay {
+            metadata: helper.metadata,
+            imported_layout: helper.imported_layout,
+            imported_title_page: helper.imported_title_page,
+            revisions: helper.revisions,
+            page_lock: helper.page_lock,
+            elements: helper.elements,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ImportedTitlePage {
     pub header_footer: ImportedTitlePageHeaderFooter,
     pub pages: Vec<ImportedTitlePagePage>,
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct ImportedTitlePageHeaderFooter {
     pub header_visible: bool,
     pub header_first_page: bool,
@@ -27,12 +115,12 @@ pub struct ImportedTitlePageHeaderFooter {
     pub starting_page: Option<u32>,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ImportedTitlePagePage {
     pub paragraphs: Vec<ImportedTitlePageParagraph>,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ImportedTitlePageParagraph {
     pub text: ElementText,
     pub alignment: ImportedTitlePageAlignment,
@@ -42,7 +130,7 @@ pub struct ImportedTitlePageParagraph {
     pub tab_stops: Vec<ImportedTitlePageTabStop>,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ImportedTitlePageAlignment {
     Left,
     Center,
@@ -50,20 +138,20 @@ pub enum ImportedTitlePageAlignment {
     Full,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ImportedTitlePageTabStop {
     pub position: f32,
     pub kind: ImportedTitlePageTabStopKind,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ImportedTitlePageTabStopKind {
     Left,
     Center,
     Right,
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct ImportedLayoutOverrides {
     pub page: ImportedPageLayoutOverrides,
     pub element_styles: BTreeMap<ImportedElementKind, ImportedElementStyle>,
@@ -78,7 +166,7 @@ impl ImportedLayoutOverrides {
     }
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct ImportedPageLayoutOverrides {
     pub page_width: Option<f32>,
     pub page_height: Option<f32>,
@@ -88,7 +176,7 @@ pub struct ImportedPageLayoutOverrides {
     pub footer_margin: Option<f32>,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum ImportedElementKind {
     Action,
     SceneHeading,
@@ -102,7 +190,7 @@ pub enum ImportedElementKind {
     EndOfAct,
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct ImportedElementStyle {
     pub first_indent: Option<f32>,
     pub left_indent: Option<f32>,
@@ -130,20 +218,20 @@ impl ElementLayoutOverrides {
 
 impl Eq for ElementLayoutOverrides {}
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ImportedAlignment {
     Left,
     Center,
     Right,
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct ImportedMoresAndContinueds {
     pub dialogue: ImportedDialogueContinueds,
     pub scene: ImportedSceneContinueds,
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct ImportedDialogueContinueds {
     pub automatic_character_continueds: Option<bool>,
     pub top_of_next: Option<bool>,
@@ -152,7 +240,7 @@ pub struct ImportedDialogueContinueds {
     pub dialogue_bottom: Option<String>,
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct ImportedSceneContinueds {
     pub top_of_next: Option<bool>,
     pub bottom_of_page: Option<bool>,
@@ -174,6 +262,7 @@ pub enum Element {
     Transition(ElementText, Attributes),
     Section(ElementText, Attributes, u8),
     Synopsis(ElementText),
+    Boneyard(ElementText),
     ColdOpening(ElementText, Attributes),
     NewAct(ElementText, Attributes),
     EndOfAct(ElementText, Attributes),
@@ -195,12 +284,178 @@ impl Element {
             Transition(_, _) => "Transition",
             Section(_, _, _) => "Section",
             Synopsis(_) => "Synopsis",
+            Boneyard(_) => "Boneyard",
             ColdOpening(_, _) => "Cold Opening",
             NewAct(_, _) => "New Act",
             EndOfAct(_, _) => "End of Act",
             PageBreak => "Page Break",
         }
     }
+
+    /// The element's own text, or `None` for blocks and page breaks.
+    pub fn text(&self) -> Option<&ElementText> {
+        use Element::*;
+        match self {
+            Action(text, _)
+            | Character(text, _)
+            | SceneHeading(text, _)
+            | Lyric(text, _)
+            | Parenthetical(text, _)
+            | Dialogue(text, _)
+            | Transition(text, _)
+            | Section(text, _, _)
+            | Synopsis(text)
+            | Boneyard(text)
+            | ColdOpening(text, _)
+            | NewAct(text, _)
+            | EndOfAct(text, _) => Some(text),
+            DialogueBlock(_) | DualDialogueBlock(_) | PageBreak => None,
+        }
+    }
+
+    /// The element's attributes, or `None` for elements without any.
+    pub fn attributes(&self) -> Option<&Attributes> {
+        use Element::*;
+        match self {
+            Action(_, attributes)
+            | Character(_, attributes)
+            | SceneHeading(_, attributes)
+            | Lyric(_, attributes)
+            | Parenthetical(_, attributes)
+            | Dialogue(_, attributes)
+            | Transition(_, attributes)
+            | Section(_, attributes, _)
+            | ColdOpening(_, attributes)
+            | NewAct(_, attributes)
+            | EndOfAct(_, attributes) => Some(attributes),
+            DialogueBlock(_) | DualDialogueBlock(_) | Synopsis(_) | Boneyard(_) | PageBreak => {
+                None
+            }
+        }
+    }
+
+    pub fn attributes_mut(&mut self) -> Option<&mut Attributes> {
+        use Element::*;
+        match self {
+            Action(_, attributes)
+            | Character(_, attributes)
+            | SceneHeading(_, attributes)
+            | Lyric(_, attributes)
+            | Parenthetical(_, attributes)
+            | Dialogue(_, attributes)
+            | Transition(_, attributes)
+            | Section(_, attributes, _)
+            | ColdOpening(_, attributes)
+            | NewAct(_, attributes)
+            | EndOfAct(_, attributes) => Some(attributes),
+            DialogueBlock(_) | DualDialogueBlock(_) | Synopsis(_) | Boneyard(_) | PageBreak => {
+                None
+            }
+        }
+    }
+
+    pub(crate) fn text_and_attributes_mut(
+        &mut self,
+    ) -> Option<(&mut ElementText, &mut Attributes)> {
+        use Element::*;
+        match self {
+            Action(text, attributes)
+            | Character(text, attributes)
+            | SceneHeading(text, attributes)
+            | Lyric(text, attributes)
+            | Parenthetical(text, attributes)
+            | Dialogue(text, attributes)
+            | Transition(text, attributes)
+            | Section(text, attributes, _)
+            | ColdOpening(text, attributes)
+            | NewAct(text, attributes)
+            | EndOfAct(text, attributes) => Some((text, attributes)),
+            DialogueBlock(_) | DualDialogueBlock(_) | Synopsis(_) | Boneyard(_) | PageBreak => {
+                None
+            }
+        }
+    }
+
+    /// The element's stable id, if it has been given one.
+    pub fn id(&self) -> Option<&str> {
+        self.attributes()?.id.as_deref()
+    }
+
+    /// The parsed cues of a character or of the speakers of a dialogue block.
+    /// In a dual dialogue block, the cue of the second speaker is the one
+    /// marked `dual`, as the `^` on it was in Fountain.
+    pub fn character_cues(&self) -> Vec<CharacterCue> {
+        match self {
+            Element::Character(text, _) => vec![CharacterCue::parse(&text.plain_text())],
+            Element::DialogueBlock(block) => {
+                block.iter().flat_map(Element::character_cues).collect()
+            }
+            Element::DualDialogueBlock(blocks) => blocks
+                .iter()
+                .enumerate()
+                .flat_map(|(index, block)| {
+                    block.character_cues().into_iter().map(move |mut cue| {
+                        cue.dual |= index > 0;
+                        cue
+                    })
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// The parts of a scene heading, or `None` for any other element.
+    pub fn scene_heading(&self) -> Option<SceneHeadingParts> {
+        match self {
+            Element::SceneHeading(text, _) => Some(SceneHeadingParts::parse(&text.plain_text())),
+            _ => None,
+        }
+    }
+
+    /// Moves the note, tag and boneyard anchors left in the text by the
+    /// parser into the offsets of the element's notes and tags, in order.
+    /// Notes and tags without an anchor sit at the end of the text. Each
+    /// boneyard anchor takes the next of `boneyard`.
+    pub(crate) fn anchor_notes(&mut self, boneyard: &mut impl Iterator<Item = String>) {
+        use Element::*;
+        match self {
+            Action(text, attributes)
+            | Character(text, attributes)
+            | SceneHeading(text, attributes)
+            | Lyric(text, attributes)
+            | Parenthetical(text, attributes)
+            | Dialogue(text, attributes)
+            | Transition(text, attributes)
+            | ColdOpening(text, attributes)
+            | NewAct(text, attributes)
+            | EndOfAct(text, attributes)
+            | Section(text, attributes, _) => {
+                let (note_offsets, tag_offsets, boneyard_offsets) = text.take_anchors();
+                let end = text.plain_text().chars().count();
+                if let Some(notes) = &mut attributes.notes {
+                    for (index, note) in notes.iter_mut().enumerate() {
+                        note.offset = note_offsets.get(index).copied().unwrap_or(end);
+                    }
+                }
+                for (index, tag) in attributes.tags.iter_mut().enumerate() {
+                    tag.offset = tag_offsets.get(index).copied().unwrap_or(end);
+                }
+                attributes.boneyard.extend(
+                    boneyard_offsets
+                        .into_iter()
+                        .zip(boneyard)
+                        .map(|(offset, text)| Note { text, offset }),
+                );
+            }
+            DialogueBlock(elements) | DualDialogueBlock(elements) => {
+                for element in elements {
+                    element.anchor_notes(boneyard);
+                }
+            }
+            // The parser cuts boneyard on synopsis lines out whole.
+            Synopsis(_) | Boneyard(_) | PageBreak => {}
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Serialize)]
@@ -209,10 +464,39 @@ struct SerializeElementHelper<'a> {
     element_type: &'a str,
     text: &'a ElementText,
     attributes: &'a Attributes,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    heading: Option<SceneHeadingParts>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cue: Option<CharacterCue>,
+}
+
+/// Serializes an element inside a dual dialogue block, where the second
+/// speaker's cue is marked `dual`.
+struct SerializeDualSide<'a> {
+    element: &'a Element,
+    dual: bool,
+}
+
+impl Serialize for SerializeDualSide<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.element.serialize_in_side(serializer, self.dual)
+    }
 }
 
 impl Serialize for Element {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.serialize_in_side(serializer, false)
+    }
+}
+
+impl Element {
+    fn serialize_in_side<S>(&self, serializer: S, dual: bool) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
@@ -231,19 +515,36 @@ impl Serialize for Element {
                     element_type: self.name(),
                     text,
                     attributes,
+                    heading: self.scene_heading(),
+                    cue: self.character_cues().pop().map(|mut cue| {
+                        cue.dual |= dual;
+                        cue
+                    }),
                 };
                 el.serialize(serializer)
             }
             Element::DialogueBlock(ref block) => {
                 let mut map = serializer.serialize_map(Some(2))?;
                 map.serialize_entry("type", "DialogueBlock")?;
-                map.serialize_entry("block", block)?;
+                let block: Vec<_> = block
+                    .iter()
+                    .map(|element| SerializeDualSide { element, dual })
+                    .collect();
+                map.serialize_entry("block", &block)?;
                 map.end()
             }
             Element::DualDialogueBlock(ref blocks) => {
                 let mut map = serializer.serialize_map(Some(2))?;
                 map.serialize_entry("type", "DualDialogueBlock")?;
-                map.serialize_entry("blocks", blocks)?;
+                let blocks: Vec<_> = blocks
+                    .iter()
+                    .enumerate()
+                    .map(|(index, element)| SerializeDualSide {
+                        element,
+                        dual: index > 0,
+                    })
+                    .collect();
+                map.serialize_entry("blocks", &blocks)?;
                 map.end()
             }
             Element::Section(ref text, ref attributes, ref level) => {
@@ -260,21 +561,169 @@ impl Serialize for Element {
                 map.serialize_entry("text", text)?;
                 map.end()
             }
+            Element::Boneyard(ref text) => {
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry("type", "Boneyard")?;
+                map.serialize_entry("text", text)?;
+                map.end()
+            }
             Element::PageBreak => serializer.serialize_none(),
         }
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize)]
+/// The shapes `Element` serializes to, keyed by their `type`. Derived fields
+/// such as `heading` and `cue` are ignored on the way back in.
+#[derive(Deserialize)]
+#[serde(tag = "type")]
+enum DeserializeElementHelper {
+    Action {
+        text: ElementText,
+        attributes: Attributes,
+    },
+    Character {
+        text: ElementText,
+        attributes: Attributes,
+    },
+    #[serde(rename = "Scene Heading")]
+    SceneHeading {
+        text: ElementText,
+        attributes: Attributes,
+    },
+    Lyric {
+        text: ElementText,
+        attributes: Attributes,
+    },
+    Parenthetical {
+        text: ElementText,
+        attributes: Attributes,
+    },
+    Dialogue {
+        text: ElementText,
+        attributes: Attributes,
+    },
+    Transition {
+        text: ElementText,
+        attributes: Attributes,
+    },
+    #[serde(rename = "Cold Opening")]
+    ColdOpening {
+        text: ElementText,
+        attributes: Attributes,
+    },
+    #[serde(rename = "New Act")]
+    NewAct {
+        text: ElementText,
+        attributes: Attributes,
+    },
+    #[serde(rename = "End of Act")]
+    EndOfAct {
+        text: ElementText,
+        attributes: Attributes,
+    },
+    DialogueBlock {
+        block: Vec<Element>,
+    },
+    DualDialogueBlock {
+        blocks: Vec<Element>,
+    },
+    Section {
+        text: ElementText,
+        attributes: Attributes,
+        level: u8,
+    },
+    Synopsis {
+        text: ElementText,
+    },
+    Boneyard {
+        text: ElementText,
+    },
+}
+
+impl<'de> Deserialize<'de> for Element {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use DeserializeElementHelper as Helper;
+        // Page breaks serialize as `null`.
+        let Some(helper) = Option::<Helper>::deserialize(deserializer)? else {
+            return Ok(Element::PageBreak);
+        };
+        Ok(match helper {
+            Helper::Action { text, attributes } => Element::Action(text, attributes),
+            Helper::Character { text, attributes } => Element::Character(text, attributes),
+            Helper::SceneHeading { text, attributes } => Element::SceneHeading(text, attributes),
+            Helper::Lyric { text, attributes } => Element::Lyric(text, attributes),
+            Helper::Parenthetical { text, attributes } => Element::Parenthetical(text, attributes),
+            Helper::Dialogue { text, attributes } => Element::Dialogue(text, attributes),
+            Helper::Transition { text, attributes } => Element::Transition(text, attributes),
+            Helper::ColdOpening { text, attributes } => Element::ColdOpening(text, attributes),
+            Helper::NewAct { text, attributes } => Element::NewAct(text, attributes),
+            Helper::EndOfAct { text, attributes } => Element::EndOfAct(text, attributes),
+            Helper::DialogueBlock { block } => Element::DialogueBlock(block),
+            Helper::DualDialogueBlock { blocks } => Element::DualDialogueBlock(blocks),
+            Helper::Section {
+                text,
+                attributes,
+                level,
+            } => Element::Section(text, attributes, level),
+            Helper::Synopsis { text } => Element::Synopsis(text),
+            Helper::Boneyard { text } => Element::Boneyard(text),
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Attributes {
     pub centered: bool,
     pub starts_new_page: bool,
     pub scene_number: Option<String>,
-    pub notes: Option<Vec<String>>,
+    pub notes: Option<Vec<Note>>,
     pub layout_overrides: ElementLayoutOverrides,
-}
-
-#[derive(Clone, Debug, PartialEq, Serialize)]
+    /// A stable id for the element, kept through JSON and FDX. See
+    /// `Screenplay::assign_element_ids`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// The id of the revision set that last changed the whole element. See
+    /// `Screenplay::mark_revisions`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revision: Option<u32>,
+    /// The production elements tagged in the element, such as the props of
+    /// `[[prop: revolver]]` notes. See [`crate::breakdown`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<BreakdownTag>,
+    /// Boneyard written inside the element, such as the `/* maybe */` of
+    /// `Bob sits. /* maybe */`, with its offset kept the way notes keep
+    /// theirs. Boneyard on lines of its own is an [`Element::Boneyard`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub boneyard: Vec<Note>,
+}
+
+/// A `[[note]]` and where it sat in its element, as a character offset into
+/// the element's plain text.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Note {
+    pub text: String,
+    pub offset: usize,
+}
+
+/// Marks where a note was taken out of element text until the note's offset
+/// is known. The parser drops it, [`TAG_ANCHOR`] and [`BONEYARD_ANCHOR`]
+/// from its input.
+pub(crate) const NOTE_ANCHOR: char = '\u{E000}';
+
+/// Marks where boneyard was taken out of element text, like [`NOTE_ANCHOR`].
+pub(crate) const BONEYARD_ANCHOR: char = '\u{E001}';
+
+/// Marks where a breakdown tag note was taken out of element text, like
+/// [`NOTE_ANCHOR`].
+pub(crate) const TAG_ANCHOR: char = '\u{E002}';
+
+/// The characters the parser anchors notes, tags and boneyard with.
+pub(crate) const ANCHORS: [char; 3] = [NOTE_ANCHOR, TAG_ANCHOR, BONEYARD_ANCHOR];
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum ElementText {
     Plain(String),
     Styled(Vec<TextRun>),
@@ -287,6 +736,99 @@ impl ElementText {
             ElementText::Styled(runs) => runs.iter().map(|run| run.content.as_str()).collect(),
         }
     }
+
+    /// Finds a character offset into the plain text as the index of a text
+    /// run and a character offset within that run. An offset between two runs
+    /// lands at the end of the earlier one. Plain text is a single run.
+    pub fn run_offset(&self, offset: usize) -> (usize, usize) {
+        let ElementText::Styled(runs) = self else {
+            return (0, offset);
+        };
+        let mut run_start = 0;
+        for (index, run) in runs.iter().enumerate() {
+            let run_len = run.content.chars().count();
+            if offset <= run_start + run_len {
+                return (index, offset - run_start);
+            }
+            run_start += run_len;
+        }
+        match runs.len() {
+            0 => (0, 0),
+            len => (len - 1, runs[len - 1].content.chars().count()),
+        }
+    }
+
+    /// A copy with `marker` inserted at each of `offsets`.
+    pub(crate) fn with_markers(&self, marker: char, offsets: &[usize]) -> ElementText {
+        let mut marked = self.clone();
+        for &offset in offsets.iter().rev() {
+            marked.insert_str(offset, marker.encode_utf8(&mut [0; 4]));
+        }
+        marked
+    }
+
+    /// Inserts `insert` at a character offset into the plain text, at the end
+    /// of the earlier run when the offset falls between two.
+    pub(crate) fn insert_str(&mut self, offset: usize, insert: &str) {
+        let (run, offset) = self.run_offset(offset);
+        let content = match self {
+            ElementText::Plain(text) => text,
+            ElementText::Styled(runs) => {
+                if runs.is_empty() {
+                    runs.push(TextRun {
+                        content: String::new(),
+                        text_style: HashSet::new(),
+                        revision: None,
+                    });
+                }
+                &mut runs[run].content
+            }
+        };
+        let byte_offset = content
+            .char_indices()
+            .nth(offset)
+            .map(|(index, _)| index)
+            .unwrap_or(content.len());
+        content.insert_str(byte_offset, insert);
+    }
+
+    /// Removes note, tag and boneyard anchors, returning their character
+    /// offsets in the text that is left.
+    pub(crate) fn take_anchors(&mut self) -> (Vec<usize>, Vec<usize>, Vec<usize>) {
+        let mut notes = Vec::new();
+        let mut tags = Vec::new();
+        let mut boneyard = Vec::new();
+        let mut position = 0;
+        let mut take = |content: &mut String| {
+            if !content.contains(ANCHORS) {
+                position += content.chars().count();
+                return;
+            }
+            let mut kept = String::with_capacity(content.len());
+            for ch in content.chars() {
+                match ch {
+                    NOTE_ANCHOR => notes.push(position),
+                    TAG_ANCHOR => tags.push(position),
+                    BONEYARD_ANCHOR => boneyard.push(position),
+                    _ => {
+                        kept.push(ch);
+                        position += 1;
+                    }
+                }
+            }
+            *content = kept;
+        };
+        match self {
+            ElementText::Plain(text) => take(text),
+            ElementText::Styled(runs) => {
+                for run in runs.iter_mut() {
+                    take(&mut run.content);
+                }
+                runs.retain(|run| !run.content.is_empty());
+            }
+        }
+        (notes, tags, boneyard)
+    }
 }
 
 impl Default for ElementText {
@@ -312,11 +854,15 @@ pub fn p(p: &str) -> ElementText {
     ElementText::Plain(p.to_string())
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct TextRun {
     pub content: String,
     #[serde(serialize_with = "text_style_serialize")]
     pub text_style: HashSet<String>,
+    /// The id of the revision set that last changed this run, when only part
+    /// of its element was changed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revision: Option<u32>,
 }
 
 // Convenience function
@@ -328,6 +874,7 @@ pub fn tr(t: &str, s: Vec<&str>) -> TextRun {
     TextRun {
         content: t.to_string(),
         text_style: styles,
+        revision: None,
     }
 }
 
@@ -348,6 +895,10 @@ impl Default for Attributes {
             scene_number: None,
             notes: None,
             layout_overrides: ElementLayoutOverrides::default(),
+            id: None,
+            revision: None,
+            tags: Vec::new(),
+            boneyard: Vec::new(),
         }
     }
 }
@@ -0,0 +1,137 @@
+use std::collections::HashSet;
+
+use crate::diff::{Step, align};
+use crate::{Element, Screenplay};
+
+impl Screenplay {
+    /// Gives every element with attributes, including those inside dialogue
+    /// blocks, an id unless it already has one.
+    ///
+    /// New ids come from the element's type and words, ignoring case,
+    /// spacing and punctuation, so parsing the same text twice gives the same
+    /// ids. Only the first element with a given type and words gets the id of
+    /// its words alone; a later one, such as a repeated character cue, also
+    /// takes the elements before and after it into account, and is numbered
+    /// in order when that is not enough. Ids that are already set are kept, so
+    /// an element read back from JSON or FDX keeps its id after its text
+    /// changes. A repeated id is replaced on every element after the first.
+    ///
+    /// A new id changes when the element's words change, and a repeated
+    /// element's id also changes when its neighbours or the copies before it
+    /// do. Only `carry_element_ids` keeps ids through every edit, so a draft
+    /// with nowhere to store ids, like Fountain, should take them from its
+    /// earlier draft with it first.
+    pub fn assign_element_ids(&mut self) {
+        let mut elements = Vec::new();
+        collect_identifiable(&mut self.elements, &mut elements);
+
+        let keys: Vec<String> = elements
+            .iter()
+            .map(|element| content_key(element))
+            .collect();
+        let mut taken = HashSet::new();
+        let mut missing = Vec::new();
+        for (index, element) in elements.iter().enumerate() {
+            match element.id() {
+                Some(id) if taken.insert(id.to_string()) => {}
+                _ => missing.push(index),
+            }
+        }
+
+        for index in missing {
+            let key = keys[index].as_str();
+            let mut base = hex_id(fnv1a(&[key]));
+            if taken.contains(&base) {
+                let before = index
+                    .checked_sub(1)
+                    .map_or("", |before| keys[before].as_str());
+                let after = keys.get(index + 1).map_or("", String::as_str);
+                base = hex_id(fnv1a(&[before, key, after]));
+            }
+            let mut id = base.clone();
+            let mut occurrence = 1;
+            while taken.contains(&id) {
+                occurrence += 1;
+                id = format!("{base}-{occurrence}");
+            }
+            taken.insert(id.clone());
+            if let Some(attributes) = elements[index].attributes_mut() {
+                attributes.id = Some(id);
+            }
+        }
+    }
+
+    /// Gives each element without an id the id of the element it lines up
+    /// with in `previous`, an earlier draft, matched the way
+    /// [`crate::diff::diff`] matches them. Edited and moved paragraphs keep
+    /// their ids this way; elements new in this draft are left without one.
+    pub fn carry_element_ids(&mut self, previous: &Screenplay) {
+        carry_sequence(&previous.elements, &mut self.elements);
+    }
+}
+
+fn carry_sequence(old: &[Element], new: &mut [Element]) {
+    for step in align(old, new) {
+        if let Step::Same(i, j) | Step::Similar(i, j) | Step::Moved(i, j) = step {
+            carry_element(&old[i], &mut new[j]);
+        }
+    }
+}
+
+fn carry_element(old: &Element, new: &mut Element) {
+    if let (
+        Element::DialogueBlock(old_block) | Element::DualDialogueBlock(old_block),
+        Element::DialogueBlock(new_block) | Element::DualDialogueBlock(new_block),
+    ) = (old, &mut *new)
+    {
+        carry_sequence(old_block, new_block);
+        return;
+    }
+    if let (Some(id), Some(attributes)) = (old.id(), new.attributes_mut())
+        && attributes.id.is_none()
+    {
+        attributes.id = Some(id.to_string());
+    }
+}
+
+fn collect_identifiable<'a>(elements: &'a mut [Element], out: &mut Vec<&'a mut Element>) {
+    for element in elements {
+        match element {
+            Element::DialogueBlock(block) | Element::DualDialogueBlock(block) => {
+                collect_identifiable(block, out);
+            }
+            other if other.attributes().is_some() => out.push(other),
+            _ => {}
+        }
+    }
+}
+
+fn content_key(element: &Element) -> String {
+    let text = element
+        .text()
+        .map(|text| text.plain_text().to_lowercase())
+        .unwrap_or_default();
+    let words: Vec<&str> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect();
+    format!("{}:{}", element.name(), words.join(" "))
+}
+
+fn hex_id(hash: u64) -> String {
+    format!("{:012x}", hash & 0xffff_ffff_ffff)
+}
+
+/// 64-bit FNV-1a, which unlike the standard library's hasher is guaranteed
+/// to give the same ids on every platform and release.
+fn fnv1a(parts: &[&str]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for (index, part) in parts.iter().enumerate() {
+        let separator: &[u8] = if index == 0 { b"" } else { b"\n" };
+        for byte in separator.iter().chain(part.as_bytes()) {
+            hash ^= u64::from(*byte);
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+    hash
+}
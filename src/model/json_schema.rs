@@ -0,0 +1,327 @@
+use serde_json::{Value, json};
+
+use crate::BreakdownCategory;
+
+/// The version of the JSON document format written by
+/// `Screenplay::to_json_string` and `Screenplay::to_json_value`. It goes up
+/// whenever the shape of that JSON changes.
+pub const SCHEMA_VERSION: u32 = 7;
+
+/// Builds the JSON Schema (draft 2020-12) describing the JSON document format
+/// at [`SCHEMA_VERSION`].
+pub fn json_schema() -> Value {
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "JumpCut screenplay",
+        "description": "A screenplay as written by JumpCut's JSON output.",
+        "type": "object",
+        "required": ["schema_version", "metadata", "imported_layout", "imported_title_page", "elements"],
+        "additionalProperties": false,
+        "properties": {
+            "schema_version": { "const": SCHEMA_VERSION },
+            "metadata": {
+                "description": "Title-page and `fmt` metadata, keyed by lowercase name, one entry per line.",
+                "type": "object",
+                "additionalProperties": { "type": "array", "items": { "$ref": "#/$defs/ElementText" } }
+            },
+            "imported_layout": nullable(json!({ "$ref": "#/$defs/ImportedLayoutOverrides" })),
+            "imported_title_page": nullable(json!({ "$ref": "#/$defs/ImportedTitlePage" })),
+            "revisions": {
+                "description": "Revision sets that elements and runs refer to by id, ordered by id. Left out when empty. Added in version 3.",
+                "type": "array",
+                "items": { "$ref": "#/$defs/RevisionSet" }
+            },
+            "page_lock": {
+                "description": "The locked pagination the screenplay is paginated against. Left out when unlocked. Added in version 4.",
+                "$ref": "#/$defs/PageLock"
+            },
+            "elements": { "type": "array", "items": { "$ref": "#/$defs/Element" } }
+        },
+        "$defs": {
+            "ElementText": {
+                "description": "Unstyled text, or a list of styled runs.",
+                "oneOf": [
+                    object(&["Plain"], json!({ "Plain": { "type": "string" } })),
+                    object(&["Styled"], json!({ "Styled": { "type": "array", "items": { "$ref": "#/$defs/TextRun" } } }))
+                ]
+            },
+            "TextRun": object(&["content", "text_style"], json!({
+                "content": { "type": "string" },
+                "text_style": {
+                    "description": "Style names such as `Bold`, `Italic` and `Underline`, sorted.",
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "uniqueItems": true
+                },
+                "revision": {
+                    "description": "Id of the revision set that last changed this run. Added in version 3.",
+                    "type": "integer",
+                    "minimum": 1
+                }
+            })),
+            "RevisionSet": object(&["id", "name", "color"], json!({
+                "id": { "type": "integer", "minimum": 1 },
+                "name": { "type": "string" },
+                "color": {
+                    "description": "A standard revision color such as `Blue` or `Pink`, or a `#RRGGBB` hex color.",
+                    "type": "string"
+                },
+                "date": { "type": "string" }
+            })),
+            "PageLock": object(&["pages"], json!({
+                "pages": { "type": "array", "items": { "$ref": "#/$defs/LockedPage" } }
+            })),
+            "LockedPage": object(&["label", "elements"], json!({
+                "label": {
+                    "description": "The printed page number, such as `12`, `12A` or `13-14`.",
+                    "type": "string"
+                },
+                "elements": {
+                    "description": "Ids of the elements on the page, in order.",
+                    "type": "array",
+                    "items": { "type": "string" }
+                },
+                "continued": {
+                    "description": "Whether the page starts partway through its first element. Left out when false.",
+                    "type": "boolean"
+                }
+            })),
+            "Attributes": object(&["centered", "starts_new_page", "scene_number", "notes", "layout_overrides"], json!({
+                "centered": { "type": "boolean" },
+                "starts_new_page": { "type": "boolean" },
+                "scene_number": nullable(json!({ "type": "string" })),
+                "notes": nullable(json!({ "type": "array", "items": { "$ref": "#/$defs/Note" } })),
+                "layout_overrides": { "$ref": "#/$defs/ElementLayoutOverrides" },
+                "id": {
+                    "description": "Stable id derived from the element's text and kept once assigned. Added in version 2.",
+                    "type": "string"
+                },
+                "revision": {
+                    "description": "Id of the revision set that last changed the whole element. Added in version 3.",
+                    "type": "integer",
+                    "minimum": 1
+                },
+                "tags": {
+                    "description": "Production elements tagged in the element, in order. Left out when empty. Added in version 6.",
+                    "type": "array",
+                    "items": { "$ref": "#/$defs/BreakdownTag" }
+                },
+                "boneyard": {
+                    "description": "Boneyard inside the element, each at the offset where it sat. Left out when empty. Added in version 7.",
+                    "type": "array",
+                    "items": { "$ref": "#/$defs/Note" }
+                }
+            })),
+            "BreakdownTag": object(&["category", "name", "offset"], json!({
+                "category": { "enum": BreakdownCategory::ALL },
+                "name": { "type": "string" },
+                "offset": {
+                    "description": "Character offset into the element's text where the tag sits.",
+                    "type": "integer",
+                    "minimum": 0
+                }
+            })),
+            "Note": object(&["text", "offset"], json!({
+                "text": { "type": "string" },
+                "offset": {
+                    "description": "Character offset into the element's text where the note sits.",
+                    "type": "integer",
+                    "minimum": 0
+                }
+            })),
+            "ElementLayoutOverrides": object(&["space_before_delta", "right_indent_delta"], json!({
+                "space_before_delta": nullable_number(),
+                "right_indent_delta": nullable_number()
+            })),
+            "Element": {
+                "description": "One screenplay element. Page breaks are written as `null`.",
+                "oneOf": [
+                    { "type": "null" },
+                    { "$ref": "#/$defs/TextElement" },
+                    object(&["type", "block"], json!({
+                        "type": { "const": "DialogueBlock" },
+                        "block": { "type": "array", "items": { "$ref": "#/$defs/Element" } }
+                    })),
+                    object(&["type", "blocks"], json!({
+                        "type": { "const": "DualDialogueBlock" },
+                        "blocks": { "type": "array", "items": { "$ref": "#/$defs/Element" } }
+                    })),
+                    object(&["type", "text", "attributes", "level"], json!({
+                        "type": { "const": "Section" },
+                        "text": { "$ref": "#/$defs/ElementText" },
+                        "attributes": { "$ref": "#/$defs/Attributes" },
+                        "level": { "type": "integer", "minimum": 1, "maximum": 255 }
+                    })),
+                    object(&["type", "text"], json!({
+                        "type": { "enum": ["Synopsis", "Boneyard"] },
+                        "text": { "$ref": "#/$defs/ElementText" }
+                    }))
+                ]
+            },
+            "TextElement": object(&["type", "text", "attributes"], json!({
+                "type": {
+                    "enum": [
+                        "Action", "Character", "Scene Heading", "Lyric", "Parenthetical",
+                        "Dialogue", "Transition", "Cold Opening", "New Act", "End of Act"
+                    ]
+                },
+                "text": { "$ref": "#/$defs/ElementText" },
+                "attributes": { "$ref": "#/$defs/Attributes" },
+                "heading": {
+                    "description": "Only on scene headings; derived from the text and ignored when read back.",
+                    "$ref": "#/$defs/SceneHeadingParts"
+                },
+                "cue": {
+                    "description": "Only on characters; derived from the text and ignored when read back.",
+                    "$ref": "#/$defs/CharacterCue"
+                },
+                "length": {
+                    "description": "Only on scene headings, and only from `Screenplay::to_json_value_with_scene_lengths`; ignored when read back. Added in version 5.",
+                    "$ref": "#/$defs/SceneLength"
+                }
+            })),
+            "SceneHeadingParts": object(&["prefix", "setting", "locations", "time_of_day"], json!({
+                "prefix": nullable(json!({ "type": "string" })),
+                "setting": nullable(json!({
+                    "enum": ["Interior", "Exterior", "InteriorExterior", "Establishing"]
+                })),
+                "locations": { "type": "array", "items": { "type": "string" } },
+                "time_of_day": nullable(json!({ "type": "string" }))
+            })),
+            "SceneLength": object(&["eighths", "text", "first_page", "last_page"], json!({
+                "eighths": {
+                    "description": "The scene's length in eighths of a page, at least 1.",
+                    "type": "integer",
+                    "minimum": 1
+                },
+                "text": {
+                    "description": "The length as written on breakdown sheets, such as `2 3/8`.",
+                    "type": "string"
+                },
+                "first_page": { "type": "string" },
+                "last_page": { "type": "string" }
+            })),
+            "CharacterCue": object(&["name", "extensions", "dual"], json!({
+                "name": { "type": "string" },
+                "extensions": { "type": "array", "items": { "type": "string" } },
+                "dual": { "type": "boolean" }
+            })),
+            "ImportedLayoutOverrides": object(&["page", "element_styles", "mores_and_continueds"], json!({
+                "page": object(
+                    &["page_width", "page_height", "top_margin", "bottom_margin", "header_margin", "footer_margin"],
+                    json!({
+                        "page_width": nullable_number(),
+                        "page_height": nullable_number(),
+                        "top_margin": nullable_number(),
+                        "bottom_margin": nullable_number(),
+                        "header_margin": nullable_number(),
+                        "footer_margin": nullable_number()
+                    })
+                ),
+                "element_styles": {
+                    "type": "object",
+                    "propertyNames": {
+                        "enum": [
+                            "Action", "SceneHeading", "Character", "Dialogue", "Parenthetical",
+                            "Transition", "Lyric", "ColdOpening", "NewAct", "EndOfAct"
+                        ]
+                    },
+                    "additionalProperties": { "$ref": "#/$defs/ImportedElementStyle" }
+                },
+                "mores_and_continueds": object(&["dialogue", "scene"], json!({
+                    "dialogue": object(
+                        &["automatic_character_continueds", "top_of_next", "bottom_of_page", "dialogue_top", "dialogue_bottom"],
+                        json!({
+                            "automatic_character_continueds": nullable(json!({ "type": "boolean" })),
+                            "top_of_next": nullable(json!({ "type": "boolean" })),
+                            "bottom_of_page": nullable(json!({ "type": "boolean" })),
+                            "dialogue_top": nullable(json!({ "type": "string" })),
+                            "dialogue_bottom": nullable(json!({ "type": "string" }))
+                        })
+                    ),
+                    "scene": object(
+                        &["top_of_next", "bottom_of_page", "continued_number", "scene_top", "scene_bottom"],
+                        json!({
+                            "top_of_next": nullable(json!({ "type": "boolean" })),
+                            "bottom_of_page": nullable(json!({ "type": "boolean" })),
+                            "continued_number": nullable(json!({ "type": "boolean" })),
+                            "scene_top": nullable(json!({ "type": "string" })),
+                            "scene_bottom": nullable(json!({ "type": "string" }))
+                        })
+                    )
+                }))
+            })),
+            "ImportedElementStyle": object(
+                &[
+                    "first_indent", "left_indent", "right_indent", "spacing_before", "line_spacing",
+                    "alignment", "starts_new_page", "underline", "bold", "italic"
+                ],
+                json!({
+                    "first_indent": nullable_number(),
+                    "left_indent": nullable_number(),
+                    "right_indent": nullable_number(),
+                    "spacing_before": nullable_number(),
+                    "line_spacing": nullable_number(),
+                    "alignment": nullable(json!({ "enum": ["Left", "Center", "Right"] })),
+                    "starts_new_page": nullable(json!({ "type": "boolean" })),
+                    "underline": nullable(json!({ "type": "boolean" })),
+                    "bold": nullable(json!({ "type": "boolean" })),
+                    "italic": nullable(json!({ "type": "boolean" }))
+                })
+            ),
+            "ImportedTitlePage": object(&["header_footer", "pages"], json!({
+                "header_footer": object(
+                    &["header_visible", "header_first_page", "header_has_page_number", "starting_page"],
+                    json!({
+                        "header_visible": { "type": "boolean" },
+                        "header_first_page": { "type": "boolean" },
+                        "header_has_page_number": { "type": "boolean" },
+                        "starting_page": nullable(json!({ "type": "integer", "minimum": 0 }))
+                    })
+                ),
+                "pages": {
+                    "type": "array",
+                    "items": object(&["paragraphs"], json!({
+                        "paragraphs": { "type": "array", "items": { "$ref": "#/$defs/ImportedTitlePageParagraph" } }
+                    }))
+                }
+            })),
+            "ImportedTitlePageParagraph": object(
+                &["text", "alignment", "first_indent", "left_indent", "space_before", "tab_stops"],
+                json!({
+                    "text": { "$ref": "#/$defs/ElementText" },
+                    "alignment": { "enum": ["Left", "Center", "Right", "Full"] },
+                    "first_indent": nullable_number(),
+                    "left_indent": nullable_number(),
+                    "space_before": nullable_number(),
+                    "tab_stops": {
+                        "type": "array",
+                        "items": object(&["position", "kind"], json!({
+                            "position": { "type": "number" },
+                            "kind": { "enum": ["Left", "Center", "Right"] }
+                        }))
+                    }
+                })
+            )
+        }
+    })
+}
+
+/// An object schema with exactly the given properties, of which `required`
+/// must be present.
+fn object(required: &[&str], properties: Value) -> Value {
+    json!({
+        "type": "object",
+        "required": required,
+        "additionalProperties": false,
+        "properties": properties
+    })
+}
+
+fn nullable(schema: Value) -> Value {
+    json!({ "oneOf": [{ "type": "null" }, schema] })
+}
+
+fn nullable_number() -> Value {
+    json!({ "type": ["number", "null"] })
+}
@@ -1,7 +1,27 @@
+pub mod breakdown_tag;
+pub mod character_cue;
 pub mod converters;
 pub mod document;
+pub mod element_id;
+pub mod json_schema;
+pub mod outline;
+pub mod page_lock;
+pub mod rename;
 pub mod render_attributes;
+pub mod revision;
+pub mod scene_heading;
+pub mod scene_numbers;
+pub mod source_map;
 pub mod styled_text;
 pub mod title_page;
 
+pub use breakdown_tag::{BreakdownCategory, BreakdownTag};
+pub use character_cue::CharacterCue;
 pub use document::*;
+pub use json_schema::{SCHEMA_VERSION, json_schema};
+pub use outline::{Outline, OutlineNode, OutlineNodeKind};
+pub use page_lock::{LockedPage, PageLock};
+pub use rename::{RenameChange, RenameOptions};
+pub use revision::RevisionSet;
+pub use scene_heading::{DayNight, SceneHeadingParts, SceneSetting};
+pub use scene_numbers::SceneNumbering;
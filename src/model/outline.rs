@@ -0,0 +1,212 @@
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::pagination::{normalized_element_ids, PaginatedScreenplay, ScreenplayLayoutProfile};
+use crate::rendering::text::default_pagination_scope;
+use crate::{Element, Screenplay};
+
+/// What an [`OutlineNode`] stands for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum OutlineNodeKind {
+    Section,
+    Scene,
+    Synopsis,
+    Note,
+}
+
+/// One entry of a screenplay's outline.
+///
+/// `element_index` points into `Screenplay::elements`; notes on dialogue
+/// point at their dialogue block. `page` is only filled in by
+/// [`Screenplay::outline_with_pages`].
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct OutlineNode {
+    pub kind: OutlineNodeKind,
+    pub text: String,
+    pub element_index: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub level: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scene_number: Option<String>,
+    pub page: Option<u32>,
+    pub children: Vec<OutlineNode>,
+}
+
+/// The sections, scenes, synopses and notes of a screenplay as a tree:
+/// sections hold deeper sections and scenes, and scenes hold the synopses and
+/// notes written in them.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct Outline {
+    pub nodes: Vec<OutlineNode>,
+}
+
+impl Outline {
+    pub fn to_json_string(&self) -> String {
+        serde_json::to_string(self)
+            .expect("Should be impossible for this JSON serialization to fail.")
+    }
+
+    pub fn to_json_value(&self) -> serde_json::Value {
+        serde_json::to_value(self)
+            .expect("Should be impossible for this JSON serialization to fail.")
+    }
+}
+
+impl Screenplay {
+    /// Builds the outline of the screenplay without page numbers.
+    pub fn outline(&self) -> Outline {
+        build_outline(self, &vec![None; self.elements.len()])
+    }
+
+    /// Builds the outline of the screenplay, paginating it to give every node
+    /// the page it starts on. Sections and synopses take the page of the next
+    /// element that prints.
+    pub fn outline_with_pages(&self) -> Outline {
+        build_outline(self, &element_pages(self))
+    }
+}
+
+#[derive(Default)]
+struct OutlineBuilder {
+    roots: Vec<OutlineNode>,
+    sections: Vec<OutlineNode>,
+    scene: Option<OutlineNode>,
+}
+
+impl OutlineBuilder {
+    fn attach(&mut self, node: OutlineNode) {
+        match (&mut self.scene, self.sections.last_mut()) {
+            (Some(scene), _) => scene.children.push(node),
+            (None, Some(section)) => section.children.push(node),
+            (None, None) => self.roots.push(node),
+        }
+    }
+
+    fn close_scene(&mut self) {
+        if let Some(scene) = self.scene.take() {
+            self.attach(scene);
+        }
+    }
+
+    fn close_sections_from(&mut self, level: u8) {
+        self.close_scene();
+        while self
+            .sections
+            .last()
+            .is_some_and(|section| section.level.unwrap_or_default() >= level)
+        {
+            let section = self.sections.pop().expect("checked above");
+            self.attach(section);
+        }
+    }
+
+    fn finish(mut self) -> Vec<OutlineNode> {
+        self.close_sections_from(0);
+        self.roots
+    }
+}
+
+fn build_outline(screenplay: &Screenplay, pages: &[Option<u32>]) -> Outline {
+    let mut builder = OutlineBuilder::default();
+
+    for (index, element) in screenplay.elements.iter().enumerate() {
+        let node = |kind, text: String| OutlineNode {
+            kind,
+            text,
+            element_index: index,
+            level: None,
+            scene_number: None,
+            page: pages[index],
+            children: Vec::new(),
+        };
+        match element {
+            Element::Section(text, _, level) => {
+                builder.close_sections_from(*level);
+                builder.sections.push(OutlineNode {
+                    level: Some(*level),
+                    ..node(OutlineNodeKind::Section, text.plain_text())
+                });
+            }
+            Element::SceneHeading(text, attributes) => {
+                builder.close_scene();
+                builder.scene = Some(OutlineNode {
+                    scene_number: attributes.scene_number.clone(),
+                    ..node(OutlineNodeKind::Scene, text.plain_text())
+                });
+            }
+            Element::Synopsis(text) => {
+                builder.attach(node(OutlineNodeKind::Synopsis, text.plain_text()));
+            }
+            _ => {}
+        }
+        for note in element_notes(element) {
+            builder.attach(node(OutlineNodeKind::Note, note));
+        }
+    }
+
+    Outline {
+        nodes: builder.finish(),
+    }
+}
+
+fn element_notes(element: &Element) -> Vec<String> {
+    match element {
+        Element::DialogueBlock(block) | Element::DualDialogueBlock(block) => {
+            block.iter().flat_map(element_notes).collect()
+        }
+        Element::Action(_, attributes)
+        | Element::Character(_, attributes)
+        | Element::SceneHeading(_, attributes)
+        | Element::Lyric(_, attributes)
+        | Element::Parenthetical(_, attributes)
+        | Element::Dialogue(_, attributes)
+        | Element::Transition(_, attributes)
+        | Element::Section(_, attributes, _)
+        | Element::ColdOpening(_, attributes)
+        | Element::NewAct(_, attributes)
+        | Element::EndOfAct(_, attributes) => attributes
+            .notes
+            .iter()
+            .flatten()
+            .map(|note| note.text.clone())
+            .collect(),
+        Element::Synopsis(_) | Element::Boneyard(_) | Element::PageBreak => Vec::new(),
+    }
+}
+
+/// The page each top-level element starts on, matching pagination items to
+/// elements through the ids `normalize_screenplay` hands out.
+fn element_pages(screenplay: &Screenplay) -> Vec<Option<u32>> {
+    let profile = ScreenplayLayoutProfile::from_screenplay(screenplay);
+    let paginated = PaginatedScreenplay::from_screenplay(
+        "outline",
+        screenplay,
+        profile.lines_per_page,
+        default_pagination_scope(screenplay),
+    );
+    let mut item_pages: HashMap<&str, u32> = HashMap::new();
+    for page in &paginated.pages {
+        for item in &page.items {
+            item_pages
+                .entry(item.element_id.as_str())
+                .or_insert(page.metadata.number);
+        }
+    }
+
+    let mut pages: Vec<Option<u32>> = vec![None; screenplay.elements.len()];
+    for (index, _, element_id) in normalized_element_ids(screenplay) {
+        if let Some(&page) = item_pages.get(element_id.as_str()) {
+            pages[index] = Some(pages[index].map_or(page, |first| first.min(page)));
+        }
+    }
+
+    let mut next_page = None;
+    for page in pages.iter_mut().rev() {
+        match page {
+            Some(number) => next_page = Some(*number),
+            None => *page = next_page,
+        }
+    }
+    pages
+}
+
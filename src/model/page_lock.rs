@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+
+/// The pages of a locked production draft. Later drafts are paginated
+/// against it, keeping its page breaks: new material flows onto inserted
+/// pages such as `12A`, and pages whose material was cut are folded into the
+/// page before, which becomes `13-14`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageLock {
+    pub pages: Vec<LockedPage>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedPage {
+    /// The printed page number, such as `12`, `12A` or `13-14`.
+    pub label: String,
+    /// The ids of the elements on the page, in order. See
+    /// `Screenplay::assign_element_ids`.
+    pub elements: Vec<String>,
+    /// Whether the page starts partway through its first element, which
+    /// began on the page before.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub continued: bool,
+}
+
+impl PageLock {
+    pub fn to_json_string(&self) -> String {
+        serde_json::to_string_pretty(self)
+            .expect("Should be impossible for this JSON serialization to fail.")
+    }
+
+    pub fn from_json_str(json: &str) -> Result<PageLock, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+/// The label of a page inserted after the page labeled `label`: `12` is
+/// followed by `12A`, `12A` by `12B`, and `13-14` by `14A`.
+pub(crate) fn insert_label(label: &str) -> String {
+    let last = label.rsplit('-').next().unwrap_or(label);
+    let mut next = last.to_string();
+    match last.chars().last() {
+        Some(letter @ 'A'..='Y') => {
+            next.pop();
+            next.push((letter as u8 + 1) as char);
+        }
+        _ => next.push('A'),
+    }
+    next
+}
+
+/// The label of a page holding the locked pages from `first` to `last`.
+pub(crate) fn range_label(first: &str, last: &str) -> String {
+    let first = first.split('-').next().unwrap_or(first);
+    let last = last.rsplit('-').next().unwrap_or(last);
+    format!("{first}-{last}")
+}
+
+/// The label of the page `offset` pages into those inserted before the page
+/// labeled `label`, which run `A1`, `B1` and so on.
+pub(crate) fn leading_label(label: &str, offset: usize) -> String {
+    let first = label.split('-').next().unwrap_or(label);
+    let letter = (b'A' + (offset % 26) as u8) as char;
+    format!("{letter}{first}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_labels_inserted_pages() {
+        assert_eq!(insert_label("12"), "12A");
+        assert_eq!(insert_label("12A"), "12B");
+        assert_eq!(insert_label("13-14"), "14A");
+        assert_eq!(insert_label("12Z"), "12ZA");
+        assert_eq!(leading_label("1", 0), "A1");
+        assert_eq!(leading_label("1-2", 1), "B1");
+    }
+
+    #[test]
+    fn it_labels_page_ranges() {
+        assert_eq!(range_label("13", "14"), "13-14");
+        assert_eq!(range_label("13-14", "16"), "13-16");
+    }
+}
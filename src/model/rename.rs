@@ -0,0 +1,298 @@
+use serde::Serialize;
+use std::ops::Range;
+
+use crate::{Attributes, Element, ElementText, Screenplay};
+
+/// What [`Screenplay::rename_character_with_options`] renames besides
+/// character cues.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RenameOptions {
+    /// Also rename the character in action, dialogue and parentheticals,
+    /// wherever the old name is a whole word that is capitalized or in
+    /// capitals. A name in capitals, like a character's first appearance,
+    /// stays in capitals.
+    pub in_text: bool,
+}
+
+/// An element changed by a rename, with its plain text before and after.
+/// Paths are indexes into `Screenplay::elements`, followed by indexes into
+/// dialogue blocks for the elements inside them.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct RenameChange {
+    pub element_type: String,
+    pub path: Vec<usize>,
+    pub before: String,
+    pub after: String,
+}
+
+struct CharacterRename<'a> {
+    from: &'a str,
+    to: &'a str,
+    options: RenameOptions,
+}
+
+impl Screenplay {
+    /// Renames every character cue whose name matches `from`, ignoring
+    /// case, keeping extensions and dual-dialogue markers. Returns the
+    /// elements that changed.
+    pub fn rename_character(&mut self, from: &str, to: &str) -> Vec<RenameChange> {
+        self.rename_character_with_options(from, to, RenameOptions::default())
+    }
+
+    /// Renames a character as [`Screenplay::rename_character`] does, and
+    /// in the text of other elements when [`RenameOptions::in_text`] is set.
+    /// Text is replaced inside each run, so styling and note positions are
+    /// kept, but a name split between differently styled runs is left alone.
+    pub fn rename_character_with_options(
+        &mut self,
+        from: &str,
+        to: &str,
+        options: RenameOptions,
+    ) -> Vec<RenameChange> {
+        let rename = CharacterRename {
+            from: from.trim(),
+            to: to.trim(),
+            options,
+        };
+        let mut changes = Vec::new();
+        for (index, element) in self.elements.iter_mut().enumerate() {
+            rename_in_element(element, vec![index], &rename, &mut changes);
+        }
+        changes
+    }
+}
+
+fn rename_in_element(
+    element: &mut Element,
+    path: Vec<usize>,
+    rename: &CharacterRename,
+    changes: &mut Vec<RenameChange>,
+) {
+    let element_type = element.name().to_string();
+    let (text, attributes, before, shifts) = match element {
+        Element::DialogueBlock(block) | Element::DualDialogueBlock(block) => {
+            for (index, element) in block.iter_mut().enumerate() {
+                let mut path = path.clone();
+                path.push(index);
+                rename_in_element(element, path, rename, changes);
+            }
+            return;
+        }
+        Element::Character(text, attributes) => {
+            let before = text.plain_text();
+            let mut first_run = true;
+            let shifts = replace_ranges(
+                text,
+                |content| {
+                    if !std::mem::take(&mut first_run) {
+                        return Vec::new();
+                    }
+                    let name = cue_name_range(content);
+                    if content[name.clone()].to_uppercase() == rename.from.to_uppercase() {
+                        vec![name]
+                    } else {
+                        Vec::new()
+                    }
+                },
+                |_| cue_name(&before, rename.to),
+            );
+            (text, attributes, before, shifts)
+        }
+        Element::Action(text, attributes)
+        | Element::Dialogue(text, attributes)
+        | Element::Parenthetical(text, attributes)
+            if rename.options.in_text =>
+        {
+            let before = text.plain_text();
+            let shifts = replace_ranges(
+                text,
+                |content| name_ranges(content, rename.from),
+                |found| cased_like(found, rename.to),
+            );
+            (text, attributes, before, shifts)
+        }
+        _ => return,
+    };
+    let after = text.plain_text();
+    if after == before {
+        return;
+    }
+    shift_notes(attributes, &shifts);
+    changes.push(RenameChange {
+        element_type,
+        path,
+        before,
+        after,
+    });
+}
+
+/// A replacement made in an element's text: the character offset where the
+/// replaced text ended, and its length in characters before and after.
+type Shift = (usize, usize, usize);
+
+/// Replaces the ranges `find` returns in each run of `text` with what
+/// `replace` makes of the text in them, leaving the runs' styles alone.
+fn replace_ranges(
+    text: &mut ElementText,
+    mut find: impl FnMut(&str) -> Vec<Range<usize>>,
+    replace: impl Fn(&str) -> String,
+) -> Vec<Shift> {
+    let contents: Vec<&mut String> = match text {
+        ElementText::Plain(text) => vec![text],
+        ElementText::Styled(runs) => runs.iter_mut().map(|run| &mut run.content).collect(),
+    };
+    let mut shifts = Vec::new();
+    let mut run_start = 0;
+    for content in contents {
+        if content.trim().is_empty() {
+            run_start += content.chars().count();
+            continue;
+        }
+        let ranges = find(content);
+        let run_len = content.chars().count();
+        let mut edited = String::with_capacity(content.len());
+        let mut cursor = 0;
+        for range in ranges {
+            let found = &content[range.clone()];
+            let replacement = replace(found);
+            shifts.push((
+                run_start + content[..range.end].chars().count(),
+                found.chars().count(),
+                replacement.chars().count(),
+            ));
+            edited.push_str(&content[cursor..range.start]);
+            edited.push_str(&replacement);
+            cursor = range.end;
+        }
+        edited.push_str(&content[cursor..]);
+        *content = edited;
+        run_start += run_len;
+    }
+    shifts
+}
+
+/// Moves the notes, tags and boneyard after each replacement by the change
+/// in its length.
+fn shift_notes(attributes: &mut Attributes, shifts: &[Shift]) {
+    let offsets = attributes
+        .notes
+        .iter_mut()
+        .flatten()
+        .chain(&mut attributes.boneyard)
+        .map(|note| &mut note.offset)
+        .chain(attributes.tags.iter_mut().map(|tag| &mut tag.offset));
+    for offset in offsets {
+        let (grown, shrunk) = shifts
+            .iter()
+            .filter(|(end, _, _)| *end <= *offset)
+            .fold((0, 0), |(grown, shrunk), (_, before, after)| {
+                (grown + after, shrunk + before)
+            });
+        *offset = *offset + grown - shrunk;
+    }
+}
+
+/// The byte range of the name in a character cue, leaving out a leading `@`
+/// and any extension, note or dual-dialogue caret that follows it.
+pub(crate) fn cue_name_range(cue: &str) -> Range<usize> {
+    let start = cue.len() - cue.trim_start().trim_start_matches('@').trim_start().len();
+    let rest = &cue[start..];
+    let name_end = rest.find(['(', '^', '[']).unwrap_or(rest.len());
+    start..start + rest[..name_end].trim_end().len()
+}
+
+/// The byte ranges where `name` appears in `text` as a whole word that is
+/// capitalized or in capitals, ignoring `[[notes]]` and `/* boneyard */`.
+pub(crate) fn name_ranges(text: &str, name: &str) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    if name.is_empty() {
+        return ranges;
+    }
+    let mut position = 0;
+    while position < text.len() {
+        let rest = &text[position..];
+        let closing = if rest.starts_with("[[") {
+            Some("]]")
+        } else if rest.starts_with("/*") {
+            Some("*/")
+        } else {
+            None
+        };
+        if let Some(closing) = closing {
+            position = rest[2..]
+                .find(closing)
+                .map_or(text.len(), |end| position + 2 + end + 2);
+            continue;
+        }
+        let starts_word = !text[..position]
+            .chars()
+            .next_back()
+            .is_some_and(char::is_alphanumeric);
+        if starts_word
+            && let Some(len) = caseless_prefix_len(rest, name)
+            && !rest[len..]
+                .chars()
+                .next()
+                .is_some_and(char::is_alphanumeric)
+            && rest[..len]
+                .chars()
+                .find(|ch| ch.is_alphabetic())
+                .is_some_and(char::is_uppercase)
+        {
+            ranges.push(position..position + len);
+            position += len;
+            continue;
+        }
+        position += rest.chars().next().map_or(1, char::len_utf8);
+    }
+    ranges
+}
+
+/// The byte length of `prefix` at the start of `text`, ignoring case.
+fn caseless_prefix_len(text: &str, prefix: &str) -> Option<usize> {
+    let mut chars = text.char_indices();
+    for wanted in prefix.chars() {
+        let (_, ch) = chars.next()?;
+        if !ch.to_lowercase().eq(wanted.to_lowercase()) {
+            return None;
+        }
+    }
+    Some(chars.next().map_or(text.len(), |(index, _)| index))
+}
+
+/// `to` as it is written in the character cue `cue`: in capitals, so that
+/// the cue is still read as one, unless the cue is forced with `@`.
+pub(crate) fn cue_name(cue: &str, to: &str) -> String {
+    if cue.trim_start().starts_with('@') {
+        to.to_string()
+    } else {
+        to.to_uppercase()
+    }
+}
+
+/// `to` in the case `found` was written in: in capitals if `found` was, and
+/// otherwise capitalized if `to` was given in capitals.
+pub(crate) fn cased_like(found: &str, to: &str) -> String {
+    if is_capitals(found) {
+        to.to_uppercase()
+    } else if is_capitals(to) {
+        let mut previous = ' ';
+        to.chars()
+            .map(|ch| {
+                let cased = if previous.is_alphabetic() {
+                    ch.to_lowercase().to_string()
+                } else {
+                    ch.to_uppercase().to_string()
+                };
+                previous = ch;
+                cased
+            })
+            .collect()
+    } else {
+        to.to_string()
+    }
+}
+
+fn is_capitals(text: &str) -> bool {
+    text.chars().any(char::is_alphabetic) && !text.chars().any(char::is_lowercase)
+}
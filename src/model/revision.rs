@@ -0,0 +1,310 @@
+use serde::{Deserialize, Serialize};
+
+use crate::diff::{Step, WordChange, align, diff_words, matched_words};
+use crate::{Element, ElementText, Screenplay, TextRun};
+
+/// A named, colored set of revisions, such as the blue pages of a production
+/// draft. Elements and text runs refer to it by `id`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RevisionSet {
+    pub id: u32,
+    pub name: String,
+    /// A standard revision color such as `Blue` or `Pink`, or a `#RRGGBB`
+    /// hex color.
+    pub color: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+}
+
+/// The page colors of the usual order of production revisions.
+const REVISION_COLORS: &[(&str, [u8; 3])] = &[
+    ("White", [0xFF, 0xFF, 0xFF]),
+    ("Blue", [0xAD, 0xD8, 0xE6]),
+    ("Pink", [0xFF, 0xC0, 0xCB]),
+    ("Yellow", [0xFF, 0xFF, 0x99]),
+    ("Green", [0x98, 0xFB, 0x98]),
+    ("Goldenrod", [0xDA, 0xA5, 0x20]),
+    ("Buff", [0xF0, 0xDC, 0x82]),
+    ("Salmon", [0xFA, 0x80, 0x72]),
+    ("Cherry", [0xDE, 0x31, 0x63]),
+    ("Tan", [0xD2, 0xB4, 0x8C]),
+    ("Gray", [0xBE, 0xBE, 0xBE]),
+];
+
+impl RevisionSet {
+    pub fn new(id: u32, name: impl Into<String>, color: impl Into<String>) -> Self {
+        RevisionSet {
+            id,
+            name: name.into(),
+            color: color.into(),
+            date: None,
+        }
+    }
+
+    /// The color as red, green and blue, or `None` if it is neither a
+    /// standard revision color nor a hex color.
+    pub fn rgb(&self) -> Option<[u8; 3]> {
+        if let Some((_, rgb)) = REVISION_COLORS
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(&self.color))
+        {
+            return Some(*rgb);
+        }
+        let hex = self.color.strip_prefix('#')?;
+        if hex.len() != 6 {
+            return None;
+        }
+        let channel = |index: usize| u8::from_str_radix(hex.get(index..index + 2)?, 16).ok();
+        Some([channel(0)?, channel(2)?, channel(4)?])
+    }
+
+    /// The name of the standard revision color `rgb`, or `rgb` as a hex
+    /// color if it is none of them.
+    pub fn color_name(rgb: [u8; 3]) -> String {
+        REVISION_COLORS
+            .iter()
+            .find(|(_, color)| *color == rgb)
+            .map(|(name, _)| name.to_string())
+            .unwrap_or_else(|| format!("#{:02X}{:02X}{:02X}", rgb[0], rgb[1], rgb[2]))
+    }
+
+    /// The label printed in the header of pages carrying this revision, such
+    /// as `Blue Rev. (10/18/26)`.
+    pub fn header_label(&self) -> String {
+        let color = if self.color.starts_with('#') {
+            &self.name
+        } else {
+            &self.color
+        };
+        match &self.date {
+            Some(date) => format!("{color} Rev. ({date})"),
+            None => format!("{color} Rev."),
+        }
+    }
+}
+
+impl Screenplay {
+    /// The revision set with `id`, if the screenplay has one.
+    pub fn revision_set(&self, id: u32) -> Option<&RevisionSet> {
+        self.revisions.iter().find(|set| set.id == id)
+    }
+
+    /// One more than the highest revision set id in use, for the next set.
+    pub fn next_revision_id(&self) -> u32 {
+        self.revisions
+            .iter()
+            .map(|set| set.id)
+            .max()
+            .map_or(1, |id| id + 1)
+    }
+
+    /// Marks what changed since `previous` as revised in `revision`, which is
+    /// added to the screenplay's revision sets.
+    ///
+    /// Elements are lined up with `previous` the way [`crate::diff::diff`]
+    /// does. Inserted and moved elements are marked as a whole; in an edited
+    /// element only the changed words are, or the word after a deletion.
+    /// Unchanged elements and the unchanged words of edited ones keep the
+    /// marks and revision sets they had in `previous`, so a draft revised
+    /// several times collates its revisions.
+    /// Removing a whole element leaves no mark.
+    pub fn mark_revisions(&mut self, previous: &Screenplay, revision: RevisionSet) {
+        let id = revision.id;
+        for set in previous.revisions.iter().chain([&revision]) {
+            self.revisions.retain(|kept| kept.id != set.id);
+            self.revisions.push(set.clone());
+        }
+        self.revisions.sort_by_key(|set| set.id);
+        mark_sequence(&previous.elements, &mut self.elements, id);
+    }
+}
+
+fn mark_sequence(old: &[Element], new: &mut [Element], revision: u32) {
+    for step in align(old, new) {
+        match step {
+            Step::Same(i, j) | Step::Similar(i, j) => mark_element(&old[i], &mut new[j], revision),
+            Step::Inserted(j) | Step::Moved(_, j) => mark_whole(&mut new[j], revision),
+            Step::Deleted(_) => {}
+        }
+    }
+}
+
+fn mark_element(old: &Element, new: &mut Element, revision: u32) {
+    if let (
+        Element::DialogueBlock(old_block) | Element::DualDialogueBlock(old_block),
+        Element::DialogueBlock(new_block) | Element::DualDialogueBlock(new_block),
+    ) = (old, &mut *new)
+    {
+        mark_sequence(old_block, new_block, revision);
+        return;
+    }
+    let (Some(old_text), Some(old_attributes)) = (old.text(), old.attributes()) else {
+        return;
+    };
+    let old_revision = old_attributes.revision;
+    let Some((text, attributes)) = new.text_and_attributes_mut() else {
+        return;
+    };
+    if attributes.revision.is_none() {
+        attributes.revision = old_revision;
+    }
+
+    let old_plain = old_text.plain_text();
+    let new_plain = text.plain_text();
+    if old_plain == new_plain {
+        if text.revision_ranges().is_empty() {
+            for (start, end, run_revision) in old_text.revision_ranges() {
+                text.mark_revision(start, end, run_revision);
+            }
+        }
+        return;
+    }
+    // Earlier marks stay on the words that are still there.
+    if text.revision_ranges().is_empty() {
+        let words = matched_words(&old_plain, &new_plain);
+        for (start, end, run_revision) in old_text.revision_ranges() {
+            for &(old_start, new_start, length) in &words {
+                let from = start.max(old_start);
+                let to = end.min(old_start + length);
+                if from < to {
+                    text.mark_revision(
+                        new_start + from - old_start,
+                        new_start + to - old_start,
+                        run_revision,
+                    );
+                }
+            }
+        }
+    }
+    for (start, end) in changed_ranges(&old_plain, &new_plain) {
+        text.mark_revision(start, end, revision);
+    }
+}
+
+fn mark_whole(element: &mut Element, revision: u32) {
+    match element {
+        Element::DialogueBlock(block) | Element::DualDialogueBlock(block) => {
+            for element in block {
+                mark_whole(element, revision);
+            }
+        }
+        _ => {
+            if let Some(attributes) = element.attributes_mut() {
+                attributes.revision = Some(revision);
+            }
+        }
+    }
+}
+
+/// The character ranges of `new` holding inserted words, and of the word
+/// after each deletion, or before it at the end of the text.
+fn changed_ranges(old: &str, new: &str) -> Vec<(usize, usize)> {
+    let new_chars: Vec<char> = new.chars().collect();
+    let word_at = |offset: usize| {
+        let end = (offset..new_chars.len())
+            .find(|&index| new_chars[index].is_whitespace())
+            .unwrap_or(new_chars.len());
+        (offset, end)
+    };
+    let last_word = || {
+        let end = new_chars
+            .iter()
+            .rposition(|character| !character.is_whitespace())
+            .map_or(0, |index| index + 1);
+        let start = new_chars[..end]
+            .iter()
+            .rposition(|character| character.is_whitespace())
+            .map_or(0, |index| index + 1);
+        (start, end)
+    };
+
+    let mut ranges = Vec::new();
+    let mut offset = 0;
+    for change in diff_words(old, new) {
+        match change {
+            WordChange::Equal(text) => offset += text.chars().count(),
+            WordChange::Inserted(text) => {
+                let length = text.trim_end().chars().count();
+                ranges.push((offset, offset + length));
+                offset += text.chars().count();
+            }
+            WordChange::Deleted(_) if offset < new_chars.len() => ranges.push(word_at(offset)),
+            WordChange::Deleted(_) => ranges.push(last_word()),
+        }
+    }
+    ranges.retain(|(start, end)| start < end);
+    ranges
+}
+
+impl ElementText {
+    /// The character ranges of runs marked with a revision of their own.
+    pub fn revision_ranges(&self) -> Vec<(usize, usize, u32)> {
+        let ElementText::Styled(runs) = self else {
+            return Vec::new();
+        };
+        let mut ranges = Vec::new();
+        let mut start = 0;
+        for run in runs {
+            let end = start + run.content.chars().count();
+            if let Some(revision) = run.revision {
+                ranges.push((start, end, revision));
+            }
+            start = end;
+        }
+        ranges
+    }
+
+    /// Marks the characters from `start` to `end` as revised in `revision`,
+    /// splitting runs where the range starts and ends. Plain text becomes a
+    /// single unstyled run first.
+    pub fn mark_revision(&mut self, start: usize, end: usize, revision: u32) {
+        if let ElementText::Plain(text) = self {
+            *self = ElementText::Styled(vec![TextRun {
+                content: std::mem::take(text),
+                text_style: Default::default(),
+                revision: None,
+            }]);
+        }
+        let ElementText::Styled(runs) = self else {
+            return;
+        };
+
+        let mut marked: Vec<TextRun> = Vec::with_capacity(runs.len() + 2);
+        let mut run_start = 0;
+        for run in runs.drain(..) {
+            let chars: Vec<char> = run.content.chars().collect();
+            let run_end = run_start + chars.len();
+            let cuts = [
+                start.clamp(run_start, run_end),
+                end.clamp(run_start, run_end),
+            ];
+            let pieces = [
+                (run_start, cuts[0], run.revision),
+                (cuts[0], cuts[1], Some(revision)),
+                (cuts[1], run_end, run.revision),
+            ];
+            for (piece_start, piece_end, piece_revision) in pieces {
+                if piece_start == piece_end {
+                    continue;
+                }
+                let content: String = chars[piece_start - run_start..piece_end - run_start]
+                    .iter()
+                    .collect();
+                match marked.last_mut() {
+                    Some(last)
+                        if last.text_style == run.text_style && last.revision == piece_revision =>
+                    {
+                        last.content.push_str(&content);
+                    }
+                    _ => marked.push(TextRun {
+                        content,
+                        text_style: run.text_style.clone(),
+                        revision: piece_revision,
+                    }),
+                }
+            }
+            run_start = run_end;
+        }
+        *runs = marked;
+    }
+}
@@ -0,0 +1,199 @@
+use serde::Serialize;
+
+/// Whether a scene plays inside, outside, or both, as given by the heading's
+/// prefix.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+pub enum SceneSetting {
+    Interior,
+    Exterior,
+    InteriorExterior,
+    Establishing,
+}
+
+/// Whether a scene plays by day or by night, as given by its time of day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+pub enum DayNight {
+    Day,
+    Night,
+}
+
+/// A scene heading split into its parts, e.g. `INT. HOUSE - KITCHEN - NIGHT`
+/// into the `INT.` prefix, the `HOUSE` / `KITCHEN` locations and `NIGHT`.
+/// A run of times at the end, as in `DAY - CONTINUOUS`, is one time of day.
+///
+/// Every part keeps the spelling and case used in the heading.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct SceneHeadingParts {
+    pub prefix: Option<String>,
+    pub setting: Option<SceneSetting>,
+    pub locations: Vec<String>,
+    pub time_of_day: Option<String>,
+}
+
+// Longest spellings first so `INT./EXT.` is not taken for `INT.`.
+const PREFIXES: [(&str, SceneSetting); 16] = [
+    ("INT./EXT.", SceneSetting::InteriorExterior),
+    ("EXT./INT.", SceneSetting::InteriorExterior),
+    ("INT/EXT.", SceneSetting::InteriorExterior),
+    ("EXT/INT.", SceneSetting::InteriorExterior),
+    ("INT./EXT", SceneSetting::InteriorExterior),
+    ("EXT./INT", SceneSetting::InteriorExterior),
+    ("INT/EXT", SceneSetting::InteriorExterior),
+    ("EXT/INT", SceneSetting::InteriorExterior),
+    ("I/E.", SceneSetting::InteriorExterior),
+    ("I/E", SceneSetting::InteriorExterior),
+    ("INT.", SceneSetting::Interior),
+    ("INT", SceneSetting::Interior),
+    ("EXT.", SceneSetting::Exterior),
+    ("EXT", SceneSetting::Exterior),
+    ("EST.", SceneSetting::Establishing),
+    ("EST", SceneSetting::Establishing),
+];
+
+const TIMES_OF_DAY: [&str; 22] = [
+    "DAY",
+    "NIGHT",
+    "MORNING",
+    "AFTERNOON",
+    "EVENING",
+    "DAWN",
+    "DUSK",
+    "SUNRISE",
+    "SUNSET",
+    "NOON",
+    "MIDNIGHT",
+    "PRE-DAWN",
+    "EARLY MORNING",
+    "LATE NIGHT",
+    "MAGIC HOUR",
+    "LATER",
+    "MOMENTS LATER",
+    "CONTINUOUS",
+    "SAME",
+    "SAME TIME",
+    "DAY/NIGHT",
+    "NIGHT/DAY",
+];
+
+impl SceneHeadingParts {
+    /// Splits a scene heading's text. A forced heading's leading `.` is
+    /// ignored, and headings without a known prefix get `prefix: None` and
+    /// treat everything before the time of day as location.
+    pub fn parse(heading: &str) -> SceneHeadingParts {
+        let mut rest = heading.trim();
+        if rest.starts_with('.') && !rest.starts_with("..") {
+            rest = rest[1..].trim_start();
+        }
+
+        let mut parts = SceneHeadingParts::default();
+        if let Some((prefix, setting)) = split_prefix(rest) {
+            parts.prefix = Some(rest[..prefix.len()].to_string());
+            parts.setting = Some(setting);
+            rest = rest[prefix.len()..].trim_start();
+        }
+
+        let mut segments: Vec<&str> = rest
+            .split(" - ")
+            .map(str::trim)
+            .filter(|segment| !segment.is_empty())
+            .collect();
+        // Keep at least one segment as the location.
+        let times = segments
+            .iter()
+            .skip(1)
+            .rev()
+            .take_while(|segment| is_time_of_day(segment))
+            .count();
+        if times > 0 {
+            let time_of_day = segments.split_off(segments.len() - times);
+            parts.time_of_day = Some(time_of_day.join(" - "));
+        }
+        parts.locations = segments.into_iter().map(str::to_string).collect();
+        parts
+    }
+
+    /// Whether the scene plays by day or by night, or `None` if its time of
+    /// day says neither, such as `MAGIC HOUR` or `CONTINUOUS`.
+    pub fn day_night(&self) -> Option<DayNight> {
+        self.bare_times().find_map(|time| {
+            if DAY_TIMES.contains(&time.as_str()) {
+                Some(DayNight::Day)
+            } else if NIGHT_TIMES.contains(&time.as_str()) {
+                Some(DayNight::Night)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Whether the time of day carries on from the scene before, such as
+    /// `CONTINUOUS` or `MOMENTS LATER`.
+    pub fn continues_previous_time(&self) -> bool {
+        self.bare_times()
+            .any(|time| CONTINUING_TIMES.contains(&time.as_str()))
+    }
+
+    /// Each time in the time of day, e.g. `DAY` and `CONTINUOUS` in
+    /// `DAY - CONTINUOUS`, as [`bare_time_of_day`] gives it.
+    fn bare_times(&self) -> impl Iterator<Item = String> + '_ {
+        self.time_of_day
+            .as_deref()
+            .into_iter()
+            .flat_map(|time| time.split(" - "))
+            .map(bare_time_of_day)
+    }
+}
+
+const DAY_TIMES: [&str; 7] = [
+    "DAY",
+    "MORNING",
+    "AFTERNOON",
+    "NOON",
+    "DAWN",
+    "SUNRISE",
+    "EARLY MORNING",
+];
+
+const NIGHT_TIMES: [&str; 7] = [
+    "NIGHT",
+    "EVENING",
+    "DUSK",
+    "SUNSET",
+    "MIDNIGHT",
+    "PRE-DAWN",
+    "LATE NIGHT",
+];
+
+// Times that carry on from the scene before rather than naming one.
+const CONTINUING_TIMES: [&str; 5] = ["CONTINUOUS", "LATER", "MOMENTS LATER", "SAME", "SAME TIME"];
+
+fn split_prefix(heading: &str) -> Option<(&'static str, SceneSetting)> {
+    PREFIXES.iter().copied().find(|(prefix, _)| {
+        let Some(candidate) = heading.get(..prefix.len()) else {
+            return false;
+        };
+        // A prefix without a closing period needs a space after it, so that
+        // `INTERCOM` is not read as `INT`.
+        candidate.eq_ignore_ascii_case(prefix)
+            && (prefix.ends_with('.')
+                || heading[prefix.len()..].is_empty()
+                || heading[prefix.len()..].starts_with(' '))
+    })
+}
+
+fn is_time_of_day(segment: &str) -> bool {
+    TIMES_OF_DAY.contains(&bare_time_of_day(segment).as_str())
+}
+
+/// The time of day in upper case without qualifiers such as
+/// `DAY (FLASHBACK)` or `NIGHT 2`.
+fn bare_time_of_day(segment: &str) -> String {
+    segment
+        .split('(')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .trim_end_matches(|c: char| c.is_ascii_digit() || c == '.')
+        .trim()
+        .to_uppercase()
+}
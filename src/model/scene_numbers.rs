@@ -0,0 +1,217 @@
+use std::collections::HashSet;
+
+use crate::page_lock::{insert_label, leading_label};
+use crate::{Attributes, Element, ElementText, Screenplay};
+
+/// How [`Screenplay::number_scenes`] numbers scene headings.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SceneNumbering {
+    /// Numbers every scene from 1, dropping `OMITTED` placeholders.
+    #[default]
+    Fresh,
+    /// Keeps the numbers scenes already have. Inserted scenes are numbered
+    /// after the scene before them, such as `12A` and `12B`, and numbers
+    /// missing from the sequence come back as `OMITTED` placeholders. A
+    /// screenplay with no scene numbers yet is numbered from 1.
+    Locked,
+}
+
+/// The text of the scene heading left in place of a cut scene.
+pub const OMITTED: &str = "OMITTED";
+
+/// What numbering does to one scene heading.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct NumberedScene {
+    /// The heading's new number, or `None` if the heading is an `OMITTED`
+    /// placeholder to remove.
+    pub number: Option<String>,
+    /// Numbers of `OMITTED` placeholders to insert before the heading.
+    pub omitted_before: Vec<String>,
+}
+
+/// A scene heading's current number and whether it is an `OMITTED`
+/// placeholder.
+pub(crate) type SceneEntry<'a> = (Option<&'a str>, bool);
+
+impl Screenplay {
+    /// Numbers the scene headings, setting their `scene_number`. See
+    /// [`SceneNumbering`].
+    pub fn number_scenes(&mut self, numbering: SceneNumbering) {
+        let entries: Vec<SceneEntry> = self.elements.iter().filter_map(scene_entry).collect();
+        let mut plan = number_scene_entries(&entries, numbering).into_iter();
+
+        let mut numbered = Vec::with_capacity(self.elements.len());
+        for mut element in std::mem::take(&mut self.elements) {
+            if let Element::SceneHeading(_, attributes) = &mut element {
+                let scene = plan.next().expect("one numbered scene per heading");
+                numbered.extend(scene.omitted_before.into_iter().map(omitted_heading));
+                let Some(number) = scene.number else {
+                    continue;
+                };
+                attributes.scene_number = Some(number);
+            }
+            numbered.push(element);
+        }
+        self.elements = numbered;
+    }
+}
+
+pub(crate) fn scene_entry(element: &Element) -> Option<SceneEntry<'_>> {
+    match element {
+        Element::SceneHeading(text, attributes) => Some((
+            attributes.scene_number.as_deref(),
+            is_omitted(&text.plain_text()),
+        )),
+        _ => None,
+    }
+}
+
+pub(crate) fn number_scene_entries(
+    entries: &[SceneEntry],
+    numbering: SceneNumbering,
+) -> Vec<NumberedScene> {
+    let locked =
+        numbering == SceneNumbering::Locked && entries.iter().any(|(number, _)| number.is_some());
+    if !locked {
+        let mut next = 0;
+        return entries
+            .iter()
+            .map(|(_, omitted)| NumberedScene {
+                number: (!omitted).then(|| {
+                    next += 1;
+                    next.to_string()
+                }),
+                omitted_before: Vec::new(),
+            })
+            .collect();
+    }
+
+    let taken: HashSet<&str> = entries.iter().filter_map(|(number, _)| *number).collect();
+    let first = entries
+        .iter()
+        .find_map(|(number, _)| *number)
+        .expect("a locked screenplay has a numbered scene");
+    let mut previous: Option<String> = None;
+    let mut leading = 0;
+    entries
+        .iter()
+        .map(|(number, _)| {
+            let Some(number) = number else {
+                let label = match &previous {
+                    Some(previous) => {
+                        let mut label = insert_label(previous);
+                        while taken.contains(label.as_str()) {
+                            label.push('A');
+                        }
+                        label
+                    }
+                    None => {
+                        leading += 1;
+                        leading_label(first, leading - 1)
+                    }
+                };
+                previous = Some(label.clone());
+                return NumberedScene {
+                    number: Some(label),
+                    omitted_before: Vec::new(),
+                };
+            };
+            let after = previous.as_deref().map_or(Some(0), leading_number);
+            let omitted_before = match (after, leading_number(number)) {
+                (Some(after), Some(current)) => ((after + 1)..current)
+                    .map(|missing| missing.to_string())
+                    .filter(|missing| !taken.contains(missing.as_str()))
+                    .collect(),
+                _ => Vec::new(),
+            };
+            previous = Some(number.to_string());
+            NumberedScene {
+                number: Some(number.to_string()),
+                omitted_before,
+            }
+        })
+        .collect()
+}
+
+/// Whether a scene heading is a placeholder for a cut scene.
+fn is_omitted(heading: &str) -> bool {
+    heading.trim().eq_ignore_ascii_case(OMITTED)
+}
+
+/// The number a scene number starts with, such as `12` for `12A`.
+fn leading_number(number: &str) -> Option<u32> {
+    let digits = number
+        .find(|character: char| !character.is_ascii_digit())
+        .unwrap_or(number.len());
+    number[..digits].parse().ok()
+}
+
+fn omitted_heading(number: String) -> Element {
+    Element::SceneHeading(
+        ElementText::Plain(OMITTED.to_string()),
+        Attributes {
+            scene_number: Some(number),
+            ..Attributes::default()
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbers(
+        entries: &[SceneEntry],
+        numbering: SceneNumbering,
+    ) -> Vec<(Option<String>, Vec<String>)> {
+        number_scene_entries(entries, numbering)
+            .into_iter()
+            .map(|scene| (scene.number, scene.omitted_before))
+            .collect()
+    }
+
+    #[test]
+    fn it_fills_gaps_with_omitted_scenes() {
+        let entries = [
+            (Some("1"), false),
+            (Some("4"), false),
+            (None, false),
+            (Some("4A"), false),
+        ];
+
+        assert_eq!(
+            numbers(&entries, SceneNumbering::Locked),
+            vec![
+                (Some("1".to_string()), vec![]),
+                (
+                    Some("4".to_string()),
+                    vec!["2".to_string(), "3".to_string()]
+                ),
+                (Some("4AA".to_string()), vec![]),
+                (Some("4A".to_string()), vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_numbers_leading_inserts_before_the_first_scene() {
+        let entries = [(None, false), (Some("1"), false), (None, true)];
+
+        assert_eq!(
+            numbers(&entries, SceneNumbering::Locked),
+            vec![
+                (Some("A1".to_string()), vec![]),
+                (Some("1".to_string()), vec![]),
+                (Some("1A".to_string()), vec![]),
+            ]
+        );
+        assert_eq!(
+            numbers(&entries, SceneNumbering::Fresh),
+            vec![
+                (Some("1".to_string()), vec![]),
+                (Some("2".to_string()), vec![]),
+                (None, vec![]),
+            ]
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use serde::Serialize;
+
+/// A region of the original Fountain input.
+///
+/// `start` and `end` are byte offsets into the text handed to the parser, with
+/// `end` exclusive. Lines and columns are 1-based; columns count characters,
+/// and `end_column` points just past the last character of the span.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct SourceSpan {
+    pub start: usize,
+    pub end: usize,
+    pub start_line: usize,
+    pub start_column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+impl SourceSpan {
+    /// Builds a span for the byte range `start..end` of `text`.
+    pub fn from_offsets(text: &str, start: usize, end: usize) -> Self {
+        let (start_line, start_column) = line_and_column(text, start);
+        let (end_line, end_column) = line_and_column(text, end);
+        SourceSpan {
+            start,
+            end,
+            start_line,
+            start_column,
+            end_line,
+            end_column,
+        }
+    }
+
+    pub fn contains(&self, offset: usize) -> bool {
+        self.start <= offset && offset < self.end
+    }
+
+    /// Returns a span running from the start of `self` to the end of `other`.
+    pub fn cover(&self, other: &SourceSpan) -> SourceSpan {
+        SourceSpan {
+            start: self.start,
+            start_line: self.start_line,
+            start_column: self.start_column,
+            end: other.end,
+            end_line: other.end_line,
+            end_column: other.end_column,
+        }
+    }
+}
+
+/// The span of one `Element`. `children` mirrors the elements nested inside a
+/// `DialogueBlock` or `DualDialogueBlock` and is empty for everything else.
+/// `file` names the file the element came from when the screenplay was parsed
+/// with includes; the span is then relative to that file.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ElementSpan {
+    pub span: SourceSpan,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<ElementSpan>,
+}
+
+/// Source spans for a parsed screenplay, parallel to `Screenplay::elements`.
+/// `metadata` covers the title-page block when the input starts with one.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct SourceMap {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<SourceSpan>,
+    pub elements: Vec<ElementSpan>,
+}
+
+impl SourceMap {
+    /// Looks up an element by its index path, e.g. `[3, 1]` for the second
+    /// child of the fourth top-level element.
+    pub fn get(&self, path: &[usize]) -> Option<&ElementSpan> {
+        let (first, rest) = path.split_first()?;
+        let mut current = self.elements.get(*first)?;
+        for index in rest {
+            current = current.children.get(*index)?;
+        }
+        Some(current)
+    }
+
+    /// Returns the index path of the innermost element covering `offset`.
+    pub fn path_at(&self, offset: usize) -> Option<Vec<usize>> {
+        let mut path = Vec::new();
+        let mut candidates = &self.elements;
+        while let Some(index) = candidates
+            .iter()
+            .position(|element| element.span.contains(offset))
+        {
+            path.push(index);
+            candidates = &candidates[index].children;
+        }
+        (!path.is_empty()).then_some(path)
+    }
+}
+
+fn line_and_column(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
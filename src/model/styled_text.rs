@@ -14,13 +14,42 @@ pub struct StyledText {
     pub runs: Vec<StyledRun>,
 }
 
+/// The style given to runs changed in a revision, carrying the revision set
+/// id so that renderers can print revision marks.
+pub(crate) fn revision_style(revision: u32) -> String {
+    format!("Revision{revision}")
+}
+
+/// The revision set id of a style made by [`revision_style`].
+#[cfg(any(feature = "html", feature = "pdf"))]
+pub(crate) fn style_revision(style: &str) -> Option<u32> {
+    style.strip_prefix("Revision")?.parse().ok()
+}
+
 impl StyledText {
     pub fn from_element_text(text: &ElementText) -> Option<Self> {
+        Self::from_revised_element_text(text, None)
+    }
+
+    /// Like [`StyledText::from_element_text`], also giving each run the
+    /// [`revision_style`] of the later of its own revision and its element's.
+    /// Revised plain text becomes a single run.
+    pub fn from_revised_element_text(text: &ElementText, revision: Option<u32>) -> Option<Self> {
         match text {
+            ElementText::Plain(plain) if revision.is_some() && !plain.is_empty() => Some(Self {
+                plain_text: plain.clone(),
+                runs: vec![StyledRun {
+                    text: plain.clone(),
+                    styles: revision.map(revision_style).into_iter().collect(),
+                }],
+            }),
             ElementText::Plain(_) => None,
             ElementText::Styled(runs) => Some(Self {
                 plain_text: runs.iter().map(|run| run.content.as_str()).collect(),
-                runs: runs.iter().map(styled_run_from_text_run).collect(),
+                runs: runs
+                    .iter()
+                    .map(|run| styled_run_from_text_run(run, revision))
+                    .collect(),
             }),
         }
     }
@@ -56,9 +85,12 @@ impl StyledText {
     }
 }
 
-fn styled_run_from_text_run(run: &TextRun) -> StyledRun {
+fn styled_run_from_text_run(run: &TextRun, revision: Option<u32>) -> StyledRun {
     let mut styles: Vec<String> = run.text_style.iter().cloned().collect();
     styles.sort();
+    if let Some(revision) = run.revision.max(revision) {
+        styles.push(revision_style(revision));
+    }
 
     StyledRun {
         text: run.content.clone(),
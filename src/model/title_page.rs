@@ -2,7 +2,9 @@ use crate::{
     ElementText, ImportedTitlePageAlignment, Metadata, Screenplay,
 };
 
-const TITLE_PAGE_METADATA_KEYS: [&str; 7] = [
+/// The metadata keys that make up the title page, in the order Fountain
+/// output writes them.
+pub(crate) const TITLE_PAGE_METADATA_KEYS: [&str; 9] = [
     "title",
     "credit",
     "author",
@@ -10,8 +12,10 @@ const TITLE_PAGE_METADATA_KEYS: [&str; 7] = [
     "source",
     "draft",
     "draft date",
+    "contact",
+    "frontmatter-page-count",
 ];
-const ALLOW_LOWERCASE_TITLE_FMT_OPTION: &str = "allow-lowercase-title";
+pub(crate) const ALLOW_LOWERCASE_TITLE_FMT_OPTION: &str = "allow-lowercase-title";
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum TitlePageRegion {
@@ -102,7 +106,7 @@ impl TitlePage {
 fn build_title_page(metadata: &Metadata, imported_frontmatter: Vec<FrontmatterPage>) -> Option<TitlePage> {
     let has_title_keys = TITLE_PAGE_METADATA_KEYS
         .iter()
-        .chain(["contact"].iter())
+        .filter(|key| **key != "frontmatter-page-count")
         .any(|key| metadata.contains_key(*key));
     let has_frontmatter = !imported_frontmatter.is_empty();
     if !has_title_keys && !has_frontmatter {
@@ -318,6 +322,8 @@ mod tests {
                     },
                 ],
             }),
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![],
         };
 
@@ -344,6 +350,8 @@ mod tests {
             metadata,
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![],
         };
 
@@ -0,0 +1,43 @@
+use crate::pagination::wrapping::{
+    wrap_config_with_overrides, wrap_text_for_element, ElementType, InterruptionDashWrap,
+};
+use crate::pagination::LayoutGeometry;
+use crate::{CharacterCue, ElementLayoutOverrides};
+
+/// The cue to repeat on a page where the speech carries on, with `(CONT'D)`
+/// added unless it already has it among its extensions.
+pub(crate) fn continued_character_cue_text(text: &str) -> String {
+    let trimmed = text.trim_end();
+    if CharacterCue::parse(trimmed).is_continued() {
+        trimmed.to_string()
+    } else {
+        format!("{trimmed} (CONT'D)")
+    }
+}
+
+/// The text of a `CONTINUED:` or `(CONTINUED)` line, indented like the
+/// element type it is written as: action at the top of a page and a
+/// transition at the bottom.
+pub(crate) fn scene_continued_text(
+    text: &str,
+    element_type: ElementType,
+    geometry: &LayoutGeometry,
+    interruption_dash_wrap: InterruptionDashWrap,
+) -> String {
+    let config = wrap_config_with_overrides(
+        geometry,
+        element_type,
+        &ElementLayoutOverrides::default(),
+        interruption_dash_wrap,
+    );
+    let left = match element_type {
+        ElementType::Transition => geometry.transition_left,
+        _ => geometry.action_left,
+    };
+    let indent = " ".repeat(((left - geometry.action_left) * geometry.cpi).floor() as usize);
+    wrap_text_for_element(text, &config)
+        .into_iter()
+        .next()
+        .map(|line| format!("{indent}{line}"))
+        .unwrap_or_else(|| text.to_string())
+}
@@ -1,8 +1,11 @@
+use std::collections::HashMap;
+
 use crate::pagination::dialogue_split::DialogueSplitPlan;
 use crate::pagination::fixtures::{
     Fragment, NormalizedElement, NormalizedScreenplay, PageBreakFixture,
     PageBreakFixtureSourceRefs, PaginationScope,
 };
+use crate::pagination::locked_pages::{label_pages, with_locked_breaks};
 use crate::pagination::margin::line_height_for_element_type;
 use crate::pagination::normalize_screenplay;
 use crate::pagination::semantic::{
@@ -41,6 +44,24 @@ pub struct PageMetadata {
     pub kind: PageKind,
     pub body_page_number: Option<u32>,
     pub title_page_number: Option<u32>,
+    /// The printed page number of a locked screenplay, such as `12A`.
+    pub label: Option<String>,
+    /// Set on a page that starts partway through a scene, when scene
+    /// continueds are on at the top of the page.
+    pub scene_continued: Option<SceneContinued>,
+    /// Whether the page ends partway through a scene, when scene continueds
+    /// are on at the bottom of the page.
+    pub scene_continues: bool,
+}
+
+/// The scene a page continues, for its `CONTINUED:` line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SceneContinued {
+    /// The number of the scene's heading, if it has one.
+    pub scene_number: Option<String>,
+    /// Which continued page of the scene this is, counting from 1, for
+    /// numbered continueds such as `CONTINUED: (2)`.
+    pub count: u32,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -114,6 +135,18 @@ impl PaginatedScreenplay {
         config: PaginationConfig,
         style_profile: impl Into<String>,
         scope: PaginationScope,
+    ) -> Self {
+        Self::paginate_with_layout(&semantic, &config, style_profile, scope, |_| {})
+    }
+
+    /// Paginates like `paginate`, handing `on_page` the layout blocks of
+    /// each page as it is laid out, in the order of the returned pages.
+    pub(crate) fn paginate_with_layout(
+        semantic: &SemanticScreenplay,
+        config: &PaginationConfig,
+        style_profile: impl Into<String>,
+        scope: PaginationScope,
+        mut on_page: impl FnMut(&[crate::pagination::composer::LayoutBlock<'_>]),
     ) -> Self {
         let mut next_page_number = semantic
             .starting_page_number
@@ -137,13 +170,14 @@ impl PaginatedScreenplay {
 
         for paged_page in paged_blocks.into_iter() {
             let mut current_items = Vec::new();
-            for block in paged_page.blocks {
-                let items = page_items_from_layout_block(&block, geometry);
+            for block in &paged_page.blocks {
+                let items = page_items_from_layout_block(block, geometry);
                 current_items.extend(items);
             }
 
             // `build_page` expects us to skip empty pages. `SemanticUnit::PageStart` yields 0 items natively.
             if !current_items.is_empty() {
+                on_page(&paged_page.blocks);
                 pages.push(build_page(
                     pages.len(),
                     next_page_number,
@@ -154,8 +188,10 @@ impl PaginatedScreenplay {
             }
         }
 
+        mark_scene_continueds(&mut pages, &semantic.units, geometry);
+
         Self {
-            screenplay: semantic.screenplay,
+            screenplay: semantic.screenplay.clone(),
             style_profile,
             source: PageBreakFixtureSourceRefs::default(),
             scope,
@@ -225,11 +261,14 @@ impl PaginatedScreenplay {
             crate::pagination::StyleProfile::Screenplay => "standard",
             crate::pagination::StyleProfile::Multicam => "multicam",
         };
-        let normalized = normalize_screenplay(screenplay_id, screenplay);
+        let locked = with_locked_breaks(screenplay);
+        let normalized = normalize_screenplay(screenplay_id, &locked);
         let semantic = build_semantic_screenplay(normalized);
         let config = PaginationConfig::from_screenplay(screenplay, lines_per_page);
 
-        Self::paginate(semantic, config, style_profile, scope)
+        let mut paginated = Self::paginate(semantic, config, style_profile, scope);
+        label_pages(&mut paginated.pages, screenplay);
+        paginated
     }
 
     pub fn from_fixture(fixture: PageBreakFixture) -> Self {
@@ -282,12 +321,60 @@ fn build_page(
             kind: page_kind(page_number, scope),
             body_page_number: body_page_number(page_number, scope),
             title_page_number: title_page_number(page_number, scope),
+            label: None,
+            scene_continued: None,
+            scene_continues: false,
         },
         items,
         blocks,
     }
 }
 
+/// Marks the pages that start partway through a scene, and the pages before
+/// them, the way the paginator left room for their scene continueds.
+fn mark_scene_continueds(pages: &mut [Page], units: &[SemanticUnit], geometry: &LayoutGeometry) {
+    let top = geometry.scene_continued_top_lines > 0.0;
+    let bottom = geometry.scene_continued_bottom_lines > 0.0;
+    if !top && !bottom {
+        return;
+    }
+    let scene_numbers: HashMap<&str, Option<&String>> = units
+        .iter()
+        .filter_map(|unit| match unit {
+            SemanticUnit::Flow(flow) if flow.kind == FlowKind::SceneHeading => Some((
+                flow.element_id.as_str(),
+                flow.render_attributes.scene_number.as_ref(),
+            )),
+            _ => None,
+        })
+        .collect();
+
+    let mut scene: Option<(Option<String>, usize)> = None;
+    for index in 0..pages.len() {
+        let starts_scene = pages[index]
+            .items
+            .first()
+            .is_some_and(|item| scene_numbers.contains_key(item.element_id.as_str()));
+        if let Some((scene_number, start)) = &scene
+            && !starts_scene
+            && index > 0
+        {
+            if top {
+                pages[index].metadata.scene_continued = Some(SceneContinued {
+                    scene_number: scene_number.clone(),
+                    count: (index - start) as u32,
+                });
+            }
+            pages[index - 1].metadata.scene_continues = bottom;
+        }
+        for item in &pages[index].items {
+            if let Some(scene_number) = scene_numbers.get(item.element_id.as_str()) {
+                scene = Some((scene_number.cloned(), index));
+            }
+        }
+    }
+}
+
 fn page_items_from_layout_block(
     block: &crate::pagination::composer::LayoutBlock<'_>,
     geometry: &LayoutGeometry,
@@ -3,6 +3,8 @@ use crate::{
     ImportedElementStyle, ImportedLayoutOverrides, ImportedSceneContinueds, Metadata, Screenplay,
 };
 
+use crate::title_page::ALLOW_LOWERCASE_TITLE_FMT_OPTION;
+
 use super::wrapping::InterruptionDashWrap;
 use super::{Alignment, LayoutGeometry};
 
@@ -64,6 +66,10 @@ pub struct ScreenplayElementStyles {
     pub end_of_act: ScreenplayElementStyle,
 }
 
+/// The lines a scene continued takes: the marker itself and the blank line
+/// between it and the page's text.
+const SCENE_CONTINUED_LINES: f32 = 2.0;
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct ScreenplayLayoutProfile {
     pub style_profile: StyleProfile,
@@ -104,6 +110,18 @@ pub struct SceneContinueds {
     pub bottom_text: String,
 }
 
+impl SceneContinueds {
+    /// The text at the top of the `count`th page a scene continues onto,
+    /// such as `CONTINUED: (2)` when continueds are numbered.
+    pub fn top_line(&self, count: u32) -> String {
+        if self.continued_number && count > 1 {
+            format!("{} ({count})", self.top_text)
+        } else {
+            self.top_text.clone()
+        }
+    }
+}
+
 impl ScreenplayLayoutProfile {
     pub fn from_metadata(metadata: &Metadata) -> Self {
         let mut profile = Self::default_screenplay();
@@ -243,6 +261,13 @@ impl ScreenplayLayoutProfile {
         geometry.footer_margin = self.footer_margin;
         geometry.lines_per_page = self.lines_per_page;
 
+        if self.continueds.scene.top_of_next {
+            geometry.scene_continued_top_lines = SCENE_CONTINUED_LINES;
+        }
+        if self.continueds.scene.bottom_of_page {
+            geometry.scene_continued_bottom_lines = SCENE_CONTINUED_LINES;
+        }
+
         geometry
     }
 
@@ -618,75 +643,189 @@ fn overlay_scene_continueds(target: &mut SceneContinueds, imported: &ImportedSce
     }
 }
 
+/// A named `fmt` option and what it does to the layout profile.
+struct FmtOption {
+    names: &'static [&'static str],
+    apply: fn(&mut ScreenplayLayoutProfile),
+}
+
+/// A numeric `fmt` option, written as its prefix followed by the value.
+struct NumericFmtOption {
+    prefix: &'static str,
+    apply: fn(&mut ScreenplayLayoutProfile, f32),
+}
+
+pub(crate) const BOLD_SCENE_HEADINGS_FMT_OPTION: &[&str] = &["bsh", "bold-scene-headings"];
+pub(crate) const UNDERLINE_SCENE_HEADINGS_FMT_OPTION: &[&str] =
+    &["ush", "underline-scene-headings"];
+pub(crate) const SINGLE_SPACE_BEFORE_SCENE_HEADINGS_FMT_OPTION: &[&str] =
+    &["ssbsh", "single-space-before-scene-headings"];
+
+/// Options that only the FDX renderer acts on.
+pub(crate) const ALL_CAPS_ACTION_FMT_OPTION: &[&str] = &["acat", "all-caps-action"];
+pub(crate) const COURIER_FINAL_DRAFT_FMT_OPTION: &[&str] = &["cfd", "courier-final-draft"];
+
+/// Base templates, applied before the geometry overrides.
+const FMT_TEMPLATE_OPTIONS: &[FmtOption] = &[
+    FmtOption {
+        names: &["multicam"],
+        apply: |profile| {
+            profile.style_profile = StyleProfile::Multicam;
+            profile.styles.dialogue.line_spacing = 2.0;
+            profile.styles.dialogue.left_indent = 2.25;
+            profile.styles.character.right_indent = 6.25;
+            profile.styles.parenthetical.left_indent = 2.75;
+            profile.styles.transition.right_indent = 7.25;
+        },
+    },
+    FmtOption {
+        names: &["a4"],
+        apply: |profile| {
+            profile.page_width = 8.26;
+            profile.page_height = 11.69;
+            profile.lines_per_page = 58.0;
+        },
+    },
+    FmtOption {
+        names: &["balanced"],
+        apply: |profile| {
+            profile.interruption_dash_wrap = InterruptionDashWrap::KeepTogether;
+            profile.dual_dialogue_counts_for_contd = false;
+        },
+    },
+    FmtOption {
+        names: &["clean-dashes"],
+        apply: |profile| profile.interruption_dash_wrap = InterruptionDashWrap::KeepTogether,
+    },
+    FmtOption {
+        names: &["no-dual-contds"],
+        apply: |profile| profile.dual_dialogue_counts_for_contd = false,
+    },
+    FmtOption {
+        names: &["scene-continueds"],
+        apply: |profile| {
+            profile.continueds.scene.top_of_next = true;
+            profile.continueds.scene.bottom_of_page = true;
+        },
+    },
+    FmtOption {
+        names: &["numbered-scene-continueds"],
+        apply: |profile| {
+            profile.continueds.scene.top_of_next = true;
+            profile.continueds.scene.bottom_of_page = true;
+            profile.continueds.scene.continued_number = true;
+        },
+    },
+];
+
+/// Explicit geometry knobs, applied after the templates.
+const FMT_GEOMETRY_OPTIONS: &[FmtOption] = &[
+    FmtOption {
+        names: SINGLE_SPACE_BEFORE_SCENE_HEADINGS_FMT_OPTION,
+        apply: |profile| profile.styles.scene_heading.spacing_before = 1.0,
+    },
+    FmtOption {
+        names: BOLD_SCENE_HEADINGS_FMT_OPTION,
+        apply: |profile| profile.styles.scene_heading.bold = true,
+    },
+    FmtOption {
+        names: UNDERLINE_SCENE_HEADINGS_FMT_OPTION,
+        apply: |profile| profile.styles.scene_heading.underline = true,
+    },
+    FmtOption {
+        names: &["dsd", "double-spaced-dialogue"],
+        apply: |profile| profile.styles.dialogue.line_spacing = 2.0,
+    },
+    FmtOption {
+        names: &["no-auto-act-breaks"],
+        apply: |profile| profile.styles.new_act.starts_new_page = false,
+    },
+    FmtOption {
+        names: &["no-act-underlines"],
+        apply: |profile| {
+            profile.styles.cold_opening.underline = false;
+            profile.styles.new_act.underline = false;
+            profile.styles.end_of_act.underline = false;
+        },
+    },
+];
+
+const NUMERIC_FMT_OPTIONS: &[NumericFmtOption] = &[
+    NumericFmtOption {
+        prefix: "dl-",
+        apply: |profile, indent| profile.styles.dialogue.left_indent = indent,
+    },
+    NumericFmtOption {
+        prefix: "dr-",
+        apply: |profile, indent| profile.styles.dialogue.right_indent = indent,
+    },
+    NumericFmtOption {
+        prefix: "tm-",
+        apply: |profile, margin| profile.top_margin = margin,
+    },
+    NumericFmtOption {
+        prefix: "bm-",
+        apply: |profile, margin| profile.bottom_margin = margin,
+    },
+    NumericFmtOption {
+        prefix: "hm-",
+        apply: |profile, margin| profile.header_margin = margin,
+    },
+    NumericFmtOption {
+        prefix: "fm-",
+        apply: |profile, margin| profile.footer_margin = margin,
+    },
+    NumericFmtOption {
+        prefix: "lpp-",
+        apply: |profile, lpp| profile.lines_per_page = lpp,
+    },
+];
+
 fn apply_fmt_template_option(profile: &mut ScreenplayLayoutProfile, option: &str) {
-    if option.eq_ignore_ascii_case("multicam") {
-        profile.style_profile = StyleProfile::Multicam;
-        profile.styles.dialogue.line_spacing = 2.0;
-        profile.styles.dialogue.left_indent = 2.25;
-        profile.styles.character.right_indent = 6.25;
-        profile.styles.parenthetical.left_indent = 2.75;
-        profile.styles.transition.right_indent = 7.25;
-    } else if option.eq_ignore_ascii_case("a4") {
-        profile.page_width = 8.26;
-        profile.page_height = 11.69;
-        profile.lines_per_page = 58.0;
-    } else if option.eq_ignore_ascii_case("balanced") {
-        profile.interruption_dash_wrap = InterruptionDashWrap::KeepTogether;
-        profile.dual_dialogue_counts_for_contd = false;
-    } else if option.eq_ignore_ascii_case("clean-dashes") {
-        profile.interruption_dash_wrap = InterruptionDashWrap::KeepTogether;
-    } else if option.eq_ignore_ascii_case("no-dual-contds") {
-        profile.dual_dialogue_counts_for_contd = false;
+    if let Some(fmt_option) = find_fmt_option(FMT_TEMPLATE_OPTIONS, option) {
+        (fmt_option.apply)(profile);
     }
 }
 
 fn apply_fmt_geometry_override_option(profile: &mut ScreenplayLayoutProfile, option: &str) {
-    if matches_fmt_option(option, &["ssbsh", "single-space-before-scene-headings"]) {
-        profile.styles.scene_heading.spacing_before = 1.0;
-    } else if matches_fmt_option(option, &["bsh", "bold-scene-headings"]) {
-        profile.styles.scene_heading.bold = true;
-    } else if matches_fmt_option(option, &["ush", "underline-scene-headings"]) {
-        profile.styles.scene_heading.underline = true;
-    } else if matches_fmt_option(option, &["dsd", "double-spaced-dialogue"]) {
-        profile.styles.dialogue.line_spacing = 2.0;
-    } else if option.eq_ignore_ascii_case("no-auto-act-breaks") {
-        profile.styles.new_act.starts_new_page = false;
-    } else if option.eq_ignore_ascii_case("no-act-underlines") {
-        profile.styles.cold_opening.underline = false;
-        profile.styles.new_act.underline = false;
-        profile.styles.end_of_act.underline = false;
-    } else if let Some(value) = option.strip_prefix("dl-") {
-        if let Ok(indent) = value.parse::<f32>() {
-            profile.styles.dialogue.left_indent = indent;
-        }
-    } else if let Some(value) = option.strip_prefix("dr-") {
-        if let Ok(indent) = value.parse::<f32>() {
-            profile.styles.dialogue.right_indent = indent;
-        }
-    } else if let Some(value) = option.strip_prefix("tm-") {
-        if let Ok(margin) = value.parse::<f32>() {
-            profile.top_margin = margin;
-        }
-    } else if let Some(value) = option.strip_prefix("bm-") {
-        if let Ok(margin) = value.parse::<f32>() {
-            profile.bottom_margin = margin;
-        }
-    } else if let Some(value) = option.strip_prefix("hm-") {
-        if let Ok(margin) = value.parse::<f32>() {
-            profile.header_margin = margin;
-        }
-    } else if let Some(value) = option.strip_prefix("fm-") {
-        if let Ok(margin) = value.parse::<f32>() {
-            profile.footer_margin = margin;
-        }
-    } else if let Some(value) = option.strip_prefix("lpp-") {
-        if let Ok(lpp) = value.parse::<f32>() {
-            profile.lines_per_page = lpp;
-        }
+    if let Some(fmt_option) = find_fmt_option(FMT_GEOMETRY_OPTIONS, option) {
+        (fmt_option.apply)(profile);
+    } else if let Some((fmt_option, value)) = find_numeric_fmt_option(option) {
+        (fmt_option.apply)(profile, value);
     }
 }
 
-fn matches_fmt_option(option: &str, accepted: &[&str]) -> bool {
+fn find_fmt_option<'a>(options: &'a [FmtOption], option: &str) -> Option<&'a FmtOption> {
+    options
+        .iter()
+        .find(|fmt_option| matches_fmt_option(option, fmt_option.names))
+}
+
+fn find_numeric_fmt_option(option: &str) -> Option<(&'static NumericFmtOption, f32)> {
+    NUMERIC_FMT_OPTIONS.iter().find_map(|fmt_option| {
+        let value = option
+            .strip_prefix(fmt_option.prefix)?
+            .parse::<f32>()
+            .ok()?;
+        Some((fmt_option, value))
+    })
+}
+
+/// Whether any renderer acts on `option` when it appears in `fmt` metadata.
+pub fn is_recognized_fmt_option(option: &str) -> bool {
+    find_fmt_option(FMT_TEMPLATE_OPTIONS, option).is_some()
+        || find_fmt_option(FMT_GEOMETRY_OPTIONS, option).is_some()
+        || find_numeric_fmt_option(option).is_some()
+        || [
+            ALL_CAPS_ACTION_FMT_OPTION,
+            COURIER_FINAL_DRAFT_FMT_OPTION,
+            &[ALLOW_LOWERCASE_TITLE_FMT_OPTION],
+        ]
+        .iter()
+        .any(|names| matches_fmt_option(option, names))
+}
+
+pub(crate) fn matches_fmt_option(option: &str, accepted: &[&str]) -> bool {
     accepted
         .iter()
         .any(|candidate| option.eq_ignore_ascii_case(candidate))
@@ -0,0 +1,277 @@
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+
+use crate::page_lock::{insert_label, leading_label, range_label};
+use crate::pagination::{
+    build_semantic_screenplay_with_options, normalize_screenplay, normalized_element_ids,
+    Fragment, Page, PageKind, PaginatedScreenplay, PaginationConfig, PaginationScope,
+    ScreenplayLayoutProfile, SemanticOptions, SemanticScreenplay, StyleProfile,
+};
+use crate::{Element, LockedPage, PageLock, Screenplay};
+
+impl Screenplay {
+    /// Locks the screenplay's pages as they are now, giving its elements ids
+    /// first if they have none. The lock is kept in `page_lock`, so this
+    /// screenplay and later drafts carrying the same lock are paginated
+    /// against it; the lock is returned for saving on its own.
+    ///
+    /// Locking a screenplay that is already locked keeps its labels, so
+    /// inserted pages such as `12A` are locked in turn.
+    pub fn lock_pages(&mut self) -> PageLock {
+        self.assign_element_ids();
+        let paginated = paginate(self);
+        let lock = PageLock::from_paginated(&paginated, self);
+        self.page_lock = Some(lock.clone());
+        lock
+    }
+}
+
+impl PageLock {
+    /// Locks the body pages of `paginated`, a pagination of `screenplay`.
+    /// Elements are recorded by id, so elements without one are left out.
+    pub fn from_paginated(paginated: &PaginatedScreenplay, screenplay: &Screenplay) -> PageLock {
+        let ids = ids_by_normalized_id(screenplay);
+        let pages = paginated
+            .pages
+            .iter()
+            .filter(|page| page.metadata.kind == PageKind::Body)
+            .map(|page| {
+                let mut elements: Vec<String> = Vec::new();
+                for item in &page.items {
+                    if let Some(id) = ids.get(item.element_id.as_str())
+                        && !elements.iter().any(|element| element == id)
+                    {
+                        elements.push(id.to_string());
+                    }
+                }
+                LockedPage {
+                    label: page.metadata.label.clone().unwrap_or_else(|| {
+                        page.metadata
+                            .body_page_number
+                            .unwrap_or(page.metadata.number)
+                            .to_string()
+                    }),
+                    elements,
+                    continued: page
+                        .items
+                        .first()
+                        .is_some_and(|item| continues_from_previous_page(&item.fragment)),
+                }
+            })
+            .collect();
+        PageLock { pages }
+    }
+}
+
+/// Paginates the screenplay the way the HTML and PDF renderers do, with its
+/// page lock applied.
+fn paginate(screenplay: &Screenplay) -> PaginatedScreenplay {
+    let input = PaginationInput::new(screenplay);
+    let mut paginated = PaginatedScreenplay::paginate(
+        input.semantic,
+        input.config,
+        input.style_profile,
+        PaginationScope {
+            first_page_number: None,
+            title_page_count: None,
+            body_start_page: None,
+        },
+    );
+    label_pages(&mut paginated.pages, screenplay);
+    paginated
+}
+
+/// What the renderers paginate a screenplay from: its semantic units, with
+/// a page break at each locked page, and the config and style profile of its
+/// layout profile.
+pub(crate) struct PaginationInput {
+    pub semantic: SemanticScreenplay,
+    pub config: PaginationConfig,
+    pub style_profile: &'static str,
+}
+
+impl PaginationInput {
+    pub fn new(screenplay: &Screenplay) -> PaginationInput {
+        let profile = ScreenplayLayoutProfile::from_screenplay(screenplay);
+        let locked = with_locked_breaks(screenplay);
+        let semantic = build_semantic_screenplay_with_options(
+            normalize_screenplay("screenplay", &locked),
+            SemanticOptions {
+                dual_dialogue_counts_for_contd: profile.dual_dialogue_counts_for_contd,
+                automatic_character_continueds: profile.automatic_character_continueds,
+            },
+        );
+        PaginationInput {
+            semantic,
+            config: PaginationConfig {
+                geometry: profile.to_pagination_geometry(),
+                interruption_dash_wrap: profile.interruption_dash_wrap,
+            },
+            style_profile: match profile.style_profile {
+                StyleProfile::Screenplay => "standard",
+                StyleProfile::Multicam => "multicam",
+            },
+        }
+    }
+}
+
+/// The screenplay with a page break before the first remaining element of
+/// every locked page, or the screenplay itself if it is not locked. Pages
+/// that started partway through an element break wherever that element
+/// splits again.
+pub(crate) fn with_locked_breaks(screenplay: &Screenplay) -> Cow<'_, Screenplay> {
+    let Some(lock) = &screenplay.page_lock else {
+        return Cow::Borrowed(screenplay);
+    };
+    let mut present = HashSet::new();
+    collect_ids(&screenplay.elements, &mut present);
+    let breaks: HashSet<&str> = lock
+        .pages
+        .iter()
+        .skip(1)
+        .filter(|page| !page.continued)
+        .filter_map(|page| {
+            page.elements
+                .iter()
+                .map(String::as_str)
+                .find(|id| present.contains(id))
+        })
+        .collect();
+
+    let mut locked = screenplay.clone();
+    mark_page_starts(&mut locked.elements, &breaks);
+    Cow::Owned(locked)
+}
+
+/// Labels the body pages of a locked screenplay's pagination.
+///
+/// Each locked page lands on the page its first remaining element starts
+/// on, or continues on for a page that started partway through it. A locked
+/// page with nothing left, or that would land before the page before it,
+/// lands with that page. Pages where several locked pages land take their
+/// range, such as `13-14`; pages where none land are inserted pages,
+/// labeled after the page before them.
+pub(crate) fn label_pages(pages: &mut [Page], screenplay: &Screenplay) {
+    let Some(lock) = &screenplay.page_lock else {
+        return;
+    };
+    let ids = ids_by_normalized_id(screenplay);
+    let body: Vec<usize> = pages
+        .iter()
+        .enumerate()
+        .filter(|(_, page)| page.metadata.kind == PageKind::Body)
+        .map(|(index, _)| index)
+        .collect();
+
+    let mut starts: HashMap<&str, usize> = HashMap::new();
+    let mut continues: HashMap<&str, usize> = HashMap::new();
+    for (position, &index) in body.iter().enumerate() {
+        for item in &pages[index].items {
+            let Some(id) = ids.get(item.element_id.as_str()) else {
+                continue;
+            };
+            let first_pages = if continues_from_previous_page(&item.fragment) {
+                &mut continues
+            } else {
+                &mut starts
+            };
+            first_pages.entry(id).or_insert(position);
+        }
+    }
+
+    let mut landed: Vec<Vec<&str>> = vec![Vec::new(); body.len()];
+    let mut unplaced = Vec::new();
+    let mut last = None;
+    for locked in &lock.pages {
+        let position = locked
+            .elements
+            .iter()
+            .enumerate()
+            .find_map(|(index, id)| {
+                if index == 0 && locked.continued {
+                    continues.get(id.as_str())
+                } else {
+                    starts.get(id.as_str())
+                }
+            })
+            .copied()
+            .filter(|position| last.is_none_or(|last| *position >= last))
+            .or(last);
+        match position {
+            Some(position) => {
+                landed[position].append(&mut unplaced);
+                landed[position].push(locked.label.as_str());
+                last = Some(position);
+            }
+            None => unplaced.push(locked.label.as_str()),
+        }
+    }
+    let Some(first_landed) = landed.iter().position(|labels| !labels.is_empty()) else {
+        return;
+    };
+
+    let taken: HashSet<&str> = lock.pages.iter().map(|page| page.label.as_str()).collect();
+    let mut previous = String::new();
+    for (position, labels) in landed.iter().enumerate() {
+        let label = match labels.as_slice() {
+            [] if position < first_landed => leading_label(landed[first_landed][0], position),
+            [] => {
+                let mut label = insert_label(&previous);
+                while taken.contains(label.as_str()) {
+                    label.push('A');
+                }
+                label
+            }
+            [only] => only.to_string(),
+            [first, .., last] => range_label(first, last),
+        };
+        pages[body[position]].metadata.label = Some(label.clone());
+        previous = label;
+    }
+}
+
+fn continues_from_previous_page(fragment: &Fragment) -> bool {
+    matches!(
+        fragment,
+        Fragment::ContinuedFromPrev | Fragment::ContinuedFromPrevAndToNext
+    )
+}
+
+/// The element ids of the screenplay keyed by the ids `normalize_screenplay`
+/// hands out.
+fn ids_by_normalized_id(screenplay: &Screenplay) -> HashMap<String, &str> {
+    normalized_element_ids(screenplay)
+        .into_iter()
+        .filter_map(|(_, element, normalized_id)| {
+            Some((normalized_id, element.id().filter(|id| !id.is_empty())?))
+        })
+        .collect()
+}
+
+fn collect_ids<'a>(elements: &'a [Element], ids: &mut HashSet<&'a str>) {
+    for element in elements {
+        match element {
+            Element::DialogueBlock(block) | Element::DualDialogueBlock(block) => {
+                collect_ids(block, ids)
+            }
+            _ => ids.extend(element.id()),
+        }
+    }
+}
+
+fn mark_page_starts(elements: &mut [Element], breaks: &HashSet<&str>) {
+    for element in elements {
+        match element {
+            Element::DialogueBlock(block) | Element::DualDialogueBlock(block) => {
+                mark_page_starts(block, breaks)
+            }
+            _ => {
+                if element.id().is_some_and(|id| breaks.contains(id))
+                    && let Some(attributes) = element.attributes_mut()
+                {
+                    attributes.starts_new_page = true;
+                }
+            }
+        }
+    }
+}
@@ -129,6 +129,12 @@ pub struct LayoutGeometry {
     pub header_margin: f32,
     pub footer_margin: f32,
     pub lines_per_page: f32,
+
+    // Scene continueds: lines taken by "CONTINUED:" at the top of a page
+    // that starts partway through a scene and "(CONTINUED)" at the bottom
+    // of the page before it, or 0 when they are off.
+    pub scene_continued_top_lines: f32,
+    pub scene_continued_bottom_lines: f32,
 }
 
 impl Default for LayoutGeometry {
@@ -224,6 +230,9 @@ impl Default for LayoutGeometry {
             header_margin: 0.5,
             footer_margin: 0.5,
             lines_per_page: 54.0,
+
+            scene_continued_top_lines: 0.0,
+            scene_continued_bottom_lines: 0.0,
         }
     }
 }
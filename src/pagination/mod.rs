@@ -1,13 +1,16 @@
 mod comparison;
 pub mod composer;
+pub(crate) mod continueds;
 pub mod dialogue_split;
 mod fixtures;
 pub mod flow_split;
 mod ir;
 pub mod layout_profile;
+pub(crate) mod locked_pages;
 pub mod margin;
 mod normalized;
 pub mod paginator;
+pub(crate) mod scene_lengths;
 mod semantic;
 mod sentence_boundary;
 mod split_scoring;
@@ -24,13 +27,14 @@ pub use fixtures::{
 };
 pub use ir::{
     BlockPlacement, ContinuationMarker, Page, PageBlock, PageItem, PageKind, PageMetadata,
-    PaginatedScreenplay, PaginationConfig,
+    PaginatedScreenplay, PaginationConfig, SceneContinued,
 };
 pub use layout_profile::{
     ScreenplayElementStyle, ScreenplayElementStyles, ScreenplayLayoutProfile, StyleProfile,
 };
 pub use margin::{Alignment, FdxExtractedSettings, FdxParagraphStyle, LayoutGeometry};
-pub use normalized::normalize_screenplay;
+pub use normalized::{normalize_screenplay, normalized_element_ids};
+pub use scene_lengths::{Eighths, SceneLength};
 pub use semantic::{
     build_semantic_screenplay, build_semantic_screenplay_with_options, Cohesion, DialoguePart,
     DialoguePartKind, DialogueUnit, DualDialogueSide, DualDialogueUnit, FlowKind, FlowUnit,
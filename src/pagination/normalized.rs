@@ -20,6 +20,35 @@ pub fn normalize_screenplay(
     }
 }
 
+/// The elements `normalize_screenplay` keeps, in order, each with the index
+/// of the top-level element it belongs to and the id it is given.
+pub fn normalized_element_ids(screenplay: &Screenplay) -> Vec<(usize, &Element, String)> {
+    fn collect<'a>(
+        index: usize,
+        element: &'a Element,
+        ids: &mut Vec<(usize, &'a Element, String)>,
+    ) {
+        match element {
+            Element::DialogueBlock(children) | Element::DualDialogueBlock(children) => {
+                for child in children {
+                    collect(index, child, ids);
+                }
+            }
+            Element::Boneyard(_) | Element::PageBreak => {}
+            _ => ids.push((index, element, normalized_element_id(ids.len() + 1))),
+        }
+    }
+    let mut ids = Vec::new();
+    for (index, element) in screenplay.elements.iter().enumerate() {
+        collect(index, element, &mut ids);
+    }
+    ids
+}
+
+fn normalized_element_id(number: usize) -> String {
+    format!("el-{number:05}")
+}
+
 #[derive(Default)]
 struct NormalizedCollector {
     next_element_id: usize,
@@ -103,7 +132,7 @@ impl NormalizedCollector {
             | Element::EndOfAct(text, attributes) => (
                 element.name().to_string(),
                 flatten_text(text),
-                StyledText::from_element_text(text),
+                StyledText::from_revised_element_text(text, attributes.revision),
                 attributes.centered,
                 attributes.starts_new_page,
                 attributes.scene_number.clone(),
@@ -112,7 +141,7 @@ impl NormalizedCollector {
             Element::Section(text, attributes, _) => (
                 "Section".to_string(),
                 flatten_text(text),
-                StyledText::from_element_text(text),
+                StyledText::from_revised_element_text(text, attributes.revision),
                 attributes.centered,
                 attributes.starts_new_page,
                 attributes.scene_number.clone(),
@@ -127,14 +156,15 @@ impl NormalizedCollector {
                 None,
                 Default::default(),
             ),
-            Element::DialogueBlock(_) | Element::DualDialogueBlock(_) | Element::PageBreak => {
-                return
-            }
+            Element::DialogueBlock(_)
+            | Element::DualDialogueBlock(_)
+            | Element::Boneyard(_)
+            | Element::PageBreak => return,
         };
 
         self.next_element_id += 1;
         self.elements.push(NormalizedElement {
-            element_id: format!("el-{:05}", self.next_element_id),
+            element_id: normalized_element_id(self.next_element_id),
             kind,
             text,
             inline_text,
@@ -74,18 +74,35 @@ pub fn paginate_with_mode<'a>(
         });
     }
 
+    // Whether the content after each chunk starts a new scene, or there is
+    // none, so that a page break after the chunk needs no scene continueds.
+    let mut next_starts_scene = vec![true; chunks.len()];
+    for index in (0..chunks.len().saturating_sub(1)).rev() {
+        let next = &chunks[index + 1];
+        next_starts_scene[index] = if chunk_is_page_start(next) {
+            next_starts_scene[index + 1]
+        } else {
+            chunk_starts_scene(next)
+        };
+    }
+
     let mut pages: Vec<Page<'a>> = Vec::new();
     let mut current_page_blocks = Vec::new();
     let mut current_page_lines: f32 = 0.0;
+    let mut scene_open = false;
 
-    for chunk in chunks {
-        if chunk.blocks.len() == 1 && matches!(chunk.blocks[0].unit, SemanticUnit::PageStart(_)) {
+    for (chunk_index, chunk) in chunks.into_iter().enumerate() {
+        if chunk_is_page_start(&chunk) {
             if current_page_blocks.iter().any(block_has_visible_content) {
                 pages.push(Page {
                     blocks: current_page_blocks,
                 });
                 current_page_blocks = Vec::new();
-                current_page_lines = 0.0;
+                current_page_lines = if scene_open && !next_starts_scene[chunk_index] {
+                    geometry.scene_continued_top_lines
+                } else {
+                    0.0
+                };
             }
 
             current_page_blocks.push(LayoutBlock {
@@ -119,14 +136,32 @@ pub fn paginate_with_mode<'a>(
             }
         }
 
-        if current_page_lines + chunk_height > page_limit_lines {
+        // A page break inside a scene leaves room for its continueds.
+        let starts_scene = chunk_starts_scene(&chunk);
+        let continues_scene = scene_open && !starts_scene;
+        scene_open |= chunk
+            .blocks
+            .iter()
+            .any(|block| block_is_scene_heading(block));
+        let split_limit_lines = page_limit_lines - geometry.scene_continued_bottom_lines;
+        let fit_limit_lines = if scene_open && !next_starts_scene[chunk_index] {
+            split_limit_lines
+        } else {
+            page_limit_lines
+        };
+
+        if current_page_lines + chunk_height > fit_limit_lines {
             if chunk.blocks.len() == 2 {
                 if let Some(split) = choose_keep_with_next_split(
                     chunk.blocks[0],
                     chunk.blocks[1],
                     current_page_blocks.iter().any(block_has_visible_content),
                     current_page_lines,
-                    page_limit_lines,
+                    if scene_open {
+                        split_limit_lines
+                    } else {
+                        page_limit_lines
+                    },
                     geometry,
                     interruption_dash_wrap,
                 ) {
@@ -169,6 +204,9 @@ pub fn paginate_with_mode<'a>(
                         widow_penalty: 0.0,
                     }];
                     current_page_lines = current_page_blocks[0].content_lines;
+                    if scene_open {
+                        current_page_lines += geometry.scene_continued_top_lines;
+                    }
                     continue;
                 }
             }
@@ -181,7 +219,13 @@ pub fn paginate_with_mode<'a>(
                 } else {
                     0.0
                 };
-                let available_lines = (page_limit_lines - current_page_lines).max(0.0);
+                let split_reserve = if scene_open {
+                    geometry.scene_continued_bottom_lines
+                } else {
+                    0.0
+                };
+                let available_lines =
+                    (page_limit_lines - split_reserve - current_page_lines).max(0.0);
 
                 if let Some(split) = choose_split_lines(
                     block,
@@ -219,6 +263,9 @@ pub fn paginate_with_mode<'a>(
                         widow_penalty: 0.0,
                     }];
                     current_page_lines = split.bottom_lines + block.widow_penalty;
+                    if scene_open {
+                        current_page_lines += geometry.scene_continued_top_lines;
+                    }
                     continue;
                 }
             }
@@ -247,6 +294,9 @@ pub fn paginate_with_mode<'a>(
                         widow_penalty: moved_block.widow_penalty,
                     }];
                     current_page_lines = current_page_blocks[0].content_lines;
+                    if continues_scene && !block_is_scene_heading(&current_page_blocks[0]) {
+                        current_page_lines += geometry.scene_continued_top_lines;
+                    }
 
                     let mut page_has_visible_content = true;
                     for block in &chunk.blocks {
@@ -285,7 +335,11 @@ pub fn paginate_with_mode<'a>(
             }
 
             current_page_blocks = Vec::new();
-            current_page_lines = 0.0;
+            current_page_lines = if continues_scene {
+                geometry.scene_continued_top_lines
+            } else {
+                0.0
+            };
 
             let mut page_has_visible_content = false;
 
@@ -563,6 +617,28 @@ fn max_top_wrapped_lines(available_lines: f32, effective_spacing: f32, line_heig
     ((available_lines - effective_spacing) / line_height).floor() as usize
 }
 
+fn chunk_is_page_start(chunk: &Chunk<'_>) -> bool {
+    chunk.blocks.len() == 1 && matches!(chunk.blocks[0].unit, SemanticUnit::PageStart(_))
+}
+
+fn chunk_starts_scene(chunk: &Chunk<'_>) -> bool {
+    chunk
+        .blocks
+        .iter()
+        .find(|block| block_has_visible_content(block))
+        .is_some_and(|block| block_is_scene_heading(block))
+}
+
+fn block_is_scene_heading(block: &LayoutBlock<'_>) -> bool {
+    matches!(
+        block.unit,
+        SemanticUnit::Flow(crate::pagination::FlowUnit {
+            kind: crate::pagination::FlowKind::SceneHeading,
+            ..
+        })
+    )
+}
+
 fn chunk_starts_with_transition(chunk: &Chunk<'_>) -> bool {
     chunk
         .blocks
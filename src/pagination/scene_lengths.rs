@@ -0,0 +1,222 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::pagination::composer::LayoutBlock;
+use crate::pagination::locked_pages::{PaginationInput, label_pages};
+use crate::pagination::{
+    FlowKind, Fragment, Page, PageKind, PageMetadata, PaginatedScreenplay, PaginationConfig,
+    SemanticUnit,
+};
+use crate::rendering::text::default_pagination_scope;
+use crate::Screenplay;
+
+/// The body pages of a screenplay and the lines each scene takes on them,
+/// from the same pagination the HTML and PDF renderers use.
+pub(crate) struct ScenePages {
+    pub config: PaginationConfig,
+    /// The printed number of each body page.
+    pub pages: Vec<String>,
+    /// The metadata of each body page in the full pagination.
+    pub page_metadata: Vec<PageMetadata>,
+    /// The scenes in the order of their headings.
+    pub scenes: Vec<PaginatedScene>,
+    /// The index of the last body page with anything before the first scene
+    /// heading on it, such as `FADE IN:`.
+    pub opening_last_page: Option<usize>,
+    /// The semantic units the pages were laid out from, in order.
+    pub units: Vec<SemanticUnit>,
+    /// The body page each element starts on, keyed by the ids
+    /// `normalize_screenplay` hands out.
+    element_pages: HashMap<String, usize>,
+}
+
+pub(crate) struct PaginatedScene {
+    pub heading: String,
+    pub scene_number: Option<String>,
+    /// The lines the scene takes, counting the blank lines above its
+    /// elements but not those at the top of a page.
+    pub lines: f32,
+    /// The index of the body page the scene starts on.
+    pub first_page: usize,
+    /// The index of the body page the scene ends on.
+    pub last_page: usize,
+}
+
+/// What scene lengths need of a layout block: the heading and scene number
+/// of a scene that starts with it, and the lines it takes.
+struct LaidOutBlock {
+    scene_heading: Option<(String, Option<String>)>,
+    spacing_above: f32,
+    content_lines: f32,
+}
+
+impl LaidOutBlock {
+    /// `None` for the page breaks that start pages, which take no lines.
+    fn new(block: &LayoutBlock) -> Option<LaidOutBlock> {
+        if matches!(block.unit, SemanticUnit::PageStart(_)) {
+            return None;
+        }
+        let scene_heading = match block.unit {
+            SemanticUnit::Flow(flow)
+                if flow.kind == FlowKind::SceneHeading
+                    && !matches!(block.fragment, Fragment::ContinuedFromPrev) =>
+            {
+                Some((
+                    flow.text.clone(),
+                    flow.render_attributes.scene_number.clone(),
+                ))
+            }
+            _ => None,
+        };
+        Some(LaidOutBlock {
+            scene_heading,
+            spacing_above: block.spacing_above,
+            content_lines: block.content_lines,
+        })
+    }
+}
+
+impl ScenePages {
+    pub fn new(screenplay: &Screenplay) -> ScenePages {
+        let PaginationInput {
+            semantic,
+            config,
+            style_profile,
+        } = PaginationInput::new(screenplay);
+        let mut layout_pages: Vec<Vec<LaidOutBlock>> = Vec::new();
+        let mut paginated = PaginatedScreenplay::paginate_with_layout(
+            &semantic,
+            &config,
+            style_profile,
+            default_pagination_scope(screenplay),
+            |blocks| layout_pages.push(blocks.iter().filter_map(LaidOutBlock::new).collect()),
+        );
+        label_pages(&mut paginated.pages, screenplay);
+
+        let mut scene_pages = ScenePages {
+            config,
+            pages: Vec::new(),
+            page_metadata: Vec::new(),
+            scenes: Vec::new(),
+            opening_last_page: None,
+            units: Vec::new(),
+            element_pages: HashMap::new(),
+        };
+        for (page, layout_page) in paginated.pages.iter().zip(layout_pages) {
+            if page.metadata.kind != PageKind::Body {
+                continue;
+            }
+            let index = scene_pages.pages.len();
+            scene_pages.pages.push(printed_page_number(page));
+            scene_pages.page_metadata.push(page.metadata.clone());
+            for item in &page.items {
+                scene_pages
+                    .element_pages
+                    .entry(item.element_id.clone())
+                    .or_insert(index);
+            }
+
+            let mut page_has_content = false;
+            for block in layout_page {
+                if let Some((heading, scene_number)) = block.scene_heading {
+                    scene_pages.scenes.push(PaginatedScene {
+                        heading,
+                        scene_number,
+                        lines: 0.0,
+                        first_page: index,
+                        last_page: index,
+                    });
+                }
+                let spacing = if page_has_content {
+                    block.spacing_above
+                } else {
+                    0.0
+                };
+                page_has_content = true;
+                match scene_pages.scenes.last_mut() {
+                    Some(scene) => {
+                        scene.lines += spacing + block.content_lines;
+                        scene.last_page = index;
+                    }
+                    None => scene_pages.opening_last_page = Some(index),
+                }
+            }
+        }
+        scene_pages.units = semantic.units;
+        scene_pages
+    }
+
+    /// The scene's length in eighths of a page, rounded to the nearest
+    /// eighth and at least one.
+    pub fn eighths(&self, scene: &PaginatedScene) -> Eighths {
+        Eighths(((scene.lines * 8.0 / self.config.geometry.lines_per_page).round() as u32).max(1))
+    }
+
+    /// The index of the body page the element with this normalized id starts
+    /// on.
+    pub fn element_page(&self, element_id: &str) -> Option<usize> {
+        self.element_pages.get(element_id).copied()
+    }
+}
+
+/// A length in eighths of a page, the unit breakdown sheets measure scenes
+/// in. Displays as it is written on them, such as `2 3/8`, `5/8` or `1`, and
+/// serializes as the number of eighths.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(transparent)]
+pub struct Eighths(pub u32);
+
+impl fmt::Display for Eighths {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.0 / 8, self.0 % 8) {
+            (0, part) => write!(f, "{part}/8"),
+            (pages, 0) => write!(f, "{pages}"),
+            (pages, part) => write!(f, "{pages} {part}/8"),
+        }
+    }
+}
+
+/// How much of the page a scene takes, as returned by
+/// [`Screenplay::scene_lengths`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SceneLength {
+    pub scene_number: Option<String>,
+    pub heading: String,
+    pub eighths: Eighths,
+    /// The printed number of the page the scene starts on.
+    pub first_page: String,
+    /// The printed number of the page the scene ends on.
+    pub last_page: String,
+}
+
+impl Screenplay {
+    /// Measures each scene in eighths of a page, in the order of their
+    /// headings. Scenes are measured on the same pages the PDF renderer
+    /// prints, counting the printed lines from a scene's heading up to the
+    /// next one, and each scene takes at least an eighth.
+    pub fn scene_lengths(&self) -> Vec<SceneLength> {
+        let scene_pages = ScenePages::new(self);
+        scene_pages
+            .scenes
+            .iter()
+            .map(|scene| SceneLength {
+                scene_number: scene.scene_number.clone(),
+                heading: scene.heading.clone(),
+                eighths: scene_pages.eighths(scene),
+                first_page: scene_pages.pages[scene.first_page].clone(),
+                last_page: scene_pages.pages[scene.last_page].clone(),
+            })
+            .collect()
+    }
+}
+
+/// The number printed on the page, such as `12` or, when locked, `12A`.
+fn printed_page_number(page: &Page) -> String {
+    page.metadata.label.clone().unwrap_or_else(|| {
+        page.metadata
+            .body_page_number
+            .unwrap_or(page.metadata.number)
+            .to_string()
+    })
+}
@@ -1,6 +1,7 @@
 use std::collections::BTreeSet;
 
 use super::fixtures::{NormalizedElement, NormalizedScreenplay};
+use crate::CharacterCue;
 use crate::render_attributes::RenderAttributes;
 use crate::styled_text::StyledText;
 
@@ -289,9 +290,15 @@ fn build_dialogue_unit(
     options: SemanticOptions,
 ) -> DialogueUnit {
     let current_speaker = dialogue_contd_speaker_for_elements(elements);
-    let should_append_contd = current_speaker.as_deref().is_some_and(|current| {
-        options.automatic_character_continueds && previous_scene_speakers.contains(current)
-    });
+    // A cue the author already marked (CONT'D) must not get a second one.
+    let already_continued = elements
+        .iter()
+        .find(|element| element.kind == "Character")
+        .is_some_and(|element| CharacterCue::parse(&element.text).is_continued());
+    let should_append_contd = !already_continued
+        && current_speaker.as_deref().is_some_and(|current| {
+            options.automatic_character_continueds && previous_scene_speakers.contains(current)
+        });
     let parts = elements
         .iter()
         .map(|element| DialoguePart {
@@ -443,45 +450,14 @@ fn dialogue_part_kind(kind: &str) -> DialoguePartKind {
 }
 
 fn dialogue_contd_speaker(parts: &[DialoguePart]) -> Option<String> {
-    let text = parts
+    let cue = parts
         .iter()
         .find(|part| part.kind == DialoguePartKind::Character)
-        .map(|part| part.text.trim())?;
-    let stripped = strip_trailing_contd(text);
+        .map(|part| CharacterCue::parse(&part.text))?;
 
-    if speaker_excludes_contd(stripped) {
+    if cue.is_voice_over() {
         return None;
     }
 
-    Some(strip_trailing_speaker_extensions(stripped).to_string())
-}
-
-fn strip_trailing_contd(text: &str) -> &str {
-    let trimmed = text.trim_end();
-    let upper = trimmed.to_ascii_uppercase();
-
-    if upper.ends_with("(CONT'D)") || upper.ends_with("(CONT’D)") {
-        let suffix_start = trimmed.rfind('(').unwrap_or(trimmed.len());
-        return trimmed[..suffix_start].trim_end();
-    }
-
-    trimmed
-}
-
-fn speaker_excludes_contd(text: &str) -> bool {
-    let upper = text.trim_end().to_ascii_uppercase();
-    upper.contains("(V.O.)") || upper.contains("(V.O)")
-}
-
-fn strip_trailing_speaker_extensions(text: &str) -> &str {
-    let mut current = text.trim_end();
-
-    while current.ends_with(')') {
-        let Some(open_paren) = current.rfind('(') else {
-            break;
-        };
-        current = current[..open_paren].trim_end();
-    }
-
-    current
+    Some(cue.name)
 }
@@ -1,4 +1,7 @@
 use crate::pagination::composer::{self, LayoutBlock};
+use crate::pagination::continueds::{continued_character_cue_text, scene_continued_text};
+use crate::pagination::layout_profile::SceneContinueds;
+use crate::pagination::locked_pages::{label_pages, with_locked_breaks};
 use crate::pagination::margin::line_height_for_element_type;
 use crate::pagination::paginator;
 use crate::pagination::wrapping::{
@@ -9,7 +12,7 @@ use crate::pagination::{
     Page, PageKind, PaginatedScreenplay, PaginationConfig, PaginationScope,
     ScreenplayLayoutProfile, SemanticOptions, SemanticUnit, StyleProfile,
 };
-use crate::styled_text::{StyledRun, StyledText};
+use crate::styled_text::{style_revision, StyledRun, StyledText};
 use crate::title_page::{frontmatter_count, TitlePage};
 use crate::{ElementLayoutOverrides, Screenplay};
 
@@ -38,6 +41,23 @@ pub(crate) struct VisualLine {
     pub scene_number: Option<String>,
 }
 
+impl VisualLine {
+    /// The latest revision marked on any of the line's text.
+    pub(crate) fn revision(&self) -> Option<u32> {
+        let dual_fragments = self
+            .dual
+            .iter()
+            .flat_map(|dual| dual.left.iter().chain(dual.right.iter()))
+            .flat_map(|side| side.fragments.iter());
+        self.fragments
+            .iter()
+            .chain(dual_fragments)
+            .flat_map(|fragment| fragment.styles.iter())
+            .filter_map(|style| style_revision(style))
+            .max()
+    }
+}
+
 #[derive(Clone, Debug)]
 pub(crate) struct VisualPage {
     pub page: Page,
@@ -71,7 +91,8 @@ pub(crate) fn render_paginated_visual_pages_with_options(
     let scope = default_pagination_scope(screenplay, options);
     let layout_profile = ScreenplayLayoutProfile::from_screenplay(screenplay);
     let style_profile = style_profile_name(&layout_profile);
-    let normalized = normalize_screenplay(screenplay_id, screenplay);
+    let locked = with_locked_breaks(screenplay);
+    let normalized = normalize_screenplay(screenplay_id, &locked);
     let semantic = build_semantic_screenplay_with_options(
         normalized,
         SemanticOptions {
@@ -84,8 +105,9 @@ pub(crate) fn render_paginated_visual_pages_with_options(
         interruption_dash_wrap: layout_profile.interruption_dash_wrap,
     };
     let blocks = composer::compose(&semantic.units, &config.geometry);
-    let actual =
+    let mut actual =
         PaginatedScreenplay::paginate(semantic.clone(), config.clone(), style_profile, scope);
+    label_pages(&mut actual.pages, screenplay);
     let layout_pages =
         nonempty_layout_pages(&blocks, &config.geometry, config.geometry.lines_per_page);
 
@@ -93,14 +115,23 @@ pub(crate) fn render_paginated_visual_pages_with_options(
         .pages
         .into_iter()
         .zip(layout_pages)
-        .map(|(page, layout_page)| VisualPage {
-            page,
-            lines: render_layout_page_lines(
+        .map(|(page, layout_page)| {
+            let mut lines = render_layout_page_lines(
                 &layout_page,
                 &config.geometry,
                 config.interruption_dash_wrap,
                 options,
-            ),
+            );
+            if options.render_continueds {
+                add_scene_continued_lines(
+                    &mut lines,
+                    &page,
+                    &layout_profile.continueds.scene,
+                    &config.geometry,
+                    config.interruption_dash_wrap,
+                );
+            }
+            VisualPage { page, lines }
         })
         .collect()
 }
@@ -651,17 +682,6 @@ fn render_dialogue_continuation_prefix(
         .collect()
 }
 
-fn continued_character_cue_text(text: &str) -> String {
-    let trimmed = text.trim_end();
-    let upper = trimmed.to_ascii_uppercase();
-
-    if upper.ends_with("(CONT'D)") || upper.ends_with("(CONT’D)") {
-        trimmed.to_string()
-    } else {
-        format!("{trimmed} (CONT'D)")
-    }
-}
-
 fn render_more_marker_lines(
     geometry: &LayoutGeometry,
     interruption_dash_wrap: InterruptionDashWrap,
@@ -715,6 +735,71 @@ fn render_more_marker_lines(
     lines
 }
 
+/// Adds the page's `CONTINUED:` line above its text and its `(CONTINUED)`
+/// line below, each a blank line away, in the lines the paginator left for
+/// them.
+fn add_scene_continued_lines(
+    lines: &mut Vec<VisualLine>,
+    page: &Page,
+    continueds: &SceneContinueds,
+    geometry: &LayoutGeometry,
+    interruption_dash_wrap: InterruptionDashWrap,
+) {
+    if let Some(continued) = &page.metadata.scene_continued {
+        let marker = scene_continued_line(
+            &continueds.top_line(continued.count),
+            ElementType::Action,
+            continued.scene_number.clone(),
+            geometry,
+            interruption_dash_wrap,
+        );
+        lines.splice(0..0, [marker, uncounted_blank_line()]);
+    }
+    if page.metadata.scene_continues {
+        lines.push(uncounted_blank_line());
+        lines.push(scene_continued_line(
+            &continueds.bottom_text,
+            ElementType::Transition,
+            None,
+            geometry,
+            interruption_dash_wrap,
+        ));
+    }
+}
+
+/// A `CONTINUED:` or `(CONTINUED)` line, not counted toward the page's
+/// lines.
+fn scene_continued_line(
+    text: &str,
+    element_type: ElementType,
+    scene_number: Option<String>,
+    geometry: &LayoutGeometry,
+    interruption_dash_wrap: InterruptionDashWrap,
+) -> VisualLine {
+    let text = scene_continued_text(text, element_type, geometry, interruption_dash_wrap);
+    VisualLine {
+        fragments: vec![plain_fragment_for_text(&text)],
+        text,
+        counted: false,
+        centered: false,
+        element_type: Some(element_type),
+        dual: None,
+        scene_number,
+    }
+}
+
+fn uncounted_blank_line() -> VisualLine {
+    VisualLine {
+        text: String::new(),
+        counted: false,
+        centered: false,
+        element_type: None,
+        fragments: Vec::new(),
+        dual: None,
+        scene_number: None,
+    }
+}
+
 fn render_semantic_unit_lines(
     unit: &SemanticUnit,
     geometry: &LayoutGeometry,
@@ -1330,6 +1415,18 @@ pub(crate) fn display_page_number(page: &Page) -> Option<u32> {
     Some(display_number)
 }
 
+/// The printed page number of the page: its locked label such as `12A`, or
+/// its display number. Like the display number, a first page labeled `1`
+/// goes unprinted.
+pub(crate) fn display_page_label(page: &Page) -> Option<String> {
+    match &page.metadata.label {
+        _ if matches!(page.metadata.kind, PageKind::Title) => None,
+        Some(label) if label == "1" => None,
+        Some(label) => Some(label.clone()),
+        None => display_page_number(page).map(|number| number.to_string()),
+    }
+}
+
 #[cfg(feature = "html")]
 pub(crate) fn visual_line_class_name(element_type: ElementType) -> &'static str {
     match element_type {
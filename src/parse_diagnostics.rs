@@ -0,0 +1,329 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Serialize, Serializer};
+
+use crate::pagination::layout_profile::is_recognized_fmt_option;
+use crate::source_map::{ElementSpan, SourceMap, SourceSpan};
+use crate::title_page::TITLE_PAGE_METADATA_KEYS;
+use crate::{ANCHORS, Element, LANGUAGE_METADATA_KEYS, Screenplay};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiagnosticCode {
+    UnterminatedBoneyard,
+    UnclosedNote,
+    UnmatchedEmphasis,
+    CharacterWithoutDialogue,
+    UnknownMetadataKey,
+    UnknownFmtOption,
+    ReservedCharacter,
+}
+
+impl DiagnosticCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiagnosticCode::UnterminatedBoneyard => "unterminated-boneyard",
+            DiagnosticCode::UnclosedNote => "unclosed-note",
+            DiagnosticCode::UnmatchedEmphasis => "unmatched-emphasis",
+            DiagnosticCode::CharacterWithoutDialogue => "character-without-dialogue",
+            DiagnosticCode::UnknownMetadataKey => "unknown-metadata-key",
+            DiagnosticCode::UnknownFmtOption => "unknown-fmt-option",
+            DiagnosticCode::ReservedCharacter => "reserved-character",
+        }
+    }
+}
+
+impl Serialize for DiagnosticCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: DiagnosticCode,
+    pub message: String,
+    pub span: SourceSpan,
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "info",
+        };
+        write!(
+            f,
+            "{}:{}: {severity}[{}]: {}",
+            self.span.start_line,
+            self.span.start_column,
+            self.code.as_str(),
+            self.message
+        )
+    }
+}
+
+/// Checks a parsed screenplay against the text it came from, returning the
+/// problems sorted by position.
+pub(crate) fn collect(
+    text: &str,
+    screenplay: &Screenplay,
+    source_map: &SourceMap,
+) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let boneyards = boneyard_ranges(text);
+    check_boneyard(text, &boneyards, &mut diagnostics);
+    check_notes(text, &boneyards, &mut diagnostics);
+    check_reserved_characters(text, &boneyards, &mut diagnostics);
+    check_metadata(text, source_map, &mut diagnostics);
+    for (index, (element, span)) in screenplay
+        .elements
+        .iter()
+        .zip(source_map.elements.iter())
+        .enumerate()
+    {
+        check_emphasis(text, element, span, &mut diagnostics);
+        if let Some(next) = screenplay.elements.get(index + 1) {
+            check_orphaned_character(text, element, next, span, &mut diagnostics);
+        }
+    }
+    diagnostics.sort_by_key(|diagnostic| (diagnostic.span.start, diagnostic.severity));
+    diagnostics
+}
+
+fn boneyard_ranges(text: &str) -> Vec<(usize, usize)> {
+    lazy_static! {
+        static ref RE: Regex = Regex::new(r"/\*[^*]*\*/").unwrap();
+    }
+    RE.find_iter(text.trim_end())
+        .map(|boneyard| (boneyard.start(), boneyard.end()))
+        .collect()
+}
+
+fn in_boneyard(boneyards: &[(usize, usize)], offset: usize) -> bool {
+    boneyards
+        .iter()
+        .any(|(start, end)| *start <= offset && offset < *end)
+}
+
+fn check_boneyard(text: &str, boneyards: &[(usize, usize)], diagnostics: &mut Vec<Diagnostic>) {
+    for (offset, _) in text.match_indices("/*") {
+        if !in_boneyard(boneyards, offset) {
+            diagnostics.push(Diagnostic {
+                severity: Severity::Warning,
+                code: DiagnosticCode::UnterminatedBoneyard,
+                message: "boneyard opened with `/*` is never closed with `*/`".to_string(),
+                span: SourceSpan::from_offsets(text, offset, offset + 2),
+            });
+        }
+    }
+}
+
+fn check_notes(text: &str, boneyards: &[(usize, usize)], diagnostics: &mut Vec<Diagnostic>) {
+    lazy_static! {
+        static ref NOTE_REGEX: Regex = Regex::new(r"\[\[([^\]]+)\]\]").unwrap();
+    }
+    let mut closed_until = 0;
+    for (offset, _) in text.match_indices("[[") {
+        if offset < closed_until || in_boneyard(boneyards, offset) {
+            continue;
+        }
+        match NOTE_REGEX.find_at(text, offset) {
+            Some(note) if note.start() == offset => closed_until = note.end(),
+            _ => diagnostics.push(Diagnostic {
+                severity: Severity::Warning,
+                code: DiagnosticCode::UnclosedNote,
+                message: "note opened with `[[` is never closed with `]]`".to_string(),
+                span: SourceSpan::from_offsets(text, offset, offset + 2),
+            }),
+        }
+    }
+}
+
+/// The parser marks where notes, tags and boneyard sat with private-use
+/// characters, so the same characters in the source are left out of the text.
+fn check_reserved_characters(
+    text: &str,
+    boneyards: &[(usize, usize)],
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    for (offset, anchor) in text.match_indices(ANCHORS) {
+        if in_boneyard(boneyards, offset) {
+            continue;
+        }
+        let code_point = anchor.chars().next().map_or(0, u32::from);
+        diagnostics.push(Diagnostic {
+            severity: Severity::Warning,
+            code: DiagnosticCode::ReservedCharacter,
+            message: format!(
+                "character U+{code_point:04X} is reserved for marking notes and boneyard, so it was left out"
+            ),
+            span: SourceSpan::from_offsets(text, offset, offset + anchor.len()),
+        });
+    }
+}
+
+/// Unmatched `*` and `_` markers survive markup parsing as literal characters,
+/// so any more of them in the parsed text than were escaped in the source
+/// means a marker was left open.
+fn check_emphasis(
+    text: &str,
+    element: &Element,
+    span: &ElementSpan,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    match element {
+        Element::DialogueBlock(children) | Element::DualDialogueBlock(children) => {
+            for (child, child_span) in children.iter().zip(span.children.iter()) {
+                check_emphasis(text, child, child_span, diagnostics);
+            }
+        }
+        Element::Section(_, _, _)
+        | Element::Synopsis(_)
+        | Element::Boneyard(_)
+        | Element::PageBreak => {}
+        _ => {
+            let Some(element_text) = element.text() else {
+                return;
+            };
+            let parsed = element_text.plain_text();
+            let source = &text[span.span.start..span.span.end];
+            // A stray `/*` is already reported as an unterminated boneyard.
+            let stray_boneyard = source.matches("/*").count();
+            for (marker, expected) in [('*', stray_boneyard), ('_', 0)] {
+                let escaped = emphasis_markers(source, &format!("\\{marker}"));
+                if emphasis_markers(&parsed, &marker.to_string()) > escaped + expected {
+                    diagnostics.push(Diagnostic {
+                        severity: Severity::Warning,
+                        code: DiagnosticCode::UnmatchedEmphasis,
+                        message: format!(
+                            "{} has an unmatched `{marker}`; escape it as `\\{marker}` to print it",
+                            element.name()
+                        ),
+                        span: span.span,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Counts the `marker`s in `text` that could open or close emphasis, leaving
+/// out those inside a word, like the `_` in `file_name`.
+fn emphasis_markers(text: &str, marker: &str) -> usize {
+    text.match_indices(marker)
+        .filter(|(offset, _)| {
+            let before = text[..*offset].chars().next_back();
+            let after = text[offset + marker.len()..].chars().next();
+            !(before.is_some_and(char::is_alphanumeric) && after.is_some_and(char::is_alphanumeric))
+        })
+        .count()
+}
+
+fn check_orphaned_character(
+    text: &str,
+    element: &Element,
+    next: &Element,
+    span: &ElementSpan,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let (Element::Action(cue, attributes), Element::Action(following, _)) = (element, next) else {
+        return;
+    };
+    let cue = cue.plain_text();
+    let source = &text[span.span.start..span.span.end];
+    let looks_like_cue = !attributes.centered
+        && !source.starts_with('!')
+        && !cue.contains('\n')
+        && cue.chars().any(char::is_alphabetic)
+        && !cue.chars().any(char::is_lowercase)
+        && cue
+            .trim_end()
+            .ends_with(|ch: char| ch.is_alphanumeric() || ch == ')');
+    if looks_like_cue && following.plain_text().chars().any(char::is_lowercase) {
+        diagnostics.push(Diagnostic {
+            severity: Severity::Warning,
+            code: DiagnosticCode::CharacterWithoutDialogue,
+            message: format!(
+                "`{}` looks like a character cue but is followed by a blank line, so it was parsed as action",
+                cue.trim()
+            ),
+            span: span.span,
+        });
+    }
+}
+
+fn check_metadata(text: &str, source_map: &SourceMap, diagnostics: &mut Vec<Diagnostic>) {
+    let Some(metadata_span) = source_map.metadata else {
+        return;
+    };
+    let mut offset = metadata_span.start;
+    let mut in_fmt = false;
+    let mut fmt_lines = 0;
+    for line in text[metadata_span.start..metadata_span.end].split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+        let line = line.trim_end();
+        let (value, value_start) = match line.split_once(':') {
+            Some((key, value))
+                if !key.trim().is_empty() && !key.starts_with(char::is_whitespace) =>
+            {
+                let key_name = key.trim_start_matches('\u{FEFF}').trim().to_lowercase();
+                in_fmt = key_name == "fmt";
+                if !is_known_metadata_key(&key_name) {
+                    diagnostics.push(Diagnostic {
+                        severity: Severity::Info,
+                        code: DiagnosticCode::UnknownMetadataKey,
+                        message: format!(
+                            "metadata key `{}` is not used by any renderer",
+                            key.trim()
+                        ),
+                        span: SourceSpan::from_offsets(text, line_start, line_start + key.len()),
+                    });
+                }
+                (value, line_start + key.len() + 1)
+            }
+            _ => (line, line_start),
+        };
+        if !in_fmt || value.trim().is_empty() {
+            continue;
+        }
+        fmt_lines += 1;
+        for (token_offset, token) in whitespace_tokens(value) {
+            let start = value_start + token_offset;
+            let message = if fmt_lines > 1 {
+                format!("fmt option `{token}` is ignored because only the first fmt line is read")
+            } else if is_recognized_fmt_option(token) {
+                continue;
+            } else {
+                format!("fmt option `{token}` is not recognized")
+            };
+            diagnostics.push(Diagnostic {
+                severity: Severity::Warning,
+                code: DiagnosticCode::UnknownFmtOption,
+                message,
+                span: SourceSpan::from_offsets(text, start, start + token.len()),
+            });
+        }
+    }
+}
+
+/// Whether a renderer reads the metadata key, given in lowercase.
+fn is_known_metadata_key(key: &str) -> bool {
+    key == "fmt" || TITLE_PAGE_METADATA_KEYS.contains(&key) || LANGUAGE_METADATA_KEYS.contains(&key)
+}
+
+fn whitespace_tokens(value: &str) -> impl Iterator<Item = (usize, &str)> {
+    value
+        .split_whitespace()
+        .map(move |token| (token.as_ptr() as usize - value.as_ptr() as usize, token))
+}
@@ -4,10 +4,12 @@ use std::borrow::Cow;
 use std::collections::HashMap;
 use std::str::Lines;
 
+use crate::parse_diagnostics::{self, Diagnostic};
+use crate::source_map::{ElementSpan, SourceMap, SourceSpan};
 use crate::Element::PageBreak;
 use crate::{
-    blank_attributes, text_style_parser, Attributes, Element, ElementLayoutOverrides, ElementText,
-    Metadata, Screenplay,
+    blank_attributes, text_style_parser, Attributes, BreakdownTag, Element, ElementLayoutOverrides,
+    ElementText, Metadata, Note, Screenplay, ANCHORS, BONEYARD_ANCHOR, NOTE_ANCHOR, TAG_ANCHOR,
 };
 use ElementText::*;
 
@@ -31,31 +33,87 @@ const SCENE_LOCATORS: [&str; 16] = [
 ];
 
 pub fn parse(text: &str) -> Screenplay {
-    let fountain_string = prepare_text(text);
+    parse_with_source_map(text).0
+}
+
+/// Parses `text` like [`parse`], also returning warnings about markup that
+/// was probably not meant the way it was parsed.
+pub fn parse_with_diagnostics(text: &str) -> (Screenplay, Vec<Diagnostic>) {
+    let (screenplay, source_map) = parse_with_source_map(text);
+    let diagnostics = parse_diagnostics::collect(text, &screenplay, &source_map);
+    (screenplay, diagnostics)
+}
+
+/// Parses `text` like [`parse`], also returning where each element came from
+/// in `text`.
+pub fn parse_with_source_map(text: &str) -> (Screenplay, SourceMap) {
+    let (fountain_string, boneyard) = prepare_text_with_cuts(text);
+    let locator = SourceLocator::new(text, &fountain_string, boneyard);
     let lines = fountain_string.lines();
     let hunks: Vec<Vec<&str>> = lines_to_hunks(lines);
     // println!("{:#?}", hunks);
-    let mut elements: Vec<Element> = hunks_to_elements(hunks);
+    let (mut elements, mut spans) = hunks_to_elements(hunks, &locator);
     // println!("{:#?}", elements);
     let mut metadata: Metadata = HashMap::new();
+    let mut metadata_span = None;
     match elements.first() {
         Some(Element::Action(Plain(txt), _)) if has_key_value(txt) => {
             process_metadata(&mut metadata, txt);
             elements.remove(0);
+            metadata_span = Some(spans.remove(0).span);
         }
         _ => (),
     }
+    let mut inline_boneyard = locator.inline_boneyard();
     for element in elements.iter_mut() {
         element.parse_and_convert_markup();
+        element.anchor_notes(&mut inline_boneyard);
     }
+    insert_boneyards(&mut elements, &mut spans, &locator);
     let mut screenplay = Screenplay {
         metadata,
         imported_layout: None,
         imported_title_page: None,
+        revisions: Vec::new(),
+        page_lock: None,
         elements,
     };
     screenplay.apply_structural_act_break_policy();
-    screenplay
+    (
+        screenplay,
+        SourceMap {
+            metadata: metadata_span,
+            elements: spans,
+        },
+    )
+}
+
+/// Puts each boneyard that was cut out whole back as a [`Element::Boneyard`]
+/// before the first element that starts after it.
+fn insert_boneyards(elements: &mut Vec<Element>, spans: &mut Vec<ElementSpan>, locator: &SourceLocator) {
+    for &(start, end) in locator.boneyard.standalone.iter().rev() {
+        // A boneyard on lines of its own leaves an empty action behind.
+        if let Some(index) = spans.iter().zip(elements.iter()).position(|(span, element)| {
+            span.span.start == span.span.end
+                && span.span.start >= end
+                && locator.original[end..span.span.start].trim().is_empty()
+                && matches!(element, Element::Action(Plain(text), _) if text.is_empty())
+        }) {
+            elements.remove(index);
+            spans.remove(index);
+        }
+        let index = spans.partition_point(|span| span.span.start < start);
+        let inner = &locator.original[start + 2..end - 2];
+        elements.insert(index, Element::Boneyard(Plain(inner.to_string())));
+        spans.insert(
+            index,
+            ElementSpan {
+                span: SourceSpan::from_offsets(locator.original, start, end),
+                file: None,
+                children: Vec::new(),
+            },
+        );
+    }
 }
 
 fn has_key_value(txt: &str) -> bool {
@@ -108,11 +166,265 @@ fn split_metadata_line(line: &str) -> Option<(&str, &str)> {
 }
 
 /// Strips out problematic unicode and the boneyard element
+#[cfg(test)]
 fn prepare_text(text: &str) -> String {
+    prepare_text_with_cuts(text).0
+}
+
+/// Boneyard taken out of the text by [`prepare_text_with_cuts`].
+#[derive(Default)]
+struct PreparedBoneyard {
+    /// `(offset in the prepared text, removed byte length)` for each boneyard,
+    /// and for each anchor character that was already in the input.
+    cuts: Vec<(usize, usize)>,
+    /// Byte ranges in the original input of boneyard cut out whole, because
+    /// it was on lines of its own, in the title page or in a synopsis.
+    standalone: Vec<(usize, usize)>,
+    /// Byte ranges in the original input of boneyard inside an element, left
+    /// in the prepared text as a [`BONEYARD_ANCHOR`].
+    inline: Vec<(usize, usize)>,
+}
+
+/// Like `prepare_text`, also returning where boneyard was taken out.
+fn prepare_text_with_cuts(text: &str) -> (String, PreparedBoneyard) {
     lazy_static! {
         static ref RE: Regex = Regex::new(r"/\*[^*]*\*/").unwrap();
     }
-    RE.replace_all(text.trim_end(), "").to_string()
+    let text = text.trim_end();
+    let ranges = RE
+        .find_iter(text)
+        .map(|boneyard| (boneyard.start(), boneyard.end()))
+        .collect::<Vec<_>>();
+    // Boneyard blanked out, keeping line breaks, to see what surrounds each.
+    let mut masked = text.to_string();
+    for &(start, end) in &ranges {
+        let blank = text[start..end].replace(|ch: char| ch != '\n', " ");
+        masked.replace_range(start..end, &blank);
+    }
+    let title_page_end = title_page_end(&masked);
+
+    let mut prepared = String::with_capacity(text.len());
+    let mut boneyard = PreparedBoneyard::default();
+    let mut last = 0;
+    for (start, end) in ranges {
+        push_without_anchors(&mut prepared, &mut boneyard.cuts, &text[last..start]);
+        if start < title_page_end
+            || is_own_paragraph(&masked, start, end)
+            || is_synopsis_line(&masked, start)
+        {
+            boneyard.cuts.push((prepared.len(), end - start));
+            boneyard.standalone.push((start, end));
+        } else {
+            // The cut sits inside the anchor so that offsets on either side
+            // of it map to either side of the boneyard.
+            let anchor_len = BONEYARD_ANCHOR.len_utf8();
+            prepared.push(BONEYARD_ANCHOR);
+            boneyard
+                .cuts
+                .push((prepared.len() - anchor_len + 1, end - start - anchor_len));
+            boneyard.inline.push((start, end));
+        }
+        last = end;
+    }
+    push_without_anchors(&mut prepared, &mut boneyard.cuts, &text[last..]);
+    // Boneyard at the very end can leave blank lines behind.
+    prepared.truncate(prepared.trim_end().len());
+    (prepared, boneyard)
+}
+
+/// Appends `text`, cutting out the characters the parser uses to anchor notes
+/// and boneyard so that input containing them can't move either.
+/// `parse_with_diagnostics` reports each one it cuts.
+fn push_without_anchors(prepared: &mut String, cuts: &mut Vec<(usize, usize)>, text: &str) {
+    let mut last = 0;
+    for (index, anchor) in text.match_indices(ANCHORS) {
+        prepared.push_str(&text[last..index]);
+        cuts.push((prepared.len(), anchor.len()));
+        last = index + anchor.len();
+    }
+    prepared.push_str(&text[last..]);
+}
+
+/// The byte offset where the title page ends, or 0 without one.
+fn title_page_end(text: &str) -> usize {
+    let start = text.len() - text.trim_start().len();
+    let first_line = text[start..].lines().next().unwrap_or_default();
+    if split_metadata_line(first_line).is_none() {
+        return 0;
+    }
+    text[start..]
+        .find("\n\n")
+        .map_or(text.len(), |blank| start + blank)
+}
+
+/// Whether nothing but whitespace separates `start..end` from the blank lines
+/// or edges of the text around it.
+fn is_own_paragraph(text: &str, start: usize, end: usize) -> bool {
+    let before = &text[..start];
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    let after = &text[end..];
+    let line_end = after.find('\n').unwrap_or(after.len());
+    let previous_line = before[..line_start.saturating_sub(1)]
+        .rsplit('\n')
+        .next()
+        .filter(|_| line_start > 0);
+    let next_line = after[line_end..]
+        .strip_prefix('\n')
+        .and_then(|rest| rest.split('\n').next());
+    before[line_start..].trim().is_empty()
+        && after[..line_end].trim().is_empty()
+        && previous_line.is_none_or(|line| line.trim().is_empty())
+        && next_line.is_none_or(|line| line.trim().is_empty())
+}
+
+/// Whether `offset` is on a `= synopsis` line.
+fn is_synopsis_line(text: &str, offset: usize) -> bool {
+    let line_start = text[..offset].rfind('\n').map_or(0, |index| index + 1);
+    let line = text[line_start..].trim_start();
+    line.starts_with('=') && !line.starts_with("===")
+}
+
+/// Maps slices of the prepared text back to positions in the original input.
+struct SourceLocator<'a> {
+    original: &'a str,
+    prepared: &'a str,
+    boneyard: PreparedBoneyard,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> SourceLocator<'a> {
+    fn new(original: &'a str, prepared: &'a str, boneyard: PreparedBoneyard) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(original.match_indices('\n').map(|(idx, _)| idx + 1))
+            .collect();
+        Self {
+            original,
+            prepared,
+            boneyard,
+            line_starts,
+        }
+    }
+
+    /// The text of each boneyard left as an anchor, in order.
+    fn inline_boneyard(&self) -> impl Iterator<Item = String> + '_ {
+        self.boneyard
+            .inline
+            .iter()
+            .map(|(start, end)| self.original[start + 2..end - 2].to_string())
+    }
+
+    fn prepared_offset(&self, slice: &str) -> usize {
+        (slice.as_ptr() as usize)
+            .checked_sub(self.prepared.as_ptr() as usize)
+            .filter(|offset| *offset <= self.prepared.len())
+            .unwrap_or(0)
+    }
+
+    /// Boneyard removed right at a start offset is skipped over, while boneyard
+    /// right at an end offset is left outside the span.
+    fn original_offset(&self, prepared_offset: usize, is_end: bool) -> usize {
+        prepared_offset
+            + self
+                .boneyard
+                .cuts
+                .iter()
+                .take_while(|(at, _)| *at < prepared_offset || (!is_end && *at == prepared_offset))
+                .map(|(_, removed)| removed)
+                .sum::<usize>()
+    }
+
+    fn line_and_column(&self, offset: usize) -> (usize, usize) {
+        let line_index = self.line_starts.partition_point(|start| *start <= offset) - 1;
+        let line_start = self.line_starts[line_index];
+        let column = self.original[line_start..offset].chars().count() + 1;
+        (line_index + 1, column)
+    }
+
+    /// Spans the hunk lines from the start of `first` to the end of `last`,
+    /// leaving off invisible characters at either edge.
+    fn span(&self, first: &str, last: &str) -> SourceSpan {
+        let visible_first = trim_classifier_start(first);
+        let start = self.prepared_offset(visible_first);
+        let end = if std::ptr::eq(first, last) && visible_first.is_empty() {
+            start
+        } else {
+            self.prepared_offset(last) + trim_classifier_end(last).len()
+        };
+        let start = self.original_offset(start, false);
+        let end = self.original_offset(end, true).max(start);
+        let (start_line, start_column) = self.line_and_column(start);
+        let (end_line, end_column) = self.line_and_column(end);
+        SourceSpan {
+            start,
+            end,
+            start_line,
+            start_column,
+            end_line,
+            end_column,
+        }
+    }
+
+    fn element_span(&self, hunk: &[&str], element: &Element) -> ElementSpan {
+        let span = self.span(hunk[0], hunk[hunk.len() - 1]);
+        let children = match element {
+            Element::DialogueBlock(children) => self.dialogue_child_spans(hunk, children),
+            Element::DualDialogueBlock(blocks) => vec![ElementSpan {
+                span,
+                file: None,
+                children: blocks
+                    .first()
+                    .map(|block| match block {
+                        Element::DialogueBlock(children) => {
+                            self.dialogue_child_spans(hunk, children)
+                        }
+                        _ => Vec::new(),
+                    })
+                    .unwrap_or_default(),
+            }],
+            _ => Vec::new(),
+        };
+        ElementSpan {
+            span,
+            file: None,
+            children,
+        }
+    }
+
+    /// Dialogue lines are merged into their child elements one per newline, so
+    /// each child consumes as many hunk lines as its text has.
+    fn dialogue_child_spans(&self, hunk: &[&str], children: &[Element]) -> Vec<ElementSpan> {
+        let mut cursor = 0;
+        children
+            .iter()
+            .map(|child| {
+                let note_lines = match child {
+                    Element::Dialogue(_, attributes)
+                    | Element::Lyric(_, attributes)
+                    | Element::Parenthetical(_, attributes) => attributes
+                        .notes
+                        .iter()
+                        .flatten()
+                        .map(|note| note.text.matches('\n').count())
+                        .sum(),
+                    _ => 0,
+                };
+                let line_count = match child {
+                    Element::Dialogue(Plain(text), _) | Element::Lyric(Plain(text), _) => {
+                        text.matches('\n').count() + 1
+                    }
+                    _ => 1,
+                } + note_lines;
+                let first = cursor.min(hunk.len() - 1);
+                let last = (cursor + line_count - 1).min(hunk.len() - 1);
+                cursor += line_count;
+                ElementSpan {
+                    span: self.span(hunk[first], hunk[last]),
+                    file: None,
+                    children: Vec::new(),
+                }
+            })
+            .collect()
+    }
 }
 
 fn is_classifier_invisible(ch: char) -> bool {
@@ -155,20 +467,24 @@ fn classifier_trimmed(line: &str) -> &str {
 }
 
 fn lines_to_hunks<'a>(lines: Lines<'a>) -> Vec<Vec<&'a str>> {
-    let mut hunks = lines.fold(vec![vec![]], |mut acc, line: &str| {
+    let lines = lines.collect::<Vec<_>>();
+    let mut note_open = false;
+    let mut hunks = lines.iter().enumerate().fold(vec![vec![]], |mut acc, (index, &line)| {
         let classified = classifier_trimmed(line);
-        match classified {
+        let hunk_count = acc.len();
+        let acc = match classified {
             // HANDLE BLANK LINES
             "" => {
-                // If there are exactly two spaces in the line, it's intentional
-                if line.len() == 2 {
+                // If there are exactly two spaces in the line, it's intentional.
+                // Blank lines inside a note that closes later are part of the note.
+                if line.len() == 2 || (note_open && note_closes_later(&lines[index + 1..])) {
                     acc.last_mut().unwrap().push(line);
                 // If the previous element was blank but it was the first element, do nothing
                 } else if acc.last().unwrap().is_empty() && acc.len() == 1 {
                     // do nothing
                 } else if acc.last().unwrap().is_empty() {
                     // If the previous element was also blank, create an empty string
-                    acc.last_mut().unwrap().push("");
+                    acc.last_mut().unwrap().push(&line[..0]);
                 } else {
                     // Otherwise, start a new element by pushing a new empty vec
                     acc.push(vec![]);
@@ -204,7 +520,12 @@ fn lines_to_hunks<'a>(lines: Lines<'a>) -> Vec<Vec<&'a str>> {
                 acc.last_mut().unwrap().push(line);
                 acc
             }
+        };
+        if acc.len() != hunk_count {
+            note_open = false;
         }
+        note_open = note_left_open(line, note_open);
+        acc
     });
     // Handle special case of an empty string
     if hunks.len() == 1
@@ -218,8 +539,40 @@ fn lines_to_hunks<'a>(lines: Lines<'a>) -> Vec<Vec<&'a str>> {
     hunks
 }
 
-fn hunks_to_elements(hunks: Vec<Vec<&str>>) -> Vec<Element> {
+/// Whether a `[[` is still waiting for its `]]` at the end of `line`.
+fn note_left_open(line: &str, mut open: bool) -> bool {
+    let mut rest = line;
+    loop {
+        let marker = if open { "]]" } else { "[[" };
+        match rest.find(marker) {
+            Some(index) => {
+                open = !open;
+                rest = &rest[index + 2..];
+            }
+            None => return open,
+        }
+    }
+}
+
+/// Whether an open note is closed before another one is opened.
+fn note_closes_later(lines: &[&str]) -> bool {
+    for line in lines {
+        match (line.find("]]"), line.find("[[")) {
+            (Some(close), Some(open)) => return close < open,
+            (Some(_), None) => return true,
+            (None, Some(_)) => return false,
+            (None, None) => {}
+        }
+    }
+    false
+}
+
+fn hunks_to_elements(
+    hunks: Vec<Vec<&str>>,
+    locator: &SourceLocator,
+) -> (Vec<Element>, Vec<ElementSpan>) {
     let initial: Vec<Element> = Vec::with_capacity(hunks.len());
+    let mut spans: Vec<ElementSpan> = Vec::with_capacity(hunks.len());
     let mut elements = hunks
         .into_iter()
         .rev()
@@ -246,10 +599,12 @@ fn hunks_to_elements(hunks: Vec<Vec<&str>>) -> Vec<Element> {
                         Some(_) | None => (),
                     }
                 } else {
+                    spans.push(locator.element_span(&hunk, &element));
                     acc.push(element);
                 }
             } else {
-                let element = make_multi_line_element(hunk);
+                let element = make_multi_line_element(hunk.clone());
+                let span = locator.element_span(&hunk, &element);
                 match (acc.last_mut(), &element) {
                     // If the previous element was a dual dialogue block and it only contains one block
                     // then put this element into that block so long as it's a dialogue element
@@ -257,17 +612,29 @@ fn hunks_to_elements(hunks: Vec<Vec<&str>>) -> Vec<Element> {
                         if dialogues.len() == 1 =>
                     {
                         dialogues.insert(0, element);
+                        let dual_span = spans.last_mut().expect("spans track elements");
+                        dual_span.span = span.span.cover(&dual_span.span);
+                        dual_span.children.insert(0, span);
                     }
                     (Some(Element::Section(_, attr, _)), Element::Synopsis(Plain(note))) => {
-                        attr.notes = Some(vec![note.to_string()]);
+                        attr.notes.get_or_insert_with(Vec::new).push(Note {
+                            text: note.clone(),
+                            offset: 0,
+                        });
+                        let section_span = spans.last_mut().expect("spans track elements");
+                        section_span.span = span.span.cover(&section_span.span);
+                    }
+                    _ => {
+                        spans.push(span);
+                        acc.push(element);
                     }
-                    _ => acc.push(element),
                 }
             }
             acc
         });
     elements.reverse();
-    elements
+    spans.reverse();
+    (elements, spans)
 }
 
 fn make_single_line_element(line: &str) -> Element {
@@ -277,10 +644,11 @@ fn make_single_line_element(line: &str) -> Element {
     let mut attributes = blank_attributes();
     let line_has_note = has_note(line);
     if line_has_note {
-        let (notes, layout_overrides) = retrieve_processed_notes(line);
+        let (notes, layout_overrides, tags) = retrieve_processed_notes(line);
         attributes = Attributes {
             notes,
             layout_overrides,
+            tags,
             ..attributes
         };
     }
@@ -302,7 +670,7 @@ fn make_single_line_element(line: &str) -> Element {
                             ..attributes
                         };
                         let final_text = if line_has_note {
-                            remove_notes(&text_without_scene_number)
+                            replace_notes_with_anchors(&text_without_scene_number)
                         } else {
                             text_without_scene_number
                         };
@@ -311,7 +679,7 @@ fn make_single_line_element(line: &str) -> Element {
                 }
             } else {
                 let final_text = if line_has_note {
-                    remove_notes(stripped)
+                    replace_notes_with_anchors(stripped)
                 } else {
                     stripped.to_string()
                 };
@@ -330,7 +698,7 @@ fn make_single_line_element(line: &str) -> Element {
                             ..attributes
                         };
                         let final_text = if line_has_note {
-                            remove_notes(&text_without_scene_number)
+                            replace_notes_with_anchors(&text_without_scene_number)
                         } else {
                             text_without_scene_number
                         };
@@ -339,7 +707,7 @@ fn make_single_line_element(line: &str) -> Element {
                 }
             } else {
                 let final_text = if line_has_note {
-                    remove_notes(line)
+                    replace_notes_with_anchors(line)
                 } else {
                     line.to_string()
                 };
@@ -349,7 +717,7 @@ fn make_single_line_element(line: &str) -> Element {
         _ if is_transition(&line) => {
             let line = classifier_trimmed(line);
             let final_text = if line_has_note {
-                remove_notes(line)
+                replace_notes_with_anchors(line)
             } else {
                 line.to_string()
             };
@@ -358,7 +726,7 @@ fn make_single_line_element(line: &str) -> Element {
         _ if is_centered(&line) => {
             let line = trim_classifier_edges(line);
             let final_text = if line_has_note {
-                remove_notes(trim_centered_marks(line))
+                replace_notes_with_anchors(trim_centered_marks(line))
             } else {
                 trim_centered_marks(line).to_string()
             };
@@ -399,7 +767,7 @@ fn make_single_line_element(line: &str) -> Element {
         }
         _ => {
             let final_text = if line_has_note {
-                remove_notes(line)
+                replace_notes_with_anchors(line)
             } else {
                 line.to_string()
             };
@@ -438,10 +806,11 @@ fn make_multi_line_element(hunk: Vec<&str>) -> Element {
         .any(|line| has_note(line))
         .then(|| hunk.join("\n"));
     if let Some(joined_hunk) = joined_hunk_with_notes.as_deref() {
-        let (notes, layout_overrides) = retrieve_processed_notes(joined_hunk);
+        let (notes, layout_overrides, tags) = retrieve_processed_notes(joined_hunk);
         attributes = Attributes {
             notes,
             layout_overrides,
+            tags,
             ..attributes
         };
     }
@@ -464,7 +833,7 @@ fn make_multi_line_element(hunk: Vec<&str>) -> Element {
                     })
                     .collect::<Vec<&str>>()
                     .join("\n");
-                let final_text = remove_notes(&stripped_string);
+                let final_text = replace_notes_with_anchors(&stripped_string);
                 make_element(Plain(final_text), attributes)
             }
         }
@@ -475,7 +844,7 @@ fn make_multi_line_element(hunk: Vec<&str>) -> Element {
                 .map(trim_centered_marks)
                 .collect::<Vec<&str>>()
                 .join("\n");
-            let final_text = remove_notes(&cleaned_text);
+            let final_text = replace_notes_with_anchors(&cleaned_text);
             Element::Action(
                 Plain(final_text),
                 Attributes {
@@ -487,7 +856,7 @@ fn make_multi_line_element(hunk: Vec<&str>) -> Element {
         _ if is_character(hunk[0]) => make_dialogue_block(hunk),
         _ => {
             let final_text = match joined_hunk_with_notes.as_deref() {
-                Some(joined_hunk) => remove_notes(joined_hunk),
+                Some(joined_hunk) => replace_notes_with_anchors(joined_hunk),
                 None => hunk.join("\n"),
             };
             Element::Action(Plain(final_text), attributes)
@@ -583,11 +952,22 @@ fn is_supported_act_label(label: &str) -> bool {
     )
 }
 
-fn remove_notes(line: &str) -> String {
+/// Takes the notes out of `line`, leaving a note or tag anchor where each one
+/// sat unless it held nothing but layout modifiers.
+fn replace_notes_with_anchors(line: &str) -> String {
     lazy_static! {
         static ref NOTE_REGEX: Regex = Regex::new(r"\[\[([^\]]+)\]\]").unwrap();
     }
-    NOTE_REGEX.replace_all(line, "").to_string()
+    NOTE_REGEX
+        .replace_all(
+            line,
+            |captures: &regex::Captures| match extract_layout_modifiers_from_note(&captures[1]).0 {
+                Some(note) if BreakdownTag::from_note(&note).is_some() => TAG_ANCHOR.to_string(),
+                Some(_) => NOTE_ANCHOR.to_string(),
+                None => String::new(),
+            },
+        )
+        .to_string()
 }
 
 fn is_centered(line: &str) -> bool {
@@ -658,9 +1038,10 @@ fn make_forced(line: &str) -> Option<fn(ElementText, Attributes) -> Element> {
     }
 }
 
-fn make_section(line: ElementText, _: Attributes) -> Element {
+fn make_section(line: ElementText, attributes: Attributes) -> Element {
     match line {
         Plain(txt) => {
+            let txt = restore_notes(&txt, attributes);
             let trimmed = txt.trim().trim_start_matches('#');
             let level: u8 = (txt.len() - trimmed.len()).try_into().unwrap();
             Element::Section(Plain(trimmed.trim().to_string()), blank_attributes(), level)
@@ -673,9 +1054,10 @@ fn make_page_break(_line: ElementText, _: Attributes) -> Element {
     PageBreak
 }
 
-fn make_synopsis(line: ElementText, _: Attributes) -> Element {
+fn make_synopsis(line: ElementText, attributes: Attributes) -> Element {
     match line {
         Plain(line) => {
+            let line = restore_notes(&line, attributes);
             let trimmed = line.trim().trim_start_matches('=').trim();
             Element::Synopsis(Plain(trimmed.to_string()))
         }
@@ -683,6 +1065,30 @@ fn make_synopsis(line: ElementText, _: Attributes) -> Element {
     }
 }
 
+/// Puts the notes and tags taken out of `line` back where their anchors
+/// are, for elements that have nowhere else to keep them.
+fn restore_notes(line: &str, attributes: Attributes) -> String {
+    let mut notes = attributes.notes.into_iter().flatten();
+    let mut tags = attributes.tags.into_iter();
+    let mut restored = String::with_capacity(line.len());
+    for ch in line.chars() {
+        match ch {
+            NOTE_ANCHOR => {
+                if let Some(note) = notes.next() {
+                    restored.push_str(&format!("[[{}]]", note.text));
+                }
+            }
+            TAG_ANCHOR => {
+                if let Some(tag) = tags.next() {
+                    restored.push_str(&format!("[[{}]]", tag.to_note()));
+                }
+            }
+            _ => restored.push(ch),
+        }
+    }
+    restored
+}
+
 fn make_dialogue_block(hunk: Vec<&str>) -> Element {
     let mut elements = Vec::with_capacity(hunk.len());
     let raw_name: &str = hunk[0];
@@ -692,38 +1098,51 @@ fn make_dialogue_block(hunk: Vec<&str>) -> Element {
         .trim();
     let character: Element = Element::Character(Plain(clean_name.to_string()), blank_attributes());
     elements.push(character);
-    for line in hunk[1..].iter() {
+    // A note running over several lines is taken out as part of one line.
+    let mut lines: Vec<Cow<str>> = Vec::with_capacity(hunk.len() - 1);
+    let mut note_open = false;
+    for &line in &hunk[1..] {
+        match lines.last_mut() {
+            Some(last) if note_open => *last = Cow::Owned(format!("{last}\n{line}")),
+            _ => lines.push(Cow::Borrowed(line)),
+        }
+        note_open = note_left_open(line, note_open);
+    }
+    for line in lines.iter() {
         let (processed_line, attributes) = if has_note(line) {
-            let (notes, layout_overrides) = retrieve_processed_notes(line);
+            let (notes, layout_overrides, tags) = retrieve_processed_notes(line);
             (
-                Cow::Owned(remove_notes(line)),
+                Cow::Owned(replace_notes_with_anchors(line)),
                 Attributes {
                     notes,
                     layout_overrides,
+                    tags,
                     ..blank_attributes()
                 },
             )
         } else {
-            (Cow::Borrowed(*line), blank_attributes())
+            (Cow::Borrowed(line.as_ref()), blank_attributes())
         };
-        if is_parenthetical(processed_line.as_ref()) {
+        let unanchored_line = processed_line.replace(ANCHORS, "");
+        if is_parenthetical(&unanchored_line) {
             elements.push(Element::Parenthetical(
                 Plain(classifier_trimmed(processed_line.as_ref()).to_string()),
                 attributes,
             ));
-        } else if is_lyric(processed_line.as_ref()) {
+        } else if is_lyric(&unanchored_line) {
             let stripped_line = classifier_trimmed(processed_line.as_ref())
                 .trim_start_matches('~')
                 .trim();
-            if let Element::Lyric(Plain(s), _) = elements.last_mut().unwrap() {
+            if let Element::Lyric(Plain(s), previous) = elements.last_mut().unwrap() {
                 // if previous element was lyric and so is this one, add this line to that previous lyric
                 s.push_str("\n");
                 s.push_str(stripped_line);
+                append_notes(previous, attributes);
             } else {
                 // this line is lyric but previous line wasn't, create new lyric element
                 elements.push(Element::Lyric(Plain(stripped_line.to_string()), attributes));
             }
-        } else if let Element::Dialogue(Plain(s), _) = elements.last_mut().unwrap() {
+        } else if let Element::Dialogue(Plain(s), previous) = elements.last_mut().unwrap() {
             // if previous element was dialogue, add this line to that dialogue
             s.push_str("\n");
             let trimmed = processed_line.as_ref();
@@ -733,6 +1152,7 @@ fn make_dialogue_block(hunk: Vec<&str>) -> Element {
                 trimmed.trim_start()
             };
             s.push_str(trimmed);
+            append_notes(previous, attributes);
         } else {
             // otherwise this is a new dialogue
             elements.push(Element::Dialogue(
@@ -754,6 +1174,17 @@ fn make_dialogue_block(hunk: Vec<&str>) -> Element {
     }
 }
 
+/// Keeps the notes and tags of a line merged into an earlier element.
+fn append_notes(attributes: &mut Attributes, line_attributes: Attributes) {
+    if let Some(notes) = line_attributes.notes {
+        attributes
+            .notes
+            .get_or_insert_with(Vec::new)
+            .extend(notes);
+    }
+    attributes.tags.extend(line_attributes.tags);
+}
+
 fn retrieve_notes(line: &str) -> Option<Vec<String>> {
     lazy_static! {
         static ref RE: Regex = Regex::new(r"\[\[([^\]]+)\]\]").unwrap();
@@ -768,8 +1199,43 @@ fn retrieve_notes(line: &str) -> Option<Vec<String>> {
     Some(result)
 }
 
-fn retrieve_processed_notes(line: &str) -> (Option<Vec<String>>, ElementLayoutOverrides) {
-    extract_layout_modifiers_from_notes(retrieve_notes(line))
+/// Offsets are filled in by [`Element::anchor_notes`] once markup is parsed.
+fn retrieve_processed_notes(
+    line: &str,
+) -> (Option<Vec<Note>>, ElementLayoutOverrides, Vec<BreakdownTag>) {
+    let (notes, layout_overrides) = extract_layout_modifiers_from_notes(retrieve_notes(line));
+    let (notes, tags) = extract_breakdown_tags_from_notes(notes);
+    let notes = notes.map(|notes| {
+        notes
+            .into_iter()
+            .map(|text| Note { text, offset: 0 })
+            .collect()
+    });
+    (notes, layout_overrides, tags)
+}
+
+/// Takes `category: name` notes such as `[[prop: revolver]]` out as
+/// breakdown tags, keeping every other note.
+fn extract_breakdown_tags_from_notes(
+    notes: Option<Vec<String>>,
+) -> (Option<Vec<String>>, Vec<BreakdownTag>) {
+    let Some(notes) = notes else {
+        return (None, Vec::new());
+    };
+
+    let mut remaining_notes = Vec::new();
+    let mut tags = Vec::new();
+    for note in notes {
+        match BreakdownTag::from_note(&note) {
+            Some(tag) => tags.push(tag),
+            None => remaining_notes.push(note),
+        }
+    }
+
+    (
+        (!remaining_notes.is_empty()).then_some(remaining_notes),
+        tags,
+    )
 }
 
 fn extract_layout_modifiers_from_notes(
@@ -1020,7 +1486,10 @@ mod tests {
     #[test]
     fn test_remove_boneyard() {
         let boneyard = "/* boneyard */Hello, World!\n\n/* More bones \n Lower bones*/Goodbye!";
-        assert_eq!(prepare_text(boneyard), "Hello, World!\n\nGoodbye!");
+        assert_eq!(
+            prepare_text(boneyard),
+            "\u{E001}Hello, World!\n\n\u{E001}Goodbye!"
+        );
     }
 
     #[test]
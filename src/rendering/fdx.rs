@@ -1,9 +1,14 @@
 use super::shared::{escape_xml_attr, escape_xml_text, join_metadata, sorted_style_names};
+use crate::pagination::layout_profile::{
+    ALL_CAPS_ACTION_FMT_OPTION, BOLD_SCENE_HEADINGS_FMT_OPTION, COURIER_FINAL_DRAFT_FMT_OPTION,
+    SINGLE_SPACE_BEFORE_SCENE_HEADINGS_FMT_OPTION, SceneContinueds,
+    UNDERLINE_SCENE_HEADINGS_FMT_OPTION, matches_fmt_option,
+};
 use crate::pagination::{Alignment, ScreenplayElementStyle, ScreenplayLayoutProfile};
 use crate::title_page::plain_title_uses_all_caps;
 use crate::{
     Element, ElementText, ImportedTitlePageAlignment, ImportedTitlePageTabStopKind, Metadata,
-    Screenplay,
+    RevisionSet, Screenplay,
 };
 use std::fmt::Write;
 
@@ -14,13 +19,14 @@ pub(crate) fn prepare_screenplay(screenplay: &mut Screenplay) {
         Element::PageBreak | Element::Section(_, _, _) | Element::Synopsis(_) => false,
         _ => true,
     });
+    screenplay.assign_element_ids();
 }
 
 pub(crate) fn render_document(screenplay: &Screenplay) -> String {
     let layout_profile = ScreenplayLayoutProfile::from_screenplay(screenplay);
     let mut out = String::with_capacity(64 * 1024);
     out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"no\" ?>\n<FinalDraft DocumentType=\"Script\" Template=\"No\" Version=\"4\">\n    <Content>\n");
-    render_content(&mut out, screenplay, &layout_profile);
+    let mut unanchored = render_content(&mut out, screenplay, &layout_profile);
     out.push_str("    </Content>\n\n");
     render_page_layout(&mut out, &layout_profile);
     out.push('\n');
@@ -29,6 +35,11 @@ pub(crate) fn render_document(screenplay: &Screenplay) -> String {
     render_element_settings(&mut out, &screenplay.metadata, &layout_profile);
     out.push('\n');
     render_title_page(&mut out, screenplay);
+    if !unanchored.pending_boneyard.is_empty() {
+        out.push_str("\n  <UnanchoredScriptNotes>\n");
+        unanchored.render_boneyard(&mut out);
+        out.push_str("  </UnanchoredScriptNotes>\n");
+    }
     out.push_str("\n  <MoresAndContinueds>\n");
     write!(
         out,
@@ -36,10 +47,59 @@ pub(crate) fn render_document(screenplay: &Screenplay) -> String {
         escape_xml_attr(&font_choice(&screenplay.metadata))
     )
     .unwrap();
-    out.push_str("    <DialogueBreaks AutomaticCharacterContinueds=\"Yes\" BottomOfPage=\"Yes\" DialogueBottom=\"(MORE)\" DialogueTop=\"(CONT'D)\" TopOfNext=\"Yes\"/>\n    <SceneBreaks ContinuedNumber=\"No\" SceneBottom=\"(CONTINUED)\" SceneBottomOfPage=\"No\" SceneTop=\"CONTINUED:\" SceneTopOfNext=\"No\"/>\n  </MoresAndContinueds>\n\n</FinalDraft>\n");
+    out.push_str("    <DialogueBreaks AutomaticCharacterContinueds=\"Yes\" BottomOfPage=\"Yes\" DialogueBottom=\"(MORE)\" DialogueTop=\"(CONT'D)\" TopOfNext=\"Yes\"/>\n");
+    render_scene_breaks(&mut out, &layout_profile.continueds.scene);
+    out.push_str("  </MoresAndContinueds>\n");
+    render_revisions(&mut out, &screenplay.revisions);
+    out.push_str("\n</FinalDraft>\n");
     out
 }
 
+fn render_scene_breaks(out: &mut String, scene: &SceneContinueds) {
+    let yes_no = |value: bool| if value { "Yes" } else { "No" };
+    writeln!(
+        out,
+        "    <SceneBreaks ContinuedNumber=\"{}\" SceneBottom=\"{}\" SceneBottomOfPage=\"{}\" SceneTop=\"{}\" SceneTopOfNext=\"{}\"/>",
+        yes_no(scene.continued_number),
+        escape_xml_attr(&scene.bottom_text),
+        yes_no(scene.bottom_of_page),
+        escape_xml_attr(&scene.top_text),
+        yes_no(scene.top_of_next),
+    )
+    .unwrap();
+}
+
+fn render_revisions(out: &mut String, revisions: &[RevisionSet]) {
+    let Some(active) = revisions.iter().map(|set| set.id).max() else {
+        return;
+    };
+    writeln!(
+        out,
+        "\n  <Revisions ActiveSet=\"{active}\" Location=\"7.75\" RevisionMode=\"OffMode\" RevisionsShown=\"All\" ShowAllMarks=\"No\" ShowAllSets=\"Yes\" ShowPageColor=\"No\">"
+    )
+    .unwrap();
+    for set in revisions {
+        let color = fdx_color(set.rgb().unwrap_or([0, 0, 0]));
+        write!(out, "    <Revision Color=\"{color}\" FullRevision=\"No\" ID=\"{}\"", set.id).unwrap();
+        if let Some(date) = &set.date {
+            write!(out, " JumpCutDate=\"{}\"", escape_xml_attr(date)).unwrap();
+        }
+        writeln!(
+            out,
+            " Mark=\"*\" Name=\"{}\" PageColor=\"{color}\" Style=\"\"/>",
+            escape_xml_attr(&set.name)
+        )
+        .unwrap();
+    }
+    out.push_str("  </Revisions>\n");
+}
+
+/// Final Draft writes colors with 16 bits per channel.
+fn fdx_color(rgb: [u8; 3]) -> String {
+    let [red, green, blue] = rgb;
+    format!("#{red:02X}{red:02X}{green:02X}{green:02X}{blue:02X}{blue:02X}")
+}
+
 pub(crate) fn add_fdx_formatting(metadata: &mut Metadata) {
     let mut scene_heading_styles = vec!["AllCaps"];
     let mut space_before_heading = "24".to_string();
@@ -53,18 +113,16 @@ pub(crate) fn add_fdx_formatting(metadata: &mut Metadata) {
             for option in opts_string.split_whitespace() {
                 if option.eq_ignore_ascii_case("multicam") {
                     style_profile = Some("multicam".to_string());
-                } else if matches_fmt_option(option, &["bsh", "bold-scene-headings"]) {
+                } else if matches_fmt_option(option, BOLD_SCENE_HEADINGS_FMT_OPTION) {
                     scene_heading_styles.push("Bold");
-                } else if matches_fmt_option(option, &["ush", "underline-scene-headings"]) {
+                } else if matches_fmt_option(option, UNDERLINE_SCENE_HEADINGS_FMT_OPTION) {
                     scene_heading_styles.push("Underline");
-                } else if matches_fmt_option(option, &["acat", "all-caps-action"]) {
+                } else if matches_fmt_option(option, ALL_CAPS_ACTION_FMT_OPTION) {
                     action_text_style.push_str("AllCaps");
-                } else if matches_fmt_option(
-                    option,
-                    &["ssbsh", "single-space-before-scene-headings"],
-                ) {
+                } else if matches_fmt_option(option, SINGLE_SPACE_BEFORE_SCENE_HEADINGS_FMT_OPTION)
+                {
                     space_before_heading = "12".to_string();
-                } else if matches_fmt_option(option, &["cfd", "courier-final-draft"]) {
+                } else if matches_fmt_option(option, COURIER_FINAL_DRAFT_FMT_OPTION) {
                     font_choice = "Courier Final Draft".to_string();
                 }
             }
@@ -120,41 +178,109 @@ pub(crate) fn add_fdx_formatting(metadata: &mut Metadata) {
     }
 }
 
-fn matches_fmt_option(option: &str, accepted: &[&str]) -> bool {
-    accepted
-        .iter()
-        .any(|candidate| option.eq_ignore_ascii_case(candidate))
-}
-
 pub(crate) fn insert_metadata_value(metadata: &mut Metadata, key: &str, value: &str) {
     metadata.insert(key.to_string(), vec![value.into()]);
 }
 
-fn render_content(out: &mut String, screenplay: &Screenplay, layout_profile: &ScreenplayLayoutProfile) {
+/// Numbers script notes and holds boneyard waiting for the next paragraph.
+/// Final Draft doesn't print script notes, so boneyard survives the round
+/// trip without showing up on the page.
+struct ScriptNotes<'a> {
+    pending_boneyard: Vec<&'a ElementText>,
+    next_id: usize,
+}
+
+impl ScriptNotes<'_> {
+    fn render_boneyard(&mut self, out: &mut String) {
+        for text in std::mem::take(&mut self.pending_boneyard) {
+            self.render_note(out, 0, &format!("/*{}*/", text.plain_text()));
+        }
+    }
+
+    /// Writes boneyard kept inside its paragraph at character `offset`.
+    fn render_inline_boneyard(&mut self, out: &mut String, offset: usize, text: &str) {
+        self.render_script_note(
+            out,
+            offset,
+            &format!("/*{text}*/"),
+            " JumpCutInline=\"Yes\"",
+        );
+    }
+
+    /// Writes a note anchored at character `offset` of its paragraph.
+    fn render_note(&mut self, out: &mut String, offset: usize, text: &str) {
+        self.render_script_note(out, offset, text, "");
+    }
+
+    fn render_script_note(
+        &mut self,
+        out: &mut String,
+        offset: usize,
+        text: &str,
+        extra_attrs: &str,
+    ) {
+        self.next_id += 1;
+        writeln!(
+            out,
+            "        <ScriptNote ID=\"{}\" Range=\"{},0\"{}>",
+            self.next_id, offset, extra_attrs
+        )
+        .unwrap();
+        for line in text.split('\n') {
+            write!(
+                out,
+                "          <Paragraph>\n            <Text>{}</Text>\n          </Paragraph>\n",
+                escape_xml_text(line)
+            )
+            .unwrap();
+        }
+        out.push_str("        </ScriptNote>\n");
+    }
+}
+
+/// Returns the boneyard left over after the last paragraph.
+fn render_content<'a>(
+    out: &mut String,
+    screenplay: &'a Screenplay,
+    layout_profile: &ScreenplayLayoutProfile,
+) -> ScriptNotes<'a> {
+    let mut notes = ScriptNotes {
+        pending_boneyard: Vec::new(),
+        next_id: 0,
+    };
     for element in &screenplay.elements {
         match element {
+            Element::Boneyard(text) => notes.pending_boneyard.push(text),
             Element::DialogueBlock(block) => {
                 for child in block {
-                    render_paragraph(out, child, layout_profile);
+                    render_paragraph(out, child, layout_profile, &mut notes);
                 }
             }
             Element::DualDialogueBlock(blocks) => {
-                out.push_str("      <Paragraph Alignment=\"Left\" FirstIndent=\"0.00\" Leading=\"Regular\" LeftIndent=\"1.50\" RightIndent=\"7.50\" SpaceBefore=\"12\" Spacing=\"1\" StartsNewPage=\"No\" Type=\"General\">\n              <DualDialogue>\n");
+                out.push_str("      <Paragraph Alignment=\"Left\" FirstIndent=\"0.00\" Leading=\"Regular\" LeftIndent=\"1.50\" RightIndent=\"7.50\" SpaceBefore=\"12\" Spacing=\"1\" StartsNewPage=\"No\" Type=\"General\">\n");
+                notes.render_boneyard(out);
+                out.push_str("              <DualDialogue>\n");
                 for block in blocks {
                     if let Element::DialogueBlock(dialogue_block) = block {
                         for child in dialogue_block {
-                            render_paragraph(out, child, layout_profile);
+                            render_paragraph(out, child, layout_profile, &mut notes);
                         }
                     }
                 }
                 out.push_str("              </DualDialogue>\n              <Text></Text>\n          </Paragraph>\n");
             }
-            _ => render_paragraph(out, element, layout_profile),
+            _ => render_paragraph(out, element, layout_profile, &mut notes),
         }
     }
+    notes
 }
 
-fn render_paragraph(out: &mut String, element: &Element, layout_profile: &ScreenplayLayoutProfile) {
+fn render_paragraph(
+    out: &mut String,
+    element: &Element,
+    layout_profile: &ScreenplayLayoutProfile,
+    notes: &mut ScriptNotes,
+) {
     let (type_name, text, attributes) = match element {
         Element::Action(text, attributes)
         | Element::Character(text, attributes)
@@ -167,7 +293,10 @@ fn render_paragraph(out: &mut String, element: &Element, layout_profile: &Screen
         | Element::NewAct(text, attributes)
         | Element::EndOfAct(text, attributes) => (element.name(), text, attributes),
         Element::DialogueBlock(_) | Element::DualDialogueBlock(_) => return,
-        Element::Section(_, _, _) | Element::Synopsis(_) | Element::PageBreak => return,
+        Element::Section(_, _, _)
+        | Element::Synopsis(_)
+        | Element::Boneyard(_)
+        | Element::PageBreak => return,
     };
 
     write!(
@@ -204,8 +333,18 @@ fn render_paragraph(out: &mut String, element: &Element, layout_profile: &Screen
             .unwrap();
         }
     }
+    if let Some(id) = &attributes.id {
+        write!(out, " JumpCutID=\"{}\"", escape_xml_attr(id)).unwrap();
+    }
     out.push_str(">\n");
-    render_text(out, text);
+    notes.render_boneyard(out);
+    for note in attributes.notes.iter().flatten() {
+        notes.render_note(out, note.offset, &note.text);
+    }
+    for boneyard in &attributes.boneyard {
+        notes.render_inline_boneyard(out, boneyard.offset, &boneyard.text);
+    }
+    render_text(out, text, attributes.revision);
     out.push_str("      </Paragraph>\n");
 }
 
@@ -228,18 +367,28 @@ fn base_style_for_element<'a>(
         | Element::DualDialogueBlock(_)
         | Element::Section(_, _, _)
         | Element::Synopsis(_)
+        | Element::Boneyard(_)
         | Element::PageBreak => None,
     }
 }
 
-fn render_text(out: &mut String, text: &ElementText) {
+/// Writes the text runs of a paragraph, each with the later of its own
+/// revision and the paragraph's.
+fn render_text(out: &mut String, text: &ElementText, revision: Option<u32>) {
     match text {
         ElementText::Plain(text) => {
-            write!(out, "        <Text>{}</Text>\n", escape_xml_text(text)).unwrap();
+            out.push_str("        <Text");
+            if let Some(revision) = revision {
+                write!(out, " RevisionID=\"{revision}\"").unwrap();
+            }
+            write!(out, ">{}</Text>\n", escape_xml_text(text)).unwrap();
         }
         ElementText::Styled(runs) => {
             for run in runs {
                 out.push_str("        <Text");
+                if let Some(revision) = run.revision.max(revision) {
+                    write!(out, " RevisionID=\"{revision}\"").unwrap();
+                }
                 let styles = sorted_style_names(run, true);
                 if !styles.is_empty() {
                     write!(out, " Style=\"{}\"", escape_xml_attr(&styles.join("+"))).unwrap();
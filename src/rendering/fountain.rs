@@ -1,17 +1,6 @@
-use crate::title_page::plain_title_uses_all_caps;
-use crate::{Attributes, Element, ElementText, Metadata, Screenplay, TextRun};
-
-const TITLE_PAGE_KEYS_IN_ORDER: &[&str] = &[
-    "title",
-    "credit",
-    "author",
-    "authors",
-    "source",
-    "draft",
-    "draft date",
-    "contact",
-    "frontmatter-page-count",
-];
+use crate::title_page::{TITLE_PAGE_METADATA_KEYS, plain_title_uses_all_caps};
+use crate::{Attributes, Element, ElementText, Metadata, NOTE_ANCHOR, Screenplay, TextRun};
+
 
 pub fn render(screenplay: &Screenplay) -> String {
     let mut paragraphs = Vec::new();
@@ -31,10 +20,10 @@ pub fn render(screenplay: &Screenplay) -> String {
     paragraphs.join("\n\n")
 }
 
-fn render_metadata(metadata: &Metadata) -> String {
+pub(crate) fn render_metadata(metadata: &Metadata) -> String {
     let mut lines = Vec::new();
 
-    for key in TITLE_PAGE_KEYS_IN_ORDER {
+    for key in &TITLE_PAGE_METADATA_KEYS {
         if let Some(values) = metadata.get(*key) {
             lines.push(render_metadata_entry(key, values, metadata));
         }
@@ -42,7 +31,7 @@ fn render_metadata(metadata: &Metadata) -> String {
 
     let mut remaining_keys = metadata
         .keys()
-        .filter(|key| !TITLE_PAGE_KEYS_IN_ORDER.contains(&key.as_str()))
+        .filter(|key| !TITLE_PAGE_METADATA_KEYS.contains(&key.as_str()))
         .cloned()
         .collect::<Vec<_>>();
     remaining_keys.sort();
@@ -124,6 +113,7 @@ fn normalize_frontmatter_body_text(text: &ElementText) -> ElementText {
                 .map(|run| TextRun {
                     content: run.content.replace('\t', "    "),
                     text_style: run.text_style.clone(),
+                    revision: run.revision,
                 })
                 .collect(),
         ),
@@ -199,7 +189,7 @@ fn metadata_display_key(key: &str) -> String {
     first.to_uppercase().collect::<String>() + chars.as_str()
 }
 
-fn render_element_with_page_breaks(element: &Element) -> Vec<String> {
+pub(crate) fn render_element_with_page_breaks(element: &Element) -> Vec<String> {
     match element {
         Element::Action(text, attributes)
         | Element::Character(text, attributes)
@@ -236,10 +226,24 @@ fn render_element_with_page_breaks(element: &Element) -> Vec<String> {
             ),
         ),
         Element::Synopsis(text) => vec![format!("= {}", render_element_text(text))],
+        Element::Boneyard(text) => vec![format!("/*{}*/", text.plain_text())],
         Element::PageBreak => vec!["===".to_string()],
     }
 }
 
+/// Writes `source`, the Fountain an element was parsed from, with a `===`
+/// before it when the element starts a new page, since the element's source
+/// span leaves the page break out.
+pub(crate) fn render_source_with_page_breaks(element: &Element, source: &str) -> Vec<String> {
+    let starts_new_page = match element {
+        Element::DialogueBlock(elements) => elements.first().and_then(block_attributes),
+        Element::DualDialogueBlock(blocks) => blocks.iter().find_map(block_attributes),
+        _ => block_attributes(element),
+    }
+    .is_some_and(|attributes| attributes.starts_new_page);
+    render_page_started_block(starts_new_page, source.to_string())
+}
+
 fn render_simple_element(
     element: &Element,
     text: &ElementText,
@@ -249,12 +253,13 @@ fn render_simple_element(
         attributes.starts_new_page,
         match element {
             Element::Action(_, _) => render_action(text, attributes),
-            Element::Character(_, _) => render_character(text, false),
+            Element::Character(_, _) => render_character(text, attributes, false),
             Element::SceneHeading(_, _) => render_scene_heading(text, attributes),
-            Element::Lyric(_, _) => render_lyric(text),
-            Element::Parenthetical(_, _) => render_parenthetical(text),
-            Element::Dialogue(_, _) => render_text_with_notes(text, attributes),
-            Element::Transition(_, _) => render_transition(text),
+            Element::Lyric(_, _) => render_lyric(text, attributes),
+            Element::Parenthetical(_, _) | Element::Dialogue(_, _) => {
+                render_text_with_notes(text, attributes)
+            }
+            Element::Transition(_, _) => render_transition(text, attributes),
             Element::ColdOpening(_, _) | Element::NewAct(_, _) | Element::EndOfAct(_, _) => {
                 render_centered(text, attributes)
             }
@@ -303,7 +308,7 @@ fn render_action(text: &ElementText, attributes: &Attributes) -> String {
 }
 
 fn render_scene_heading(text: &ElementText, attributes: &Attributes) -> String {
-    let mut rendered = render_element_text(text);
+    let mut rendered = render_text_with_notes(text, attributes);
     if let Some(scene_number) = &attributes.scene_number {
         rendered.push(' ');
         rendered.push('#');
@@ -318,9 +323,9 @@ fn render_scene_heading(text: &ElementText, attributes: &Attributes) -> String {
     }
 }
 
-fn render_character(text: &ElementText, dual: bool) -> String {
-    let rendered = render_element_text(text);
-    let mut cue = if character_requires_force(&rendered) {
+fn render_character(text: &ElementText, attributes: &Attributes, dual: bool) -> String {
+    let rendered = render_text_with_notes(text, attributes);
+    let mut cue = if character_requires_force(&render_element_text(text)) {
         format!("@{rendered}")
     } else {
         rendered
@@ -331,21 +336,20 @@ fn render_character(text: &ElementText, dual: bool) -> String {
     cue
 }
 
-fn render_parenthetical(text: &ElementText) -> String {
-    render_element_text(text)
-}
-
-fn render_transition(text: &ElementText) -> String {
-    let rendered = render_element_text(text);
-    if rendered.to_ascii_uppercase().ends_with("TO:") {
+fn render_transition(text: &ElementText, attributes: &Attributes) -> String {
+    let rendered = render_text_with_notes(text, attributes);
+    if render_element_text(text)
+        .to_ascii_uppercase()
+        .ends_with("TO:")
+    {
         rendered
     } else {
         format!("> {rendered}")
     }
 }
 
-fn render_lyric(text: &ElementText) -> String {
-    render_element_text(text)
+fn render_lyric(text: &ElementText, attributes: &Attributes) -> String {
+    render_text_with_notes(text, attributes)
         .split('\n')
         .map(|line| format!("~{line}"))
         .collect::<Vec<_>>()
@@ -365,10 +369,11 @@ fn render_dialogue_block(elements: &[Element], dual: bool) -> String {
     elements
         .iter()
         .map(|element| match element {
-            Element::Character(text, _) => render_character(text, dual),
-            Element::Parenthetical(text, _) => render_parenthetical(text),
-            Element::Dialogue(text, attributes) => render_text_with_notes(text, attributes),
-            Element::Lyric(text, _) => render_lyric(text),
+            Element::Character(text, attributes) => render_character(text, attributes, dual),
+            Element::Parenthetical(text, attributes) | Element::Dialogue(text, attributes) => {
+                render_text_with_notes(text, attributes)
+            }
+            Element::Lyric(text, attributes) => render_lyric(text, attributes),
             _ => String::new(),
         })
         .collect::<Vec<_>>()
@@ -388,17 +393,51 @@ fn render_dual_dialogue_block(blocks: &[Element]) -> String {
 }
 
 fn render_text_with_notes(text: &ElementText, attributes: &Attributes) -> String {
-    let mut rendered = render_element_text(text);
-    if let Some(modifier_note) = render_layout_modifier_note(&attributes.layout_overrides) {
-        rendered.push_str("[[");
-        rendered.push_str(&modifier_note);
-        rendered.push_str("]]");
-    }
-    if let Some(notes) = &attributes.notes {
-        for note in notes {
-            rendered.push_str("[[");
-            rendered.push_str(note);
-            rendered.push_str("]]");
+    // Layout modifiers go at the end of the text, ahead of any tags and
+    // notes there.
+    let end = text.plain_text().chars().count();
+    let modifier_note = render_layout_modifier_note(&attributes.layout_overrides);
+    let mut insertions = modifier_note
+        .iter()
+        .map(|note| (end, format!("[[{note}]]")))
+        .chain(
+            attributes
+                .tags
+                .iter()
+                .map(|tag| (tag.offset, format!("[[{}]]", tag.to_note()))),
+        )
+        .chain(
+            attributes
+                .notes
+                .iter()
+                .flatten()
+                .map(|note| (note.offset, format!("[[{}]]", note.text))),
+        )
+        .chain(
+            attributes
+                .boneyard
+                .iter()
+                .map(|boneyard| (boneyard.offset, format!("/*{}*/", boneyard.text))),
+        )
+        .collect::<Vec<_>>();
+    if insertions.is_empty() {
+        return render_element_text(text);
+    }
+    insertions.sort_by_key(|(offset, _)| *offset);
+
+    // Mark where each note and boneyard goes before rendering so that the
+    // markup and escapes around it come out the same.
+    let offsets = insertions
+        .iter()
+        .map(|(offset, _)| *offset)
+        .collect::<Vec<_>>();
+    let marked = render_element_text(&text.with_markers(NOTE_ANCHOR, &offsets));
+    let mut insertions = insertions.into_iter().peekable();
+    let mut rendered = String::with_capacity(marked.len());
+    for ch in marked.chars() {
+        match insertions.next_if(|_| ch == NOTE_ANCHOR) {
+            Some((_, insertion)) => rendered.push_str(&insertion),
+            None => rendered.push(ch),
         }
     }
     rendered
@@ -512,7 +551,7 @@ fn action_requires_force(text: &str) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{blank_attributes, p, tr, ElementLayoutOverrides, ElementText::Styled};
+    use crate::{blank_attributes, p, tr, ElementLayoutOverrides, ElementText::Styled, Note};
 
     #[test]
     fn serializer_round_trips_metadata_and_core_body() {
@@ -532,6 +571,8 @@ mod tests {
             metadata,
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![
                 Element::SceneHeading(
                     p("INT. HOUSE - DAY"),
@@ -572,6 +613,8 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![
                 Element::SceneHeading(p("inside the school bus"), blank_attributes()),
                 Element::Action(p("INT. HOUSE - DAY"), blank_attributes()),
@@ -598,6 +641,8 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![
                 Element::Action(
                     p("THE END"),
@@ -640,6 +685,8 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::Action(
                 p("...come to find Edward making the shapes."),
                 blank_attributes(),
@@ -658,6 +705,8 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::Action(
                 p("John enters."),
                 Attributes {
@@ -682,10 +731,15 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::Action(
                 p("John enters."),
                 Attributes {
-                    notes: Some(vec!["comment".into()]),
+                    notes: Some(vec![Note {
+                        text: "comment".into(),
+                        offset: 12,
+                    }]),
                     layout_overrides: ElementLayoutOverrides {
                         space_before_delta: Some(-1.0),
                         right_indent_delta: None,
@@ -2,21 +2,22 @@ use super::shared::{escape_html, join_metadata, sorted_style_names};
 
 use crate::pagination::margin::dual_dialogue_character_left_indent;
 use crate::pagination::visual_lines::{
-    display_page_number, render_paginated_visual_pages_with_options,
+    display_page_label, render_paginated_visual_pages_with_options,
     render_unpaginated_visual_lines_with_options, visual_line_class_name, VisualLine,
     VisualRenderOptions,
 };
 use crate::pagination::wrapping::{
     wrap_styled_text_for_element, wrap_text_for_element, ElementType, WrapConfig,
 };
-use crate::pagination::{ScreenplayLayoutProfile, StyleProfile};
-use crate::styled_text::{StyledRun, StyledText};
+use crate::pagination::{SceneLength, ScreenplayLayoutProfile, StyleProfile};
+use crate::styled_text::{StyledRun, StyledText, style_revision};
 use crate::title_page::{TitlePage, TitlePageBlockKind};
-use crate::{Attributes, Element, ElementText, Screenplay};
+use crate::{Attributes, Element, ElementText, Note, Screenplay, NOTE_ANCHOR};
 use std::collections::HashSet;
 use std::fmt::Write;
 
 const HTML_STYLE: &str = include_str!("../templates/html_style.css");
+const REVISION_MARK: &str = "<span class=\"revisionMark\">*</span>";
 #[cfg(not(target_arch = "wasm32"))]
 const COURIER_PRIME_REGULAR_TTF: &[u8] =
     include_bytes!("../templates/fonts/CourierPrime-Regular.ttf");
@@ -38,6 +39,13 @@ pub struct HtmlRenderOptions {
     pub render_title_page: bool,
     pub embed_courier_prime: bool,
     pub embedded_courier_prime_css: Option<String>,
+    /// Show boneyard content instead of leaving it out.
+    pub draft: bool,
+    /// Mark where each note sits in unpaginated output.
+    pub render_notes: bool,
+    /// Give each scene heading its length in eighths of a page, in
+    /// `data-eighths` and `data-scene-length` attributes.
+    pub scene_lengths: bool,
 }
 
 impl Default for HtmlRenderOptions {
@@ -50,6 +58,9 @@ impl Default for HtmlRenderOptions {
             render_title_page: true,
             embed_courier_prime: false,
             embedded_courier_prime_css: None,
+            draft: false,
+            render_notes: false,
+            scene_lengths: false,
         }
     }
 }
@@ -191,13 +202,32 @@ fn render_body(
         }
     }
 
+    // Paginated and exact-wrap output lay boneyard out as action.
+    let draft_screenplay;
+    let screenplay = if options.draft && (options.paginated || options.exact_wraps) {
+        draft_screenplay = screenplay.with_boneyard_as_action();
+        &draft_screenplay
+    } else if options.draft {
+        draft_screenplay = screenplay.with_inline_boneyard_shown();
+        &draft_screenplay
+    } else {
+        screenplay
+    };
+
+    let scene_lengths = if options.scene_lengths {
+        screenplay.scene_lengths()
+    } else {
+        Vec::new()
+    };
+    let mut scene_lengths = scene_lengths.iter();
+
     if options.paginated {
-        render_paginated_body(out, screenplay, layout_profile, options);
+        render_paginated_body(out, screenplay, layout_profile, options, scene_lengths);
         return;
     }
 
     if options.exact_wraps {
-        render_exact_wrap_body(out, screenplay, layout_profile, options);
+        render_exact_wrap_body(out, screenplay, layout_profile, options, scene_lengths);
         return;
     }
 
@@ -207,7 +237,7 @@ fn render_body(
             Element::DialogueBlock(block) => {
                 out.push_str("    <div class=\"dialogueBlock\">\n");
                 for child in block {
-                    render_paragraph(out, child, layout_profile);
+                    render_paragraph(out, child, None, layout_profile, options);
                 }
                 out.push_str("                </div>\n");
             }
@@ -217,14 +247,18 @@ fn render_body(
                     out.push_str("                    <div class=\"dialogueBlock\">\n");
                     if let Element::DialogueBlock(dialogue_block) = block {
                         for child in dialogue_block {
-                            render_paragraph(out, child, layout_profile);
+                            render_paragraph(out, child, None, layout_profile, options);
                         }
                     }
                     out.push_str("                    </div>\n");
                 }
                 out.push_str("                </div>\n");
             }
-            _ => render_paragraph(out, element, layout_profile),
+            Element::Boneyard(_) if !options.draft => {}
+            Element::SceneHeading(_, _) => {
+                render_paragraph(out, element, scene_lengths.next(), layout_profile, options)
+            }
+            _ => render_paragraph(out, element, None, layout_profile, options),
         }
     }
     out.push_str("        </section>\n");
@@ -235,7 +269,9 @@ fn render_exact_wrap_body(
     screenplay: &Screenplay,
     layout_profile: &ScreenplayLayoutProfile,
     options: &HtmlRenderOptions,
+    scene_lengths: std::slice::Iter<'_, SceneLength>,
 ) {
+    let mut scene_lengths = SceneLengthMarks::new(scene_lengths);
     out.push_str("        <section class=\"body exactWrapBody\">\n");
     for line in render_unpaginated_visual_lines_with_options(
         screenplay,
@@ -244,7 +280,8 @@ fn render_exact_wrap_body(
             render_title_page: options.render_title_page,
         },
     ) {
-        render_visual_line(out, &line, layout_profile);
+        let scene_length = scene_lengths.mark(&line);
+        render_visual_line(out, &line, scene_length, layout_profile);
     }
     out.push_str("        </section>\n");
 }
@@ -254,7 +291,9 @@ fn render_paginated_body(
     screenplay: &Screenplay,
     layout_profile: &ScreenplayLayoutProfile,
     options: &HtmlRenderOptions,
+    scene_lengths: std::slice::Iter<'_, SceneLength>,
 ) {
+    let mut scene_lengths = SceneLengthMarks::new(scene_lengths);
     out.push_str("        <section class=\"body paginatedBody\">\n");
 
     for page in render_paginated_visual_pages_with_options(
@@ -277,13 +316,23 @@ fn render_paginated_body(
         .unwrap();
 
         out.push_str("                <div class=\"pageHeader\">");
-        if let Some(display_number) = display_page_number(&page.page) {
-            write!(out, "<span class=\"pageNumber\">{}.</span>", display_number).unwrap();
+        let revision = page.lines.iter().filter_map(VisualLine::revision).max();
+        if let Some(revision_set) = revision.and_then(|id| screenplay.revision_set(id)) {
+            write!(
+                out,
+                "<span class=\"revisionLabel\">{}</span>",
+                escape_html(&revision_set.header_label())
+            )
+            .unwrap();
+        }
+        if let Some(display_label) = display_page_label(&page.page) {
+            write!(out, "<span class=\"pageNumber\">{}.</span>", display_label).unwrap();
         }
         out.push_str("</div>\n");
         out.push_str("                <div class=\"pageBody\">\n");
         for line in page.lines {
-            render_visual_line(out, &line, layout_profile);
+            let scene_length = scene_lengths.mark(&line);
+            render_visual_line(out, &line, scene_length, layout_profile);
         }
         out.push_str("                </div>\n");
         out.push_str("            </section>\n");
@@ -292,9 +341,35 @@ fn render_paginated_body(
     out.push_str("        </section>\n");
 }
 
+/// Hands each scene's length to the first visual line of its heading.
+struct SceneLengthMarks<'a> {
+    scene_lengths: std::slice::Iter<'a, SceneLength>,
+    in_heading: bool,
+}
+
+impl<'a> SceneLengthMarks<'a> {
+    fn new(scene_lengths: std::slice::Iter<'a, SceneLength>) -> Self {
+        Self {
+            scene_lengths,
+            in_heading: false,
+        }
+    }
+
+    fn mark(&mut self, line: &VisualLine) -> Option<&'a SceneLength> {
+        let starts_heading = !self.in_heading;
+        self.in_heading = matches!(line.element_type, Some(ElementType::SceneHeading));
+        if self.in_heading && starts_heading {
+            self.scene_lengths.next()
+        } else {
+            None
+        }
+    }
+}
+
 fn render_visual_line(
     out: &mut String,
     line: &VisualLine,
+    scene_length: Option<&SceneLength>,
     layout_profile: &ScreenplayLayoutProfile,
 ) {
     let mut classes = vec!["visualLine"];
@@ -324,13 +399,21 @@ fn render_visual_line(
     if line.dual.is_some() {
         classes.push("dualDialogueLine");
     }
+    if line.revision().is_some() {
+        classes.push("revised");
+    }
 
     write!(
         out,
-        "                    <div class=\"{}\">",
+        "                    <div class=\"{}\"",
         classes.join(" ")
     )
     .unwrap();
+    render_scene_length_attributes(out, scene_length);
+    out.push('>');
+    if line.revision().is_some() {
+        out.push_str(REVISION_MARK);
+    }
     if line.text.is_empty() {
         out.push_str("&nbsp;");
     } else if let Some(dual) = &line.dual {
@@ -424,14 +507,15 @@ fn render_visual_fragments(
     fragments: &[crate::pagination::visual_lines::VisualFragment],
 ) {
     for fragment in fragments {
-        if fragment.styles.is_empty() {
+        let classes = fragment
+            .styles
+            .iter()
+            .filter(|style| style_revision(style).is_none())
+            .map(|style| style.to_lowercase())
+            .collect::<Vec<_>>();
+        if classes.is_empty() {
             out.push_str(&escape_html(&fragment.text));
         } else {
-            let classes = fragment
-                .styles
-                .iter()
-                .map(|style| style.to_lowercase())
-                .collect::<Vec<_>>();
             write!(out, "<span class=\"{}\">", classes.join(" ")).unwrap();
             out.push_str(&escape_html(&fragment.text));
             out.push_str("</span>");
@@ -439,7 +523,13 @@ fn render_visual_fragments(
     }
 }
 
-fn render_paragraph(out: &mut String, element: &Element, layout_profile: &ScreenplayLayoutProfile) {
+fn render_paragraph(
+    out: &mut String,
+    element: &Element,
+    scene_length: Option<&SceneLength>,
+    layout_profile: &ScreenplayLayoutProfile,
+    options: &HtmlRenderOptions,
+) {
     let (type_name, text, attributes) = match element {
         Element::Action(text, attributes)
         | Element::Character(text, attributes)
@@ -453,6 +543,7 @@ fn render_paragraph(out: &mut String, element: &Element, layout_profile: &Screen
         | Element::EndOfAct(text, attributes) => (element.name(), text, attributes),
         Element::Section(text, attributes, _) => ("Section", text, attributes),
         Element::Synopsis(text) => ("Synopsis", text, &Attributes::default()),
+        Element::Boneyard(text) => ("Boneyard", text, &Attributes::default()),
         Element::DialogueBlock(_) | Element::DualDialogueBlock(_) | Element::PageBreak => return,
     };
 
@@ -481,7 +572,16 @@ fn render_paragraph(out: &mut String, element: &Element, layout_profile: &Screen
     if attributes.centered {
         out.push_str(" centered");
     }
-    out.push_str("\">");
+    let revised = attributes.revision.is_some() || !text.revision_ranges().is_empty();
+    if revised {
+        out.push_str(" revised");
+    }
+    out.push('"');
+    render_scene_length_attributes(out, scene_length);
+    out.push('>');
+    if revised {
+        out.push_str(REVISION_MARK);
+    }
     if type_name == "Scene Heading" {
         if let Some(scene_number) = &attributes.scene_number {
             write!(
@@ -492,7 +592,12 @@ fn render_paragraph(out: &mut String, element: &Element, layout_profile: &Screen
             .unwrap();
         }
     }
-    render_text(out, text);
+    match attributes.notes.as_deref() {
+        Some(notes) if options.render_notes && !notes.is_empty() => {
+            render_text_with_note_markers(out, text, notes)
+        }
+        _ => render_text(out, text),
+    }
     if type_name == "Scene Heading" {
         if let Some(scene_number) = &attributes.scene_number {
             write!(
@@ -506,6 +611,39 @@ fn render_paragraph(out: &mut String, element: &Element, layout_profile: &Screen
     out.push_str("</p>\n");
 }
 
+fn render_scene_length_attributes(out: &mut String, scene_length: Option<&SceneLength>) {
+    if let Some(scene_length) = scene_length {
+        write!(
+            out,
+            " data-eighths=\"{}\" data-scene-length=\"{}\"",
+            scene_length.eighths.0, scene_length.eighths
+        )
+        .unwrap();
+    }
+}
+
+/// Renders `text` with a marker where each note sat, carrying the note text
+/// in the marker's title.
+fn render_text_with_note_markers(out: &mut String, text: &ElementText, notes: &[Note]) {
+    let mut notes = notes.iter().collect::<Vec<_>>();
+    notes.sort_by_key(|note| note.offset);
+    let offsets = notes.iter().map(|note| note.offset).collect::<Vec<_>>();
+    let mut marked = String::new();
+    render_text(&mut marked, &text.with_markers(NOTE_ANCHOR, &offsets));
+    let mut notes = notes.into_iter().peekable();
+    for ch in marked.chars() {
+        match notes.next_if(|_| ch == NOTE_ANCHOR) {
+            Some(note) => write!(
+                out,
+                "<span class=\"noteMarker\" title=\"{}\">*</span>",
+                escape_html(&note.text)
+            )
+            .unwrap(),
+            None => out.push(ch),
+        }
+    }
+}
+
 fn render_text(out: &mut String, text: &ElementText) {
     match text {
         ElementText::Plain(text) => out.push_str(&escape_html(text)),
@@ -848,6 +986,7 @@ fn imported_title_display_text(text: &ElementText) -> ElementText {
                     crate::TextRun {
                         content: display_text,
                         text_style: run.text_style.clone(),
+                        revision: run.revision,
                     }
                 })
                 .collect(),
@@ -873,6 +1012,7 @@ fn split_element_text_on_tabs(text: &ElementText) -> Vec<ElementText> {
                             .push(crate::TextRun {
                                 content: part.to_string(),
                                 text_style: run.text_style.clone(),
+                                revision: run.revision,
                             });
                     }
                     if parts.peek().is_some() {
@@ -894,6 +1034,7 @@ fn combine_element_text_segments(segments: &[ElementText]) -> ElementText {
                     runs.push(crate::TextRun {
                         content: text.clone(),
                         text_style: HashSet::new(),
+                        revision: None,
                     });
                 }
             }
@@ -970,6 +1111,7 @@ fn wrap_element_text_to_lines_html(text: &ElementText, width_chars: usize) -> Ve
                         .map(|fragment| crate::TextRun {
                             content: fragment.text,
                             text_style: fragment.styles.into_iter().collect(),
+                            revision: None,
                         })
                         .collect(),
                 )
@@ -1038,6 +1180,7 @@ fn class_name(type_name: &str) -> &'static str {
         "Lyric" => "lyric",
         "Section" => "section",
         "Synopsis" => "synopsis",
+        "Boneyard" => "boneyard",
         "Cold Opening" => "coldOpening",
         "New Act" => "newAct",
         "End of Act" => "endOfAct",
@@ -1059,6 +1202,9 @@ mod tests {
             render_title_page: true,
             embed_courier_prime: false,
             embedded_courier_prime_css: None,
+            draft: false,
+            render_notes: false,
+            scene_lengths: false,
         }
     }
 
@@ -1068,6 +1214,8 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::Action(
                 p("THIS IS A LONG ACTION LINE THAT SHOULD WRAP WHEN EXACT HTML WRAPS ARE ENABLED"),
                 blank_attributes(),
@@ -1088,6 +1236,8 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![],
         };
 
@@ -1105,6 +1255,8 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![],
         };
 
@@ -1121,6 +1273,8 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![],
         };
 
@@ -1136,6 +1290,8 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![],
         };
 
@@ -1157,6 +1313,8 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![],
         };
 
@@ -1185,6 +1343,8 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::Action(
                 ElementText::Styled(vec![
                     tr("BOLD", vec!["Bold"]),
@@ -1208,6 +1368,8 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::Action(
                 p("THE END"),
                 Attributes {
@@ -1230,6 +1392,8 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::NewAct(
                 p("ACT TWO"),
                 Attributes {
@@ -1251,6 +1415,8 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::DialogueBlock(vec![
                 Element::Character(p("ALEX"), blank_attributes()),
                 Element::Parenthetical(p("(quietly)"), blank_attributes()),
@@ -1269,6 +1435,8 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::NewAct(
                 p("ACT TWO"),
                 Attributes {
@@ -1290,6 +1458,8 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::ColdOpening(
                 p("COLD OPENING"),
                 Attributes {
@@ -1312,6 +1482,8 @@ mod tests {
             metadata,
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::NewAct(
                 p("ACT TWO"),
                 Attributes {
@@ -1355,6 +1527,8 @@ mod tests {
             metadata,
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![],
         };
 
@@ -1430,6 +1604,8 @@ mod tests {
                 metadata: plain_metadata,
                 imported_layout: None,
                 imported_title_page: None,
+                revisions: Vec::new(),
+                page_lock: None,
                 elements: vec![],
             },
             html_options(false, false, false),
@@ -1448,6 +1624,8 @@ mod tests {
                 metadata: styled_metadata,
                 imported_layout: None,
                 imported_title_page: None,
+                revisions: Vec::new(),
+                page_lock: None,
                 elements: vec![],
             },
             html_options(false, false, false),
@@ -1467,6 +1645,8 @@ mod tests {
                 metadata,
                 imported_layout: None,
                 imported_title_page: None,
+                revisions: Vec::new(),
+                page_lock: None,
                 elements: vec![Element::Action(p("BODY"), blank_attributes())],
             },
             HtmlRenderOptions {
@@ -1489,6 +1669,8 @@ mod tests {
                 metadata,
                 imported_layout: None,
                 imported_title_page: None,
+                revisions: Vec::new(),
+                page_lock: None,
                 elements: vec![],
             },
             html_options(false, false, true),
@@ -1504,6 +1686,8 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![
                 Element::Action(p("FIRST PAGE"), blank_attributes()),
                 Element::Action(
@@ -1531,6 +1715,8 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::Action(
                 ElementText::Styled(vec![tr(&"BOLD SENTENCE. ".repeat(500), vec!["Bold"])]),
                 blank_attributes(),
@@ -1556,6 +1742,8 @@ mod tests {
             metadata,
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::Action(p("FIRST PAGE"), blank_attributes())],
         };
 
@@ -1578,6 +1766,8 @@ mod tests {
             metadata,
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::Action(p("FIRST PAGE"), blank_attributes())],
         };
 
@@ -1595,6 +1785,8 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::DualDialogueBlock(vec![
                 Element::DialogueBlock(vec![
                     Element::Character(
@@ -1633,6 +1825,8 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::DualDialogueBlock(vec![
                 Element::DialogueBlock(vec![
                     Element::Character(p("BRICK"), blank_attributes()),
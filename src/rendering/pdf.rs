@@ -2,8 +2,8 @@
 
 use crate::pagination::margin::{dual_dialogue_character_left_indent, LayoutGeometry};
 use crate::pagination::visual_lines::{
-    display_page_number, render_paginated_visual_pages_with_options, VisualDualLine,
-    VisualDualSide, VisualFragment, VisualRenderOptions,
+    display_page_label, display_page_number, render_paginated_visual_pages_with_options, VisualDualLine,
+    VisualDualSide, VisualFragment, VisualLine, VisualRenderOptions,
 };
 use crate::pagination::wrapping::{
     wrap_styled_text_for_element, wrap_text_for_element, ElementType, WrapConfig,
@@ -12,19 +12,21 @@ use crate::pagination::ScreenplayLayoutProfile;
 use crate::pagination::{
     BlockPlacement, ContinuationMarker, Fragment, PageItem, PaginatedScreenplay, PaginationScope,
 };
+use crate::sides::{Sides, SidesSelection};
 use crate::title_page::{
     frontmatter_count, plain_title_uses_all_caps, TitlePage, TitlePageBlockKind, TitlePageRegion,
 };
 use crate::{
-    styled_text::{StyledRun, StyledText},
-    ElementText, ImportedTitlePageAlignment, ImportedTitlePageTabStop, Metadata, Screenplay,
+    styled_text::{style_revision, StyledRun, StyledText},
+    ElementText, ImportedTitlePageAlignment, ImportedTitlePageTabStop, LANGUAGE_METADATA_KEYS,
+    Metadata, RevisionSet, Screenplay,
 };
 use pdf_writer::types::{
     ArtifactAttachment, ArtifactSubtype, ArtifactType, CidFontType, FontFlags, NumberingStyle,
     StructRole, SystemInfo, UnicodeCmap,
 };
 use pdf_writer::{Content, Date, Finish, Name, Pdf, Rect, Ref, Str, TextStr};
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use time::{format_description::well_known::Rfc3339, OffsetDateTime};
 use ttf_parser::Face;
 
@@ -51,6 +53,13 @@ const UNDERLINE_LINE_WIDTH: f32 = 0.75;
 const UNDERLINE_Y_OFFSET: f32 = 1.5;
 const SCENE_NUMBER_LEFT_X: f32 = 0.75 * 72.0;
 const SCENE_NUMBER_Y_OFFSET: f32 = 1.129;
+/// How far the revision asterisk sits from the right edge of the page.
+const REVISION_MARK_RIGHT_INCHES: f32 = 0.75;
+/// The cells between a page's revision label and its page number.
+const REVISION_LABEL_GAP_CELLS: f32 = 3.0;
+const SIDES_START_LABEL: &str = "START";
+const SIDES_END_LABEL: &str = "END";
+const SIDES_LABEL_BASELINE_OFFSET: f32 = 3.5;
 const COURIER_PRIME_REGULAR_BYTES: &[u8] =
     include_bytes!("../templates/fonts/CourierPrime-Regular.ttf");
 const COURIER_PRIME_BOLD_BYTES: &[u8] = include_bytes!("../templates/fonts/CourierPrime-Bold.ttf");
@@ -69,6 +78,8 @@ const ADOBE_IDENTITY: SystemInfo<'static> = SystemInfo {
 pub struct PdfRenderOptions {
     pub render_continueds: bool,
     pub render_title_page: bool,
+    /// Show boneyard content on the page as action.
+    pub draft: bool,
 }
 
 impl Default for PdfRenderOptions {
@@ -76,6 +87,7 @@ impl Default for PdfRenderOptions {
         Self {
             render_continueds: true,
             render_title_page: true,
+            draft: false,
         }
     }
 }
@@ -171,6 +183,8 @@ enum PdfPageLabelStyle {
     Blank,
     LowerRoman { offset: i32 },
     Arabic { offset: i32 },
+    /// The locked label of the body page at this index, such as `12A`.
+    Locked { body_page_index: usize },
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -233,7 +247,26 @@ pub(crate) enum PdfTitleBlockRegion {
 pub(crate) struct PdfRenderPage {
     pub page_number: u32,
     pub display_page_number: Option<u32>,
+    /// The printed page number, which is the locked label of a locked
+    /// screenplay's page.
+    pub display_page_label: Option<String>,
     pub lines: Vec<PdfRenderLine>,
+    /// The header label of the latest revision marked on the page.
+    pub revision_label: Option<String>,
+    /// Where the chosen scenes start and end on a page of sides.
+    pub sides_marks: Vec<PdfSidesMark>,
+}
+
+/// A mark on a page of sides that also has scenes that were not chosen.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum PdfSidesMark {
+    /// A `START` rule above the line where a chosen scene starts.
+    Start { line: usize },
+    /// An `END` rule below the line where a chosen scene ends.
+    End { line: usize },
+    /// A cross over the lines of scenes that were not chosen, from the first
+    /// line to the last.
+    CrossOut { first_line: usize, last_line: usize },
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -266,6 +299,23 @@ pub(crate) struct PdfRenderLine {
     pub scene_number: Option<String>,
 }
 
+impl PdfRenderLine {
+    /// The latest revision marked on any of the line's text.
+    fn revision(&self) -> Option<u32> {
+        let dual_fragments = self
+            .dual
+            .iter()
+            .flat_map(|dual| dual.left.iter().chain(dual.right.iter()))
+            .flat_map(|side| side.fragments.iter());
+        self.fragments
+            .iter()
+            .chain(dual_fragments)
+            .flat_map(|fragment| fragment.styles.iter())
+            .filter_map(|style| style_revision(style))
+            .max()
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub(crate) struct PdfRenderFragment {
     pub text: String,
@@ -414,26 +464,38 @@ pub(crate) fn build_render_document(
         },
     )
     .into_iter()
-    .map(|page| PdfRenderPage {
-        page_number: page.page.metadata.number,
-        display_page_number: display_page_number(&page.page),
-        lines: page
+    .map(|page| {
+        let revision_label = page
             .lines
-            .into_iter()
-            .map(|line| PdfRenderLine {
-                text: line.text,
-                counted: line.counted,
-                centered: line.centered,
-                kind: line.element_type.map(Into::into),
-                fragments: line
-                    .fragments
-                    .into_iter()
-                    .map(PdfRenderFragment::from)
-                    .collect(),
-                dual: line.dual.map(Into::into),
-                scene_number: line.scene_number,
-            })
-            .collect(),
+            .iter()
+            .filter_map(VisualLine::revision)
+            .max()
+            .and_then(|id| screenplay.revision_set(id))
+            .map(RevisionSet::header_label);
+        PdfRenderPage {
+            page_number: page.page.metadata.number,
+            display_page_number: display_page_number(&page.page),
+            display_page_label: display_page_label(&page.page),
+            lines: page
+                .lines
+                .into_iter()
+                .map(|line| PdfRenderLine {
+                    text: line.text,
+                    counted: line.counted,
+                    centered: line.centered,
+                    kind: line.element_type.map(Into::into),
+                    fragments: line
+                        .fragments
+                        .into_iter()
+                        .map(PdfRenderFragment::from)
+                        .collect(),
+                    dual: line.dual.map(Into::into),
+                    scene_number: line.scene_number,
+                })
+                .collect(),
+            revision_label,
+            sides_marks: Vec::new(),
+        }
     })
     .collect();
 
@@ -456,10 +518,42 @@ pub(crate) fn render(screenplay: &Screenplay) -> Vec<u8> {
 }
 
 pub(crate) fn render_with_options(screenplay: &Screenplay, options: PdfRenderOptions) -> Vec<u8> {
+    render_pages(screenplay, options, None)
+}
+
+/// Renders sides: only the pages with the chosen scenes, numbered as in the
+/// full screenplay, with the scenes that were not chosen crossed out.
+pub(crate) fn render_sides(
+    screenplay: &Screenplay,
+    selection: &SidesSelection,
+    options: PdfRenderOptions,
+) -> Vec<u8> {
+    render_pages(screenplay, options, Some(selection))
+}
+
+fn render_pages(
+    screenplay: &Screenplay,
+    options: PdfRenderOptions,
+    sides: Option<&SidesSelection>,
+) -> Vec<u8> {
+    let draft_screenplay;
+    let screenplay = if options.draft {
+        draft_screenplay = screenplay.with_boneyard_as_action();
+        &draft_screenplay
+    } else {
+        screenplay
+    };
     let profile = ScreenplayLayoutProfile::from_screenplay(screenplay);
     let geometry = profile.to_pagination_geometry();
-    let document = build_render_document(screenplay, options, &geometry);
+    let mut document = build_render_document(screenplay, options, &geometry);
     let mut tagged_document = build_tagged_document(screenplay, &geometry);
+    if let Some(selection) = sides {
+        keep_sides_pages(
+            &mut document,
+            &mut tagged_document,
+            &screenplay.sides(selection),
+        );
+    }
     if !options.render_title_page {
         tagged_document.title_page = None;
     }
@@ -703,12 +797,106 @@ pub(crate) fn render_with_options(screenplay: &Screenplay, options: PdfRenderOpt
             PdfPageLabelStyle::Arabic { offset } => {
                 page_label.style(NumberingStyle::Arabic).offset(offset);
             }
+            PdfPageLabelStyle::Locked { body_page_index } => {
+                if let Some(label) = &document.body_pages[body_page_index].display_page_label {
+                    page_label.prefix(TextStr(label));
+                }
+            }
         }
     }
 
     pdf.finish()
 }
 
+/// Keeps the body pages the sides are on, marking the chosen scenes on pages
+/// that also have others.
+fn keep_sides_pages(
+    document: &mut PdfRenderDocument,
+    tagged_document: &mut PdfTaggedDocument,
+    sides: &Sides,
+) {
+    let chosen: HashSet<usize> = sides.scenes.iter().map(|scene| scene.index).collect();
+    let kept: HashMap<usize, bool> = sides
+        .pages
+        .iter()
+        .map(|page| (page.metadata.index, page.partial))
+        .collect();
+
+    // Scenes are counted from 1 by the first line of each heading, as
+    // `Screenplay::sides` counts them.
+    let mut scene = 0;
+    let mut in_heading = false;
+    let mut body_pages = Vec::new();
+    for (index, mut page) in std::mem::take(&mut document.body_pages)
+        .into_iter()
+        .enumerate()
+    {
+        let line_scenes: Vec<usize> = page
+            .lines
+            .iter()
+            .map(|line| {
+                let heading = line.counted && matches!(line.kind, Some(PdfLineKind::SceneHeading));
+                if heading && !in_heading {
+                    scene += 1;
+                }
+                in_heading = heading;
+                scene
+            })
+            .collect();
+        match kept.get(&index) {
+            None => continue,
+            Some(true) => page.sides_marks = sides_marks(&page.lines, &line_scenes, &chosen),
+            Some(false) => {}
+        }
+        body_pages.push(page);
+    }
+    document.body_pages = body_pages;
+    tagged_document.body_pages = std::mem::take(&mut tagged_document.body_pages)
+        .into_iter()
+        .enumerate()
+        .filter(|(index, _)| kept.contains_key(index))
+        .map(|(_, page)| page)
+        .collect();
+}
+
+/// Splits the text on a page into runs of chosen and other scenes, putting
+/// `START` and `END` rules around the chosen runs and crossing out the rest.
+fn sides_marks(
+    lines: &[PdfRenderLine],
+    line_scenes: &[usize],
+    chosen: &HashSet<usize>,
+) -> Vec<PdfSidesMark> {
+    let mut runs: Vec<(bool, usize, usize)> = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        if line.text.is_empty() {
+            continue;
+        }
+        let is_chosen = chosen.contains(&line_scenes[index]);
+        match runs.last_mut() {
+            Some((run_chosen, _, last)) if *run_chosen == is_chosen => *last = index,
+            _ => runs.push((is_chosen, index, index)),
+        }
+    }
+
+    let mut marks = Vec::new();
+    for (run_index, &(is_chosen, first_line, last_line)) in runs.iter().enumerate() {
+        if !is_chosen {
+            marks.push(PdfSidesMark::CrossOut {
+                first_line,
+                last_line,
+            });
+            continue;
+        }
+        if run_index > 0 {
+            marks.push(PdfSidesMark::Start { line: first_line });
+        }
+        if run_index + 1 < runs.len() {
+            marks.push(PdfSidesMark::End { line: last_line });
+        }
+    }
+    marks
+}
+
 fn write_tagged_pdf_role_map(struct_tree_root: &mut pdf_writer::writers::StructTreeRoot<'_>) {
     let mut role_map = struct_tree_root.role_map();
     role_map.insert(Name(b"Title"), StructRole::P);
@@ -755,9 +943,9 @@ fn document_author(metadata: &Metadata) -> Option<String> {
 }
 
 fn document_language(metadata: &Metadata) -> String {
-    metadata
-        .get("lang")
-        .or_else(|| metadata.get("language"))
+    LANGUAGE_METADATA_KEYS
+        .iter()
+        .find_map(|key| metadata.get(*key))
         .and_then(|values| {
             let language = values
                 .iter()
@@ -890,8 +1078,8 @@ fn render_body_page_content(
     content.set_font(FONT_REGULAR_NAME, BODY_TEXT_FONT_SIZE);
     let line_step = body_line_step_points(geometry);
 
-    if let Some(display_page_number) = page.display_page_number {
-        let page_number = format!("{display_page_number}.");
+    if let Some(display_page_label) = &page.display_page_label {
+        let page_number = format!("{display_page_label}.");
         render_artifact_runs(
             &mut content,
             fonts,
@@ -901,7 +1089,22 @@ fn render_body_page_content(
                 text: page_number,
                 styles: StyleFlags::default(),
             }],
-            page_number_x(display_page_number, geometry),
+            page_label_x(display_page_label, geometry),
+            page_number_y(geometry),
+            BODY_TEXT_FONT_SIZE,
+            &mut underlines,
+            geometry,
+        );
+    }
+
+    if let Some(label) = &page.revision_label {
+        let label_right = page_label_x(page.display_page_label.as_deref().unwrap_or("1"), geometry)
+            - (REVISION_LABEL_GAP_CELLS * BODY_TEXT_CELL_WIDTH);
+        render_artifact_runs(
+            &mut content,
+            fonts,
+            &[plain_artifact_run(label)],
+            label_right - (label.chars().count() as f32 * BODY_TEXT_CELL_WIDTH),
             page_number_y(geometry),
             BODY_TEXT_FONT_SIZE,
             &mut underlines,
@@ -916,6 +1119,18 @@ fn render_body_page_content(
         }
 
         let line_y = body_top - (index as f32 * line_step);
+        if line.revision().is_some() {
+            render_artifact_runs(
+                &mut content,
+                fonts,
+                &[plain_artifact_run("*")],
+                (geometry.page_width - REVISION_MARK_RIGHT_INCHES) * 72.0,
+                line_y,
+                BODY_TEXT_FONT_SIZE,
+                &mut underlines,
+                geometry,
+            );
+        }
         if let Some(dual) = &line.dual {
             render_dual_body_line(
                 &mut content,
@@ -1018,11 +1233,98 @@ fn render_body_page_content(
         }
     }
 
+    for mark in &page.sides_marks {
+        let (label, y) = match *mark {
+            PdfSidesMark::Start { line } => (
+                SIDES_START_LABEL,
+                sides_rule_above_y(body_top, line, line_step),
+            ),
+            PdfSidesMark::End { line } => (
+                SIDES_END_LABEL,
+                sides_rule_above_y(body_top, line + 1, line_step),
+            ),
+            PdfSidesMark::CrossOut { .. } => continue,
+        };
+        render_artifact_runs(
+            &mut content,
+            fonts,
+            &[plain_artifact_run(label)],
+            (geometry.action_right * 72.0) + BODY_TEXT_CELL_WIDTH,
+            y - SIDES_LABEL_BASELINE_OFFSET,
+            BODY_TEXT_FONT_SIZE,
+            &mut underlines,
+            geometry,
+        );
+    }
+
     content.end_text();
     render_underlines(&mut content, &underlines);
+    render_sides_rules(
+        &mut content,
+        &page.sides_marks,
+        body_top,
+        line_step,
+        geometry,
+    );
     content.finish().to_vec()
 }
 
+/// The height of the gap above a body line, where sides rules are drawn.
+fn sides_rule_above_y(body_top: f32, line: usize, line_step: f32) -> f32 {
+    body_top - (line as f32 * line_step) + (line_step * 0.75)
+}
+
+fn render_sides_rules(
+    content: &mut Content,
+    marks: &[PdfSidesMark],
+    body_top: f32,
+    line_step: f32,
+    geometry: &LayoutGeometry,
+) {
+    if marks.is_empty() {
+        return;
+    }
+
+    let left = geometry.action_left * 72.0;
+    let right = geometry.action_right * 72.0;
+    content.set_line_width(UNDERLINE_LINE_WIDTH);
+    for mark in marks {
+        match *mark {
+            PdfSidesMark::Start { line } => {
+                let y = sides_rule_above_y(body_top, line, line_step);
+                content.move_to(left, y);
+                content.line_to(right, y);
+            }
+            PdfSidesMark::End { line } => {
+                let y = sides_rule_above_y(body_top, line + 1, line_step);
+                content.move_to(left, y);
+                content.line_to(right, y);
+            }
+            PdfSidesMark::CrossOut {
+                first_line,
+                last_line,
+            } => {
+                let top = sides_rule_above_y(body_top, first_line, line_step);
+                let bottom = sides_rule_above_y(body_top, last_line + 1, line_step);
+                content.move_to(left, top);
+                content.line_to(right, bottom);
+                content.move_to(left, bottom);
+                content.line_to(right, top);
+            }
+        }
+        content.stroke();
+    }
+}
+
+fn plain_artifact_run(text: &str) -> ResolvedRun {
+    ResolvedRun {
+        actual_text: None,
+        tagged_span: false,
+        text: text.to_string(),
+        styles: StyleFlags::default(),
+    }
+}
+
 fn render_dual_body_line(
     content: &mut Content,
     dual: &PdfRenderDualLine,
@@ -1083,7 +1385,7 @@ fn render_artifact_runs(
     geometry: &LayoutGeometry,
 ) {
     let is_page_number =
-        line_y == page_number_y(geometry) && line_left >= page_number_x(10, geometry);
+        line_y == page_number_y(geometry) && line_left >= page_label_x("100-100", geometry);
     if is_page_number {
         let mut marked_content = content.begin_marked_content_with_properties(Name(b"Artifact"));
         marked_content
@@ -1118,7 +1420,13 @@ fn build_page_label_plans(document: &PdfRenderDocument) -> Vec<PdfPageLabelPlan>
                         .unwrap_or(PdfPageLabelStyle::Blank)
                 }),
         )
-        .chain(document.body_pages.iter().map(|page| {
+        .chain(document.body_pages.iter().enumerate().map(|(index, page)| {
+            let numbered_label = page.display_page_number.map(|number| number.to_string());
+            if page.display_page_label != numbered_label {
+                return PdfPageLabelStyle::Locked {
+                    body_page_index: index,
+                };
+            }
             page.display_page_number
                 .map(|number| PdfPageLabelStyle::Arabic {
                     offset: number as i32,
@@ -1148,6 +1456,7 @@ fn build_page_label_plans(document: &PdfRenderDocument) -> Vec<PdfPageLabelPlan>
                 Some((start_page_index, PdfPageLabelStyle::Arabic { offset: start_offset })),
                 PdfPageLabelStyle::Arabic { offset },
             ) => offset != start_offset + (page_index - start_page_index),
+            (_, PdfPageLabelStyle::Locked { .. }) => true,
             (Some(_), PdfPageLabelStyle::Blank) => true,
             (Some(_), _) => true,
             (None, PdfPageLabelStyle::Blank) => plans.is_empty(),
@@ -1162,7 +1471,7 @@ fn build_page_label_plans(document: &PdfRenderDocument) -> Vec<PdfPageLabelPlan>
             PdfPageLabelStyle::LowerRoman { .. } | PdfPageLabelStyle::Arabic { .. } => {
                 Some((page_index, style))
             }
-            PdfPageLabelStyle::Blank => None,
+            PdfPageLabelStyle::Blank | PdfPageLabelStyle::Locked { .. } => None,
         };
     }
 
@@ -1349,10 +1658,14 @@ fn title_page_bottom_top_y(geometry: &LayoutGeometry) -> f32 {
 }
 
 fn page_number_x(display_page_number: u32, geometry: &LayoutGeometry) -> f32 {
+    page_label_x(&display_page_number.to_string(), geometry)
+}
+
+fn page_label_x(display_page_label: &str, geometry: &LayoutGeometry) -> f32 {
     let right_inches = geometry.page_width - 1.4375;
     let right_pts = right_inches * 72.0;
-    let extra_digits = display_page_number.to_string().len().saturating_sub(1) as f32;
-    right_pts - (extra_digits * BODY_TEXT_CELL_WIDTH)
+    let extra_cells = display_page_label.chars().count().saturating_sub(1) as f32;
+    right_pts - (extra_cells * BODY_TEXT_CELL_WIDTH)
 }
 
 fn title_page_number_text_x(page_number: &str) -> f32 {
@@ -2050,12 +2363,21 @@ fn collect_document_chars(document: &PdfRenderDocument) -> BTreeSet<char> {
     }
 
     for page in &document.body_pages {
-        if let Some(display_page_number) = page.display_page_number {
-            chars.extend(format!("{display_page_number}.").chars());
+        if let Some(display_page_label) = &page.display_page_label {
+            chars.extend(format!("{display_page_label}.").chars());
+        }
+        if let Some(label) = &page.revision_label {
+            chars.extend(label.chars());
+        }
+        if !page.sides_marks.is_empty() {
+            chars.extend(SIDES_START_LABEL.chars().chain(SIDES_END_LABEL.chars()));
         }
 
         for line in &page.lines {
             chars.extend(rendered_line_chars(line));
+            if line.revision().is_some() {
+                chars.insert('*');
+            }
             if let Some(scene_number) = &line.scene_number {
                 chars.extend(scene_number.chars());
             }
@@ -2289,6 +2611,7 @@ fn imported_title_display_text(text: &ElementText) -> ElementText {
                     crate::TextRun {
                         content: display_text,
                         text_style: run.text_style.clone(),
+                        revision: run.revision,
                     }
                 })
                 .collect(),
@@ -2671,7 +2994,11 @@ fn resolve_runs(fragments: &[PdfRenderFragment], default_styles: StyleFlags) ->
         }
         runs.push(ResolvedRun {
             actual_text: fragment.actual_text.clone(),
-            tagged_span: !fragment.styles.is_empty() || fragment.actual_text.is_some(),
+            tagged_span: fragment
+                .styles
+                .iter()
+                .any(|style| style_revision(style).is_none())
+                || fragment.actual_text.is_some(),
             text: fragment.text.clone(),
             styles: merge_style_flags(default_styles, style_flags_from_names(&fragment.styles)),
         });
@@ -3331,6 +3658,8 @@ mod tests {
             metadata,
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: Vec::new(),
         };
         let geometry = LayoutGeometry::default();
@@ -3701,6 +4030,8 @@ mod tests {
             metadata,
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![
                 Element::Action(p("FIRST BODY PAGE"), blank_attributes()),
                 Element::Action(
@@ -3759,6 +4090,8 @@ mod tests {
             metadata,
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::Action(p("FIRST BODY PAGE"), blank_attributes())],
         };
 
@@ -4004,6 +4337,8 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::DualDialogueBlock(vec![
                 Element::DialogueBlock(vec![
                     Element::Character(p("BRICK"), blank_attributes()),
@@ -4084,6 +4419,8 @@ mod tests {
             metadata,
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![
                 Element::Action(p("FIRST BODY PAGE"), blank_attributes()),
                 Element::Action(
@@ -4117,6 +4454,8 @@ mod tests {
             metadata,
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::Action(p("BODY PAGE"), blank_attributes())],
         };
 
@@ -4154,6 +4493,8 @@ mod tests {
             metadata,
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::Action(p("BODY PAGE"), blank_attributes())],
         };
 
@@ -4187,6 +4528,8 @@ mod tests {
             metadata,
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::Action(p("BODY PAGE"), blank_attributes())],
         };
 
@@ -4206,6 +4549,8 @@ mod tests {
             metadata,
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::Action(p("BODY PAGE"), blank_attributes())],
         };
 
@@ -4227,6 +4572,8 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::Action(p("BODY PAGE"), blank_attributes())],
         };
 
@@ -4251,6 +4598,8 @@ mod tests {
             metadata,
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::Action(p("BODY PAGE"), blank_attributes())],
         };
 
@@ -4272,6 +4621,8 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![
                 Element::Action(p("FIRST BODY PAGE"), blank_attributes()),
                 Element::DialogueBlock(vec![
@@ -4310,6 +4661,8 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![
                 Element::SceneHeading(p("INT. LAB - DAY"), blank_attributes()),
                 Element::Action(p("Machines hum."), blank_attributes()),
@@ -4361,6 +4714,8 @@ mod tests {
             metadata,
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::Action(p("BODY PAGE"), blank_attributes())],
         };
 
@@ -4412,6 +4767,8 @@ mod tests {
             metadata,
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::Action(p("BODY PAGE"), blank_attributes())],
         };
 
@@ -4433,6 +4790,8 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::DualDialogueBlock(vec![
                 Element::DialogueBlock(vec![
                     Element::Character(p("BRICK"), blank_attributes()),
@@ -4637,6 +4996,8 @@ mod tests {
             metadata,
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![
                 Element::Action(p("FIRST BODY PAGE"), blank_attributes()),
                 Element::SceneHeading(
@@ -4697,6 +5058,8 @@ mod tests {
             metadata,
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![
                 Element::Action(p("FIRST BODY PAGE"), blank_attributes()),
                 Element::Action(
@@ -4734,6 +5097,8 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![
                 Element::Action(p("FIRST BODY PAGE"), blank_attributes()),
                 Element::DialogueBlock(vec![
@@ -4779,6 +5144,8 @@ mod tests {
             metadata,
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![
                 Element::Action(p("FIRST BODY PAGE"), blank_attributes()),
                 Element::Action(
@@ -4823,6 +5190,8 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::Action(
                 p("CENTERED LINE"),
                 Attributes {
@@ -4859,6 +5228,8 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::DualDialogueBlock(vec![
                 Element::DialogueBlock(vec![
                     Element::Character(p("BOB"), blank_attributes()),
@@ -4900,6 +5271,9 @@ mod tests {
             body_pages: vec![PdfRenderPage {
                 page_number: 35,
                 display_page_number: Some(34),
+                display_page_label: Some("34".into()),
+                revision_label: None,
+                sides_marks: Vec::new(),
                 lines: vec![
                     PdfRenderLine {
                         text: "MAYOR (CONT'D)".into(),
@@ -4971,6 +5345,9 @@ mod tests {
             body_pages: vec![PdfRenderPage {
                 page_number: 12,
                 display_page_number: Some(11),
+                display_page_label: Some("11".into()),
+                revision_label: None,
+                sides_marks: Vec::new(),
                 lines: vec![
                     PdfRenderLine {
                         text: "        (MORE)".into(),
@@ -5052,6 +5429,8 @@ mod tests {
             metadata,
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::Action(p("BODY PAGE"), blank_attributes())],
         };
 
@@ -5144,6 +5523,8 @@ mod tests {
             metadata,
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::Action(p("BODY PAGE"), blank_attributes())],
         };
 
@@ -5196,6 +5577,8 @@ mod tests {
             metadata,
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::Action(p("BODY PAGE"), blank_attributes())],
         };
 
@@ -5241,6 +5624,8 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::Action(
                 ElementText::Styled(vec![
                     tr("PLAIN ", vec![]),
@@ -5305,6 +5690,8 @@ mod tests {
             metadata,
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::Action(p("BODY PAGE"), blank_attributes())],
         };
 
@@ -5356,6 +5743,8 @@ mod tests {
             metadata,
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::Action(p("BODY PAGE"), blank_attributes())],
         };
 
@@ -5413,6 +5802,8 @@ mod tests {
             metadata,
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::Action(p("BODY PAGE"), blank_attributes())],
         };
 
@@ -5458,6 +5849,8 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::Action(
                 ElementText::Styled(vec![
                     tr("PLAIN ", vec![]),
@@ -5493,6 +5886,8 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![
                 Element::SceneHeading(p("INT. OFFICE - DAY"), blank_attributes()),
                 Element::Lyric(p("I love to sing"), blank_attributes()),
@@ -5521,6 +5916,8 @@ mod tests {
             metadata,
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::SceneHeading(
                 p("INT. OFFICE - DAY"),
                 blank_attributes(),
@@ -5806,6 +6203,9 @@ mod tests {
         let page = PdfRenderPage {
             page_number: 35,
             display_page_number: Some(34),
+            display_page_label: Some("34".into()),
+            revision_label: None,
+            sides_marks: Vec::new(),
             lines: vec![
                 PdfRenderLine {
                     text: "MAYOR (CONT'D)".into(),
@@ -5841,6 +6241,9 @@ mod tests {
         let page = PdfRenderPage {
             page_number: 35,
             display_page_number: Some(34),
+            display_page_label: Some("34".into()),
+            revision_label: None,
+            sides_marks: Vec::new(),
             lines: vec![PdfRenderLine {
                 text: "MAYOR (CONT'D)".into(),
                 counted: true,
@@ -5919,4 +6322,93 @@ mod tests {
         }
         escaped
     }
+
+    #[test]
+    fn revised_pages_carry_the_revision_label_and_marked_lines() {
+        let previous = parse("INT. HOUSE - DAY\n\nBob waits by the door.\n\nRain falls.");
+        let mut screenplay =
+            parse("INT. HOUSE - DAY\n\nBob waits by the window.\n\nRain falls.");
+        let mut revision = RevisionSet::new(1, "Blue Revision", "Blue");
+        revision.date = Some("10/18/26".into());
+        screenplay.mark_revisions(&previous, revision);
+
+        let geometry = LayoutGeometry::default();
+        let document = build_render_document(&screenplay, PdfRenderOptions::default(), &geometry);
+        let page = document.body_pages.first().expect("expected body page");
+        let revised = page
+            .lines
+            .iter()
+            .filter(|line| line.revision().is_some())
+            .map(|line| line.text.trim())
+            .collect::<Vec<_>>();
+
+        assert_eq!(page.revision_label.as_deref(), Some("Blue Rev. (10/18/26)"));
+        assert_eq!(revised, vec!["Bob waits by the window."]);
+        assert!(collect_document_chars(&document).contains(&'*'));
+    }
+
+    #[test]
+    fn pages_that_split_a_scene_carry_scene_continueds() {
+        let mut text = String::from("fmt: numbered-scene-continueds\n\nINT. HOUSE - DAY #7#\n\n");
+        for line in 1..=60 {
+            text.push_str(&format!("Bob waits by the door for the {line}th time.\n\n"));
+        }
+        let screenplay = parse(&text);
+
+        let geometry = LayoutGeometry::default();
+        let document = build_render_document(&screenplay, PdfRenderOptions::default(), &geometry);
+        let first = &document.body_pages[0];
+        let second = &document.body_pages[1];
+
+        assert_eq!(
+            first.lines.last().map(|line| line.text.trim()),
+            Some("(CONTINUED)")
+        );
+        assert_eq!(second.lines[0].text.trim(), "CONTINUED:");
+        assert_eq!(second.lines[0].scene_number.as_deref(), Some("7"));
+        assert!(!second.lines[0].counted);
+    }
+
+    #[test]
+    fn sides_mark_where_the_chosen_scenes_start_and_end() {
+        let screenplay = parse(&fs::read_to_string("tests/fixtures/sides.fountain").unwrap());
+        let geometry = LayoutGeometry::default();
+        let mut document =
+            build_render_document(&screenplay, PdfRenderOptions::default(), &geometry);
+        let mut tagged_document = build_tagged_document(&screenplay, &geometry);
+        let sides = screenplay.sides(&SidesSelection {
+            characters: vec!["ALICE".to_string()],
+            scene_numbers: Vec::new(),
+        });
+        keep_sides_pages(&mut document, &mut tagged_document, &sides);
+
+        let labels: Vec<_> = document
+            .body_pages
+            .iter()
+            .map(|page| page.display_page_label.as_deref())
+            .collect();
+        assert_eq!(labels, [None, Some("3"), Some("4")]);
+        assert_eq!(tagged_document.body_pages.len(), 3);
+
+        let first = &document.body_pages[0];
+        let [PdfSidesMark::End { line: end }, PdfSidesMark::CrossOut {
+            first_line,
+            last_line,
+        }] = first.sides_marks[..]
+        else {
+            panic!("unexpected marks {:?}", first.sides_marks);
+        };
+        assert_eq!(first.lines[end].text.trim(), "This is scene 1.");
+        assert_eq!(first.lines[first_line].text.trim(), "INT. ROOM 2 - DAY");
+        assert_eq!(last_line, first.lines.len() - 1);
+
+        let third = &document.body_pages[1];
+        let [PdfSidesMark::CrossOut { first_line: 0, .. }, PdfSidesMark::Start { line: start }] =
+            third.sides_marks[..]
+        else {
+            panic!("unexpected marks {:?}", third.sides_marks);
+        };
+        assert_eq!(third.lines[start].text.trim(), "INT. ROOM 4 - DAY");
+        assert!(document.body_pages[2].sides_marks.is_empty());
+    }
 }
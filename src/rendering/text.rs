@@ -1,6 +1,9 @@
 use crate::pagination::composer::{self, LayoutBlock};
+use crate::pagination::layout_profile::SceneContinueds;
+use crate::pagination::locked_pages::{label_pages, with_locked_breaks};
 use crate::pagination::margin::{calculate_element_width, line_height_for_element_type};
 use crate::pagination::paginator;
+use crate::pagination::continueds::{continued_character_cue_text, scene_continued_text};
 use crate::pagination::wrapping::{
     self, wrap_config_with_overrides, ElementType, InterruptionDashWrap,
 };
@@ -38,7 +41,8 @@ pub fn render(screenplay: &Screenplay, options: &TextRenderOptions) -> String {
     let scope = default_pagination_scope(screenplay);
     let layout_profile = ScreenplayLayoutProfile::from_screenplay(screenplay);
     let style_profile = style_profile_name(&layout_profile);
-    let normalized = normalize_screenplay(screenplay_id, screenplay);
+    let locked = with_locked_breaks(screenplay);
+    let normalized = normalize_screenplay(screenplay_id, &locked);
     let semantic = build_semantic_screenplay_with_options(
         normalized,
         SemanticOptions {
@@ -55,13 +59,15 @@ pub fn render(screenplay: &Screenplay, options: &TextRenderOptions) -> String {
     let blocks = composer::compose(&semantic.units, &config.geometry);
 
     if options.paginated {
-        let actual =
+        let mut actual =
             PaginatedScreenplay::paginate(semantic.clone(), config.clone(), style_profile, scope);
+        label_pages(&mut actual.pages, screenplay);
         let layout_pages =
             nonempty_layout_pages(&blocks, &config.geometry, config.geometry.lines_per_page);
         render_paginated_text(
             &actual.pages,
             &layout_pages,
+            &layout_profile.continueds.scene,
             &config.geometry,
             config.interruption_dash_wrap,
             options,
@@ -83,7 +89,7 @@ fn style_profile_name(layout_profile: &ScreenplayLayoutProfile) -> &'static str
     }
 }
 
-fn default_pagination_scope(screenplay: &Screenplay) -> PaginationScope {
+pub(crate) fn default_pagination_scope(screenplay: &Screenplay) -> PaginationScope {
     if let Some(title_page) = TitlePage::from_screenplay(screenplay) {
         let count = frontmatter_count(screenplay).unwrap_or_else(|| title_page.total_page_count());
         let first_page_number = if screenplay.metadata.contains_key("frontmatter-page-count") {
@@ -123,6 +129,7 @@ fn nonempty_layout_pages<'a>(
 fn render_paginated_text(
     pages: &[Page],
     layout_pages: &[paginator::Page<'_>],
+    continueds: &SceneContinueds,
     geometry: &LayoutGeometry,
     interruption_dash_wrap: InterruptionDashWrap,
     options: &TextRenderOptions,
@@ -151,12 +158,37 @@ fn render_paginated_text(
             });
         }
 
+        let show_continueds = options.render_continueds;
+        if show_continueds && let Some(continued) = &page.metadata.scene_continued {
+            lines.push(render_scene_continued_line(
+                &continueds.top_line(continued.count),
+                ElementType::Action,
+                geometry,
+                interruption_dash_wrap,
+            ));
+            lines.push(RenderedTextLine {
+                text: String::new(),
+                counted: false,
+            });
+        }
         lines.extend(render_layout_page_lines(
             layout_page,
             geometry,
             interruption_dash_wrap,
             options,
         ));
+        if show_continueds && page.metadata.scene_continues {
+            lines.push(RenderedTextLine {
+                text: String::new(),
+                counted: false,
+            });
+            lines.push(render_scene_continued_line(
+                &continueds.bottom_text,
+                ElementType::Transition,
+                geometry,
+                interruption_dash_wrap,
+            ));
+        }
         rendered_pages.push(render_text_lines(&lines, options.line_numbers));
     }
 
@@ -168,11 +200,15 @@ fn render_page_header(page: &Page, geometry: &LayoutGeometry) -> Option<String>
         return None;
     }
 
-    let display_number = page
-        .metadata
-        .body_page_number
-        .unwrap_or(page.metadata.number);
-    if display_number == 1 {
+    let display_number = match &page.metadata.label {
+        Some(label) => label.clone(),
+        None => page
+            .metadata
+            .body_page_number
+            .unwrap_or(page.metadata.number)
+            .to_string(),
+    };
+    if display_number == "1" {
         return None;
     }
     let indent = page_number_indent_spaces(geometry);
@@ -509,17 +545,6 @@ fn render_dialogue_continuation_prefix(
         .collect()
 }
 
-fn continued_character_cue_text(text: &str) -> String {
-    let trimmed = text.trim_end();
-    let upper = trimmed.to_ascii_uppercase();
-
-    if upper.ends_with("(CONT'D)") || upper.ends_with("(CONT’D)") {
-        trimmed.to_string()
-    } else {
-        format!("{trimmed} (CONT'D)")
-    }
-}
-
 fn render_more_marker_line(
     geometry: &LayoutGeometry,
     interruption_dash_wrap: InterruptionDashWrap,
@@ -539,6 +564,18 @@ fn render_more_marker_line(
     }
 }
 
+fn render_scene_continued_line(
+    text: &str,
+    element_type: ElementType,
+    geometry: &LayoutGeometry,
+    interruption_dash_wrap: InterruptionDashWrap,
+) -> RenderedTextLine {
+    RenderedTextLine {
+        text: scene_continued_text(text, element_type, geometry, interruption_dash_wrap),
+        counted: false,
+    }
+}
+
 fn render_semantic_unit_lines(
     unit: &SemanticUnit,
     geometry: &LayoutGeometry,
@@ -862,6 +899,8 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![
                 Element::Action(p("FIRST PAGE"), blank_attributes()),
                 Element::Action(
@@ -903,6 +942,8 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::Action(p("HELLO"), blank_attributes())],
         };
 
@@ -918,6 +959,8 @@ mod tests {
             metadata: Metadata::new(),
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::Action(p("HELLO"), blank_attributes())],
         };
 
@@ -942,6 +985,8 @@ mod tests {
             metadata,
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![
                 Element::Action(p("BODY PAGE ONE"), blank_attributes()),
                 Element::Action(
@@ -984,6 +1029,8 @@ mod tests {
             metadata,
             imported_layout: None,
             imported_title_page: None,
+            revisions: Vec::new(),
+            page_lock: None,
             elements: vec![Element::Action(p("BODY PAGE"), blank_attributes())],
         };
 
@@ -1002,6 +1049,9 @@ mod tests {
                 kind: PageKind::Body,
                 body_page_number: Some(2),
                 title_page_number: None,
+                label: None,
+                scene_continued: None,
+                scene_continues: false,
             },
             items: Vec::new(),
             blocks: Vec::new(),
@@ -0,0 +1,335 @@
+use serde::Serialize;
+use std::fmt::Write;
+
+use crate::Screenplay;
+use crate::pagination::scene_lengths::ScenePages;
+use crate::pagination::wrapping::{ElementType, wrap_config_with_overrides, wrap_text_for_element};
+use crate::pagination::{
+    DialoguePartKind, DialogueUnit, FlowKind, ScreenplayLayoutProfile, SemanticUnit, StyleProfile,
+};
+use crate::stats::write_table;
+
+/// The rates [`Screenplay::runtime_with_options`] estimates with.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct RuntimeOptions {
+    /// Words of dialogue and lyrics spoken per minute.
+    pub dialogue_words_per_minute: f64,
+    /// Printed lines of action played out per second.
+    pub action_lines_per_second: f64,
+    /// Seconds each transition, such as `CUT TO:`, adds.
+    pub transition_seconds: f64,
+}
+
+impl RuntimeOptions {
+    /// Rates for single-camera screenplays, which come to a little under a
+    /// minute for a typical page.
+    pub fn screenplay() -> RuntimeOptions {
+        RuntimeOptions {
+            dialogue_words_per_minute: 140.0,
+            action_lines_per_second: 0.7,
+            transition_seconds: 2.0,
+        }
+    }
+
+    /// Rates for multicam scripts, whose double-spaced pages play in about
+    /// half a minute.
+    pub fn multicam() -> RuntimeOptions {
+        RuntimeOptions {
+            dialogue_words_per_minute: 190.0,
+            action_lines_per_second: 1.5,
+            transition_seconds: 1.0,
+        }
+    }
+
+    /// The rates for the screenplay's `fmt` profile: multicam rates for
+    /// `Fmt: multicam`, and screenplay rates otherwise.
+    pub fn for_screenplay(screenplay: &Screenplay) -> RuntimeOptions {
+        match ScreenplayLayoutProfile::from_screenplay(screenplay).style_profile {
+            StyleProfile::Multicam => RuntimeOptions::multicam(),
+            StyleProfile::Screenplay => RuntimeOptions::screenplay(),
+        }
+    }
+}
+
+/// How long a screenplay should run on screen, as estimated by
+/// [`Screenplay::runtime`]. Times are in seconds.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct RuntimeEstimate {
+    /// The rates the estimate was made with.
+    pub options: RuntimeOptions,
+    pub total: Timings,
+    pub scenes: Vec<SceneRuntime>,
+    /// The acts, started by a cold opening or new act and ended by an end of
+    /// act or the next one. Empty for screenplays without acts.
+    pub acts: Vec<ActRuntime>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SceneRuntime {
+    /// The scene's position in the screenplay, counting from 1.
+    pub index: usize,
+    pub scene_number: Option<String>,
+    pub heading: String,
+    /// The printed number of the page the scene starts on.
+    pub page: String,
+    pub timings: Timings,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ActRuntime {
+    pub title: String,
+    pub scene_count: usize,
+    pub timings: Timings,
+}
+
+/// Time in seconds, in all and by what fills it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
+pub struct Timings {
+    pub seconds: f64,
+    pub dialogue_seconds: f64,
+    pub action_seconds: f64,
+    pub transition_seconds: f64,
+}
+
+impl Timings {
+    fn add(&mut self, timing: Timing) {
+        let (seconds, part) = match timing {
+            Timing::Dialogue(seconds) => (seconds, &mut self.dialogue_seconds),
+            Timing::Action(seconds) => (seconds, &mut self.action_seconds),
+            Timing::Transition(seconds) => (seconds, &mut self.transition_seconds),
+        };
+        *part += seconds;
+        self.seconds += seconds;
+    }
+}
+
+impl RuntimeEstimate {
+    /// The estimate as plain-text tables, with times as `m:ss`.
+    pub fn to_report(&self) -> String {
+        let mut out = String::new();
+        let total = &self.total;
+        writeln!(out, "Estimated runtime: {}", format_duration(total.seconds)).unwrap();
+        writeln!(
+            out,
+            "Dialogue: {}, action: {}, transitions: {}",
+            format_duration(total.dialogue_seconds),
+            format_duration(total.action_seconds),
+            format_duration(total.transition_seconds)
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "Rates: {} words of dialogue per minute, {} lines of action per second, {}s per transition",
+            self.options.dialogue_words_per_minute,
+            self.options.action_lines_per_second,
+            self.options.transition_seconds
+        )
+        .unwrap();
+
+        let rows = self
+            .scenes
+            .iter()
+            .map(|scene| {
+                vec![
+                    scene.index.to_string(),
+                    scene.scene_number.clone().unwrap_or_default(),
+                    scene.page.clone(),
+                    format_duration(scene.timings.seconds),
+                    scene.heading.clone(),
+                ]
+            })
+            .collect();
+        write_table(
+            &mut out,
+            "Scenes",
+            &["#", "Number", "Page", "Runtime", "Heading"],
+            rows,
+        );
+
+        if !self.acts.is_empty() {
+            let rows = self
+                .acts
+                .iter()
+                .map(|act| {
+                    vec![
+                        act.title.clone(),
+                        act.scene_count.to_string(),
+                        format_duration(act.timings.seconds),
+                    ]
+                })
+                .collect();
+            write_table(&mut out, "Acts", &["Act", "Scenes", "Runtime"], rows);
+        }
+        out
+    }
+}
+
+impl Screenplay {
+    /// Estimates the screenplay's runtime with the default rates for its
+    /// `fmt` profile. See [`Screenplay::runtime_with_options`].
+    pub fn runtime(&self) -> RuntimeEstimate {
+        self.runtime_with_options(RuntimeOptions::for_screenplay(self))
+    }
+
+    /// Estimates the screenplay's runtime from the words of its dialogue,
+    /// the printed lines of its action and its transitions. Only one side
+    /// of dual dialogue is counted, the longer one, since both are spoken
+    /// at once.
+    pub fn runtime_with_options(&self, options: RuntimeOptions) -> RuntimeEstimate {
+        let scene_pages = ScenePages::new(self);
+        let mut estimator = RuntimeEstimator {
+            scene_pages: &scene_pages,
+            estimate: RuntimeEstimate {
+                options,
+                total: Timings::default(),
+                scenes: Vec::new(),
+                acts: Vec::new(),
+            },
+            act: None,
+        };
+        for unit in &scene_pages.units {
+            estimator.estimate_unit(unit);
+        }
+        estimator.finish()
+    }
+}
+
+/// Time spent on one kind of element.
+#[derive(Clone, Copy)]
+enum Timing {
+    Dialogue(f64),
+    Action(f64),
+    Transition(f64),
+}
+
+struct RuntimeEstimator<'a> {
+    scene_pages: &'a ScenePages,
+    estimate: RuntimeEstimate,
+    act: Option<ActRuntime>,
+}
+
+impl RuntimeEstimator<'_> {
+    fn estimate_unit(&mut self, unit: &SemanticUnit) {
+        let options = self.estimate.options;
+        match unit {
+            SemanticUnit::Flow(flow) => match flow.kind {
+                FlowKind::SceneHeading => self.start_scene(
+                    flow.text.clone(),
+                    flow.render_attributes.scene_number.clone(),
+                ),
+                FlowKind::Action => {
+                    let config = &self.scene_pages.config;
+                    let wrap = wrap_config_with_overrides(
+                        &config.geometry,
+                        ElementType::Action,
+                        &flow.render_attributes.layout_overrides,
+                        config.interruption_dash_wrap,
+                    );
+                    let lines = wrap_text_for_element(&flow.text, &wrap).len();
+                    self.add(Timing::Action(
+                        lines as f64 / options.action_lines_per_second,
+                    ));
+                }
+                FlowKind::Transition => self.add(Timing::Transition(options.transition_seconds)),
+                FlowKind::ColdOpening | FlowKind::NewAct => {
+                    self.close_act();
+                    self.act = Some(ActRuntime {
+                        title: flow.text.clone(),
+                        scene_count: 0,
+                        timings: Timings::default(),
+                    });
+                }
+                FlowKind::EndOfAct => self.close_act(),
+                FlowKind::Section | FlowKind::Synopsis => {}
+            },
+            SemanticUnit::Dialogue(dialogue) => {
+                self.add(Timing::Dialogue(self.dialogue_seconds(dialogue)))
+            }
+            SemanticUnit::DualDialogue(dual) => {
+                let seconds = dual
+                    .sides
+                    .iter()
+                    .map(|side| self.dialogue_seconds(&side.dialogue))
+                    .fold(0.0, f64::max);
+                self.add(Timing::Dialogue(seconds));
+            }
+            SemanticUnit::Lyric(lyric) => {
+                self.add(Timing::Dialogue(self.spoken_seconds(&lyric.text)))
+            }
+            SemanticUnit::PageStart(_) => {}
+        }
+    }
+
+    fn dialogue_seconds(&self, dialogue: &DialogueUnit) -> f64 {
+        dialogue
+            .parts
+            .iter()
+            .filter(|part| {
+                matches!(
+                    part.kind,
+                    DialoguePartKind::Dialogue | DialoguePartKind::Lyric
+                )
+            })
+            .map(|part| self.spoken_seconds(&part.text))
+            .sum()
+    }
+
+    fn spoken_seconds(&self, text: &str) -> f64 {
+        text.split_whitespace().count() as f64 * 60.0
+            / self.estimate.options.dialogue_words_per_minute
+    }
+
+    fn start_scene(&mut self, heading: String, scene_number: Option<String>) {
+        let index = self.estimate.scenes.len();
+        let page = self
+            .scene_pages
+            .scenes
+            .get(index)
+            .map(|scene| self.scene_pages.pages[scene.first_page].clone())
+            .unwrap_or_default();
+        if let Some(act) = &mut self.act {
+            act.scene_count += 1;
+        }
+        self.estimate.scenes.push(SceneRuntime {
+            index: index + 1,
+            scene_number,
+            heading,
+            page,
+            timings: Timings::default(),
+        });
+    }
+
+    /// Adds time to the total, the current scene and the current act.
+    fn add(&mut self, timing: Timing) {
+        self.estimate.total.add(timing);
+        if let Some(scene) = self.estimate.scenes.last_mut() {
+            scene.timings.add(timing);
+        }
+        if let Some(act) = &mut self.act {
+            act.timings.add(timing);
+        }
+    }
+
+    fn close_act(&mut self) {
+        if let Some(act) = self.act.take() {
+            self.estimate.acts.push(act);
+        }
+    }
+
+    fn finish(mut self) -> RuntimeEstimate {
+        self.close_act();
+        self.estimate
+    }
+}
+
+/// Formats seconds as `m:ss`, or `h:mm:ss` from an hour on, rounded to the
+/// nearest second.
+pub fn format_duration(seconds: f64) -> String {
+    let seconds = seconds.round() as u64;
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes}:{seconds:02}")
+    }
+}
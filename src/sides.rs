@@ -0,0 +1,126 @@
+use std::collections::HashSet;
+
+use crate::pagination::PageMetadata;
+use crate::pagination::scene_lengths::ScenePages;
+use crate::{Element, Screenplay};
+
+/// Which scenes go into a set of sides. A scene is chosen when one of the
+/// characters speaks in it, or when its scene number is one of the scene
+/// numbers. Both are matched without regard to case.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SidesSelection {
+    pub characters: Vec<String>,
+    pub scene_numbers: Vec<String>,
+}
+
+/// The scenes chosen for a set of sides and the pages they are printed on,
+/// as built by [`Screenplay::sides`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Sides {
+    /// The chosen scenes, in screenplay order.
+    pub scenes: Vec<SidesScene>,
+    /// The pages of the full screenplay the chosen scenes are on, in order.
+    pub pages: Vec<SidesPage>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SidesScene {
+    /// The scene's position in the screenplay, counting from 1.
+    pub index: usize,
+    pub scene_number: Option<String>,
+    pub heading: String,
+    /// The printed number of the page the scene starts on.
+    pub first_page: String,
+    /// The printed number of the page the scene ends on.
+    pub last_page: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SidesPage {
+    /// The page as it is numbered in the full screenplay.
+    pub metadata: PageMetadata,
+    /// The printed page number, such as `12` or, when locked, `12A`.
+    pub label: String,
+    /// Whether the page also has scenes that were not chosen, which are
+    /// crossed out in the PDF.
+    pub partial: bool,
+}
+
+impl Sides {
+    pub fn is_empty(&self) -> bool {
+        self.scenes.is_empty()
+    }
+}
+
+impl Screenplay {
+    /// Chooses the scenes for a set of sides, and finds the pages they are
+    /// on in the full pagination, so that sides keep the screenplay's page
+    /// numbers. See [`Screenplay::to_sides_pdf`] to print them.
+    pub fn sides(&self, selection: &SidesSelection) -> Sides {
+        let characters: HashSet<String> = selection
+            .characters
+            .iter()
+            .map(|name| name.trim().to_uppercase())
+            .collect();
+        let scene_numbers: HashSet<String> = selection
+            .scene_numbers
+            .iter()
+            .map(|number| number.trim().to_uppercase())
+            .collect();
+
+        let mut chosen = Vec::new();
+        for element in &self.elements {
+            match element {
+                Element::SceneHeading(_, attributes) => chosen.push(
+                    attributes
+                        .scene_number
+                        .as_ref()
+                        .is_some_and(|number| scene_numbers.contains(&number.to_uppercase())),
+                ),
+                Element::Character(_, _)
+                | Element::DialogueBlock(_)
+                | Element::DualDialogueBlock(_) => {
+                    if let Some(scene) = chosen.last_mut() {
+                        *scene |= element
+                            .character_cues()
+                            .iter()
+                            .any(|cue| characters.contains(&cue.name.to_uppercase()));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let scene_pages = ScenePages::new(self);
+        let mut sides = Sides::default();
+        let mut has_chosen = vec![false; scene_pages.pages.len()];
+        let mut has_other = vec![false; scene_pages.pages.len()];
+        if let Some(last_page) = scene_pages.opening_last_page {
+            has_other[..=last_page].fill(true);
+        }
+        for (index, scene) in scene_pages.scenes.iter().enumerate() {
+            let pages = scene.first_page..=scene.last_page;
+            if !chosen.get(index).copied().unwrap_or(false) {
+                has_other[pages].fill(true);
+                continue;
+            }
+            has_chosen[pages].fill(true);
+            sides.scenes.push(SidesScene {
+                index: index + 1,
+                scene_number: scene.scene_number.clone(),
+                heading: scene.heading.clone(),
+                first_page: scene_pages.pages[scene.first_page].clone(),
+                last_page: scene_pages.pages[scene.last_page].clone(),
+            });
+        }
+        sides.pages = (0..scene_pages.pages.len())
+            .filter(|&page| has_chosen[page])
+            .map(|page| SidesPage {
+                metadata: scene_pages.page_metadata[page].clone(),
+                label: scene_pages.pages[page].clone(),
+                partial: has_other[page],
+            })
+            .collect();
+        sides
+    }
+}
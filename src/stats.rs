@@ -0,0 +1,468 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::pagination::scene_lengths::ScenePages;
+use crate::pagination::wrapping::{ElementType, wrap_config_with_overrides, wrap_text_for_element};
+use crate::pagination::{Eighths, normalized_element_ids};
+use crate::{CharacterCue, DayNight, Element, SceneSetting, Screenplay};
+
+/// Counts and lengths for a screenplay, as built by
+/// [`Screenplay::statistics`].
+///
+/// Lengths come from the same pagination as the PDF, measured in eighths of
+/// a page. Lines are printed lines, after wrapping.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct ScriptStatistics {
+    pub page_count: usize,
+    pub scene_count: usize,
+    pub scenes: Vec<SceneStatistics>,
+    /// The speaking characters, in order of their first speech.
+    pub characters: Vec<CharacterStatistics>,
+    pub settings: SettingCounts,
+    pub times_of_day: TimeOfDayCounts,
+    pub action_lines: usize,
+    pub dialogue_lines: usize,
+    /// Action lines per line of dialogue, or `None` without dialogue.
+    pub action_to_dialogue: Option<f64>,
+    /// The acts, started by a cold opening or new act and ended by an end of
+    /// act or the next one. Empty for screenplays without acts.
+    pub acts: Vec<ActStatistics>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SceneStatistics {
+    /// The scene's position in the screenplay, counting from 1.
+    pub index: usize,
+    pub scene_number: Option<String>,
+    pub heading: String,
+    /// The printed number of the page the scene starts on.
+    pub page: String,
+    pub eighths: u32,
+    /// The characters who speak in the scene, in order of their first speech.
+    pub characters: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct CharacterStatistics {
+    pub name: String,
+    pub speeches: usize,
+    pub words: usize,
+    pub dialogue_lines: usize,
+    pub first_appearance: Appearance,
+    pub last_appearance: Appearance,
+}
+
+/// Where a character speaks. `scene` is the scene's index, or `None` before
+/// the first scene heading.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Appearance {
+    pub scene: Option<usize>,
+    pub page: Option<String>,
+}
+
+/// Scenes counted by the prefix of their heading. `other` counts establishing
+/// shots and headings without a known prefix.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct SettingCounts {
+    pub interior: usize,
+    pub exterior: usize,
+    pub interior_exterior: usize,
+    pub other: usize,
+}
+
+/// Scenes counted by their time of day. Scenes that are `CONTINUOUS` or
+/// `LATER` count with the scene before them.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct TimeOfDayCounts {
+    pub day: usize,
+    pub night: usize,
+    pub other: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ActStatistics {
+    pub title: String,
+    pub scene_count: usize,
+    pub eighths: u32,
+    pub speeches: usize,
+    pub words: usize,
+}
+
+impl ScriptStatistics {
+    /// The statistics as plain-text tables.
+    pub fn to_report(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "Pages: {}", self.page_count).unwrap();
+        writeln!(out, "Scenes: {}", self.scene_count).unwrap();
+        match self.action_to_dialogue {
+            Some(ratio) => writeln!(
+                out,
+                "Action to dialogue: {ratio:.2} ({} action lines, {} dialogue lines)",
+                self.action_lines, self.dialogue_lines
+            ),
+            None => writeln!(out, "Action lines: {}", self.action_lines),
+        }
+        .unwrap();
+
+        let settings = [
+            ("Interior", self.settings.interior),
+            ("Exterior", self.settings.exterior),
+            ("Interior/exterior", self.settings.interior_exterior),
+            ("Other", self.settings.other),
+        ];
+        let times = [
+            ("Day", self.times_of_day.day),
+            ("Night", self.times_of_day.night),
+            ("Other", self.times_of_day.other),
+        ];
+        for (title, counts) in [("Settings", &settings[..]), ("Time of day", &times[..])] {
+            let rows = counts
+                .iter()
+                .map(|(label, count)| {
+                    vec![
+                        label.to_string(),
+                        count.to_string(),
+                        percentage(*count, self.scene_count),
+                    ]
+                })
+                .collect();
+            write_table(&mut out, title, &["", "Scenes", "Share"], rows);
+        }
+
+        let rows = self
+            .scenes
+            .iter()
+            .map(|scene| {
+                vec![
+                    scene.index.to_string(),
+                    scene.scene_number.clone().unwrap_or_default(),
+                    scene.page.clone(),
+                    Eighths(scene.eighths).to_string(),
+                    scene.heading.clone(),
+                ]
+            })
+            .collect();
+        write_table(
+            &mut out,
+            "Scenes",
+            &["#", "Number", "Page", "Length", "Heading"],
+            rows,
+        );
+
+        let rows = self
+            .characters
+            .iter()
+            .map(|character| {
+                vec![
+                    character.name.clone(),
+                    character.speeches.to_string(),
+                    character.words.to_string(),
+                    character.dialogue_lines.to_string(),
+                    format_appearance(&character.first_appearance),
+                    format_appearance(&character.last_appearance),
+                ]
+            })
+            .collect();
+        write_table(
+            &mut out,
+            "Characters",
+            &["Name", "Speeches", "Words", "Lines", "First", "Last"],
+            rows,
+        );
+
+        if !self.acts.is_empty() {
+            let rows = self
+                .acts
+                .iter()
+                .map(|act| {
+                    vec![
+                        act.title.clone(),
+                        act.scene_count.to_string(),
+                        Eighths(act.eighths).to_string(),
+                        act.speeches.to_string(),
+                        act.words.to_string(),
+                    ]
+                })
+                .collect();
+            write_table(
+                &mut out,
+                "Acts",
+                &["Act", "Scenes", "Length", "Speeches", "Words"],
+                rows,
+            );
+        }
+        out
+    }
+}
+
+impl Screenplay {
+    /// Counts the screenplay's pages, scenes, speeches and words, paginating
+    /// it the way the PDF renderer does. See [`ScriptStatistics`].
+    pub fn statistics(&self) -> ScriptStatistics {
+        let scene_pages = ScenePages::new(self);
+        let mut counter = StatisticsCounter {
+            scene_pages: &scene_pages,
+            stats: ScriptStatistics {
+                page_count: scene_pages.pages.len(),
+                ..ScriptStatistics::default()
+            },
+            characters: HashMap::new(),
+            element_ids: normalized_element_ids(self).into_iter(),
+            previous_day_night: None,
+            act: None,
+        };
+        for element in &self.elements {
+            counter.count_element(element);
+        }
+        counter.finish()
+    }
+}
+
+struct StatisticsCounter<'a> {
+    scene_pages: &'a ScenePages,
+    stats: ScriptStatistics,
+    /// Indexes into `stats.characters` by upper-case name.
+    characters: HashMap<String, usize>,
+    /// The ids `normalize_screenplay` hands out, for the elements not yet
+    /// counted.
+    element_ids: std::vec::IntoIter<(usize, &'a Element, String)>,
+    previous_day_night: Option<DayNight>,
+    act: Option<ActStatistics>,
+}
+
+impl StatisticsCounter<'_> {
+    fn count_element(&mut self, element: &Element) {
+        match element {
+            Element::DialogueBlock(block) => self.count_speech(block, ElementType::Dialogue),
+            Element::DualDialogueBlock(blocks) => {
+                for (side, block) in blocks.iter().enumerate() {
+                    let dialogue_type = if side == 0 {
+                        ElementType::DualDialogueLeft
+                    } else {
+                        ElementType::DualDialogueRight
+                    };
+                    match block {
+                        Element::DialogueBlock(block) => self.count_speech(block, dialogue_type),
+                        other => self.count_element(other),
+                    }
+                }
+            }
+            Element::Boneyard(_) | Element::PageBreak => {}
+            _ => {
+                self.element_ids.next();
+                match element {
+                    Element::SceneHeading(text, attributes) => {
+                        self.count_scene(text.plain_text(), attributes.scene_number.clone())
+                    }
+                    Element::Action(text, attributes) => {
+                        self.stats.action_lines += self.printed_lines(
+                            &text.plain_text(),
+                            ElementType::Action,
+                            &attributes.layout_overrides,
+                        );
+                    }
+                    Element::ColdOpening(text, _) | Element::NewAct(text, _) => {
+                        self.close_act();
+                        self.act = Some(ActStatistics {
+                            title: text.plain_text(),
+                            scene_count: 0,
+                            eighths: 0,
+                            speeches: 0,
+                            words: 0,
+                        });
+                    }
+                    Element::EndOfAct(_, _) => self.close_act(),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    fn count_scene(&mut self, heading: String, scene_number: Option<String>) {
+        let index = self.stats.scenes.len();
+        let pages = &self.scene_pages;
+        let (page, eighths) = pages
+            .scenes
+            .get(index)
+            .map(|scene| {
+                (
+                    pages.pages[scene.first_page].clone(),
+                    pages.eighths(scene).0,
+                )
+            })
+            .unwrap_or_default();
+
+        let parts = crate::SceneHeadingParts::parse(&heading);
+        match parts.setting {
+            Some(SceneSetting::Interior) => self.stats.settings.interior += 1,
+            Some(SceneSetting::Exterior) => self.stats.settings.exterior += 1,
+            Some(SceneSetting::InteriorExterior) => self.stats.settings.interior_exterior += 1,
+            Some(SceneSetting::Establishing) | None => self.stats.settings.other += 1,
+        }
+        let day_night = match parts.day_night() {
+            None if parts.continues_previous_time() => self.previous_day_night,
+            day_night => day_night,
+        };
+        match day_night {
+            Some(DayNight::Day) => self.stats.times_of_day.day += 1,
+            Some(DayNight::Night) => self.stats.times_of_day.night += 1,
+            None => self.stats.times_of_day.other += 1,
+        }
+        self.previous_day_night = day_night;
+
+        if let Some(act) = &mut self.act {
+            act.scene_count += 1;
+            act.eighths += eighths;
+        }
+        self.stats.scenes.push(SceneStatistics {
+            index: index + 1,
+            scene_number,
+            heading,
+            page,
+            eighths,
+            characters: Vec::new(),
+        });
+    }
+
+    fn count_speech(&mut self, block: &[Element], dialogue_type: ElementType) {
+        let mut speaker = None;
+        let mut words = 0;
+        let mut lines = 0;
+        for element in block {
+            let element_id = self
+                .element_ids
+                .next()
+                .map(|(_, _, id)| id)
+                .unwrap_or_default();
+            match element {
+                Element::Character(text, _) if speaker.is_none() => {
+                    let name = CharacterCue::parse(&text.plain_text()).name;
+                    let page = self
+                        .scene_pages
+                        .element_page(&element_id)
+                        .map(|page| self.scene_pages.pages[page].clone());
+                    speaker = Some((name, page));
+                }
+                Element::Dialogue(text, attributes) | Element::Lyric(text, attributes) => {
+                    let text = text.plain_text();
+                    words += text.split_whitespace().count();
+                    let element_type = match element {
+                        Element::Lyric(..) => ElementType::Lyric,
+                        _ => dialogue_type,
+                    };
+                    lines += self.printed_lines(&text, element_type, &attributes.layout_overrides);
+                }
+                _ => {}
+            }
+        }
+        self.stats.dialogue_lines += lines;
+        if let Some(act) = &mut self.act {
+            act.speeches += 1;
+            act.words += words;
+        }
+        let Some((name, page)) = speaker else {
+            return;
+        };
+
+        let appearance = Appearance {
+            scene: self.stats.scenes.last().map(|scene| scene.index),
+            page,
+        };
+        let key = name.to_uppercase();
+        let index = *self.characters.entry(key).or_insert_with(|| {
+            self.stats.characters.push(CharacterStatistics {
+                name: name.clone(),
+                speeches: 0,
+                words: 0,
+                dialogue_lines: 0,
+                first_appearance: appearance.clone(),
+                last_appearance: appearance.clone(),
+            });
+            self.stats.characters.len() - 1
+        });
+        let character = &mut self.stats.characters[index];
+        character.speeches += 1;
+        character.words += words;
+        character.dialogue_lines += lines;
+        character.last_appearance = appearance;
+
+        if let Some(scene) = self.stats.scenes.last_mut()
+            && !scene
+                .characters
+                .iter()
+                .any(|speaker| speaker.eq_ignore_ascii_case(&character.name))
+        {
+            scene.characters.push(character.name.clone());
+        }
+    }
+
+    fn printed_lines(
+        &self,
+        text: &str,
+        element_type: ElementType,
+        overrides: &crate::ElementLayoutOverrides,
+    ) -> usize {
+        let config = &self.scene_pages.config;
+        let wrap = wrap_config_with_overrides(
+            &config.geometry,
+            element_type,
+            overrides,
+            config.interruption_dash_wrap,
+        );
+        wrap_text_for_element(text, &wrap).len()
+    }
+
+    fn close_act(&mut self) {
+        if let Some(act) = self.act.take() {
+            self.stats.acts.push(act);
+        }
+    }
+
+    fn finish(mut self) -> ScriptStatistics {
+        self.close_act();
+        self.stats.scene_count = self.stats.scenes.len();
+        self.stats.action_to_dialogue = (self.stats.dialogue_lines > 0)
+            .then(|| self.stats.action_lines as f64 / self.stats.dialogue_lines as f64);
+        self.stats
+    }
+}
+
+fn format_appearance(appearance: &Appearance) -> String {
+    let page = appearance.page.as_deref().unwrap_or("?");
+    match appearance.scene {
+        Some(scene) => format!("p. {page}, scene {scene}"),
+        None => format!("p. {page}"),
+    }
+}
+
+fn percentage(count: usize, total: usize) -> String {
+    if total == 0 {
+        return "-".to_string();
+    }
+    format!("{}%", (count as f64 * 100.0 / total as f64).round())
+}
+
+/// Writes a titled table with left-aligned columns, leaving out trailing
+/// spaces.
+pub(crate) fn write_table(out: &mut String, title: &str, headers: &[&str], rows: Vec<Vec<String>>) {
+    let mut widths: Vec<usize> = headers
+        .iter()
+        .map(|header| header.chars().count())
+        .collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    writeln!(out, "\n{title}").unwrap();
+    let headers = headers.iter().map(|header| header.to_string()).collect();
+    for row in std::iter::once(headers).chain(rows) {
+        let mut line = String::new();
+        for (cell, width) in row.iter().zip(&widths) {
+            write!(line, "  {cell:<width$}").unwrap();
+        }
+        writeln!(out, "{}", line.trim_end()).unwrap();
+    }
+}
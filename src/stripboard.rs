@@ -0,0 +1,311 @@
+use serde::Serialize;
+
+use crate::breakdown::{day_night_label, write_csv_row};
+use crate::pagination::Eighths;
+use crate::{DayNight, SceneSetting, Screenplay};
+
+/// A strip for every scene and the cast they call for, as built by
+/// [`Screenplay::stripboard`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct Stripboard {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Every character in the screenplay, numbered in the order they first
+    /// appear.
+    pub cast: Vec<CastMember>,
+    pub strips: Vec<Strip>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct CastMember {
+    /// The character's cast ID, counting from 1.
+    pub id: u32,
+    pub name: String,
+}
+
+/// One scene on the stripboard.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Strip {
+    /// The scene's position in the screenplay, counting from 1.
+    pub index: usize,
+    pub scene_number: Option<String>,
+    pub heading: String,
+    pub setting: Option<SceneSetting>,
+    pub day_night: Option<DayNight>,
+    /// The heading's locations, joined by ` - `.
+    pub location: String,
+    pub time_of_day: Option<String>,
+    pub eighths: Eighths,
+    /// The printed number of the page the scene starts on.
+    pub first_page: String,
+    /// The cast IDs of the scene's characters, in ascending order.
+    pub cast_ids: Vec<u32>,
+    pub color: StripColor,
+}
+
+/// The color of a strip, following the usual scheduling convention: white
+/// for interior day, yellow for exterior day, blue for interior night and
+/// green for exterior night. Scenes that are partly or wholly outside count
+/// as exterior, and scenes without a known setting or time are gray.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+pub enum StripColor {
+    White,
+    Yellow,
+    Blue,
+    Green,
+    Gray,
+}
+
+impl StripColor {
+    pub fn for_scene(setting: Option<SceneSetting>, day_night: Option<DayNight>) -> StripColor {
+        let exterior = match setting {
+            Some(SceneSetting::Interior) => false,
+            Some(
+                SceneSetting::Exterior
+                | SceneSetting::InteriorExterior
+                | SceneSetting::Establishing,
+            ) => true,
+            None => return StripColor::Gray,
+        };
+        match (exterior, day_night) {
+            (false, Some(DayNight::Day)) => StripColor::White,
+            (true, Some(DayNight::Day)) => StripColor::Yellow,
+            (false, Some(DayNight::Night)) => StripColor::Blue,
+            (true, Some(DayNight::Night)) => StripColor::Green,
+            (_, None) => StripColor::Gray,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            StripColor::White => "White",
+            StripColor::Yellow => "Yellow",
+            StripColor::Blue => "Blue",
+            StripColor::Green => "Green",
+            StripColor::Gray => "Gray",
+        }
+    }
+
+    #[cfg(feature = "html")]
+    fn css(self) -> &'static str {
+        match self {
+            StripColor::White => "#ffffff",
+            StripColor::Yellow => "#fff59d",
+            StripColor::Blue => "#90caf9",
+            StripColor::Green => "#a5d6a7",
+            StripColor::Gray => "#e0e0e0",
+        }
+    }
+}
+
+impl Stripboard {
+    /// The cast member with the given ID.
+    pub fn cast_member(&self, id: u32) -> Option<&CastMember> {
+        self.cast.iter().find(|member| member.id == id)
+    }
+
+    /// The strips as CSV, one row per scene, for importing into a
+    /// scheduling tool. Cast IDs and names are separated by `; `.
+    pub fn to_csv(&self) -> String {
+        let headers = [
+            "Scene",
+            "Int/Ext",
+            "Day/Night",
+            "Location",
+            "Time of Day",
+            "Pages",
+            "Eighths",
+            "Script Page",
+            "Cast IDs",
+            "Cast",
+            "Color",
+            "Heading",
+        ];
+        let mut out = String::new();
+        write_csv_row(&mut out, headers.into_iter().map(str::to_string));
+        for strip in &self.strips {
+            write_csv_row(
+                &mut out,
+                [
+                    strip.scene_label(),
+                    setting_label(strip.setting).to_string(),
+                    day_night_label(strip.day_night).to_string(),
+                    strip.location.clone(),
+                    strip.time_of_day.clone().unwrap_or_default(),
+                    strip.eighths.to_string(),
+                    strip.eighths.0.to_string(),
+                    strip.first_page.clone(),
+                    strip.cast_id_list("; "),
+                    self.cast_names(strip).join("; "),
+                    strip.color.label().to_string(),
+                    strip.heading.clone(),
+                ],
+            );
+        }
+        out
+    }
+
+    /// The strips as a standalone HTML page, colored by setting and time
+    /// of day, with the cast IDs listed below them.
+    #[cfg(feature = "html")]
+    pub fn to_html(&self) -> String {
+        use crate::rendering::shared::escape_html;
+        use std::fmt::Write;
+
+        let title = match &self.title {
+            Some(title) => format!("{title} Stripboard"),
+            None => "Stripboard".to_string(),
+        };
+        let mut out = String::new();
+        out.push_str("<!doctype html>\n\n<html>\n<head>\n  <meta charset=\"utf-8\">\n");
+        writeln!(out, "  <title>{}</title>", escape_html(&title)).unwrap();
+        out.push_str(STRIPBOARD_CSS);
+        out.push_str("</head>\n\n<body>\n");
+        writeln!(out, "  <h1>{}</h1>", escape_html(&title)).unwrap();
+        out.push_str("  <table class=\"strips\">\n    <thead>\n      <tr>");
+        for header in [
+            "Scene",
+            "Int/Ext",
+            "Location",
+            "Day/Night",
+            "Pages",
+            "Page",
+            "Cast",
+        ] {
+            write!(out, "<th>{header}</th>").unwrap();
+        }
+        out.push_str("</tr>\n    </thead>\n    <tbody>\n");
+        for strip in &self.strips {
+            writeln!(
+                out,
+                "      <tr class=\"strip {}\" style=\"background-color: {}\" title=\"{}\">\
+                 <td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                strip.color.label().to_lowercase(),
+                strip.color.css(),
+                escape_html(&strip.heading),
+                escape_html(&strip.scene_label()),
+                setting_label(strip.setting),
+                escape_html(&strip.location),
+                day_night_label(strip.day_night),
+                strip.eighths,
+                escape_html(&strip.first_page),
+                strip.cast_id_list(", "),
+            )
+            .unwrap();
+        }
+        out.push_str("    </tbody>\n  </table>\n\n  <h2>Cast</h2>\n  <ol class=\"cast\">\n");
+        for member in &self.cast {
+            writeln!(
+                out,
+                "    <li value=\"{}\">{}</li>",
+                member.id,
+                escape_html(&member.name)
+            )
+            .unwrap();
+        }
+        out.push_str("  </ol>\n</body>\n</html>\n");
+        out
+    }
+
+    fn cast_names(&self, strip: &Strip) -> Vec<String> {
+        strip
+            .cast_ids
+            .iter()
+            .filter_map(|id| self.cast_member(*id))
+            .map(|member| member.name.clone())
+            .collect()
+    }
+}
+
+impl Strip {
+    fn cast_id_list(&self, separator: &str) -> String {
+        self.cast_ids
+            .iter()
+            .map(u32::to_string)
+            .collect::<Vec<_>>()
+            .join(separator)
+    }
+
+    /// The scene number, or the scene's position for unnumbered scenes.
+    fn scene_label(&self) -> String {
+        self.scene_number
+            .clone()
+            .unwrap_or_else(|| self.index.to_string())
+    }
+}
+
+impl Screenplay {
+    /// Builds a stripboard with a strip for each scene, from the same
+    /// scenes, lengths and cast as [`Screenplay::breakdown`]. Characters get
+    /// cast IDs in the order they first appear, so the IDs only change when
+    /// a new character is added ahead of them.
+    pub fn stripboard(&self) -> Stripboard {
+        let breakdown = self.breakdown();
+        let mut stripboard = Stripboard {
+            title: breakdown.title,
+            cast: Vec::new(),
+            strips: Vec::new(),
+        };
+        for sheet in breakdown.sheets {
+            let mut cast_ids = sheet
+                .cast
+                .iter()
+                .map(|name| cast_id(&mut stripboard.cast, name))
+                .collect::<Vec<_>>();
+            cast_ids.sort_unstable();
+            cast_ids.dedup();
+            let parts = sheet.heading_parts;
+            stripboard.strips.push(Strip {
+                index: sheet.index,
+                scene_number: sheet.scene_number,
+                heading: sheet.heading,
+                setting: parts.setting,
+                day_night: sheet.day_night,
+                location: parts.locations.join(" - "),
+                time_of_day: parts.time_of_day,
+                eighths: sheet.eighths,
+                first_page: sheet.first_page,
+                cast_ids,
+                color: StripColor::for_scene(parts.setting, sheet.day_night),
+            });
+        }
+        stripboard
+    }
+}
+
+/// The cast ID of `name`, numbering it next if it is new.
+fn cast_id(cast: &mut Vec<CastMember>, name: &str) -> u32 {
+    if let Some(member) = cast
+        .iter()
+        .find(|member| member.name.eq_ignore_ascii_case(name))
+    {
+        return member.id;
+    }
+    let id = cast.len() as u32 + 1;
+    cast.push(CastMember {
+        id,
+        name: name.to_string(),
+    });
+    id
+}
+
+fn setting_label(setting: Option<SceneSetting>) -> &'static str {
+    match setting {
+        Some(SceneSetting::Interior) => "INT",
+        Some(SceneSetting::Exterior) => "EXT",
+        Some(SceneSetting::InteriorExterior) => "INT/EXT",
+        Some(SceneSetting::Establishing) => "EST",
+        None => "",
+    }
+}
+
+#[cfg(feature = "html")]
+const STRIPBOARD_CSS: &str = "  <style type=\"text/css\">
+   body{font-family:Helvetica,Arial,sans-serif;font-size:10pt;margin:1em}
+   table.strips{border-collapse:collapse;width:100%}
+   table.strips th{text-align:left;border-bottom:2px solid #000;padding:.25em .5em}
+   table.strips td{border:1px solid #999;padding:.25em .5em;white-space:nowrap}
+   table.strips tr.strip td:nth-child(3){white-space:normal;width:100%}
+   @media print{table.strips tr.strip{-webkit-print-color-adjust:exact;print-color-adjust:exact}}
+  </style>
+";
@@ -0,0 +1,443 @@
+use std::ops::Range;
+
+use crate::parser::parse_with_source_map;
+use crate::rename::{cased_like, cue_name, cue_name_range, name_ranges};
+use crate::scene_numbers::{OMITTED, SceneEntry, number_scene_entries, scene_entry};
+use crate::source_map::ElementSpan;
+use crate::{Element, RenameOptions, SceneNumbering, Screenplay};
+
+/// A lossless view of a Fountain document.
+///
+/// Every byte of the input belongs to exactly one top-level node, so writing
+/// the nodes back out reproduces the input unchanged. Edits are recorded as
+/// replacements against the original text and only touch the bytes they
+/// name, which keeps the writer's spacing, forcing markers, boneyard and notes
+/// intact everywhere else.
+#[derive(Clone, Debug)]
+pub struct SyntaxTree {
+    source: String,
+    screenplay: Screenplay,
+    nodes: Vec<SyntaxNode>,
+    edits: Vec<TextEdit>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SyntaxKind {
+    /// Blank lines, page breaks and anything else between elements.
+    Trivia,
+    Metadata,
+    Action,
+    Character,
+    SceneHeading,
+    Lyric,
+    Parenthetical,
+    Dialogue,
+    DialogueBlock,
+    DualDialogueBlock,
+    Transition,
+    Section,
+    Synopsis,
+    Boneyard,
+    ColdOpening,
+    NewAct,
+    EndOfAct,
+}
+
+/// A node covering `range` of the original text. The children of block nodes
+/// cover their parent's range end to end, with trivia between elements.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SyntaxNode {
+    pub kind: SyntaxKind,
+    pub range: Range<usize>,
+    pub children: Vec<SyntaxNode>,
+}
+
+impl SyntaxNode {
+    pub fn text<'a>(&self, source: &'a str) -> &'a str {
+        &source[self.range.clone()]
+    }
+
+    fn collect_kind<'a>(&'a self, kind: SyntaxKind, found: &mut Vec<&'a SyntaxNode>) {
+        if self.kind == kind {
+            found.push(self);
+        }
+        for child in &self.children {
+            child.collect_kind(kind, found);
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TextEdit {
+    pub range: Range<usize>,
+    pub replacement: String,
+}
+
+/// Lines of the source changed by pending edits, as listed by
+/// [`SyntaxTree::changed_lines`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LineChange {
+    /// The number of the first changed line, counting from 1.
+    pub line: usize,
+    pub before: String,
+    pub after: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SyntaxEditError(String);
+
+impl std::fmt::Display for SyntaxEditError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::error::Error for SyntaxEditError {}
+
+impl SyntaxTree {
+    pub fn parse(text: &str) -> Self {
+        let (screenplay, source_map) = parse_with_source_map(text);
+        let mut nodes = Vec::with_capacity(source_map.elements.len() * 2 + 1);
+        let mut cursor = 0;
+        if let Some(metadata) = source_map.metadata {
+            push_trivia(&mut nodes, cursor, metadata.start);
+            nodes.push(SyntaxNode {
+                kind: SyntaxKind::Metadata,
+                range: metadata.start..metadata.end,
+                children: Vec::new(),
+            });
+            cursor = metadata.end;
+        }
+        cursor = build_nodes(
+            &mut nodes,
+            &screenplay.elements,
+            &source_map.elements,
+            cursor,
+        );
+        push_trivia(&mut nodes, cursor, text.len());
+
+        SyntaxTree {
+            source: text.to_string(),
+            screenplay,
+            nodes,
+            edits: Vec::new(),
+        }
+    }
+
+    /// The text the tree was parsed from, without pending edits.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// The screenplay parsed from `source`, without pending edits.
+    pub fn screenplay(&self) -> &Screenplay {
+        &self.screenplay
+    }
+
+    pub fn nodes(&self) -> &[SyntaxNode] {
+        &self.nodes
+    }
+
+    pub fn edits(&self) -> &[TextEdit] {
+        &self.edits
+    }
+
+    /// All nodes of `kind` in document order, at any depth.
+    pub fn find(&self, kind: SyntaxKind) -> Vec<&SyntaxNode> {
+        let mut found = Vec::new();
+        for node in &self.nodes {
+            node.collect_kind(kind, &mut found);
+        }
+        found
+    }
+
+    /// Records a replacement of `range` in the original text. Ranges may not
+    /// overlap an edit that is already pending.
+    pub fn replace(
+        &mut self,
+        range: Range<usize>,
+        replacement: impl Into<String>,
+    ) -> Result<(), SyntaxEditError> {
+        if range.start > range.end
+            || !self.source.is_char_boundary(range.start)
+            || !self.source.is_char_boundary(range.end)
+        {
+            return Err(SyntaxEditError(format!(
+                "edit range {}..{} does not fall on character boundaries of the source",
+                range.start, range.end
+            )));
+        }
+        if let Some(conflict) = self.edits.iter().find(|edit| {
+            (edit.range.start < range.end && range.start < edit.range.end)
+                || (edit.range.start == range.start && edit.range.end == range.end)
+        }) {
+            return Err(SyntaxEditError(format!(
+                "edit range {}..{} overlaps pending edit {}..{}",
+                range.start, range.end, conflict.range.start, conflict.range.end
+            )));
+        }
+        let position = self
+            .edits
+            .partition_point(|edit| edit.range.start <= range.start);
+        self.edits.insert(
+            position,
+            TextEdit {
+                range,
+                replacement: replacement.into(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Renames every character cue whose name matches `from`, keeping forcing
+    /// markers, extensions and dual-dialogue carets. Returns the number of cues
+    /// changed.
+    pub fn rename_character(&mut self, from: &str, to: &str) -> Result<usize, SyntaxEditError> {
+        self.rename_character_with_options(from, to, RenameOptions::default())
+    }
+
+    /// Renames a character the way [`Screenplay::rename_character_with_options`]
+    /// does, editing only the names. Returns the number of names changed.
+    pub fn rename_character_with_options(
+        &mut self,
+        from: &str,
+        to: &str,
+        options: RenameOptions,
+    ) -> Result<usize, SyntaxEditError> {
+        let (from, to) = (from.trim(), to.trim());
+        let wanted = from.to_uppercase();
+        let mut renames = self
+            .find(SyntaxKind::Character)
+            .into_iter()
+            .filter_map(|node| {
+                let cue = node.text(&self.source);
+                let name = cue_name_range(cue);
+                let offset = node.range.start;
+                (cue[name.clone()].to_uppercase() == wanted)
+                    .then(|| (offset + name.start..offset + name.end, cue_name(cue, to)))
+            })
+            .collect::<Vec<_>>();
+        if options.in_text {
+            for kind in [
+                SyntaxKind::Action,
+                SyntaxKind::Dialogue,
+                SyntaxKind::Parenthetical,
+            ] {
+                for node in self.find(kind) {
+                    let offset = node.range.start;
+                    for range in name_ranges(node.text(&self.source), from) {
+                        let range = offset + range.start..offset + range.end;
+                        let replacement = cased_like(&self.source[range.clone()], to);
+                        renames.push((range, replacement));
+                    }
+                }
+            }
+        }
+        renames.retain(|(range, replacement)| self.source[range.clone()] != *replacement);
+        for (range, replacement) in &renames {
+            self.replace(range.clone(), replacement.as_str())?;
+        }
+        Ok(renames.len())
+    }
+
+    /// Sets or clears the `#number#` of the scene heading at `scene_index`
+    /// (counting scene headings from zero).
+    pub fn set_scene_number(
+        &mut self,
+        scene_index: usize,
+        number: Option<&str>,
+    ) -> Result<(), SyntaxEditError> {
+        if number.is_some_and(|number| number.contains(['#', '\n'])) {
+            return Err(SyntaxEditError(
+                "scene numbers cannot contain `#` or line breaks".to_string(),
+            ));
+        }
+        let node = self
+            .nodes
+            .iter()
+            .filter(|node| node.kind == SyntaxKind::SceneHeading)
+            .nth(scene_index)
+            .ok_or_else(|| SyntaxEditError(format!("there is no scene heading {scene_index}")))?;
+        let text = node.text(&self.source).trim_end();
+        let text_end = node.range.start + text.len();
+        let replacement = number
+            .map(|number| format!(" #{number}#"))
+            .unwrap_or_default();
+        match existing_scene_number_start(text) {
+            Some(start) => self.replace(node.range.start + start..text_end, replacement),
+            None if number.is_some() => self.replace(text_end..text_end, replacement),
+            None => Ok(()),
+        }
+    }
+
+    /// Numbers the scene headings the way [`Screenplay::number_scenes`]
+    /// does, editing only their `#number#` markers. `OMITTED` placeholders
+    /// are written as forced headings such as `.OMITTED #12#`.
+    pub fn number_scenes(&mut self, numbering: SceneNumbering) -> Result<(), SyntaxEditError> {
+        let entries: Vec<SceneEntry> = self
+            .screenplay
+            .elements
+            .iter()
+            .filter_map(scene_entry)
+            .collect();
+        let plan = number_scene_entries(&entries, numbering);
+        let current: Vec<Option<String>> = entries
+            .iter()
+            .map(|(number, _)| number.map(str::to_string))
+            .collect();
+        let headings: Vec<(usize, Range<usize>)> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| node.kind == SyntaxKind::SceneHeading)
+            .map(|(index, node)| (index, node.range.clone()))
+            .collect();
+        if headings.len() != plan.len() {
+            return Err(SyntaxEditError(
+                "scene headings could not be matched to the source".to_string(),
+            ));
+        }
+
+        for (scene_index, ((index, range), scene)) in headings.into_iter().zip(plan).enumerate() {
+            if !scene.omitted_before.is_empty() {
+                let placeholders: String = scene
+                    .omitted_before
+                    .iter()
+                    .map(|number| format!(".{OMITTED} #{number}#\n\n"))
+                    .collect();
+                self.replace(range.start..range.start, placeholders)?;
+            }
+            match scene.number {
+                Some(number) if current[scene_index].as_ref() != Some(&number) => {
+                    self.set_scene_number(scene_index, Some(&number))?
+                }
+                Some(_) => {}
+                None => {
+                    let end = match self.nodes.get(index + 1) {
+                        Some(next) if next.kind == SyntaxKind::Trivia => next.range.end,
+                        _ => range.end,
+                    };
+                    self.replace(range.start..end, "")?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The lines touched by pending edits, with their text before and after.
+    /// Edits on the same line are listed together.
+    pub fn changed_lines(&self) -> Vec<LineChange> {
+        let line_start = |offset: usize| {
+            self.source[..offset]
+                .rfind('\n')
+                .map_or(0, |index| index + 1)
+        };
+        let line_end = |offset: usize| {
+            self.source[offset..]
+                .find('\n')
+                .map_or(self.source.len(), |index| offset + index)
+        };
+        let mut changes = Vec::new();
+        let mut edits = self.edits.iter().peekable();
+        while let Some(mut edit) = edits.next() {
+            let start = line_start(edit.range.start);
+            let mut end = line_end(edit.range.end);
+            let mut after = String::new();
+            let mut cursor = start;
+            loop {
+                after.push_str(&self.source[cursor..edit.range.start]);
+                after.push_str(&edit.replacement);
+                cursor = edit.range.end;
+                end = end.max(line_end(edit.range.end));
+                match edits.next_if(|next| next.range.start <= end) {
+                    Some(next) => edit = next,
+                    None => break,
+                }
+            }
+            after.push_str(&self.source[cursor..end]);
+            changes.push(LineChange {
+                line: self.source[..start].matches('\n').count() + 1,
+                before: self.source[start..end].to_string(),
+                after,
+            });
+        }
+        changes
+    }
+
+    /// The source with all pending edits applied.
+    pub fn to_fountain(&self) -> String {
+        let mut out = String::with_capacity(self.source.len());
+        let mut cursor = 0;
+        for edit in &self.edits {
+            out.push_str(&self.source[cursor..edit.range.start]);
+            out.push_str(&edit.replacement);
+            cursor = edit.range.end;
+        }
+        out.push_str(&self.source[cursor..]);
+        out
+    }
+}
+
+fn push_trivia(nodes: &mut Vec<SyntaxNode>, start: usize, end: usize) {
+    if start < end {
+        nodes.push(SyntaxNode {
+            kind: SyntaxKind::Trivia,
+            range: start..end,
+            children: Vec::new(),
+        });
+    }
+}
+
+fn build_nodes(
+    nodes: &mut Vec<SyntaxNode>,
+    elements: &[Element],
+    spans: &[ElementSpan],
+    mut cursor: usize,
+) -> usize {
+    for (element, span) in elements.iter().zip(spans) {
+        // Boneyard from inside an element is already covered by that element.
+        if span.span.start < cursor {
+            continue;
+        }
+        push_trivia(nodes, cursor, span.span.start);
+        let mut children = Vec::new();
+        if let Element::DialogueBlock(inner) | Element::DualDialogueBlock(inner) = element {
+            let end = build_nodes(&mut children, inner, &span.children, span.span.start);
+            push_trivia(&mut children, end, span.span.end);
+        }
+        nodes.push(SyntaxNode {
+            kind: syntax_kind(element),
+            range: span.span.start..span.span.end,
+            children,
+        });
+        cursor = span.span.end;
+    }
+    cursor
+}
+
+fn syntax_kind(element: &Element) -> SyntaxKind {
+    match element {
+        Element::Action(_, _) => SyntaxKind::Action,
+        Element::Character(_, _) => SyntaxKind::Character,
+        Element::SceneHeading(_, _) => SyntaxKind::SceneHeading,
+        Element::Lyric(_, _) => SyntaxKind::Lyric,
+        Element::Parenthetical(_, _) => SyntaxKind::Parenthetical,
+        Element::Dialogue(_, _) => SyntaxKind::Dialogue,
+        Element::DialogueBlock(_) => SyntaxKind::DialogueBlock,
+        Element::DualDialogueBlock(_) => SyntaxKind::DualDialogueBlock,
+        Element::Transition(_, _) => SyntaxKind::Transition,
+        Element::Section(_, _, _) => SyntaxKind::Section,
+        Element::Synopsis(_) => SyntaxKind::Synopsis,
+        Element::Boneyard(_) => SyntaxKind::Boneyard,
+        Element::ColdOpening(_, _) => SyntaxKind::ColdOpening,
+        Element::NewAct(_, _) => SyntaxKind::NewAct,
+        Element::EndOfAct(_, _) => SyntaxKind::EndOfAct,
+        Element::PageBreak => SyntaxKind::Trivia,
+    }
+}
+
+fn existing_scene_number_start(heading: &str) -> Option<usize> {
+    let start = heading.rfind(" #")?;
+    let number = &heading[start + 1..];
+    (number.len() >= 2 && number.ends_with('#')).then_some(start)
+}
@@ -29,6 +29,7 @@ impl Element {
             }
             Section(_, _, _) => (),
             Synopsis(_) => (),
+            Boneyard(_) => (),
             PageBreak => (),
         };
     }
@@ -111,6 +112,7 @@ pub(crate) fn parse_plain_text_markup(txt: &mut String) -> ElementText {
                             styled_textruns.push(TextRun {
                                 content: mem::take(&mut current_text),
                                 text_style: current_styles.clone(),
+                                revision: None,
                             });
                         }
                         current_styles.remove("Bold");
@@ -122,6 +124,7 @@ pub(crate) fn parse_plain_text_markup(txt: &mut String) -> ElementText {
                             styled_textruns.push(TextRun {
                                 content: mem::take(&mut current_text),
                                 text_style: current_styles.clone(),
+                                revision: None,
                             });
                         }
                         current_styles.insert("Bold".to_string());
@@ -143,6 +146,7 @@ pub(crate) fn parse_plain_text_markup(txt: &mut String) -> ElementText {
                             styled_textruns.push(TextRun {
                                 content: mem::take(&mut current_text),
                                 text_style: current_styles.clone(),
+                                revision: None,
                             });
                         }
                         current_styles.remove("Italic");
@@ -153,6 +157,7 @@ pub(crate) fn parse_plain_text_markup(txt: &mut String) -> ElementText {
                             styled_textruns.push(TextRun {
                                 content: mem::take(&mut current_text),
                                 text_style: current_styles.clone(),
+                                revision: None,
                             });
                         }
                         current_styles.insert("Italic".to_string());
@@ -173,6 +178,7 @@ pub(crate) fn parse_plain_text_markup(txt: &mut String) -> ElementText {
                             styled_textruns.push(TextRun {
                                 content: mem::take(&mut current_text),
                                 text_style: current_styles.clone(),
+                                revision: None,
                             });
                         }
                         current_styles.remove("Bold");
@@ -183,6 +189,7 @@ pub(crate) fn parse_plain_text_markup(txt: &mut String) -> ElementText {
                             styled_textruns.push(TextRun {
                                 content: mem::take(&mut current_text),
                                 text_style: current_styles.clone(),
+                                revision: None,
                             });
                         }
                         current_styles.insert("Bold".to_string());
@@ -203,6 +210,7 @@ pub(crate) fn parse_plain_text_markup(txt: &mut String) -> ElementText {
                             styled_textruns.push(TextRun {
                                 content: mem::take(&mut current_text),
                                 text_style: current_styles.clone(),
+                                revision: None,
                             });
                         }
                         current_styles.remove("Underline");
@@ -213,6 +221,7 @@ pub(crate) fn parse_plain_text_markup(txt: &mut String) -> ElementText {
                             styled_textruns.push(TextRun {
                                 content: mem::take(&mut current_text),
                                 text_style: current_styles.clone(),
+                                revision: None,
                             });
                         }
                         current_styles.insert("Underline".to_string());
@@ -226,6 +235,7 @@ pub(crate) fn parse_plain_text_markup(txt: &mut String) -> ElementText {
             styled_textruns.push(TextRun {
                 content: current_text,
                 text_style: current_styles.clone(),
+                revision: None,
             });
         }
         Styled(styled_textruns)
@@ -249,6 +259,7 @@ mod tests {
         TextRun {
             content: content.to_string(),
             text_style: style_strings,
+            revision: None,
         }
     }
 
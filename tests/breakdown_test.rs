@@ -0,0 +1,244 @@
+use jumpcut::{BreakdownCategory, BreakdownTag, DayNight, Element, Note, parse, parse_fdx};
+use pretty_assertions::assert_eq;
+
+const SCRIPT: &str = "\
+Title: Heist
+
+INT. BANK - DAY #1#
+
+Bob points a revolver at the teller. [[prop: revolver]] [[extras: bank customers]]
+
+BOB
+Nobody move! [[wardrobe: ski mask]] [[props: Revolver]] [[Sound: too loud?]]
+
+TELLER
+Please don't shoot. [[cast: Guard]] [[TODO: punch this up]]
+
+EXT. STREET - CONTINUOUS #2#
+
+A getaway car screeches up, \"dented\". [[vehicle: sedan, red]] [[sfx: tire smoke]]
+
+ALICE
+Get in!
+";
+
+#[test]
+fn it_reads_breakdown_tags_from_notes() {
+    assert_eq!(
+        BreakdownTag::from_note(" prop:  revolver "),
+        Some(BreakdownTag::new(BreakdownCategory::Props, "revolver"))
+    );
+    assert_eq!(
+        BreakdownTag::from_note("set dressing: a neon sign"),
+        Some(BreakdownTag::new(
+            BreakdownCategory::SetDressing,
+            "a neon sign"
+        ))
+    );
+    assert_eq!(BreakdownTag::from_note("TODO: fix this"), None);
+    assert_eq!(BreakdownTag::from_note("Prop: revolver"), None);
+    assert_eq!(BreakdownTag::from_note("sound: too loud?"), None);
+    assert_eq!(
+        BreakdownTag::from_note("music: cue it here: no, later"),
+        None
+    );
+    assert_eq!(BreakdownTag::from_note("sfx:"), None);
+    assert_eq!(BreakdownTag::from_note("a plain note"), None);
+    for category in BreakdownCategory::ALL {
+        let tag = BreakdownTag::new(category, "thing");
+        assert_eq!(BreakdownTag::from_note(&tag.to_note()), Some(tag));
+    }
+}
+
+#[test]
+fn it_takes_tags_out_of_the_notes() {
+    let screenplay = parse(SCRIPT);
+
+    let Element::DialogueBlock(block) = &screenplay.elements[2] else {
+        panic!("expected Bob's dialogue");
+    };
+    let Element::Dialogue(_, attributes) = &block[1] else {
+        panic!("expected dialogue");
+    };
+    assert_eq!(
+        attributes.tags,
+        vec![
+            BreakdownTag::new(BreakdownCategory::Wardrobe, "ski mask").at(13),
+            BreakdownTag::new(BreakdownCategory::Props, "Revolver").at(14),
+        ]
+    );
+    assert_eq!(
+        attributes.notes,
+        Some(vec![Note {
+            text: "Sound: too loud?".to_string(),
+            offset: 15,
+        }])
+    );
+
+    let Element::DialogueBlock(block) = &screenplay.elements[3] else {
+        panic!("expected the teller's dialogue");
+    };
+    let Element::Dialogue(text, attributes) = &block[1] else {
+        panic!("expected dialogue");
+    };
+    assert_eq!(text.plain_text(), "Please don't shoot.  ");
+    assert_eq!(
+        attributes.tags,
+        vec![BreakdownTag::new(BreakdownCategory::Cast, "Guard").at(20)]
+    );
+    assert_eq!(
+        attributes.notes,
+        Some(vec![Note {
+            text: "TODO: punch this up".to_string(),
+            offset: 21,
+        }])
+    );
+
+    let fountain = screenplay.to_fountain();
+    assert!(fountain.contains("teller. [[prop: revolver]] [[extras: bank customers]]"));
+    assert!(fountain.contains("Please don't shoot. [[cast: Guard]] [[TODO: punch this up]]"));
+    assert_eq!(parse(&fountain).breakdown(), screenplay.breakdown());
+}
+
+#[test]
+fn it_writes_tags_back_on_every_kind_of_element() {
+    for text in [
+        "INT. HOUSE [[prop: gun]] - DAY",
+        "BOB [[wardrobe: hat]]\n(lighting up [[prop: cigar]])\nHi.",
+        "BOB\nHi.\n~La la [[music: lullaby]]",
+        "Bob waits.\n\nCUT TO: [[sfx: thunder]]",
+    ] {
+        assert_eq!(parse(text).to_fountain(), text);
+    }
+
+    let Element::SceneHeading(_, attributes) = &parse("INT. HOUSE [[prop: gun]] - DAY").elements[0]
+    else {
+        panic!("expected a scene heading");
+    };
+    assert_eq!(
+        attributes.tags,
+        vec![BreakdownTag::new(BreakdownCategory::Props, "gun").at(11)]
+    );
+}
+
+#[test]
+fn it_builds_a_sheet_for_each_scene() {
+    let breakdown = parse(SCRIPT).breakdown();
+
+    assert_eq!(breakdown.title.as_deref(), Some("Heist"));
+    assert_eq!(breakdown.sheets.len(), 2);
+    let bank = &breakdown.sheets[0];
+    assert_eq!(bank.scene_number.as_deref(), Some("1"));
+    assert_eq!(bank.heading_parts.locations, ["BANK"]);
+    assert_eq!(bank.eighths.0, 1);
+    assert_eq!(
+        (bank.first_page.as_str(), bank.last_page.as_str()),
+        ("1", "1")
+    );
+    assert_eq!(bank.cast, ["BOB", "TELLER", "GUARD"]);
+    assert_eq!(bank.category(BreakdownCategory::Props), ["revolver"]);
+    assert_eq!(bank.category(BreakdownCategory::Extras), ["bank customers"]);
+    assert_eq!(bank.category(BreakdownCategory::Wardrobe), ["ski mask"]);
+    assert!(bank.category(BreakdownCategory::Vehicles).is_empty());
+
+    let street = &breakdown.sheets[1];
+    assert_eq!(street.day_night, Some(DayNight::Day));
+    assert_eq!(street.cast, ["ALICE"]);
+    assert_eq!(
+        street.category(BreakdownCategory::SpecialEffects),
+        ["tire smoke"]
+    );
+}
+
+#[test]
+fn it_writes_breakdowns_as_csv_and_json() {
+    let breakdown = parse(SCRIPT).breakdown();
+    let csv = breakdown.to_csv();
+    let rows = csv.lines().collect::<Vec<_>>();
+
+    assert_eq!(rows.len(), 3);
+    assert!(rows[0].starts_with(
+        "Index,Scene Number,Heading,Setting,Location,Time of Day,Day/Night,Eighths,Length,First Page,Last Page,Cast,Extras,Stunts,Props,"
+    ));
+    assert!(rows[1].starts_with(
+        "1,1,INT. BANK - DAY,INT.,BANK,DAY,Day,1,1/8,1,1,BOB; TELLER; GUARD,bank customers,,revolver,ski mask,"
+    ));
+    assert!(rows[2].contains(",,\"sedan, red\",,,tire smoke,"));
+
+    let json = serde_json::to_value(&breakdown).unwrap();
+    assert_eq!(json["sheets"][0]["elements"]["Props"][0], "revolver");
+    assert_eq!(json["sheets"][1]["day_night"], "Day");
+}
+
+#[cfg(feature = "pdf")]
+#[test]
+fn it_prints_a_page_for_each_sheet() {
+    let pdf = parse(SCRIPT).breakdown().to_pdf();
+
+    assert!(pdf.starts_with(b"%PDF"));
+    assert_eq!(
+        String::from_utf8_lossy(&pdf)
+            .matches("/Type /Page\n")
+            .count(),
+        2
+    );
+}
+
+#[test]
+fn it_imports_final_draft_tags() {
+    let xml = r##"<?xml version="1.0" encoding="UTF-8" standalone="no" ?>
+<FinalDraft DocumentType="Script" Template="No" Version="5">
+  <Content>
+    <Paragraph Type="Scene Heading">
+      <Text>INT. BARN - NIGHT</Text>
+    </Paragraph>
+    <Paragraph Type="Action">
+      <Text>Ted saddles a </Text>
+      <Text TagNumber="1">horse</Text>
+      <Text> by </Text>
+      <Text TagNumber="2">lantern</Text>
+      <Text> light.</Text>
+    </Paragraph>
+  </Content>
+  <TagData>
+    <TagCategories>
+      <TagCategory Color="#000000000000" Id="{C1}" Name="Animals" Number="7" Style="Bold"/>
+      <TagCategory Color="#000000000000" Id="{C2}" Name="Props" Number="4" Style="Bold"/>
+      <TagCategory Color="#000000000000" Id="{C3}" Name="Greenery" Number="20" Style="Bold"/>
+    </TagCategories>
+    <TagDefinitions>
+      <TagDefinition CatId="{C1}" Id="{D1}" Label="HORSE" Number="1"/>
+      <TagDefinition CatId="{C2}" Id="{D2}" Label="LANTERN" Number="2"/>
+      <TagDefinition CatId="{C3}" Id="{D3}" Label="HAY BALES" Number="3"/>
+    </TagDefinitions>
+    <Tags>
+      <Tag Number="1">
+        <DefId>{D1}</DefId>
+      </Tag>
+      <Tag Number="2">
+        <DefId>{D2}</DefId>
+        <DefId>{D3}</DefId>
+      </Tag>
+    </Tags>
+  </TagData>
+</FinalDraft>"##;
+
+    let screenplay = parse_fdx(xml).expect("fdx should parse");
+
+    let Element::Action(text, attributes) = &screenplay.elements[1] else {
+        panic!("expected action");
+    };
+    assert_eq!(text.plain_text(), "Ted saddles a horse by lantern light.");
+    assert_eq!(
+        attributes.tags,
+        vec![
+            BreakdownTag::new(BreakdownCategory::Animals, "HORSE").at(19),
+            BreakdownTag::new(BreakdownCategory::Props, "LANTERN").at(30),
+            BreakdownTag::new(BreakdownCategory::Other, "HAY BALES").at(30),
+        ]
+    );
+    assert_eq!(
+        screenplay.breakdown().sheets[0].category(BreakdownCategory::Other),
+        ["HAY BALES"]
+    );
+}
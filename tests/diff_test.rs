@@ -0,0 +1,227 @@
+use jumpcut::diff::{AttributeChange, ChangeKind, ElementChange, MetadataChange, WordChange, diff};
+use jumpcut::{Screenplay, parse};
+use pretty_assertions::assert_eq;
+
+const DRAFT: &str = "Title: Draft\n\nINT. HOUSE - DAY\n\nBob waits by the door.\n\nBOB\nWhere is she?\n\nMARY\nRight here.\n\nEXT. GARDEN - NIGHT\n\nThe lights go out.";
+
+fn changes(old: &str, new: &str) -> Vec<ElementChange> {
+    diff(&parse(old), &parse(new)).elements
+}
+
+#[test]
+fn it_finds_no_changes_between_equal_drafts() {
+    let result = diff(&parse(DRAFT), &parse(DRAFT));
+
+    assert!(result.is_empty());
+    assert_eq!(result.to_report(), "No differences.\n");
+}
+
+#[test]
+fn it_reports_inserted_and_deleted_paragraphs() {
+    let new = DRAFT
+        .replace("Bob waits by the door.\n\n", "")
+        .replace("The lights go out.", "The lights go out.\n\nA dog barks.");
+
+    assert_eq!(
+        changes(DRAFT, &new),
+        vec![
+            ElementChange {
+                kind: ChangeKind::Deleted,
+                element_type: "Action".to_string(),
+                old_path: Some(vec![1]),
+                new_path: None,
+                old_text: Some("Bob waits by the door.".to_string()),
+                new_text: None,
+                scene: Some("INT. HOUSE - DAY".to_string()),
+                words: Vec::new(),
+                attributes: Vec::new(),
+            },
+            ElementChange {
+                kind: ChangeKind::Inserted,
+                element_type: "Action".to_string(),
+                old_path: None,
+                new_path: Some(vec![5]),
+                old_text: None,
+                new_text: Some("A dog barks.".to_string()),
+                scene: Some("EXT. GARDEN - NIGHT".to_string()),
+                words: Vec::new(),
+                attributes: Vec::new(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn it_reports_word_changes_in_modified_paragraphs() {
+    let new = DRAFT.replace("Bob waits by the door.", "Bob waits by the window.");
+    let changes = changes(DRAFT, &new);
+
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].kind, ChangeKind::Modified);
+    assert_eq!(changes[0].old_path, Some(vec![1]));
+    assert_eq!(
+        changes[0].words,
+        vec![
+            WordChange::Equal("Bob waits by the ".to_string()),
+            WordChange::Deleted("door.".to_string()),
+            WordChange::Inserted("window.".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn it_reports_changed_scene_numbers_and_centering() {
+    let new = DRAFT
+        .replace("INT. HOUSE - DAY", "INT. HOUSE - DAY #4A#")
+        .replace("The lights go out.", "> The lights go out. <");
+    let changes = changes(DRAFT, &new);
+
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes[0].kind, ChangeKind::Modified);
+    assert!(changes[0].words.is_empty());
+    assert_eq!(
+        changes[0].attributes,
+        vec![AttributeChange {
+            name: "scene_number".to_string(),
+            old: None,
+            new: Some("4A".to_string()),
+        }]
+    );
+    assert_eq!(
+        changes[1].attributes,
+        vec![AttributeChange {
+            name: "centered".to_string(),
+            old: None,
+            new: Some("true".to_string()),
+        }]
+    );
+    assert_eq!(
+        diff(&parse(DRAFT), &parse(&new)).to_report(),
+        "Elements\n  ~ Scene Heading #1 (INT. HOUSE - DAY)\n      INT. HOUSE - DAY\n      scene_number: none -> \"4A\"\n  ~ Action #6 (EXT. GARDEN - NIGHT)\n      The lights go out.\n      centered: none -> \"true\"\n"
+    );
+}
+
+#[test]
+fn it_reports_changed_dialogue_inside_its_block() {
+    let new = DRAFT.replace("Where is she?", "Where is Mary?");
+    let changes = changes(DRAFT, &new);
+
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].kind, ChangeKind::Modified);
+    assert_eq!(changes[0].element_type, "Dialogue");
+    assert_eq!(changes[0].old_path, Some(vec![2, 1]));
+    assert_eq!(changes[0].new_path, Some(vec![2, 1]));
+}
+
+#[test]
+fn it_reports_lines_added_to_a_dialogue_block() {
+    let new = DRAFT.replace("BOB\nWhere is she?", "BOB\n(quietly)\nWhere is she?");
+    let changes = changes(DRAFT, &new);
+
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].kind, ChangeKind::Inserted);
+    assert_eq!(changes[0].element_type, "Parenthetical");
+    assert_eq!(changes[0].new_path, Some(vec![2, 1]));
+}
+
+#[test]
+fn it_reports_a_whole_new_dialogue_block_once() {
+    let new = DRAFT.replace("MARY\nRight here.", "MARY\nRight here.\n\nBOB\nOh.");
+    let changes = changes(DRAFT, &new);
+
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].kind, ChangeKind::Inserted);
+    assert_eq!(changes[0].element_type, "Dialogue Block");
+    assert_eq!(changes[0].new_text, Some("BOB\nOh.".to_string()));
+}
+
+#[test]
+fn it_reports_moved_paragraphs() {
+    let new = "Title: Draft\n\nEXT. GARDEN - NIGHT\n\nThe lights go out.\n\nINT. HOUSE - DAY\n\nBob waits by the door.\n\nBOB\nWhere is she?\n\nMARY\nRight here.";
+    let changes = changes(DRAFT, new);
+
+    assert_eq!(changes.len(), 2);
+    assert!(
+        changes
+            .iter()
+            .all(|change| change.kind == ChangeKind::Moved)
+    );
+    assert_eq!(changes[0].new_text, Some("EXT. GARDEN - NIGHT".to_string()));
+    assert_eq!(changes[0].old_path, Some(vec![4]));
+    assert_eq!(changes[0].new_path, Some(vec![0]));
+}
+
+#[test]
+fn it_matches_paragraphs_by_id_when_both_drafts_have_ids() {
+    let mut old = parse(DRAFT);
+    old.assign_element_ids();
+    let mut new = old.clone();
+    new.elements[1] = parse("Completely different words here.").elements[0].clone();
+    let id = old.elements[1].attributes().unwrap().id.clone();
+    new.elements[1].attributes_mut().unwrap().id = id;
+
+    let changes = diff(&old, &new).elements;
+
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].kind, ChangeKind::Modified);
+}
+
+#[test]
+fn it_reports_changed_metadata_keys() {
+    let new = DRAFT.replace("Title: Draft", "Title: Final\nDraft date: June 1");
+
+    assert_eq!(
+        diff(&parse(DRAFT), &parse(&new)).metadata,
+        vec![
+            MetadataChange {
+                key: "draft date".to_string(),
+                old: None,
+                new: Some(vec!["June 1".to_string()]),
+            },
+            MetadataChange {
+                key: "title".to_string(),
+                old: Some(vec!["Draft".to_string()]),
+                new: Some(vec!["Final".to_string()]),
+            },
+        ]
+    );
+}
+
+#[test]
+fn it_writes_a_readable_report() {
+    let new = DRAFT
+        .replace("Title: Draft", "Title: Final")
+        .replace("Where is she?", "Where is Mary?")
+        .replace("\n\nThe lights go out.", "");
+
+    assert_eq!(
+        diff(&parse(DRAFT), &parse(&new)).to_report(),
+        "Metadata\n  ~ title: \"Draft\" -> \"Final\"\n\nElements\n  ~ Dialogue #3.2 (INT. HOUSE - DAY)\n      Where is [-she?-]{+Mary?+}\n  - Action #6 (EXT. GARDEN - NIGHT)\n      The lights go out.\n"
+    );
+}
+
+#[test]
+fn it_writes_json() {
+    let new = DRAFT.replace("Right here.", "Right here, Bob.");
+    let json = diff(&parse(DRAFT), &parse(&new)).to_json_value();
+
+    assert_eq!(json["metadata"], serde_json::json!([]));
+    assert_eq!(json["elements"][0]["kind"], "Modified");
+    assert_eq!(json["elements"][0]["old_path"], serde_json::json!([3, 1]));
+    assert_eq!(
+        json["elements"][0]["words"][1],
+        serde_json::json!({"op": "Deleted", "text": "here."})
+    );
+}
+
+#[test]
+fn it_compares_screenplays_read_back_from_json() {
+    let old = parse(DRAFT);
+    let mut new = Screenplay::from_json_value(old.clone().to_json_value()).unwrap();
+    new.elements.remove(1);
+
+    let changes = diff(&old, &new).elements;
+
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].kind, ChangeKind::Deleted);
+}
@@ -0,0 +1,187 @@
+use jumpcut::{Element, Screenplay, parse, parse_fdx};
+use pretty_assertions::assert_eq;
+use std::collections::HashSet;
+
+const SCRIPT: &str =
+    "INT. HOUSE - DAY\n\nBob waits.\n\nBOB\nYes.\n\nMARY\nNo.\n\nBOB\nYes.\n\nMARY\nFine.";
+
+fn ids(screenplay: &Screenplay) -> Vec<String> {
+    fn collect(elements: &[Element], out: &mut Vec<String>) {
+        for element in elements {
+            match element {
+                Element::DialogueBlock(block) | Element::DualDialogueBlock(block) => {
+                    collect(block, out)
+                }
+                _ => out.extend(element.id().map(str::to_string)),
+            }
+        }
+    }
+    let mut out = Vec::new();
+    collect(&screenplay.elements, &mut out);
+    out
+}
+
+fn with_ids(text: &str) -> Screenplay {
+    let mut screenplay = parse(text);
+    screenplay.assign_element_ids();
+    screenplay
+}
+
+#[test]
+fn it_leaves_ids_unset_until_assigned() {
+    assert!(ids(&parse(SCRIPT)).is_empty());
+}
+
+#[test]
+fn it_gives_every_element_a_unique_id() {
+    let assigned = ids(&with_ids(SCRIPT));
+
+    assert_eq!(assigned.len(), 10);
+    assert_eq!(assigned.iter().collect::<HashSet<_>>().len(), 10);
+}
+
+#[test]
+fn it_derives_the_same_ids_on_every_parse() {
+    assert_eq!(ids(&with_ids(SCRIPT)), ids(&with_ids(SCRIPT)));
+}
+
+#[test]
+fn it_keeps_ids_when_a_paragraph_is_inserted_above() {
+    let before = ids(&with_ids(SCRIPT));
+    let after = ids(&with_ids(
+        &SCRIPT.replace("Bob waits.", "The kettle boils.\n\nBob waits."),
+    ));
+
+    assert_eq!(after.len(), before.len() + 1);
+    assert_eq!(after[0], before[0]);
+    assert_eq!(after[2..], before[1..]);
+}
+
+#[test]
+fn it_ignores_case_and_spacing_changes() {
+    let before = with_ids(SCRIPT);
+    let after = with_ids(&SCRIPT.replace("Bob waits.", "Bob   WAITS."));
+
+    assert_eq!(ids(&after), ids(&before));
+}
+
+#[test]
+fn it_ignores_punctuation_changes() {
+    let before = with_ids(SCRIPT);
+    let after = with_ids(&SCRIPT.replace("MARY\nNo.", "MARY\nNo!"));
+
+    assert_eq!(ids(&after), ids(&before));
+}
+
+#[test]
+fn it_keeps_ids_when_a_repeated_paragraph_is_added_in_another_scene() {
+    let before = ids(&with_ids(SCRIPT));
+    let after = ids(&with_ids(&format!(
+        "{SCRIPT}\n\nEXT. YARD - NIGHT\n\nBob waits."
+    )));
+
+    assert_eq!(after[..10], before[..]);
+    assert_eq!(after.len(), 12);
+    assert!(!before.contains(&after[11]));
+}
+
+#[test]
+fn it_only_changes_the_neighbouring_repeated_cue_when_a_paragraph_is_inserted() {
+    let previous = with_ids(SCRIPT);
+    let before = ids(&previous);
+    let edited = SCRIPT.replace("No.\n\nBOB", "No.\n\nBob sighs.\n\nBOB");
+
+    let after = ids(&with_ids(&edited));
+    assert_eq!(after.len(), 11);
+    assert_eq!(after[..6], before[..6]);
+    assert_ne!(after[7], before[6]);
+    assert_eq!(after[8..], before[7..]);
+
+    let mut carried = parse(&edited);
+    carried.carry_element_ids(&previous);
+    carried.assign_element_ids();
+    let carried = ids(&carried);
+    assert_eq!(carried[..6], before[..6]);
+    assert_eq!(carried[7..], before[6..]);
+}
+
+#[test]
+fn it_keeps_ids_already_set_when_the_text_changes() {
+    let json = with_ids(SCRIPT).to_json_value();
+    assert!(json["elements"][1]["attributes"]["id"].is_string());
+
+    let mut edited = Screenplay::from_json_value(json).unwrap();
+    edited.elements[1] = Element::Action(
+        "Bob paces.".into(),
+        edited.elements[1].attributes().unwrap().clone(),
+    );
+    let id = edited.elements[1].id().map(str::to_string);
+    edited.assign_element_ids();
+
+    assert_eq!(edited.elements[1].id().map(str::to_string), id);
+    assert_eq!(ids(&edited), ids(&with_ids(SCRIPT)));
+}
+
+#[test]
+fn it_writes_json_without_assigning_ids() {
+    let screenplay = parse(SCRIPT);
+    let json = screenplay.to_json_value();
+
+    assert!(json["elements"][1]["attributes"].get("id").is_none());
+    assert!(ids(&screenplay).is_empty());
+}
+
+#[test]
+fn it_carries_ids_from_an_earlier_draft_through_edits() {
+    let previous = with_ids(SCRIPT);
+    let mut edited = parse(
+        &SCRIPT
+            .replace("Bob waits.", "Bob paces by the window.")
+            .replace("MARY\nFine.", "MARY\nFine.\n\nThe kettle boils."),
+    );
+    edited.carry_element_ids(&previous);
+    edited.assign_element_ids();
+
+    let carried = ids(&edited);
+    assert_eq!(carried[..10], ids(&previous)[..]);
+    assert_eq!(carried.len(), 11);
+    assert!(!ids(&previous).contains(&carried[10]));
+}
+
+#[test]
+fn it_replaces_repeated_ids() {
+    let mut screenplay = with_ids(SCRIPT);
+    let first_id = screenplay.elements[0].attributes().unwrap().id.clone();
+    screenplay.elements[1].attributes_mut().unwrap().id = first_id;
+    screenplay.assign_element_ids();
+
+    let assigned = ids(&screenplay);
+    assert_eq!(
+        assigned.iter().collect::<HashSet<_>>().len(),
+        assigned.len()
+    );
+    assert_eq!(assigned[0], ids(&with_ids(SCRIPT))[0]);
+}
+
+#[test]
+fn it_writes_ids_to_fdx_and_reads_them_back() {
+    let mut screenplay = parse(SCRIPT);
+    let fdx = screenplay.to_final_draft();
+    let first_id = screenplay.elements[0].id().unwrap();
+
+    assert!(fdx.contains(&format!(
+        "<Paragraph Type=\"Scene Heading\" JumpCutID=\"{first_id}\">"
+    )));
+    assert_eq!(ids(&parse_fdx(&fdx).unwrap()), ids(&screenplay));
+}
+
+#[test]
+fn it_keeps_fdx_ids_through_edits_in_final_draft() {
+    let mut screenplay = parse(SCRIPT);
+    let fdx = screenplay.to_final_draft();
+
+    let mut imported = parse_fdx(&fdx.replace("Bob waits.", "Bob paces.")).unwrap();
+    imported.assign_element_ids();
+
+    assert_eq!(ids(&imported), ids(&screenplay));
+}
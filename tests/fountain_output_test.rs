@@ -22,6 +22,8 @@ fn fountain_output_round_trips_metadata_and_core_body_elements() {
         metadata,
         imported_layout: None,
         imported_title_page: None,
+        revisions: Vec::new(),
+        page_lock: None,
         elements: vec![
             Element::SceneHeading(
                 p("INT. HOUSE - DAY"),
@@ -62,6 +64,8 @@ fn fountain_output_forces_ambiguous_elements_to_preserve_type() {
         metadata: Metadata::new(),
         imported_layout: None,
         imported_title_page: None,
+        revisions: Vec::new(),
+        page_lock: None,
         elements: vec![
             Element::SceneHeading(p("inside the school bus"), blank_attributes()),
             Element::Action(p("INT. HOUSE - DAY"), blank_attributes()),
@@ -88,6 +92,8 @@ fn fountain_output_round_trips_dual_dialogue_page_breaks_and_centered_markers()
         metadata: Metadata::new(),
         imported_layout: None,
         imported_title_page: None,
+        revisions: Vec::new(),
+        page_lock: None,
         elements: vec![
             Element::Action(
                 p("THE END"),
@@ -198,6 +204,8 @@ fn fountain_output_drops_legacy_frontmatter_metadata_key() {
         metadata,
         imported_layout: None,
         imported_title_page: None,
+        revisions: Vec::new(),
+        page_lock: None,
         elements: vec![Element::Action(p("Body."), blank_attributes())],
     };
 
@@ -0,0 +1,51 @@
+use jumpcut::{Screenplay, parse, parse_fdx};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn it_reads_back_the_json_it_writes() {
+    let text = "Title: Round Trip\nAuthor: Someone\n\n# Act One\n\n= Setup.\n\n.INT. HOUSE - DAY #1#\n\nBob **waits** [[too slow?]] for *Mary*.\n\n/* cut */\n\nBOB (V.O.)\n(beat)\nHello.\n~La la la\n\nMARY ^\nHi.\n\n===\n\n> FADE OUT.\n\n>THE END<";
+    let mut screenplay = parse(text);
+    screenplay.assign_element_ids();
+
+    assert_eq!(
+        Screenplay::from_json_str(&screenplay.clone().to_json_string()).unwrap(),
+        screenplay
+    );
+    assert_eq!(
+        Screenplay::from_json_value(screenplay.clone().to_json_value()).unwrap(),
+        screenplay
+    );
+}
+
+#[test]
+fn it_reads_back_imported_fdx_layout_and_title_pages() {
+    for fixture in [
+        "tests/fixtures/fdx-import/paragraph-layout-overrides.fdx",
+        "tests/fixtures/fdx-import/title-pages-multi.fdx",
+    ] {
+        let xml = std::fs::read_to_string(fixture).unwrap();
+        let mut screenplay = parse_fdx(&xml).unwrap();
+        screenplay.assign_element_ids();
+
+        assert_eq!(
+            Screenplay::from_json_str(&screenplay.clone().to_json_string()).unwrap(),
+            screenplay,
+            "{fixture}"
+        );
+    }
+}
+
+#[test]
+fn it_renders_screenplays_read_from_json() {
+    let mut screenplay = parse("INT. HOUSE - DAY\n\nBOB\nHello.");
+    let mut restored = Screenplay::from_json_str(&screenplay.clone().to_json_string()).unwrap();
+
+    assert_eq!(restored.to_final_draft(), screenplay.to_final_draft());
+}
+
+#[test]
+fn it_rejects_unknown_element_types() {
+    let json = r#"{"metadata":{},"imported_layout":null,"imported_title_page":null,"elements":[{"type":"Shot","text":{"Plain":"ANGLE"}}]}"#;
+
+    assert!(Screenplay::from_json_str(json).is_err());
+}
@@ -0,0 +1,315 @@
+use jumpcut::{
+    Attributes, BreakdownCategory, BreakdownTag, Element, ElementLayoutOverrides, ElementText,
+    LockedPage, Note, PageLock, RevisionSet, SCHEMA_VERSION, Screenplay, TextRun, json_schema, p,
+    parse, parse_fdx,
+};
+use pretty_assertions::assert_eq;
+use serde_json::{Value, json};
+use std::collections::{BTreeSet, HashSet};
+
+const PUBLISHED_SCHEMA: &str = "docs/screenplay.schema.json";
+
+/// Checks `value` against the subset of JSON Schema that `json_schema()` uses.
+fn validate(value: &Value, schema: &Value, root: &Value, path: &str) -> Result<(), String> {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        let name = reference.trim_start_matches("#/$defs/");
+        return validate(value, &root["$defs"][name], root, path);
+    }
+    if let Some(options) = schema.get("oneOf").and_then(Value::as_array) {
+        let matches = options
+            .iter()
+            .filter(|option| validate(value, option, root, path).is_ok())
+            .count();
+        if matches != 1 {
+            return Err(format!("{path}: matched {matches} of oneOf for {value}"));
+        }
+    }
+    if let Some(expected) = schema.get("const")
+        && value != expected
+    {
+        return Err(format!("{path}: expected {expected}, got {value}"));
+    }
+    if let Some(options) = schema.get("enum").and_then(Value::as_array)
+        && !options.contains(value)
+    {
+        return Err(format!("{path}: {value} is not one of {options:?}"));
+    }
+    if let Some(types) = schema.get("type") {
+        let types: Vec<&str> = match types {
+            Value::Array(types) => types.iter().filter_map(Value::as_str).collect(),
+            other => vec![other.as_str().unwrap()],
+        };
+        let matches = types.iter().any(|kind| match *kind {
+            "null" => value.is_null(),
+            "boolean" => value.is_boolean(),
+            "string" => value.is_string(),
+            "number" => value.is_number(),
+            "integer" => value.is_u64() || value.is_i64(),
+            "array" => value.is_array(),
+            "object" => value.is_object(),
+            other => panic!("unsupported type {other}"),
+        });
+        if !matches {
+            return Err(format!("{path}: {value} is not {types:?}"));
+        }
+    }
+    if let Some(object) = value.as_object() {
+        for key in schema
+            .get("required")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            if !object.contains_key(key.as_str().unwrap()) {
+                return Err(format!("{path}: missing {key}"));
+            }
+        }
+        for (key, item) in object {
+            let item_path = format!("{path}.{key}");
+            if let Some(names) = schema.get("propertyNames") {
+                validate(&json!(key), names, root, &item_path)?;
+            }
+            match schema
+                .get("properties")
+                .and_then(|properties| properties.get(key))
+            {
+                Some(property) => validate(item, property, root, &item_path)?,
+                None => match schema.get("additionalProperties") {
+                    Some(Value::Bool(false)) => {
+                        return Err(format!("{path}: unexpected property {key}"));
+                    }
+                    Some(additional) if additional.is_object() => {
+                        validate(item, additional, root, &item_path)?
+                    }
+                    _ => {}
+                },
+            }
+        }
+    }
+    if let (Some(items), Some(array)) = (schema.get("items"), value.as_array()) {
+        for (index, item) in array.iter().enumerate() {
+            validate(item, items, root, &format!("{path}[{index}]"))?;
+        }
+    }
+    Ok(())
+}
+
+fn assert_valid(screenplay: &Screenplay) {
+    let schema = json_schema();
+    if let Err(error) = validate(&screenplay.clone().to_json_value(), &schema, &schema, "$") {
+        panic!("{error}");
+    }
+}
+
+#[test]
+fn it_tags_json_with_the_schema_version() {
+    let json = parse("INT. HOUSE - DAY").to_json_value();
+
+    assert_eq!(json["schema_version"], json!(SCHEMA_VERSION));
+    assert_eq!(json["imported_layout"], Value::Null);
+    assert_eq!(json["imported_title_page"], Value::Null);
+}
+
+#[test]
+fn it_writes_json_that_matches_the_schema() {
+    assert_valid(&parse(
+        "Title: Schema\nfmt: bsh\n\n# Act One\n\n= Setup.\n\nINT. HOUSE - DAY #1#\n\nBob **waits** [[too slow?]] for _Mary_. [[.lift]]\n\n/* cut */\n\nBOB (V.O.)\n(beat)\nHello.\n~La la la\n\nMARY ^\nHi.\n\n===\n\n> FADE OUT.\n\n>THE END<\n\nEND OF ACT ONE",
+    ));
+
+    for fixture in [
+        "tests/fixtures/fdx-import/paragraph-layout-overrides.fdx",
+        "tests/fixtures/fdx-import/settings-normalized.fdx",
+        "tests/fixtures/fdx-import/title-pages-multi.fdx",
+    ] {
+        let xml = std::fs::read_to_string(fixture).unwrap();
+        assert_valid(&parse_fdx(&xml).unwrap());
+    }
+}
+
+/// Attributes with every field set. Built without `..Default::default()` so
+/// that a new field doesn't compile until it is added here.
+fn every_attribute() -> Attributes {
+    Attributes {
+        centered: true,
+        starts_new_page: true,
+        scene_number: Some("1".to_string()),
+        notes: Some(vec![Note {
+            text: "note".to_string(),
+            offset: 0,
+        }]),
+        layout_overrides: ElementLayoutOverrides {
+            space_before_delta: Some(-1.0),
+            right_indent_delta: Some(0.5),
+        },
+        id: Some("action-1".to_string()),
+        revision: Some(1),
+        tags: vec![BreakdownTag {
+            category: BreakdownCategory::Props,
+            name: "revolver".to_string(),
+            offset: 4,
+        }],
+        boneyard: vec![Note {
+            text: " cut ".to_string(),
+            offset: 0,
+        }],
+    }
+}
+
+/// One of each element, with fully set attributes and styled text.
+fn every_element() -> Vec<Element> {
+    let text = ElementText::Styled(vec![TextRun {
+        content: "Bob waits.".to_string(),
+        text_style: HashSet::from(["Bold".to_string()]),
+        revision: Some(1),
+    }]);
+    let attributes = every_attribute();
+    let dialogue_block = Element::DialogueBlock(vec![
+        Element::Character(p("BOB"), attributes.clone()),
+        Element::Parenthetical(p("(beat)"), attributes.clone()),
+        Element::Dialogue(text.clone(), attributes.clone()),
+        Element::Lyric(p("La la la"), attributes.clone()),
+    ]);
+    let elements = vec![
+        Element::Action(text.clone(), attributes.clone()),
+        Element::SceneHeading(p("INT. HOUSE - DAY"), attributes.clone()),
+        dialogue_block.clone(),
+        Element::DualDialogueBlock(vec![dialogue_block.clone(), dialogue_block]),
+        Element::Transition(p("CUT TO:"), attributes.clone()),
+        Element::Section(p("Act One"), attributes.clone(), 1),
+        Element::Synopsis(p("Setup.")),
+        Element::Boneyard(p(" cut ")),
+        Element::ColdOpening(p("COLD OPEN"), attributes.clone()),
+        Element::NewAct(p("ACT ONE"), attributes.clone()),
+        Element::EndOfAct(p("END OF ACT ONE"), attributes),
+        Element::PageBreak,
+    ];
+
+    // Fails to compile when a variant is added, as a reminder to add it above.
+    let covered = elements
+        .iter()
+        .map(|element| match element {
+            Element::Action(..) => "Action",
+            Element::Character(..) => unreachable!("only inside dialogue blocks"),
+            Element::SceneHeading(..) => "SceneHeading",
+            Element::Lyric(..) => unreachable!("only inside dialogue blocks"),
+            Element::Parenthetical(..) => unreachable!("only inside dialogue blocks"),
+            Element::Dialogue(..) => unreachable!("only inside dialogue blocks"),
+            Element::DialogueBlock(..) => "DialogueBlock",
+            Element::DualDialogueBlock(..) => "DualDialogueBlock",
+            Element::Transition(..) => "Transition",
+            Element::Section(..) => "Section",
+            Element::Synopsis(..) => "Synopsis",
+            Element::Boneyard(..) => "Boneyard",
+            Element::ColdOpening(..) => "ColdOpening",
+            Element::NewAct(..) => "NewAct",
+            Element::EndOfAct(..) => "EndOfAct",
+            Element::PageBreak => "PageBreak",
+        })
+        .collect::<HashSet<_>>();
+    assert_eq!(covered.len(), elements.len());
+    elements
+}
+
+fn property_names(schema: &Value) -> BTreeSet<String> {
+    schema["properties"]
+        .as_object()
+        .unwrap()
+        .keys()
+        .cloned()
+        .collect()
+}
+
+#[test]
+fn it_describes_every_element_and_attribute_in_the_schema() {
+    let screenplay = Screenplay {
+        metadata: Default::default(),
+        imported_layout: None,
+        imported_title_page: None,
+        revisions: vec![RevisionSet {
+            id: 1,
+            name: "Blue".to_string(),
+            color: "Blue".to_string(),
+            date: Some("2026-01-01".to_string()),
+        }],
+        page_lock: Some(PageLock {
+            pages: vec![LockedPage {
+                label: "1".to_string(),
+                elements: vec!["action-1".to_string()],
+                continued: true,
+            }],
+        }),
+        elements: every_element(),
+    };
+    assert_valid(&screenplay);
+
+    let schema = json_schema();
+    let json = screenplay.to_json_value();
+    let written = |value: &Value| {
+        value
+            .as_object()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect::<BTreeSet<_>>()
+    };
+    assert_eq!(written(&json), property_names(&schema));
+    assert_eq!(
+        written(&json["elements"][0]["attributes"]),
+        property_names(&schema["$defs"]["Attributes"])
+    );
+    assert_eq!(
+        written(&json["elements"][0]["text"]["Styled"][0]),
+        property_names(&schema["$defs"]["TextRun"])
+    );
+    assert_eq!(
+        written(&json["revisions"][0]),
+        property_names(&schema["$defs"]["RevisionSet"])
+    );
+    assert_eq!(
+        written(&json["page_lock"]["pages"][0]),
+        property_names(&schema["$defs"]["LockedPage"])
+    );
+}
+
+#[test]
+fn it_reads_json_from_older_schema_versions() {
+    let mut json = parse("INT. HOUSE - DAY\n\nBob waits.").to_json_value();
+    json["schema_version"] = json!(1);
+    for element in json["elements"].as_array_mut().unwrap() {
+        element["attributes"].as_object_mut().unwrap().remove("id");
+    }
+
+    let screenplay = Screenplay::from_json_value(json).unwrap();
+    assert_eq!(screenplay, parse("INT. HOUSE - DAY\n\nBob waits."));
+}
+
+#[test]
+fn it_rejects_json_from_a_newer_schema_version() {
+    let mut json = parse("Bob waits.").to_json_value();
+    json["schema_version"] = json!(SCHEMA_VERSION + 1);
+
+    let error = Screenplay::from_json_value(json.clone()).unwrap_err();
+    assert!(error.to_string().contains("unsupported schema_version"));
+
+    json.as_object_mut().unwrap().remove("schema_version");
+    assert_eq!(
+        Screenplay::from_json_value(json).unwrap(),
+        parse("Bob waits.")
+    );
+}
+
+/// Run with `UPDATE_JSON_SCHEMA=1` to rewrite the published schema after
+/// changing the format.
+#[test]
+fn it_keeps_the_published_schema_up_to_date() {
+    let schema = serde_json::to_string_pretty(&json_schema()).unwrap() + "\n";
+    if std::env::var_os("UPDATE_JSON_SCHEMA").is_some() {
+        std::fs::write(PUBLISHED_SCHEMA, &schema).unwrap();
+    }
+
+    assert_eq!(
+        std::fs::read_to_string(PUBLISHED_SCHEMA).unwrap_or_default(),
+        schema,
+        "{PUBLISHED_SCHEMA} is out of date; rerun with UPDATE_JSON_SCHEMA=1"
+    );
+}
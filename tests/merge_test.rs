@@ -0,0 +1,195 @@
+use jumpcut::merge::{
+    MergeLabels, merge, merge_fountain, merge_fountain_with_labels, merge_with_labels,
+};
+use jumpcut::parse;
+use pretty_assertions::assert_eq;
+
+const BASE: &str = "Title: Draft\n\nINT. HOUSE - DAY\n\nBob waits by the door.\n\nThe kettle boils.\n\nBOB\nWhere is she?\n(beat)\nWhere is Mary?\n\nMARY\nRight here.";
+
+#[test]
+fn it_merges_changes_to_different_paragraphs_of_a_scene() {
+    let ours = BASE.replace("Bob waits by the door.", "Bob waits by the window.");
+    let theirs = BASE.replace("The kettle boils.", "The kettle whistles.");
+
+    let result = merge_fountain(BASE, &ours, &theirs);
+
+    assert!(result.is_clean());
+    assert_eq!(
+        parse(&result.fountain),
+        parse(&ours.replace("The kettle boils.", "The kettle whistles."))
+    );
+}
+
+#[test]
+fn it_merges_changes_to_different_lines_of_a_dialogue_block() {
+    let ours = BASE.replace("Where is she?", "Where did she go?");
+    let theirs = BASE.replace("(beat)", "(louder)");
+
+    let result = merge_fountain(BASE, &ours, &theirs);
+
+    assert!(result.is_clean());
+    assert_eq!(
+        parse(&result.fountain),
+        parse(&ours.replace("(beat)", "(louder)"))
+    );
+}
+
+#[test]
+fn it_merges_insertions_and_deletions_in_different_places() {
+    let ours = BASE.replace("The kettle boils.\n\n", "");
+    let theirs = format!("{BASE}\n\nEXT. GARDEN - NIGHT");
+
+    let result = merge_fountain(BASE, &ours, &theirs);
+
+    assert!(result.is_clean());
+    assert_eq!(
+        parse(&result.fountain),
+        parse(&format!("{ours}\n\nEXT. GARDEN - NIGHT"))
+    );
+}
+
+#[test]
+fn it_takes_the_same_change_from_both_sides_once() {
+    let changed = BASE.replace("Right here.", "Over here.");
+
+    let result = merge_fountain(BASE, &changed, &changed);
+
+    assert!(result.is_clean());
+    assert_eq!(parse(&result.fountain), parse(&changed));
+}
+
+#[test]
+fn it_marks_a_paragraph_both_sides_changed_as_a_conflict() {
+    let ours = BASE.replace("Bob waits by the door.", "Bob waits by the window.");
+    let theirs = BASE.replace("Bob waits by the door.", "Bob paces by the door.");
+
+    let result = merge_fountain(BASE, &ours, &theirs);
+
+    assert_eq!(result.conflicts, 1);
+    assert_eq!(
+        result.fountain,
+        "Title: Draft\n\nINT. HOUSE - DAY\n\n<<<<<<< ours\nBob waits by the window.\n=======\nBob paces by the door.\n>>>>>>> theirs\n\nThe kettle boils.\n\nBOB\nWhere is she?\n(beat)\nWhere is Mary?\n\nMARY\nRight here.\n"
+    );
+}
+
+#[test]
+fn it_marks_a_paragraph_changed_on_one_side_and_deleted_on_the_other() {
+    let ours = BASE.replace("The kettle boils.\n\n", "");
+    let theirs = BASE.replace("The kettle boils.", "The kettle whistles.");
+
+    let result = merge_fountain(BASE, &ours, &theirs);
+
+    assert_eq!(result.conflicts, 1);
+    assert!(
+        result
+            .fountain
+            .contains("<<<<<<< ours\n=======\nThe kettle whistles.\n>>>>>>> theirs")
+    );
+}
+
+#[test]
+fn it_marks_different_insertions_in_the_same_place_as_a_conflict() {
+    let ours = format!("{BASE}\n\nBob sits.");
+    let theirs = format!("{BASE}\n\nMary leaves.");
+
+    let result = merge_fountain(BASE, &ours, &theirs);
+
+    assert_eq!(result.conflicts, 1);
+    assert!(
+        result
+            .fountain
+            .ends_with("<<<<<<< ours\nBob sits.\n=======\nMary leaves.\n>>>>>>> theirs\n")
+    );
+}
+
+#[test]
+fn it_merges_metadata_key_by_key() {
+    let ours = BASE.replace("Title: Draft", "Title: Final");
+    let theirs = BASE.replace("Title: Draft", "Title: Draft\nAuthor: Someone");
+
+    let result = merge_fountain(BASE, &ours, &theirs);
+
+    assert!(result.is_clean());
+    assert!(
+        result
+            .fountain
+            .starts_with("Title: Final\nAuthor: Someone\n\n")
+    );
+}
+
+#[test]
+fn it_puts_conflicting_title_pages_in_a_conflict_section() {
+    let ours = BASE.replace("Title: Draft", "Title: Final");
+    let theirs = BASE.replace("Title: Draft", "Title: Last");
+
+    let result = merge_fountain(BASE, &ours, &theirs);
+
+    assert_eq!(result.conflicts, 1);
+    assert!(
+        result
+            .fountain
+            .starts_with("<<<<<<< ours\nTitle: Final\n=======\nTitle: Last\n>>>>>>> theirs\n\n")
+    );
+}
+
+#[test]
+fn it_labels_conflicts_with_the_given_names() {
+    let ours = BASE.replace("Right here.", "Over here.");
+    let theirs = BASE.replace("Right here.", "Down here.");
+    let labels = MergeLabels {
+        ours: "HEAD".to_string(),
+        theirs: "rewrite".to_string(),
+    };
+
+    let result = merge_with_labels(&parse(BASE), &parse(&ours), &parse(&theirs), &labels);
+
+    assert_eq!(result.conflicts, 1);
+    assert!(result.fountain.contains("<<<<<<< HEAD\n"));
+    assert!(result.fountain.contains("\n>>>>>>> rewrite"));
+}
+
+#[test]
+fn it_keeps_paragraphs_as_they_were_written() {
+    let base = "Title:   Draft\n\n!FADE IN:\n\nINT. HOUSE - DAY\n\nBob waits by the door.\n\n===\n\n!SMASH CUT TO:\n\n.MONTAGE";
+    let ours = base.replace("Bob waits by the door.", "Bob waits by the window.");
+    let theirs = base.replace(".MONTAGE", ".MONTAGE\n\n!SMASH CUT TO:");
+
+    let result = merge_fountain(base, &ours, &theirs);
+
+    assert!(result.is_clean());
+    assert_eq!(
+        result.fountain,
+        format!(
+            "{}\n",
+            ours.replace(".MONTAGE", ".MONTAGE\n\n!SMASH CUT TO:")
+        )
+    );
+}
+
+#[test]
+fn it_copies_conflicting_paragraphs_as_they_were_written() {
+    let base = "INT. HOUSE - DAY\n\n!Bob waits.";
+    let labels = MergeLabels::default();
+
+    let result = merge_fountain_with_labels(
+        base,
+        "INT. HOUSE - DAY\n\n!Bob paces.",
+        "INT. HOUSE - DAY\n\n!Bob sits.",
+        &labels,
+    );
+
+    assert_eq!(
+        result.fountain,
+        "INT. HOUSE - DAY\n\n<<<<<<< ours\n!Bob paces.\n=======\n!Bob sits.\n>>>>>>> theirs\n"
+    );
+}
+
+#[test]
+fn it_merges_screenplays_as_well_as_fountain_text() {
+    let ours = BASE.replace("Bob waits by the door.", "Bob waits by the window.");
+
+    assert_eq!(
+        merge(&parse(BASE), &parse(&ours), &parse(BASE)),
+        merge_fountain(BASE, &ours, BASE)
+    );
+}
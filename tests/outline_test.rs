@@ -0,0 +1,110 @@
+use jumpcut::{OutlineNode, OutlineNodeKind, parse};
+use pretty_assertions::assert_eq;
+
+fn summary(nodes: &[OutlineNode]) -> Vec<(OutlineNodeKind, String, usize, Vec<String>)> {
+    nodes
+        .iter()
+        .map(|node| {
+            (
+                node.kind,
+                node.text.clone(),
+                node.element_index,
+                node.children
+                    .iter()
+                    .map(|child| child.text.clone())
+                    .collect(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn it_nests_scenes_synopses_and_notes_under_sections() {
+    let screenplay = parse(
+        "# Act One\n\n## Setup\n\nINT. HOUSE - DAY #1#\n\n= Bob wakes up.\n\nBob yawns. [[too slow?]]\n\nBOB\nMorning. [[louder]]\n\nEXT. YARD - DAY\n\nBob digs.\n\n# Act Two\n\nINT. SHED - NIGHT",
+    );
+    let outline = screenplay.outline();
+
+    assert_eq!(outline.nodes.len(), 2);
+    let act_one = &outline.nodes[0];
+    assert_eq!(act_one.kind, OutlineNodeKind::Section);
+    assert_eq!(act_one.level, Some(1));
+    assert_eq!(
+        summary(&act_one.children),
+        vec![(
+            OutlineNodeKind::Section,
+            "Setup".to_string(),
+            1,
+            vec![
+                "INT. HOUSE - DAY".to_string(),
+                "EXT. YARD - DAY".to_string()
+            ],
+        )]
+    );
+    let house = &act_one.children[0].children[0];
+    assert_eq!(house.scene_number.as_deref(), Some("1"));
+    assert_eq!(
+        summary(&house.children),
+        vec![
+            (
+                OutlineNodeKind::Synopsis,
+                "Bob wakes up.".to_string(),
+                3,
+                vec![]
+            ),
+            (OutlineNodeKind::Note, "too slow?".to_string(), 4, vec![]),
+            (OutlineNodeKind::Note, "louder".to_string(), 5, vec![]),
+        ]
+    );
+    assert_eq!(
+        summary(&outline.nodes[1..]),
+        vec![(
+            OutlineNodeKind::Section,
+            "Act Two".to_string(),
+            8,
+            vec!["INT. SHED - NIGHT".to_string()],
+        )]
+    );
+    assert!(house.page.is_none());
+}
+
+#[test]
+fn it_keeps_scenes_before_any_section_at_the_top() {
+    let outline = parse("INT. HOUSE - DAY\n\n= Intro.\n\n# Act One\n\nINT. YARD - DAY").outline();
+
+    assert_eq!(
+        summary(&outline.nodes),
+        vec![
+            (
+                OutlineNodeKind::Scene,
+                "INT. HOUSE - DAY".to_string(),
+                0,
+                vec!["Intro.".to_string()],
+            ),
+            (
+                OutlineNodeKind::Section,
+                "Act One".to_string(),
+                2,
+                vec!["INT. YARD - DAY".to_string()],
+            ),
+        ]
+    );
+}
+
+#[test]
+fn it_adds_page_numbers_when_paginated() {
+    let filler = "Bob waits.\n\n".repeat(40);
+    let text = format!(
+        "# Act One\n\nINT. HOUSE - DAY\n\n{filler}# Act Two\n\nINT. YARD - DAY\n\nBob digs."
+    );
+    let outline = parse(&text).outline_with_pages();
+
+    assert_eq!(outline.nodes[0].page, Some(1));
+    assert_eq!(outline.nodes[0].children[0].page, Some(1));
+    assert_eq!(outline.nodes[1].page, Some(2));
+    assert_eq!(outline.nodes[1].children[0].page, Some(2));
+
+    let json = outline.to_json_value();
+    assert_eq!(json["nodes"][1]["kind"], "Section");
+    assert_eq!(json["nodes"][1]["children"][0]["page"], 2);
+}
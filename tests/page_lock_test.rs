@@ -0,0 +1,137 @@
+use jumpcut::pagination::{PageKind, PaginatedScreenplay, PaginationScope};
+use jumpcut::rendering::html::HtmlRenderOptions;
+use jumpcut::rendering::text::TextRenderOptions;
+use jumpcut::{PageLock, Screenplay, parse};
+use pretty_assertions::assert_eq;
+
+fn scene(number: usize) -> String {
+    format!(
+        "INT. ROOM {number} - DAY\n\nThe first thing that happens in room {number}.\n\nThe second thing that happens in room {number}.\n\nThe third thing that happens in room {number}, which goes on for a while longer than the others before it ends."
+    )
+}
+
+fn draft(scenes: impl IntoIterator<Item = String>) -> String {
+    scenes.into_iter().collect::<Vec<_>>().join("\n\n")
+}
+
+fn original() -> String {
+    draft((1..=40).map(scene))
+}
+
+fn locked(current: &str) -> (Screenplay, PageLock) {
+    let lock = parse(&original()).lock_pages();
+    let mut screenplay = parse(current);
+    screenplay.assign_element_ids();
+    screenplay.page_lock = Some(lock.clone());
+    (screenplay, lock)
+}
+
+fn labels(screenplay: &Screenplay) -> Vec<String> {
+    let scope = PaginationScope {
+        first_page_number: None,
+        title_page_count: None,
+        body_start_page: None,
+    };
+    PaginatedScreenplay::from_screenplay("test", screenplay, 55.0, scope)
+        .pages
+        .into_iter()
+        .filter(|page| page.metadata.kind == PageKind::Body)
+        .map(|page| page.metadata.label.unwrap_or_default())
+        .collect()
+}
+
+#[test]
+fn it_locks_an_unchanged_draft_with_its_own_page_numbers() {
+    let (screenplay, lock) = locked(&original());
+    let numbers: Vec<String> = (1..=lock.pages.len()).map(|n| n.to_string()).collect();
+
+    assert!(lock.pages.len() > 3);
+    assert_eq!(
+        lock.pages
+            .iter()
+            .map(|page| page.label.clone())
+            .collect::<Vec<_>>(),
+        numbers
+    );
+    assert_eq!(labels(&screenplay), numbers);
+}
+
+#[test]
+fn it_puts_inserted_material_on_a_pages() {
+    let extra = (1..=12).map(|n| scene(100 + n));
+    let current = draft((1..=5).map(scene).chain(extra).chain((6..=40).map(scene)));
+    let (screenplay, _) = locked(&current);
+
+    assert_eq!(
+        labels(&screenplay),
+        ["1", "1A", "1B", "1C", "2", "3", "4", "5", "6", "7", "8"]
+    );
+}
+
+#[test]
+fn it_folds_cut_pages_into_the_page_before() {
+    let current = draft((1..=5).map(scene).chain((18..=40).map(scene)));
+    let (screenplay, _) = locked(&current);
+
+    assert_eq!(labels(&screenplay), ["1-3", "4", "5", "6", "7", "8"]);
+}
+
+#[test]
+fn it_keeps_the_lock_through_json() {
+    let mut screenplay = parse(&original());
+    let lock = screenplay.lock_pages();
+
+    let read_back =
+        Screenplay::from_json_str(&screenplay.clone().to_json_string()).expect("json should parse");
+
+    assert_eq!(read_back.page_lock, Some(lock.clone()));
+    assert_eq!(
+        PageLock::from_json_str(&lock.to_json_string()).unwrap(),
+        lock
+    );
+    assert!(!parse(&original()).to_json_string().contains("page_lock"));
+}
+
+#[test]
+fn it_prints_locked_labels_in_html_and_text() {
+    let extra = (1..=12).map(|n| scene(100 + n));
+    let current = draft((1..=5).map(scene).chain(extra).chain((6..=40).map(scene)));
+    let (mut screenplay, _) = locked(&current);
+    let label = labels(&screenplay)
+        .into_iter()
+        .find(|label| label.ends_with('A'))
+        .expect("an inserted page");
+
+    let html = screenplay.to_html_with_options(HtmlRenderOptions {
+        exact_wraps: true,
+        paginated: true,
+        ..HtmlRenderOptions::default()
+    });
+    let text = screenplay.to_text(&TextRenderOptions {
+        paginated: true,
+        ..TextRenderOptions::default()
+    });
+
+    assert!(html.contains(&format!("<span class=\"pageNumber\">{label}.</span>")));
+    assert!(text.contains(&format!(" {label}.\n")));
+}
+
+#[cfg(feature = "pdf")]
+#[test]
+fn it_labels_locked_pages_in_pdf() {
+    let extra = (1..=12).map(|n| scene(100 + n));
+    let current = draft((1..=5).map(scene).chain(extra).chain((6..=40).map(scene)));
+    let (screenplay, _) = locked(&current);
+    let label = labels(&screenplay)
+        .into_iter()
+        .find(|label| label.ends_with('A'))
+        .expect("an inserted page");
+
+    let pdf = screenplay.to_pdf();
+
+    let needle = format!("/P ({label})");
+    assert!(
+        pdf.windows(needle.len())
+            .any(|window| window == needle.as_bytes())
+    );
+}
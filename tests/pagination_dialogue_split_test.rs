@@ -298,3 +298,24 @@ fn stub_dialogue_unit() -> DialogueUnit {
         },
     }
 }
+
+#[test]
+fn dialogue_split_keeps_an_authored_contd_before_other_extensions() {
+    let mut draft = String::from("INT. OFFICE - DAY\n\n");
+    for line in 1..=22 {
+        draft.push_str(&format!("Line {line} of action fills space on the page.\n\n"));
+    }
+    draft.push_str("EDWARD (CONT'D) (O.S.)\n");
+    for sentence in 1..=12 {
+        draft.push_str(&format!("Sentence {sentence} of a speech that runs long. "));
+    }
+
+    let text = jumpcut::parse(&draft).to_text(&jumpcut::rendering::text::TextRenderOptions {
+        paginated: true,
+        ..Default::default()
+    });
+
+    assert!(text.contains("(MORE)"), "the speech should split:\n{text}");
+    assert_eq!(text.matches("EDWARD (CONT'D) (O.S.)").count(), 2);
+    assert!(!text.contains("(O.S.) (CONT'D)"));
+}
@@ -184,6 +184,8 @@ fn styled_runs_survive_normalization_and_semantic_building() {
         metadata: Default::default(),
         imported_layout: None,
         imported_title_page: None,
+        revisions: Vec::new(),
+        page_lock: None,
         elements: vec![
             Element::Action(
                 ElementText::Styled(vec![tr("BOLD", vec!["Bold"]), tr(" plain", vec![])]),
@@ -258,6 +260,8 @@ fn centered_flag_survives_normalization_and_semantic_building() {
         metadata: Default::default(),
         imported_layout: None,
         imported_title_page: None,
+        revisions: Vec::new(),
+        page_lock: None,
         elements: vec![Element::Action(
             p("THE END"),
             Attributes {
@@ -283,6 +287,8 @@ fn render_attributes_survive_normalization_and_semantic_building() {
         metadata: Default::default(),
         imported_layout: None,
         imported_title_page: None,
+        revisions: Vec::new(),
+        page_lock: None,
         elements: vec![Element::SceneHeading(
             p("INT. OFFICE - DAY"),
             Attributes {
@@ -537,3 +543,29 @@ impl NormalizedElementExt for NormalizedElement {
         self
     }
 }
+
+#[test]
+fn author_typed_contd_is_not_appended_twice() {
+    let screenplay = parse(
+        "INT. OFFICE - DAY\n\nEDWARD\nFirst line.\n\nA beat.\n\nEDWARD (CONT'D) (O.S.)\nSecond line.",
+    );
+
+    let semantic = build_semantic_screenplay(normalize_screenplay("contd-typed", &screenplay));
+    let dialogue_units = semantic
+        .units
+        .iter()
+        .filter_map(|unit| match unit {
+            SemanticUnit::Dialogue(dialogue) => Some(dialogue),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(dialogue_units.len(), 2);
+    assert!(!dialogue_units[1].should_append_contd);
+    assert!(
+        dialogue_units[1]
+            .parts
+            .iter()
+            .all(|part| !part.should_append_contd)
+    );
+}
@@ -0,0 +1,164 @@
+use jumpcut::rendering::html::HtmlRenderOptions;
+use jumpcut::{
+    Attributes, Element, Note, blank_attributes, p, parse, parse_fdx, parse_with_source_map,
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn it_keeps_boneyard_in_place() {
+    let text = "INT. HOUSE - DAY\n\n/* EXT. YARD - NIGHT\n\nBob digs. */\n\nBob sits. /* maybe stands */\n\n/* the end */";
+    let expected = vec![
+        Element::SceneHeading(p("INT. HOUSE - DAY"), blank_attributes()),
+        Element::Boneyard(p(" EXT. YARD - NIGHT\n\nBob digs. ")),
+        Element::Action(
+            p("Bob sits. "),
+            Attributes {
+                boneyard: vec![Note {
+                    text: " maybe stands ".to_string(),
+                    offset: 10,
+                }],
+                ..Attributes::default()
+            },
+        ),
+        Element::Boneyard(p(" the end ")),
+    ];
+
+    assert_eq!(parse(text).elements, expected);
+}
+
+#[test]
+fn it_keeps_boneyard_inside_dialogue_blocks() {
+    let text = "BOB\n/* (beat) */\nHello.";
+    let screenplay = parse(text);
+
+    let [Element::DialogueBlock(block)] = screenplay.elements.as_slice() else {
+        panic!("expected one dialogue block, got {:?}", screenplay.elements);
+    };
+    assert_eq!(block[0], Element::Character(p("BOB"), blank_attributes()));
+    let Element::Dialogue(dialogue, attributes) = &block[1] else {
+        panic!("expected dialogue, got {:?}", block[1]);
+    };
+    assert_eq!(dialogue.plain_text(), "\nHello.");
+    assert_eq!(
+        attributes.boneyard,
+        [Note {
+            text: " (beat) ".to_string(),
+            offset: 0,
+        }]
+    );
+}
+
+#[test]
+fn it_keeps_inline_boneyard_within_the_element_span() {
+    let text = "INT. HOUSE - DAY /* or night */\n\nBob sits.";
+    let (screenplay, source_map) = parse_with_source_map(text);
+
+    assert_eq!(screenplay.elements.len(), 2);
+    assert_eq!(
+        &text[source_map.elements[0].span.start..source_map.elements[0].span.end],
+        "INT. HOUSE - DAY /* or night */"
+    );
+    assert_eq!(
+        &text[source_map.elements[1].span.start..source_map.elements[1].span.end],
+        "Bob sits."
+    );
+}
+
+#[test]
+fn it_writes_boneyard_back_to_fountain_verbatim() {
+    let text = "INT. HOUSE - DAY\n\n/* EXT. YARD - NIGHT\n\nBob digs. */\n\nBob sits.";
+
+    assert_eq!(parse(text).to_fountain(), text);
+}
+
+#[test]
+fn it_writes_inline_boneyard_back_to_fountain_in_place() {
+    let text = "INT. HOUSE - DAY\n\nBob sits. /* maybe stands */ Then leaves.\n\nBOB\n/* (beat) */\nHello.";
+
+    assert_eq!(parse(text).to_fountain(), text);
+}
+
+#[test]
+fn it_writes_inline_boneyard_back_on_every_kind_of_element() {
+    for text in [
+        "INT. HOUSE /* old */ - DAY",
+        "BOB /* sic */\n(softly /* x */)\nHi.",
+        "BOB\nHi.\n~La la /* la */",
+        "Bob waits.\n\nCUT TO: /* or dissolve */",
+        "> THE END /* really */ <",
+    ] {
+        assert_eq!(parse(text).to_fountain(), text);
+    }
+}
+
+#[test]
+fn it_round_trips_boneyard_through_fdx_as_script_notes() {
+    let text =
+        "/* cold open */\n\nINT. HOUSE - DAY\n\n/* cut\n& gone */\n\nBOB\nHi.\n\n/* the end */";
+    let mut screenplay = parse(text);
+    let fdx = screenplay.to_final_draft();
+
+    assert!(fdx.contains("<ScriptNote ID=\"2\" Range=\"0,0\">"));
+    assert!(fdx.contains("<Text>/* cut</Text>"));
+    assert!(fdx.contains("<Text>&amp; gone */</Text>"));
+    assert!(fdx.contains("<UnanchoredScriptNotes>"));
+
+    let imported = parse_fdx(&fdx).unwrap();
+    let boneyard = imported
+        .elements
+        .iter()
+        .filter(|element| matches!(element, Element::Boneyard(_)))
+        .cloned()
+        .collect::<Vec<_>>();
+    assert_eq!(
+        boneyard,
+        vec![
+            Element::Boneyard(p(" cold open ")),
+            Element::Boneyard(p(" cut\n& gone ")),
+            Element::Boneyard(p(" the end ")),
+        ]
+    );
+    assert!(matches!(imported.elements[0], Element::Boneyard(_)));
+    assert!(matches!(imported.elements[3], Element::DialogueBlock(_)));
+}
+
+#[test]
+fn it_shows_boneyard_in_html_only_in_draft_mode() {
+    let text = "Bob sits. /* Then paces. */\n\n/* Bob stands. */";
+    let options = HtmlRenderOptions {
+        head: false,
+        ..Default::default()
+    };
+    let draft_options = HtmlRenderOptions {
+        draft: true,
+        ..options.clone()
+    };
+
+    let html = parse(text).to_html_with_options(options);
+    assert!(!html.contains("Bob stands."));
+    assert!(!html.contains("Then paces."));
+
+    let html = parse(text).to_html_with_options(draft_options.clone());
+    assert!(html.contains("<p class=\"boneyard\">"));
+    assert!(html.contains("Bob stands."));
+    assert!(html.contains("Bob sits. /* Then paces. */"));
+
+    let paginated = parse(text).to_html_with_options(HtmlRenderOptions {
+        paginated: true,
+        exact_wraps: true,
+        ..draft_options
+    });
+    assert!(paginated.contains("/* Bob stands. */"));
+}
+
+#[test]
+fn it_round_trips_inline_boneyard_through_fdx() {
+    let text = "INT. HOUSE - DAY\n\nBob sits. /* maybe stands */ Then leaves.";
+    let mut screenplay = parse(text);
+    let fdx = screenplay.to_final_draft();
+
+    assert!(fdx.contains("<ScriptNote ID=\"1\" Range=\"10,0\" JumpCutInline=\"Yes\">"));
+    let imported = parse_fdx(&fdx).unwrap();
+    assert_eq!(imported.elements.len(), 2);
+    assert_eq!(imported.elements[1], screenplay.elements[1]);
+}
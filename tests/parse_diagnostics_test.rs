@@ -0,0 +1,136 @@
+use jumpcut::parse_diagnostics::{DiagnosticCode, Severity};
+use jumpcut::{parse, parse_with_diagnostics};
+use pretty_assertions::assert_eq;
+
+fn codes(text: &str) -> Vec<DiagnosticCode> {
+    parse_with_diagnostics(text)
+        .1
+        .into_iter()
+        .map(|diagnostic| diagnostic.code)
+        .collect()
+}
+
+#[test]
+fn it_reports_nothing_for_a_clean_script() {
+    let text = "Title: Example\nfmt: bsh dl-2.5\n\nINT. HOUSE - DAY\n\nBOB\nHello, *friend*.\n\n/* cut */\n\nBob leaves.";
+    let (screenplay, diagnostics) = parse_with_diagnostics(text);
+
+    assert_eq!(screenplay, parse(text));
+    assert_eq!(diagnostics, vec![]);
+}
+
+#[test]
+fn it_reports_unterminated_boneyard() {
+    let text = "INT. HOUSE - DAY\n\n/* never closed\n\nBob waits.";
+    let (_, diagnostics) = parse_with_diagnostics(text);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code, DiagnosticCode::UnterminatedBoneyard);
+    assert_eq!(diagnostics[0].severity, Severity::Warning);
+    assert_eq!(diagnostics[0].span.start_line, 3);
+    assert_eq!(diagnostics[0].span.start_column, 1);
+}
+
+#[test]
+fn it_reports_unclosed_notes() {
+    let text = "Bob waits. [[closed]]\n\nBob leaves. [[open";
+    let (_, diagnostics) = parse_with_diagnostics(text);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code, DiagnosticCode::UnclosedNote);
+    assert_eq!(diagnostics[0].span.start_line, 3);
+    assert_eq!(diagnostics[0].span.start_column, 13);
+}
+
+#[test]
+fn it_reports_unmatched_emphasis_but_not_escapes() {
+    assert_eq!(
+        codes("BOB\nI *really mean it.\n\nAn \\*escaped\\* star."),
+        vec![DiagnosticCode::UnmatchedEmphasis]
+    );
+    assert_eq!(
+        codes("Bob reads _file aloud."),
+        vec![DiagnosticCode::UnmatchedEmphasis]
+    );
+}
+
+#[test]
+fn it_ignores_markers_inside_words() {
+    assert_eq!(codes("Bob reads file_name aloud. 2*3 is six."), vec![]);
+}
+
+#[test]
+fn it_reports_reserved_characters_left_out_of_the_text() {
+    let text = "Bob\u{E000} waits.\n\n/* \u{E001} */\n\nBob leaves.\u{E002}";
+    let (_, diagnostics) = parse_with_diagnostics(text);
+
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].code, DiagnosticCode::ReservedCharacter);
+    assert_eq!(diagnostics[0].span.start_line, 1);
+    assert_eq!(diagnostics[0].span.start_column, 4);
+    assert_eq!(
+        diagnostics[0].message,
+        "character U+E000 is reserved for marking notes and boneyard, so it was left out"
+    );
+    assert_eq!(diagnostics[1].span.start_line, 5);
+}
+
+#[test]
+fn it_recognizes_every_fmt_option_a_renderer_reads() {
+    let text = "fmt: multicam a4 balanced clean-dashes no-dual-contds numbered-scene-continueds ssbsh BSH ush dsd no-auto-act-breaks no-act-underlines dl-2.5 lpp-50 acat cfd allow-lowercase-title\n\nINT. HOUSE - DAY";
+
+    assert_eq!(codes(text), vec![]);
+    assert_eq!(
+        codes("fmt: dl-wide\n\nINT. HOUSE - DAY"),
+        vec![DiagnosticCode::UnknownFmtOption]
+    );
+}
+
+#[test]
+fn it_reports_character_cue_followed_by_blank_line() {
+    let text = "INT. HOUSE - DAY\n\nBOB (V.O.)\n\nI should have been dialogue.";
+    let (_, diagnostics) = parse_with_diagnostics(text);
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].code,
+        DiagnosticCode::CharacterWithoutDialogue
+    );
+    assert_eq!(diagnostics[0].span.start_line, 3);
+    assert_eq!(codes("!BOB\n\nForced action is fine."), vec![]);
+    assert_eq!(codes("BANG!\n\nThe door flies open."), vec![]);
+}
+
+#[test]
+fn it_reports_unknown_metadata_keys_and_fmt_options() {
+    let text = "Title: Example\nDirector: Someone\nfmt: bsh sparkles dl-wide\n\nINT. HOUSE - DAY";
+    let (_, diagnostics) = parse_with_diagnostics(text);
+
+    let summary = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            (
+                diagnostic.code,
+                diagnostic.severity,
+                diagnostic.span.start_line,
+                diagnostic.span.start_column,
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        summary,
+        vec![
+            (DiagnosticCode::UnknownMetadataKey, Severity::Info, 2, 1),
+            (DiagnosticCode::UnknownFmtOption, Severity::Warning, 3, 10),
+            (DiagnosticCode::UnknownFmtOption, Severity::Warning, 3, 19),
+        ]
+    );
+    assert_eq!(
+        diagnostics[1].to_string(),
+        "3:10: warning[unknown-fmt-option]: fmt option `sparkles` is not recognized"
+    );
+    assert_eq!(
+        serde_json::to_value(&diagnostics[1]).unwrap()["code"],
+        "unknown-fmt-option"
+    );
+}
@@ -1,5 +1,6 @@
-use jumpcut::{blank_attributes, p, parse, Element};
+use jumpcut::{CharacterCue, Element, blank_attributes, p, parse};
 use pretty_assertions::assert_eq;
+use serde_json::json;
 
 #[test]
 fn it_handles_basic_dual_dialogue() {
@@ -21,3 +22,47 @@ fn it_handles_basic_dual_dialogue() {
         "it should handle basic dual dialogue"
     );
 }
+
+#[test]
+fn it_parses_character_cues_into_parts() {
+    let screenplay =
+        parse("JOHN (V.O.) (CONT'D)\nHello.\n\n@McCLANE (on radio)\nHi.\n\nHOLLY ^\nHey.");
+    let cue = |name: &str, extensions: &[&str], dual: bool| CharacterCue {
+        name: name.to_string(),
+        extensions: extensions.iter().map(|e| e.to_string()).collect(),
+        dual,
+    };
+
+    assert_eq!(
+        screenplay.elements[0].character_cues(),
+        vec![cue("JOHN", &["V.O.", "CONT'D"], false)]
+    );
+    assert!(screenplay.elements[0].character_cues()[0].is_continued());
+    assert_eq!(
+        screenplay.elements[1].character_cues(),
+        vec![
+            cue("McCLANE", &["on radio"], false),
+            cue("HOLLY", &[], true)
+        ]
+    );
+    assert_eq!(
+        CharacterCue::parse("HOLLY (O.C.) ^"),
+        cue("HOLLY", &["O.C."], true)
+    );
+}
+
+#[test]
+fn it_includes_character_cues_in_json() {
+    let json = parse("JOHN (O.S.)\nHello.\n\nMARY ^\nHi.").to_json_value();
+    let blocks = &json["elements"][0]["blocks"];
+
+    assert_eq!(
+        blocks[0]["block"][0]["cue"],
+        json!({"name": "JOHN", "extensions": ["O.S."], "dual": false})
+    );
+    assert_eq!(
+        blocks[1]["block"][0]["cue"],
+        json!({"name": "MARY", "extensions": [], "dual": true})
+    );
+    assert!(blocks[1]["block"][1].get("cue").is_none());
+}
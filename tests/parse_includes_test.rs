@@ -0,0 +1,160 @@
+use jumpcut::include::{FileSystemResolver, IncludeError, MemoryResolver};
+use jumpcut::{Element, blank_attributes, p, parse, parse_with_includes};
+use pretty_assertions::assert_eq;
+
+fn resolver() -> MemoryResolver {
+    let mut resolver = MemoryResolver::new();
+    resolver.insert(
+        "show/acts/act1.fountain",
+        "INT. HOUSE - DAY\n\nBOB\nHello.\n\n{{include: ../shared/tag.fountain}}\n",
+    );
+    resolver.insert("show/shared/tag.fountain", "CUT TO:");
+    resolver
+}
+
+#[test]
+fn it_replaces_include_lines_with_the_included_files() {
+    let text = "Title: Pilot\n\nCOLD OPEN\n\n{{include: acts/act1.fountain}}\n\nEXT. YARD - NIGHT";
+    let (screenplay, _) = parse_with_includes(text, "show/pilot.fountain", &resolver()).unwrap();
+    let expected = parse(
+        "Title: Pilot\n\nCOLD OPEN\n\nINT. HOUSE - DAY\n\nBOB\nHello.\n\nCUT TO:\n\nEXT. YARD - NIGHT",
+    );
+
+    assert_eq!(screenplay, expected);
+}
+
+#[test]
+fn it_records_which_file_each_element_came_from() {
+    let text = "Bob waits.\n\n{{ include: acts/act1.fountain }}\nEXT. YARD - NIGHT";
+    let (screenplay, source_map) =
+        parse_with_includes(text, "show/pilot.fountain", &resolver()).unwrap();
+
+    assert_eq!(
+        screenplay.elements[1],
+        Element::SceneHeading(p("INT. HOUSE - DAY"), blank_attributes())
+    );
+    let files: Vec<_> = source_map
+        .elements
+        .iter()
+        .map(|span| span.file.as_deref().unwrap())
+        .collect();
+    assert_eq!(
+        files,
+        vec![
+            "show/pilot.fountain",
+            "show/acts/act1.fountain",
+            "show/acts/act1.fountain",
+            "show/shared/tag.fountain",
+            "show/pilot.fountain",
+        ]
+    );
+
+    let dialogue = &source_map.elements[2];
+    assert_eq!(
+        (
+            dialogue.span.start,
+            dialogue.span.end,
+            dialogue.span.start_line
+        ),
+        (18, 28, 3)
+    );
+    assert_eq!(
+        dialogue.children[1].file.as_deref(),
+        Some("show/acts/act1.fountain")
+    );
+    assert_eq!(dialogue.children[1].span.start_line, 4);
+    let last = &source_map.elements[4].span;
+    assert_eq!((last.start, last.start_line), (46, 4));
+}
+
+#[test]
+fn it_reports_include_cycles() {
+    let mut resolver = MemoryResolver::new();
+    resolver.insert("a.fountain", "{{include: b.fountain}}");
+    resolver.insert("b.fountain", "Bob waits.\n\n{{include: ./a.fountain}}");
+
+    assert_eq!(
+        parse_with_includes("{{include: a.fountain}}", "main.fountain", &resolver),
+        Err(IncludeError::Cycle(vec![
+            "main.fountain".to_string(),
+            "a.fountain".to_string(),
+            "b.fountain".to_string(),
+            "a.fountain".to_string(),
+        ]))
+    );
+}
+
+#[test]
+fn it_reports_missing_files() {
+    let error = parse_with_includes("{{include: gone.fountain}}", "main.fountain", &resolver())
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        IncludeError::NotFound { ref path, .. } if path == "gone.fountain"
+    ));
+    assert_eq!(
+        error.to_string(),
+        "Could not include 'gone.fountain' from 'main.fountain': no file named 'gone.fountain'"
+    );
+}
+
+#[test]
+fn it_leaves_include_lines_in_boneyard_alone() {
+    let text = "/*\n{{include: gone.fountain}}\n*/\n\nBob waits.";
+    let (screenplay, _) = parse_with_includes(text, "main.fountain", &resolver()).unwrap();
+
+    assert_eq!(screenplay, parse(text));
+}
+
+#[test]
+fn it_reads_included_files_from_disk() {
+    let directory = std::env::temp_dir().join(format!("jumpcut-includes-{}", std::process::id()));
+    std::fs::create_dir_all(directory.join("acts")).unwrap();
+    std::fs::write(directory.join("acts/act2.fountain"), "EXT. YARD - NIGHT\n").unwrap();
+    let main = directory.join("main.fountain");
+
+    let result = parse_with_includes(
+        "{{include: acts/act2.fountain}}",
+        &main.to_string_lossy(),
+        &FileSystemResolver::default(),
+    );
+    std::fs::remove_dir_all(&directory).unwrap();
+
+    let (screenplay, source_map) = result.unwrap();
+    assert_eq!(screenplay, parse("EXT. YARD - NIGHT"));
+    assert!(
+        source_map.elements[0]
+            .file
+            .as_deref()
+            .unwrap()
+            .ends_with("/acts/act2.fountain")
+    );
+}
+
+#[test]
+fn it_reads_included_files_relative_to_a_base_directory() {
+    let directory =
+        std::env::temp_dir().join(format!("jumpcut-includes-base-{}", std::process::id()));
+    std::fs::create_dir_all(directory.join("acts")).unwrap();
+    std::fs::write(
+        directory.join("acts/act1.fountain"),
+        "{{include: ../act2.fountain}}\n",
+    )
+    .unwrap();
+    std::fs::write(directory.join("act2.fountain"), "EXT. YARD - NIGHT\n").unwrap();
+
+    let result = parse_with_includes(
+        "{{include: ./acts/act1.fountain}}",
+        "-",
+        &FileSystemResolver::new(&directory),
+    );
+    std::fs::remove_dir_all(&directory).unwrap();
+
+    let (screenplay, source_map) = result.unwrap();
+    assert_eq!(screenplay, parse("EXT. YARD - NIGHT"));
+    assert_eq!(
+        source_map.elements[0].file.as_deref(),
+        Some(directory.join("act2.fountain").to_string_lossy().as_ref())
+    );
+}
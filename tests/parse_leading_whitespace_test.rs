@@ -11,6 +11,7 @@ fn tr(content: &str, styles: Vec<&str>) -> TextRun {
     TextRun {
         content: content.to_string(),
         text_style: style_strings,
+        revision: None,
     }
 }
 
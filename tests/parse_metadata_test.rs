@@ -26,6 +26,8 @@ fn it_handles_complex_metadata_without_elements() {
         ],
     );
     let mut expected = Screenplay {
+        revisions: Vec::new(),
+        page_lock: None,
         elements: vec![],
         metadata: expected_metadata,
         imported_layout: None,
@@ -55,6 +57,8 @@ fn it_handles_complex_metadata_without_elements() {
         ],
     );
     expected = Screenplay {
+        revisions: Vec::new(),
+        page_lock: None,
         elements: vec![],
         metadata: expected_metadata,
         imported_layout: None,
@@ -92,6 +96,8 @@ fn it_handles_complex_metadata_with_elements() {
         ],
     );
     let expected = Screenplay {
+        revisions: Vec::new(),
+        page_lock: None,
         elements: vec![Element::SceneHeading(p("INT. THE ZOO"), blank_attributes())],
         metadata: expected_metadata,
         imported_layout: None,
@@ -113,6 +119,8 @@ fn it_handles_unusual_metadata() {
     expected_metadata.insert("revision color".to_string(), vec!["blue".into()]);
 
     let expected = Screenplay {
+        revisions: Vec::new(),
+        page_lock: None,
         elements: vec![],
         metadata: expected_metadata,
         imported_layout: None,
@@ -1,4 +1,7 @@
-use jumpcut::{blank_attributes, p, parse, Attributes, Element, ElementLayoutOverrides};
+use jumpcut::rendering::html::HtmlRenderOptions;
+use jumpcut::{
+    blank_attributes, p, parse, parse_fdx, Attributes, Element, ElementLayoutOverrides, Note,
+};
 use pretty_assertions::assert_eq;
 
 #[test]
@@ -8,8 +11,14 @@ fn it_handles_single_line_interspersed_notes() {
         p("Jack smells the liquor.  Not good. "),
         Attributes {
             notes: Some(vec![
-                "Or should he taste it?".to_string(),
-                "Or bad?".to_string(),
+                Note {
+                    text: "Or should he taste it?".to_string(),
+                    offset: 24,
+                },
+                Note {
+                    text: "Or bad?".to_string(),
+                    offset: 35,
+                },
             ]),
             ..Attributes::default()
         },
@@ -29,7 +38,10 @@ fn it_handles_multi_line_interspersed_notes() {
         p(        "His hand is an inch from the receiver when the phone RINGS.  Scott pauses for a moment, suspicious for some reason. He looks around.  Phone ringing."),
         Attributes {
             notes: Some(vec![
-                "This section needs work.\nEither that, or I need coffee.\n  \nDefinitely coffee.".to_string(),
+                Note {
+                    text: "This section needs work.\nEither that, or I need coffee.\n  \nDefinitely coffee.".to_string(),
+                    offset: 115,
+                },
             ]),
             ..Attributes::default()
         },
@@ -53,7 +65,10 @@ fn it_handles_notes_on_dialogue() {
         Element::Dialogue(
             p("What's the meaning of this shit?"),
             Attributes {
-                notes: Some(vec!["that".to_string()]),
+                notes: Some(vec![Note {
+                    text: "that".to_string(),
+                    offset: 27,
+                }]),
                 ..Attributes::default()
             },
         ),
@@ -72,7 +87,10 @@ fn it_handles_an_empty_line_note() {
     let expected = vec![Element::Action(
         p(""),
         Attributes {
-            notes: Some(vec!["Dogs?".to_string()]),
+            notes: Some(vec![Note {
+                text: "Dogs?".to_string(),
+                offset: 0,
+            }]),
             ..Attributes::default()
         },
     )];
@@ -124,7 +142,10 @@ fn it_preserves_remaining_note_text_after_parsing_layout_modifiers() {
     let expected = vec![Element::Action(
         p("Jack smells the liquor. "),
         Attributes {
-            notes: Some(vec!["comment".to_string()]),
+            notes: Some(vec![Note {
+                text: "comment".to_string(),
+                offset: 24,
+            }]),
             layout_overrides: ElementLayoutOverrides {
                 space_before_delta: Some(-1.0),
                 right_indent_delta: None,
@@ -159,7 +180,142 @@ fn it_leaves_widen_auto_as_an_ordinary_note() {
     let expected = vec![Element::Action(
         p("Jack smells the liquor. "),
         Attributes {
-            notes: Some(vec![" .widen-auto ".to_string()]),
+            notes: Some(vec![Note {
+                text: " .widen-auto ".to_string(),
+                offset: 24,
+            }]),
+            ..Attributes::default()
+        },
+    )];
+
+    assert_eq!(parse(text).elements, expected);
+}
+
+#[test]
+fn it_anchors_notes_inside_styled_text() {
+    let text = "Jack **smells [[sniff]]the** liquor.";
+    let element = parse(text).elements.remove(0);
+    let Element::Action(text, attributes) = element else {
+        panic!("expected action");
+    };
+
+    assert_eq!(text.plain_text(), "Jack smells the liquor.");
+    let notes = attributes.notes.unwrap();
+    assert_eq!(notes[0].offset, 12);
+    assert_eq!(text.run_offset(notes[0].offset), (1, 7));
+}
+
+#[test]
+fn it_handles_notes_spanning_blank_lines() {
+    let text = "Bob waits.[[First thought.\n\nSecond thought.]] Then he leaves.\n\nBOB\nHi [[one\n\ntwo]]there.";
+    let expected = vec![
+        Element::Action(
+            p("Bob waits. Then he leaves."),
+            Attributes {
+                notes: Some(vec![Note {
+                    text: "First thought.\n\nSecond thought.".to_string(),
+                    offset: 10,
+                }]),
+                ..Attributes::default()
+            },
+        ),
+        Element::DialogueBlock(vec![
+            Element::Character(p("BOB"), blank_attributes()),
+            Element::Dialogue(
+                p("Hi there."),
+                Attributes {
+                    notes: Some(vec![Note {
+                        text: "one\n\ntwo".to_string(),
+                        offset: 3,
+                    }]),
+                    ..Attributes::default()
+                },
+            ),
+        ]),
+    ];
+
+    assert_eq!(parse(text).elements, expected);
+}
+
+#[test]
+fn it_does_not_join_paragraphs_for_a_note_that_never_closes() {
+    let text = "Bob waits. [[open\n\nBob leaves.";
+
+    assert_eq!(parse(text).elements.len(), 2);
+}
+
+#[test]
+fn it_writes_notes_back_where_they_were() {
+    let text = "Jack **smells [[sniff]]the** liquor.[[ .lift ]]\n\nBOB\nHi [[one\n\ntwo]]there.";
+
+    assert_eq!(
+        parse(text).to_fountain(),
+        "Jack **smells [[sniff]]the** liquor.[[.lift-1]]\n\nBOB\nHi [[one\n\ntwo]]there."
+    );
+}
+
+#[test]
+fn it_writes_notes_back_on_every_kind_of_element() {
+    for text in [
+        "INT. HOUSE #1# [[scene note]]",
+        "INT. HOUSE [[which one?]] - DAY",
+        "BOB [[new name?]]\n(quietly [[n]])\nHi.",
+        "BOB\nHi.\n~La la [[off key]]",
+        "Bob waits.\n\nCUT TO: [[or dissolve?]]",
+        "> FADE OUT. [[end]]",
+        "> THE END [[really?]] <",
+    ] {
+        let screenplay = parse(text);
+        assert_eq!(
+            parse(&screenplay.to_fountain()).elements,
+            screenplay.elements,
+            "{text}"
+        );
+        assert!(screenplay.to_fountain().contains("[["), "{text}");
+    }
+}
+
+#[test]
+fn it_anchors_fdx_script_notes_to_their_offsets() {
+    let text = "Jack smells [[sniff]]the liquor.\n\nBOB\nHi [[wave]]there.";
+    let mut screenplay = parse(text);
+    let fdx = screenplay.to_final_draft();
+
+    assert!(fdx.contains("<ScriptNote ID=\"1\" Range=\"12,0\">"));
+    assert!(fdx.contains("<Text>sniff</Text>"));
+    assert_eq!(parse_fdx(&fdx).unwrap().elements, screenplay.elements);
+}
+
+#[test]
+fn it_marks_notes_inline_in_html() {
+    let text = "Jack smells [[\"sniff\"]]the liquor.";
+    let options = HtmlRenderOptions {
+        head: false,
+        ..Default::default()
+    };
+
+    let html = parse(text).to_html_with_options(options.clone());
+    assert!(!html.contains("noteMarker\""));
+
+    let html = parse(text).to_html_with_options(HtmlRenderOptions {
+        render_notes: true,
+        ..options
+    });
+    assert!(html.contains(
+        "Jack smells <span class=\"noteMarker\" title=\"&quot;sniff&quot;\">*</span>the liquor."
+    ));
+}
+
+#[test]
+fn it_leaves_out_private_use_characters_that_would_anchor_notes() {
+    let text = "Bob\u{E000} waits.\u{E001} [[Too long?]] Leaves.";
+    let expected = vec![Element::Action(
+        p("Bob waits.  Leaves."),
+        Attributes {
+            notes: Some(vec![Note {
+                text: "Too long?".to_string(),
+                offset: 11,
+            }]),
             ..Attributes::default()
         },
     )];
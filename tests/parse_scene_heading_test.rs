@@ -1,5 +1,8 @@
-use jumpcut::{blank_attributes, p, parse, Attributes, Element};
+use jumpcut::{
+    blank_attributes, p, parse, Attributes, DayNight, Element, SceneHeadingParts, SceneSetting,
+};
 use pretty_assertions::assert_eq;
+use serde_json::json;
 
 #[test]
 fn it_handles_typical_scene_headings() {
@@ -140,3 +143,155 @@ fn it_handles_scene_headings_with_scene_numbers() {
         );
     }
 }
+
+fn parts(
+    prefix: Option<&str>,
+    setting: Option<SceneSetting>,
+    locations: &[&str],
+    time_of_day: Option<&str>,
+) -> SceneHeadingParts {
+    SceneHeadingParts {
+        prefix: prefix.map(str::to_string),
+        setting,
+        locations: locations
+            .iter()
+            .map(|location| location.to_string())
+            .collect(),
+        time_of_day: time_of_day.map(str::to_string),
+    }
+}
+
+#[test]
+fn it_splits_scene_headings_into_parts() {
+    let cases = vec![
+        (
+            "INT. HOUSE - KITCHEN - NIGHT",
+            parts(
+                Some("INT."),
+                Some(SceneSetting::Interior),
+                &["HOUSE", "KITCHEN"],
+                Some("NIGHT"),
+            ),
+        ),
+        (
+            "ext. yard - day",
+            parts(
+                Some("ext."),
+                Some(SceneSetting::Exterior),
+                &["yard"],
+                Some("day"),
+            ),
+        ),
+        (
+            "INT./EXT. CAR - MOVING - CONTINUOUS",
+            parts(
+                Some("INT./EXT."),
+                Some(SceneSetting::InteriorExterior),
+                &["CAR", "MOVING"],
+                Some("CONTINUOUS"),
+            ),
+        ),
+        (
+            "I/E TRUCK - DAY (FLASHBACK)",
+            parts(
+                Some("I/E"),
+                Some(SceneSetting::InteriorExterior),
+                &["TRUCK"],
+                Some("DAY (FLASHBACK)"),
+            ),
+        ),
+        (
+            "EST. CITY SKYLINE",
+            parts(
+                Some("EST."),
+                Some(SceneSetting::Establishing),
+                &["CITY SKYLINE"],
+                None,
+            ),
+        ),
+        (
+            "INT. HOUSE - KITCHEN",
+            parts(
+                Some("INT."),
+                Some(SceneSetting::Interior),
+                &["HOUSE", "KITCHEN"],
+                None,
+            ),
+        ),
+        (
+            ".FLASHBACK - NIGHT",
+            parts(None, None, &["FLASHBACK"], Some("NIGHT")),
+        ),
+        (
+            "INTERCOM ROOM - DAY",
+            parts(None, None, &["INTERCOM ROOM"], Some("DAY")),
+        ),
+        (
+            "INT. HOUSE - DAY - CONTINUOUS",
+            parts(
+                Some("INT."),
+                Some(SceneSetting::Interior),
+                &["HOUSE"],
+                Some("DAY - CONTINUOUS"),
+            ),
+        ),
+    ];
+
+    for (heading, expected) in cases {
+        assert_eq!(SceneHeadingParts::parse(heading), expected, "{heading}");
+    }
+}
+
+#[test]
+fn it_tells_day_from_night() {
+    let cases = [
+        ("INT. HOUSE - DAY", Some(DayNight::Day), false),
+        ("EXT. YARD - early morning", Some(DayNight::Day), false),
+        ("INT. HOUSE - NIGHT (FLASHBACK)", Some(DayNight::Night), false),
+        ("EXT. PIER - DUSK", Some(DayNight::Night), false),
+        ("INT. CAR - CONTINUOUS", None, true),
+        ("INT. HOUSE - MOMENTS LATER", None, true),
+        ("EXT. FIELD - MAGIC HOUR", None, false),
+        ("INT. HOUSE - KITCHEN", None, false),
+        ("INT. HOUSE - DAY - CONTINUOUS", Some(DayNight::Day), true),
+    ];
+
+    for (heading, day_night, continues) in cases {
+        let parts = SceneHeadingParts::parse(heading);
+        assert_eq!(parts.day_night(), day_night, "{heading}");
+        assert_eq!(parts.continues_previous_time(), continues, "{heading}");
+    }
+}
+
+#[test]
+fn it_exposes_parts_on_parsed_scene_headings() {
+    let screenplay = parse(".SPACE STATION - MORNING #12#\n\nBob floats.");
+
+    assert_eq!(
+        screenplay.elements[0].scene_heading(),
+        Some(parts(None, None, &["SPACE STATION"], Some("MORNING")))
+    );
+    assert_eq!(screenplay.elements[1].scene_heading(), None);
+    assert_eq!(
+        Element::SceneHeading(p("EXT.PIER - DUSK"), Default::default())
+            .scene_heading()
+            .map(|parts| parts.locations),
+        Some(vec!["PIER".to_string()])
+    );
+}
+
+#[test]
+fn it_includes_parts_in_json() {
+    let json = parse("INT. HOUSE - KITCHEN - NIGHT\n\nBob eats.").to_json_value();
+
+    assert_eq!(
+        json["elements"][0]["heading"],
+        json!({
+            "prefix": "INT.",
+            "setting": "Interior",
+            "locations": ["HOUSE", "KITCHEN"],
+            "time_of_day": "NIGHT",
+        })
+    );
+    assert!(json["elements"][1].get("heading").is_none());
+}
@@ -31,3 +31,16 @@ fn it_handles_isolated_synopsis() {
         "it should handle an isolated synopsis"
     );
 }
+
+#[test]
+fn it_keeps_notes_in_synopses_and_sections() {
+    let text = "# Act 1 [[too long?]]\n\n= A synopsis [[with note]] and [[prop: gun]]";
+    let screenplay = parse(text);
+    let expected = vec![
+        Element::Section(p("Act 1 [[too long?]]"), blank_attributes(), 1),
+        Element::Synopsis(p("A synopsis [[with note]] and [[prop: gun]]")),
+    ];
+
+    assert_eq!(screenplay.elements, expected);
+    assert_eq!(screenplay.to_fountain(), text);
+}
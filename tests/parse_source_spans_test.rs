@@ -0,0 +1,115 @@
+use jumpcut::parse_with_source_map;
+use jumpcut::source_map::SourceSpan;
+use pretty_assertions::assert_eq;
+
+fn slice<'a>(text: &'a str, span: &SourceSpan) -> &'a str {
+    &text[span.start..span.end]
+}
+
+#[test]
+fn it_spans_top_level_elements() {
+    let text = "INT. HOUSE - DAY\n\nBob walks in.\nHe sits.";
+    let (screenplay, source_map) = parse_with_source_map(text);
+
+    assert_eq!(source_map.elements.len(), screenplay.elements.len());
+    assert_eq!(
+        source_map.elements[0].span,
+        SourceSpan {
+            start: 0,
+            end: 16,
+            start_line: 1,
+            start_column: 1,
+            end_line: 1,
+            end_column: 17,
+        }
+    );
+    assert_eq!(
+        slice(text, &source_map.elements[1].span),
+        "Bob walks in.\nHe sits."
+    );
+    assert_eq!(source_map.elements[1].span.start_line, 3);
+    assert_eq!(source_map.elements[1].span.end_line, 4);
+    assert_eq!(source_map.elements[1].span.end_column, 9);
+}
+
+#[test]
+fn it_spans_children_of_dialogue_blocks() {
+    let text = "BRICK\n(quietly)\nScrew retirement.\nAnd you.\n\nSTEEL ^\nNo.";
+    let (_, source_map) = parse_with_source_map(text);
+
+    let dual = &source_map.elements[0];
+    assert_eq!(slice(text, &dual.span), text);
+    let left = &dual.children[0];
+    assert_eq!(
+        slice(text, &left.span),
+        "BRICK\n(quietly)\nScrew retirement.\nAnd you."
+    );
+    let left_children = left
+        .children
+        .iter()
+        .map(|child| slice(text, &child.span))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        left_children,
+        vec!["BRICK", "(quietly)", "Screw retirement.\nAnd you."]
+    );
+    let right_children = dual.children[1]
+        .children
+        .iter()
+        .map(|child| slice(text, &child.span))
+        .collect::<Vec<_>>();
+    assert_eq!(right_children, vec!["STEEL ^", "No."]);
+    assert_eq!(
+        source_map
+            .get(&[0, 1, 1])
+            .map(|element| element.span.start_line),
+        Some(7)
+    );
+}
+
+#[test]
+fn it_maps_spans_around_boneyard() {
+    let text = "/* cut\nthis */Bob walks in.\n\n/* gone */\n\nINT. HOUSE - DAY /* note */";
+    let (screenplay, source_map) = parse_with_source_map(text);
+
+    assert_eq!(screenplay.elements.len(), 3);
+    assert_eq!(
+        slice(text, &source_map.elements[0].span),
+        "/* cut\nthis */Bob walks in."
+    );
+    assert_eq!(source_map.elements[0].span.start_line, 1);
+    assert_eq!(slice(text, &source_map.elements[1].span), "/* gone */");
+    assert_eq!(
+        slice(text, &source_map.elements[2].span),
+        "INT. HOUSE - DAY /* note */"
+    );
+    assert_eq!(source_map.elements[2].span.start_line, 6);
+}
+
+#[test]
+fn it_excludes_invisible_characters_and_metadata() {
+    let text = "Title: Example\n\n\u{FEFF}INT. HOUSE - DAY\u{200B}\n\nÉmile waits.";
+    let (screenplay, source_map) = parse_with_source_map(text);
+
+    assert_eq!(source_map.elements.len(), screenplay.elements.len());
+    assert_eq!(
+        slice(text, &source_map.elements[0].span),
+        "INT. HOUSE - DAY"
+    );
+    assert_eq!(source_map.elements[0].span.start_column, 2);
+    assert_eq!(source_map.elements[1].span.end_column, 13);
+    assert_eq!(source_map.path_at(text.len() - 2), Some(vec![1]));
+}
+
+#[test]
+fn it_handles_windows_line_endings() {
+    let text = "INT. HOUSE - DAY\r\n\r\nBOB\r\nHi.";
+    let (_, source_map) = parse_with_source_map(text);
+
+    assert_eq!(
+        slice(text, &source_map.elements[0].span),
+        "INT. HOUSE - DAY"
+    );
+    assert_eq!(slice(text, &source_map.elements[1].span), "BOB\r\nHi.");
+    assert_eq!(slice(text, &source_map.elements[1].children[1].span), "Hi.");
+}
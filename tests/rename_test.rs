@@ -0,0 +1,128 @@
+use jumpcut::{Element, ElementText, Note, RenameChange, RenameOptions, parse, tr};
+use pretty_assertions::assert_eq;
+
+const SCRIPT: &str = "\
+INT. BANK - DAY
+
+BOB, 40s, walks in. Bob's hat is wet. Bobby follows. [[Bob is late]]
+
+BOB (V.O.)
+I hate rain.
+
+ALICE
+(to Bob)
+Hi, *Bob*. [[wave]] Ready?
+
+BOB ^
+Ready.
+";
+
+fn cue(screenplay: &jumpcut::Screenplay, path: &[usize]) -> String {
+    let mut element = &screenplay.elements[path[0]];
+    for &index in &path[1..] {
+        element = match element {
+            Element::DialogueBlock(block) | Element::DualDialogueBlock(block) => &block[index],
+            _ => panic!("expected a block at {path:?}"),
+        };
+    }
+    element.text().expect("expected text").plain_text()
+}
+
+#[test]
+fn it_renames_character_cues() {
+    let mut screenplay = parse(SCRIPT);
+    let changes = screenplay.rename_character("bob", "ROBERT");
+
+    assert_eq!(
+        changes,
+        [
+            RenameChange {
+                element_type: "Character".to_string(),
+                path: vec![2, 0],
+                before: "BOB (V.O.)".to_string(),
+                after: "ROBERT (V.O.)".to_string(),
+            },
+            RenameChange {
+                element_type: "Character".to_string(),
+                path: vec![3, 1, 0],
+                before: "BOB".to_string(),
+                after: "ROBERT".to_string(),
+            },
+        ]
+    );
+    assert_eq!(cue(&screenplay, &[3, 0, 0]), "ALICE");
+    assert_eq!(
+        screenplay.elements[3].character_cues()[1].name,
+        "ROBERT",
+        "the second cue keeps its place in the dual dialogue"
+    );
+    assert!(screenplay.to_fountain().contains("ROBERT ^\nReady."));
+    assert!(screenplay.to_fountain().contains("BOB, 40s, walks in."));
+}
+
+#[test]
+fn it_writes_a_mixed_case_name_in_capitals_in_cues() {
+    let mut screenplay = parse(SCRIPT);
+    let changes = screenplay.rename_character("bob", "Robert");
+
+    assert_eq!(changes[0].after, "ROBERT (V.O.)");
+    assert_eq!(changes[1].after, "ROBERT");
+
+    let reparsed = parse(&screenplay.to_fountain());
+    assert_eq!(reparsed.elements, screenplay.elements);
+    assert_eq!(reparsed.elements[2].character_cues()[0].name, "ROBERT");
+}
+
+#[test]
+fn it_renames_characters_in_text_when_asked() {
+    let mut screenplay = parse(SCRIPT);
+    let changes =
+        screenplay.rename_character_with_options("Bob", "ROBERT", RenameOptions { in_text: true });
+
+    assert_eq!(changes.len(), 5);
+    let Element::Action(text, attributes) = &screenplay.elements[1] else {
+        panic!("expected action");
+    };
+    assert_eq!(
+        text.plain_text(),
+        "ROBERT, 40s, walks in. Robert's hat is wet. Bobby follows. "
+    );
+    assert_eq!(
+        attributes.notes,
+        Some(vec![Note {
+            text: "Bob is late".to_string(),
+            offset: 59,
+        }])
+    );
+    assert_eq!(cue(&screenplay, &[3, 0, 1]), "(to Robert)");
+
+    let Element::DualDialogueBlock(sides) = &screenplay.elements[3] else {
+        panic!("expected dual dialogue");
+    };
+    let Element::DialogueBlock(alice) = &sides[0] else {
+        panic!("expected alice's side");
+    };
+    let Element::Dialogue(ElementText::Styled(runs), attributes) = &alice[2] else {
+        panic!("expected styled dialogue");
+    };
+    assert_eq!(
+        runs,
+        &[
+            tr("Hi, ", vec![]),
+            tr("Robert", vec!["Italic"]),
+            tr(".  Ready?", vec![]),
+        ]
+    );
+    assert_eq!(attributes.notes.as_ref().unwrap()[0].offset, 12);
+}
+
+#[test]
+fn it_leaves_lowercase_words_and_unknown_names_alone() {
+    let mut screenplay = parse("INT. POND - DAY\n\nThe cork starts to bob. Bob.\n");
+
+    assert!(screenplay.rename_character("Nobody", "Somebody").is_empty());
+    let changes =
+        screenplay.rename_character_with_options("bob", "Robert", RenameOptions { in_text: true });
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].after, "The cork starts to bob. Robert.");
+}
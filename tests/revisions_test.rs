@@ -0,0 +1,249 @@
+use jumpcut::rendering::html::HtmlRenderOptions;
+use jumpcut::{Element, ElementText, RevisionSet, Screenplay, parse, parse_fdx};
+use pretty_assertions::assert_eq;
+
+const DRAFT: &str = "INT. HOUSE - DAY\n\nBob waits by the door.\n\nBOB\nWhere is she?\n\nEXT. GARDEN - NIGHT\n\nRain falls.";
+
+fn blue() -> RevisionSet {
+    let mut revision = RevisionSet::new(1, "Blue Revision", "Blue");
+    revision.date = Some("10/18/26".into());
+    revision
+}
+
+fn revised(previous: &str, current: &str, revision: RevisionSet) -> Screenplay {
+    let mut screenplay = parse(current);
+    screenplay.mark_revisions(&parse(previous), revision);
+    screenplay
+}
+
+/// An element's text and revision, and the revised runs inside it.
+type Marks = (String, Option<u32>, Vec<(String, u32)>);
+
+fn marks(elements: &[Element]) -> Vec<Marks> {
+    let mut out = Vec::new();
+    for element in elements {
+        match element {
+            Element::DialogueBlock(block) | Element::DualDialogueBlock(block) => {
+                out.extend(marks(block))
+            }
+            _ => {
+                let (Some(text), Some(attributes)) = (element.text(), element.attributes()) else {
+                    continue;
+                };
+                let plain: Vec<char> = text.plain_text().chars().collect();
+                let runs = text
+                    .revision_ranges()
+                    .into_iter()
+                    .map(|(start, end, id)| (plain[start..end].iter().collect(), id))
+                    .collect();
+                out.push((text.plain_text(), attributes.revision, runs));
+            }
+        }
+    }
+    out
+}
+
+#[test]
+fn it_leaves_an_unchanged_draft_unmarked() {
+    let screenplay = revised(DRAFT, DRAFT, blue());
+
+    assert!(
+        marks(&screenplay.elements)
+            .iter()
+            .all(|(_, revision, runs)| revision.is_none() && runs.is_empty())
+    );
+    assert_eq!(screenplay.revisions, vec![blue()]);
+}
+
+#[test]
+fn it_marks_inserted_elements_as_a_whole() {
+    let current = DRAFT.replace("Rain falls.", "Rain falls.\n\nThunder.");
+    let screenplay = revised(DRAFT, &current, blue());
+
+    assert_eq!(
+        marks(&screenplay.elements).last(),
+        Some(&("Thunder.".to_string(), Some(1), Vec::new()))
+    );
+}
+
+#[test]
+fn it_marks_only_the_changed_words_of_an_edited_element() {
+    let current = DRAFT.replace("by the door.", "by the open door.");
+    let screenplay = revised(DRAFT, &current, blue());
+
+    assert_eq!(
+        marks(&screenplay.elements)[1],
+        (
+            "Bob waits by the open door.".to_string(),
+            None,
+            vec![("open".to_string(), 1)]
+        )
+    );
+}
+
+#[test]
+fn it_marks_the_word_after_a_deletion() {
+    let current = DRAFT.replace("by the door.", "by door.");
+    let screenplay = revised(DRAFT, &current, blue());
+
+    assert_eq!(
+        marks(&screenplay.elements)[1].2,
+        vec![("door.".to_string(), 1)]
+    );
+}
+
+#[test]
+fn it_keeps_earlier_revisions_on_unchanged_text() {
+    let blue_draft = DRAFT.replace("Rain falls.", "Rain falls.\n\nThunder.");
+    let mut blue_pages = parse(&blue_draft);
+    blue_pages.mark_revisions(&parse(DRAFT), blue());
+
+    let pink_draft = blue_draft.replace("Where is she?", "Where did she go?");
+    let mut pink_pages = parse(&pink_draft);
+    pink_pages.mark_revisions(&blue_pages, RevisionSet::new(2, "Pink Revision", "Pink"));
+    let marked = marks(&pink_pages.elements);
+
+    assert_eq!(marked.last().unwrap().1, Some(1));
+    assert_eq!(marked[3].2, vec![("did she go?".to_string(), 2)]);
+    assert_eq!(
+        pink_pages
+            .revisions
+            .iter()
+            .map(|set| set.id)
+            .collect::<Vec<_>>(),
+        vec![1, 2]
+    );
+}
+
+#[test]
+fn it_keeps_earlier_revisions_on_the_unchanged_words_of_an_edited_element() {
+    let blue_draft = DRAFT.replace("by the door.", "by the open door.");
+    let blue_pages = revised(DRAFT, &blue_draft, blue());
+
+    let pink_draft = blue_draft.replace("Bob waits", "Bob sits");
+    let mut pink_pages = parse(&pink_draft);
+    pink_pages.mark_revisions(&blue_pages, RevisionSet::new(2, "Pink Revision", "Pink"));
+
+    assert_eq!(
+        marks(&pink_pages.elements)[1].2,
+        vec![("sits".to_string(), 2), ("open".to_string(), 1)]
+    );
+}
+
+#[test]
+fn it_keeps_revisions_through_json() {
+    let current = DRAFT.replace("by the door.", "by the open door.\n\nThunder.");
+    let screenplay = revised(DRAFT, &current, blue());
+
+    let json = screenplay.clone().to_json_string();
+    let read_back = Screenplay::from_json_str(&json).expect("json should parse");
+
+    assert_eq!(read_back.revisions, screenplay.revisions);
+    assert_eq!(marks(&read_back.elements), marks(&screenplay.elements));
+}
+
+#[test]
+fn it_leaves_revisions_out_of_unrevised_json() {
+    let json = parse(DRAFT).to_json_string();
+
+    assert!(!json.contains("revision"));
+}
+
+#[test]
+fn it_keeps_revisions_through_fdx() {
+    let current = DRAFT.replace("by the door.", "by the open door.\n\nThunder.");
+    let mut screenplay = revised(DRAFT, &current, blue());
+
+    let fdx = screenplay.to_final_draft();
+    let read_back = parse_fdx(&fdx).expect("fdx should parse");
+
+    assert!(fdx.contains("<Text RevisionID=\"1\">open</Text>"));
+    assert!(fdx.contains(
+        "<Revision Color=\"#ADADD8D8E6E6\" FullRevision=\"No\" ID=\"1\" JumpCutDate=\"10/18/26\""
+    ));
+    assert_eq!(read_back.revisions, vec![blue()]);
+    assert_eq!(marks(&read_back.elements), marks(&screenplay.elements));
+}
+
+#[test]
+fn it_reads_final_draft_revision_sets() {
+    let xml = r##"<?xml version="1.0" encoding="UTF-8" standalone="no" ?>
+<FinalDraft DocumentType="Script" Template="No" Version="5">
+  <Content>
+    <Paragraph Type="Action">
+      <Text RevisionID="0">Bob waits by the </Text>
+      <Text RevisionID="2">window.</Text>
+    </Paragraph>
+    <Paragraph Type="Action">
+      <Text RevisionID="2">Rain falls.</Text>
+    </Paragraph>
+  </Content>
+  <Revisions ActiveSet="2" Location="7.75" RevisionMode="OffMode" RevisionsShown="Active" ShowAllMarks="No" ShowAllSets="No" ShowPageColor="No">
+    <Revision Color="#000000000000" FullRevision="No" ID="0" Mark="*" Name="First Revision" PageColor="#FFFFFFFFFFFF" Style=""/>
+    <Revision Color="#FFFFC0C0CBCB" FullRevision="No" ID="2" Mark="*" Name="Second Revision" PageColor="#FFFFC0C0CBCB" Style=""/>
+  </Revisions>
+</FinalDraft>"##;
+
+    let screenplay = parse_fdx(xml).expect("fdx should parse");
+
+    assert_eq!(
+        screenplay.revisions,
+        vec![RevisionSet::new(2, "Second Revision", "Pink")]
+    );
+    assert_eq!(
+        marks(&screenplay.elements),
+        vec![
+            (
+                "Bob waits by the window.".to_string(),
+                None,
+                vec![("window.".to_string(), 2)]
+            ),
+            ("Rain falls.".to_string(), Some(2), Vec::new()),
+        ]
+    );
+    assert!(matches!(
+        &screenplay.elements[1],
+        Element::Action(ElementText::Plain(_), _)
+    ));
+}
+
+#[test]
+fn it_marks_revised_lines_and_labels_pages_in_paginated_html() {
+    let current = DRAFT.replace("by the door.", "by the open door.");
+    let mut screenplay = revised(DRAFT, &current, blue());
+
+    let html = screenplay.to_html_with_options(HtmlRenderOptions {
+        exact_wraps: true,
+        paginated: true,
+        ..HtmlRenderOptions::default()
+    });
+
+    assert!(html.contains("<span class=\"revisionLabel\">Blue Rev. (10/18/26)</span>"));
+    assert!(html.contains(
+        "<div class=\"visualLine action revised\"><span class=\"revisionMark\">*</span>Bob waits by the open door.</div>"
+    ));
+    assert_eq!(html.matches("class=\"revisionMark\"").count(), 1);
+}
+
+#[test]
+fn it_marks_revised_paragraphs_in_html() {
+    let current = DRAFT.replace("Rain falls.", "Rain falls.\n\nThunder.");
+    let mut screenplay = revised(DRAFT, &current, blue());
+
+    let html = screenplay.to_html(false);
+
+    assert_eq!(html.matches("class=\"revisionMark\"").count(), 1);
+    assert!(html.contains("revised"));
+}
+
+#[test]
+fn it_names_standard_and_custom_revision_colors() {
+    let mut custom = RevisionSet::new(3, "Lavender", "#E6E6FA");
+    assert_eq!(custom.rgb(), Some([0xE6, 0xE6, 0xFA]));
+    assert_eq!(custom.header_label(), "Lavender Rev.");
+    custom.date = Some("11/2/26".into());
+    assert_eq!(custom.header_label(), "Lavender Rev. (11/2/26)");
+
+    assert_eq!(RevisionSet::color_name([0xFF, 0xC0, 0xCB]), "Pink");
+    assert_eq!(RevisionSet::color_name([1, 2, 3]), "#010203");
+}
@@ -0,0 +1,113 @@
+use jumpcut::parse;
+use jumpcut::runtime::{RuntimeOptions, format_duration};
+use pretty_assertions::assert_eq;
+
+const RATES: RuntimeOptions = RuntimeOptions {
+    dialogue_words_per_minute: 120.0,
+    action_lines_per_second: 1.0,
+    transition_seconds: 3.0,
+};
+
+const SCRIPT: &str = "\
+Title: Heist
+
+> ACT ONE <
+
+INT. BANK - DAY #1#
+
+Bob walks in.
+
+BOB
+Nobody move. Nobody gets hurt.
+
+CUT TO:
+
+EXT. STREET - DAY #2#
+
+ALICE
+Get in the car!
+
+BOB ^
+Coming, coming!
+
+> END OF ACT ONE <
+
+> ACT TWO <
+
+INT. CAR - NIGHT #3#
+
+They drive.
+";
+
+#[test]
+fn it_estimates_each_scene_from_its_words_lines_and_transitions() {
+    let estimate = parse(SCRIPT).runtime_with_options(RATES);
+
+    assert_eq!(estimate.options, RATES);
+    assert_eq!(estimate.scenes.len(), 3);
+    let bank = &estimate.scenes[0];
+    assert_eq!(bank.scene_number.as_deref(), Some("1"));
+    assert_eq!(bank.page, "1");
+    // Five words at two a second, one line of action and one transition.
+    assert_eq!(bank.timings.dialogue_seconds, 2.5);
+    assert_eq!(bank.timings.action_seconds, 1.0);
+    assert_eq!(bank.timings.transition_seconds, 3.0);
+    assert_eq!(bank.timings.seconds, 6.5);
+    // Dual dialogue is spoken at once, so only the longer side counts.
+    assert_eq!(estimate.scenes[1].timings.seconds, 2.0);
+    assert_eq!(estimate.scenes[2].timings.seconds, 1.0);
+    assert_eq!(estimate.total.seconds, 9.5);
+}
+
+#[test]
+fn it_totals_each_act() {
+    let estimate = parse(SCRIPT).runtime_with_options(RATES);
+
+    assert_eq!(
+        estimate
+            .acts
+            .iter()
+            .map(|act| (act.title.as_str(), act.scene_count, act.timings.seconds))
+            .collect::<Vec<_>>(),
+        [("ACT ONE", 2, 8.5), ("ACT TWO", 1, 1.0)]
+    );
+}
+
+#[test]
+fn it_uses_faster_default_rates_for_multicam() {
+    let screenplay = parse(SCRIPT);
+    let multicam = parse(&SCRIPT.replace("Title: Heist", "Title: Heist\nFmt: multicam"));
+
+    assert_eq!(
+        RuntimeOptions::for_screenplay(&screenplay),
+        RuntimeOptions::screenplay()
+    );
+    assert_eq!(
+        RuntimeOptions::for_screenplay(&multicam),
+        RuntimeOptions::multicam()
+    );
+    assert_eq!(screenplay.runtime().options, RuntimeOptions::screenplay());
+    assert!(
+        RuntimeOptions::multicam().dialogue_words_per_minute
+            > RuntimeOptions::screenplay().dialogue_words_per_minute
+    );
+}
+
+#[test]
+fn it_reports_runtimes_as_text_and_json() {
+    let estimate = parse(SCRIPT).runtime_with_options(RATES);
+    let report = estimate.to_report();
+
+    assert!(report.starts_with("Estimated runtime: 0:10\n"));
+    assert!(report.contains("\n  1  1       1     0:07     INT. BANK - DAY\n"));
+    assert!(report.contains("\nActs\n  Act      Scenes  Runtime\n  ACT ONE  2       0:09\n"));
+
+    let json = serde_json::to_value(&estimate).unwrap();
+    assert_eq!(json["total"]["seconds"], 9.5);
+    assert_eq!(json["scenes"][0]["timings"]["transition_seconds"], 3.0);
+    assert_eq!(json["options"]["dialogue_words_per_minute"], 120.0);
+
+    assert_eq!(format_duration(59.4), "0:59");
+    assert_eq!(format_duration(61.0), "1:01");
+    assert_eq!(format_duration(3725.0), "1:02:05");
+}
@@ -0,0 +1,123 @@
+use jumpcut::pagination::{PaginatedScreenplay, PaginationScope, SceneContinued};
+use jumpcut::parse;
+use jumpcut::rendering::html::HtmlRenderOptions;
+use jumpcut::rendering::text::TextRenderOptions;
+use pretty_assertions::assert_eq;
+
+fn draft(fmt: &str) -> String {
+    let mut draft = format!("fmt: {fmt}\n\n");
+    for scene in 1..=2 {
+        draft.push_str(&format!("INT. ROOM {scene} - DAY #{scene}#\n\n"));
+        for line in 1..=40 {
+            draft.push_str(&format!(
+                "Line {line} of scene {scene} is a piece of action that fills space on the page.\n\n"
+            ));
+        }
+    }
+    draft
+}
+
+fn paginated_text(fmt: &str) -> String {
+    parse(&draft(fmt)).to_text(&TextRenderOptions {
+        paginated: true,
+        ..TextRenderOptions::default()
+    })
+}
+
+fn continued(scene_number: &str, count: u32) -> Option<SceneContinued> {
+    Some(SceneContinued {
+        scene_number: Some(scene_number.to_string()),
+        count,
+    })
+}
+
+#[test]
+fn it_leaves_scene_continueds_off_by_default() {
+    let text = paginated_text("allow-lowercase-title");
+
+    assert!(!text.contains("CONTINUED"));
+}
+
+#[test]
+fn it_marks_pages_that_split_a_scene() {
+    let screenplay = parse(&draft("numbered-scene-continueds"));
+    let scope = PaginationScope {
+        first_page_number: None,
+        title_page_count: None,
+        body_start_page: None,
+    };
+    let pages = PaginatedScreenplay::from_screenplay("test", &screenplay, 54.0, scope).pages;
+
+    assert_eq!(
+        pages
+            .iter()
+            .map(|page| page.metadata.scene_continued.clone())
+            .collect::<Vec<_>>(),
+        vec![
+            None,
+            continued("1", 1),
+            continued("1", 2),
+            continued("2", 1),
+            continued("2", 2),
+        ]
+    );
+    assert_eq!(
+        pages
+            .iter()
+            .map(|page| page.metadata.scene_continues)
+            .collect::<Vec<_>>(),
+        vec![true, true, true, true, false]
+    );
+}
+
+#[test]
+fn it_prints_scene_continueds_in_text() {
+    let text = paginated_text("scene-continueds");
+    let lines: Vec<&str> = text.lines().collect();
+    let second_page = lines
+        .iter()
+        .position(|line| line.ends_with(" 2."))
+        .expect("a second page");
+    let first_page = &lines[..second_page - 2];
+
+    assert_eq!(first_page.len(), 54);
+    assert_eq!(first_page.last().unwrap().trim(), "(CONTINUED)");
+    assert_eq!(first_page[first_page.len() - 2], "");
+    assert_eq!(lines[second_page + 3], "CONTINUED:");
+    assert_eq!(lines[second_page + 4], "");
+    assert!(!text.contains("CONTINUED: (2)"));
+}
+
+#[test]
+fn it_numbers_scene_continueds_in_html() {
+    let html = parse(&draft("numbered-scene-continueds")).to_html_with_options(HtmlRenderOptions {
+        exact_wraps: true,
+        paginated: true,
+        ..HtmlRenderOptions::default()
+    });
+
+    assert!(html.contains(
+        "<span class=\"sceneNumberLeft\">1</span>CONTINUED: (2)<span class=\"sceneNumberRight\">1</span>"
+    ));
+    assert!(html.contains("(CONTINUED)</div>"));
+}
+
+#[test]
+fn it_hides_scene_continueds_with_the_other_continueds() {
+    let text = parse(&draft("scene-continueds")).to_text(&TextRenderOptions {
+        paginated: true,
+        render_continueds: false,
+        ..TextRenderOptions::default()
+    });
+
+    assert!(!text.contains("CONTINUED"));
+}
+
+#[test]
+fn it_writes_scene_continueds_to_fdx() {
+    let fdx = parse(&draft("numbered-scene-continueds")).to_final_draft();
+
+    assert!(fdx.contains(
+        "<SceneBreaks ContinuedNumber=\"Yes\" SceneBottom=\"(CONTINUED)\" SceneBottomOfPage=\"Yes\" SceneTop=\"CONTINUED:\" SceneTopOfNext=\"Yes\"/>"
+    ));
+}
@@ -0,0 +1,111 @@
+use jumpcut::pagination::{Eighths, SceneLength};
+use jumpcut::rendering::html::HtmlRenderOptions;
+use jumpcut::{Screenplay, parse};
+use pretty_assertions::assert_eq;
+
+fn draft() -> String {
+    let scene = |number: u32| {
+        format!("INT. ROOM - DAY #{number}#\n\nA line of action that fills exactly one line.\n\n")
+    };
+    format!(
+        "{}INT. HALL - NIGHT #2#\n\n{}{}",
+        scene(1),
+        "Another line of action fills a line.\n\n".repeat(80),
+        scene(3)
+    )
+}
+
+#[test]
+fn it_writes_eighths_like_breakdown_sheets() {
+    assert_eq!(Eighths(19).to_string(), "2 3/8");
+    assert_eq!(Eighths(5).to_string(), "5/8");
+    assert_eq!(Eighths(8).to_string(), "1");
+    assert_eq!(Eighths(16).to_string(), "2");
+}
+
+#[test]
+fn it_measures_scenes_on_the_printed_pages() {
+    let lengths = parse(&draft()).scene_lengths();
+
+    assert_eq!(
+        lengths[1],
+        SceneLength {
+            scene_number: Some("2".to_string()),
+            heading: "INT. HALL - NIGHT".to_string(),
+            eighths: Eighths(24),
+            first_page: "1".to_string(),
+            last_page: "4".to_string(),
+        }
+    );
+    assert_eq!(
+        lengths
+            .iter()
+            .map(|length| length.eighths.to_string())
+            .collect::<Vec<_>>(),
+        ["1/8", "3", "1/8"]
+    );
+    assert_eq!(lengths[2].first_page, "4");
+}
+
+#[test]
+fn it_agrees_with_the_statistics() {
+    let screenplay = parse(&draft());
+    let lengths = screenplay.scene_lengths();
+
+    assert_eq!(
+        screenplay
+            .statistics()
+            .scenes
+            .iter()
+            .map(|scene| Eighths(scene.eighths))
+            .collect::<Vec<_>>(),
+        lengths
+            .iter()
+            .map(|length| length.eighths)
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn it_attaches_scene_lengths_to_json() {
+    let json = parse(&draft()).to_json_value_with_scene_lengths();
+    let headings: Vec<_> = json["elements"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|element| element["type"] == "Scene Heading")
+        .collect();
+
+    assert_eq!(headings.len(), 3);
+    assert_eq!(
+        headings[1]["length"],
+        serde_json::json!({
+            "eighths": 24,
+            "text": "3",
+            "first_page": "1",
+            "last_page": "4",
+        })
+    );
+    assert!(json["elements"][1].get("length").is_none());
+
+    let read_back = Screenplay::from_json_value(json).unwrap();
+    assert_eq!(read_back.to_json_value(), parse(&draft()).to_json_value());
+}
+
+#[test]
+fn it_attaches_scene_lengths_to_html() {
+    for (exact_wraps, paginated) in [(false, false), (true, false), (true, true)] {
+        let html = parse(&draft()).to_html_with_options(HtmlRenderOptions {
+            exact_wraps,
+            paginated,
+            scene_lengths: true,
+            ..HtmlRenderOptions::default()
+        });
+
+        assert_eq!(html.matches("data-scene-length=").count(), 3);
+        assert!(html.contains(" data-eighths=\"24\" data-scene-length=\"3\">"));
+    }
+
+    let html = parse(&draft()).to_html_with_options(HtmlRenderOptions::default());
+    assert!(!html.contains("data-scene-length"));
+}
@@ -0,0 +1,98 @@
+use jumpcut::syntax::SyntaxTree;
+use jumpcut::{Element, SceneNumbering, Screenplay, parse};
+use pretty_assertions::assert_eq;
+
+const DRAFT: &str = "INT. HOUSE - DAY #1#\n\nBob waits.\n\nINT. KITCHEN - DAY\n\nBob eats.\n\n.OMITTED #2#\n\nEXT. GARDEN - NIGHT #4#\n\nRain falls.\n";
+
+fn headings(screenplay: &Screenplay) -> Vec<(String, Option<String>)> {
+    screenplay
+        .elements
+        .iter()
+        .filter_map(|element| match element {
+            Element::SceneHeading(text, attributes) => {
+                Some((text.plain_text(), attributes.scene_number.clone()))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn heading(text: &str, number: &str) -> (String, Option<String>) {
+    (text.to_string(), Some(number.to_string()))
+}
+
+#[test]
+fn it_numbers_every_scene_from_one() {
+    let mut screenplay = parse(DRAFT);
+    screenplay.number_scenes(SceneNumbering::Fresh);
+
+    assert_eq!(
+        headings(&screenplay),
+        vec![
+            heading("INT. HOUSE - DAY", "1"),
+            heading("INT. KITCHEN - DAY", "2"),
+            heading("EXT. GARDEN - NIGHT", "3"),
+        ]
+    );
+}
+
+#[test]
+fn it_keeps_locked_numbers_and_marks_cut_scenes_omitted() {
+    let mut screenplay = parse(DRAFT);
+    screenplay.number_scenes(SceneNumbering::Locked);
+
+    assert_eq!(
+        headings(&screenplay),
+        vec![
+            heading("INT. HOUSE - DAY", "1"),
+            heading("INT. KITCHEN - DAY", "1A"),
+            heading("OMITTED", "2"),
+            heading("OMITTED", "3"),
+            heading("EXT. GARDEN - NIGHT", "4"),
+        ]
+    );
+}
+
+#[test]
+fn it_numbers_an_unnumbered_screenplay_from_one_when_locked() {
+    let mut screenplay = parse("INT. HOUSE - DAY\n\nBob waits.\n\nEXT. GARDEN - NIGHT\n\nRain.");
+    screenplay.number_scenes(SceneNumbering::Locked);
+
+    assert_eq!(
+        headings(&screenplay),
+        vec![
+            heading("INT. HOUSE - DAY", "1"),
+            heading("EXT. GARDEN - NIGHT", "2"),
+        ]
+    );
+}
+
+#[test]
+fn it_rewrites_only_scene_numbers_in_the_fountain_source() {
+    let mut tree = SyntaxTree::parse(DRAFT);
+    tree.number_scenes(SceneNumbering::Locked).unwrap();
+
+    assert_eq!(
+        tree.to_fountain(),
+        "INT. HOUSE - DAY #1#\n\nBob waits.\n\nINT. KITCHEN - DAY #1A#\n\nBob eats.\n\n.OMITTED #2#\n\n.OMITTED #3#\n\nEXT. GARDEN - NIGHT #4#\n\nRain falls.\n"
+    );
+
+    let mut tree = SyntaxTree::parse(DRAFT);
+    tree.number_scenes(SceneNumbering::Fresh).unwrap();
+
+    assert_eq!(
+        tree.to_fountain(),
+        "INT. HOUSE - DAY #1#\n\nBob waits.\n\nINT. KITCHEN - DAY #2#\n\nBob eats.\n\nEXT. GARDEN - NIGHT #3#\n\nRain falls.\n"
+    );
+}
+
+#[test]
+fn it_writes_scene_numbers_to_fdx() {
+    let mut screenplay = parse(DRAFT);
+    screenplay.number_scenes(SceneNumbering::Locked);
+
+    let fdx = screenplay.to_final_draft();
+
+    assert!(fdx.contains("Number=\"1A\""));
+    assert!(fdx.contains("Number=\"3\""));
+}
@@ -0,0 +1,87 @@
+use jumpcut::parse;
+use jumpcut::sides::SidesSelection;
+use pretty_assertions::assert_eq;
+
+// Five scenes of about 33 lines each, so most pages hold parts of two.
+fn draft() -> String {
+    std::fs::read_to_string("tests/fixtures/sides.fountain").unwrap()
+}
+
+fn selection(characters: &[&str], scene_numbers: &[&str]) -> SidesSelection {
+    SidesSelection {
+        characters: characters.iter().map(|name| name.to_string()).collect(),
+        scene_numbers: scene_numbers
+            .iter()
+            .map(|number| number.to_string())
+            .collect(),
+    }
+}
+
+#[test]
+fn it_chooses_the_scenes_a_character_speaks_in() {
+    let sides = parse(&draft()).sides(&selection(&["alice"], &[]));
+
+    assert_eq!(
+        sides
+            .scenes
+            .iter()
+            .map(|scene| (scene.index, scene.scene_number.as_deref().unwrap()))
+            .collect::<Vec<_>>(),
+        vec![(1, "1"), (5, "4")]
+    );
+    assert_eq!(sides.scenes[1].heading, "INT. ROOM 4 - DAY");
+    assert_eq!(
+        (
+            sides.scenes[1].first_page.as_str(),
+            sides.scenes[1].last_page.as_str()
+        ),
+        ("3", "4")
+    );
+}
+
+#[test]
+fn it_keeps_the_page_numbers_of_the_full_screenplay() {
+    let sides = parse(&draft()).sides(&selection(&["ALICE"], &[]));
+
+    assert_eq!(
+        sides
+            .pages
+            .iter()
+            .map(|page| (page.metadata.index, page.label.as_str(), page.partial))
+            .collect::<Vec<_>>(),
+        vec![(0, "1", true), (2, "3", true), (3, "4", false)]
+    );
+    assert_eq!(sides.pages[1].metadata.number, 3);
+}
+
+#[test]
+fn it_chooses_scenes_by_number() {
+    let screenplay = parse(&draft());
+    let sides = screenplay.sides(&selection(&[], &["3a", "2"]));
+
+    assert_eq!(
+        sides
+            .scenes
+            .iter()
+            .map(|scene| scene.scene_number.as_deref().unwrap())
+            .collect::<Vec<_>>(),
+        ["2", "3A"]
+    );
+    assert!(screenplay.sides(&selection(&["CAROL"], &["99"])).is_empty());
+}
+
+#[test]
+fn it_prints_only_the_pages_of_the_sides() {
+    let screenplay = parse(&draft());
+    let page_count = |pdf: Vec<u8>| {
+        String::from_utf8_lossy(&pdf)
+            .matches("/Type /Page\n")
+            .count()
+    };
+
+    assert_eq!(page_count(screenplay.to_pdf()), 4);
+    assert_eq!(
+        page_count(screenplay.to_sides_pdf(&selection(&["ALICE"], &[]), Default::default())),
+        3
+    );
+}
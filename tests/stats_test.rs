@@ -0,0 +1,161 @@
+use jumpcut::parse;
+use jumpcut::stats::{ActStatistics, Appearance, SettingCounts, TimeOfDayCounts};
+use pretty_assertions::assert_eq;
+
+const SCRIPT: &str = "\
+>**ACT ONE**<
+
+INT. HOUSE - DAY #1#
+
+Bob waits by the door.
+
+BOB
+Where is everyone?
+
+ALICE (O.S.)
+Out here!
+
+EXT. GARDEN - CONTINUOUS #2#
+
+Alice waves.
+
+BOB
+There you are.
+
+ALICE ^
+Here I am.
+
+>**END OF ACT ONE**<
+
+>**ACT TWO**<
+
+EXT. GARDEN - NIGHT #3#
+
+The lights go out.
+
+BOB (V.O.)
+Goodnight, garden.
+";
+
+#[test]
+fn it_counts_scenes_and_settings() {
+    let stats = parse(SCRIPT).statistics();
+
+    // A new act starts a new page.
+    assert_eq!(stats.page_count, 2);
+    assert_eq!(stats.scene_count, 3);
+    assert_eq!(
+        stats
+            .scenes
+            .iter()
+            .map(|scene| (scene.scene_number.as_deref(), scene.page.as_str()))
+            .collect::<Vec<_>>(),
+        vec![(Some("1"), "1"), (Some("2"), "1"), (Some("3"), "2")]
+    );
+    assert!(stats.scenes.iter().all(|scene| scene.eighths >= 1));
+    assert_eq!(stats.scenes[0].characters, ["BOB", "ALICE"]);
+    assert_eq!(
+        stats.settings,
+        SettingCounts {
+            interior: 1,
+            exterior: 2,
+            interior_exterior: 0,
+            other: 0,
+        }
+    );
+    assert_eq!(
+        stats.times_of_day,
+        TimeOfDayCounts {
+            day: 2,
+            night: 1,
+            other: 0,
+        }
+    );
+}
+
+#[test]
+fn it_counts_speeches_per_character() {
+    let stats = parse(SCRIPT).statistics();
+
+    let summary: Vec<(&str, usize, usize)> = stats
+        .characters
+        .iter()
+        .map(|character| (character.name.as_str(), character.speeches, character.words))
+        .collect();
+    assert_eq!(summary, vec![("BOB", 3, 8), ("ALICE", 2, 5)]);
+
+    let bob = &stats.characters[0];
+    assert_eq!(
+        bob.first_appearance,
+        Appearance {
+            scene: Some(1),
+            page: Some("1".to_string()),
+        }
+    );
+    assert_eq!(bob.last_appearance.scene, Some(3));
+    assert_eq!(stats.dialogue_lines, 5);
+    assert_eq!(stats.action_lines, 3);
+    assert_eq!(stats.action_to_dialogue, Some(0.6));
+}
+
+#[test]
+fn it_counts_acts() {
+    let stats = parse(SCRIPT).statistics();
+    let eighths: Vec<u32> = stats.scenes.iter().map(|scene| scene.eighths).collect();
+
+    assert_eq!(
+        stats.acts,
+        vec![
+            ActStatistics {
+                title: "ACT ONE".to_string(),
+                scene_count: 2,
+                eighths: eighths[0] + eighths[1],
+                speeches: 4,
+                words: 11,
+            },
+            ActStatistics {
+                title: "ACT TWO".to_string(),
+                scene_count: 1,
+                eighths: eighths[2],
+                speeches: 1,
+                words: 2,
+            },
+        ]
+    );
+}
+
+#[test]
+fn it_measures_scenes_from_the_pagination() {
+    let scene = "INT. ROOM - DAY\n\nA line of action that fills exactly one line.\n\n";
+    let long_scene = format!(
+        "INT. HALL - NIGHT\n\n{}",
+        "Another line of action fills a line.\n\n".repeat(80)
+    );
+    let stats = parse(&format!("{scene}{long_scene}{scene}")).statistics();
+
+    assert_eq!(stats.page_count, 4);
+    assert_eq!(
+        stats
+            .scenes
+            .iter()
+            .map(|scene| scene.eighths)
+            .collect::<Vec<_>>(),
+        vec![1, 24, 1]
+    );
+    assert_eq!(stats.scenes[2].page, "4");
+}
+
+#[test]
+fn it_reports_statistics_as_tables_and_json() {
+    let stats = parse(SCRIPT).statistics();
+    let report = stats.to_report();
+
+    assert!(report.starts_with("Pages: 2\nScenes: 3\n"));
+    assert!(report.contains("\nCharacters\n  Name   Speeches  Words  Lines  First"));
+    assert!(report.contains("\n  BOB    3         8      3      p. 1, scene 1  p. 2, scene 3\n"));
+    assert!(report.contains("\nActs\n"));
+    assert_eq!(
+        serde_json::to_value(&stats).unwrap()["characters"][1]["name"],
+        "ALICE"
+    );
+}
@@ -0,0 +1,123 @@
+use jumpcut::parse;
+use jumpcut::stripboard::StripColor;
+use jumpcut::{DayNight, SceneSetting};
+use pretty_assertions::assert_eq;
+
+const SCRIPT: &str = "\
+Title: Heist
+
+INT. BANK - DAY #1#
+
+BOB
+Nobody move!
+
+TELLER
+Please don't shoot. [[cast: Guard]]
+
+EXT. STREET - CONTINUOUS #2#
+
+ALICE
+Get in!
+
+BOB
+Drive!
+
+INT. SAFEHOUSE - NIGHT #3#
+
+Alice counts the money.
+
+ALICE
+It's all here.
+
+EXT. ROOFTOP - NIGHT #4#
+
+Empty.
+
+.THE VAULT
+
+Darkness.
+";
+
+#[test]
+fn it_builds_a_strip_for_each_scene() {
+    let stripboard = parse(SCRIPT).stripboard();
+
+    assert_eq!(stripboard.title.as_deref(), Some("Heist"));
+    assert_eq!(stripboard.strips.len(), 5);
+    let bank = &stripboard.strips[0];
+    assert_eq!(bank.scene_number.as_deref(), Some("1"));
+    assert_eq!(bank.setting, Some(SceneSetting::Interior));
+    assert_eq!(bank.day_night, Some(DayNight::Day));
+    assert_eq!(bank.location, "BANK");
+    assert_eq!(bank.first_page, "1");
+    assert_eq!(
+        stripboard
+            .strips
+            .iter()
+            .map(|strip| strip.color)
+            .collect::<Vec<_>>(),
+        [
+            StripColor::White,
+            StripColor::Yellow,
+            StripColor::Blue,
+            StripColor::Green,
+            StripColor::Gray,
+        ]
+    );
+}
+
+#[test]
+fn it_numbers_the_cast_in_order_of_first_appearance() {
+    let stripboard = parse(SCRIPT).stripboard();
+
+    assert_eq!(
+        stripboard
+            .cast
+            .iter()
+            .map(|member| (member.id, member.name.as_str()))
+            .collect::<Vec<_>>(),
+        [(1, "BOB"), (2, "TELLER"), (3, "GUARD"), (4, "ALICE")]
+    );
+    assert_eq!(stripboard.strips[0].cast_ids, [1, 2, 3]);
+    assert_eq!(stripboard.strips[1].cast_ids, [1, 4]);
+    assert_eq!(stripboard.strips[2].cast_ids, [4]);
+    assert!(stripboard.strips[3].cast_ids.is_empty());
+    assert_eq!(parse(SCRIPT).stripboard(), stripboard);
+}
+
+#[test]
+fn it_writes_stripboards_as_csv_and_json() {
+    let stripboard = parse(SCRIPT).stripboard();
+    let csv = stripboard.to_csv();
+    let rows = csv.lines().collect::<Vec<_>>();
+
+    assert_eq!(rows.len(), 6);
+    assert_eq!(
+        rows[0],
+        "Scene,Int/Ext,Day/Night,Location,Time of Day,Pages,Eighths,Script Page,Cast IDs,Cast,Color,Heading"
+    );
+    assert!(rows[1].starts_with("1,INT,Day,BANK,DAY,"));
+    assert!(rows[1].ends_with(",1,1; 2; 3,BOB; TELLER; GUARD,White,INT. BANK - DAY"));
+    assert!(rows[2].starts_with("2,EXT,Day,STREET,CONTINUOUS,"));
+    assert!(rows[5].starts_with("5,,,THE VAULT,,"));
+
+    let json = serde_json::to_value(&stripboard).unwrap();
+    assert_eq!(json["cast"][3]["name"], "ALICE");
+    assert_eq!(json["strips"][1]["color"], "Yellow");
+    assert_eq!(json["strips"][1]["cast_ids"][1], 4);
+}
+
+#[cfg(feature = "html")]
+#[test]
+fn it_writes_a_standalone_html_stripboard() {
+    let html = parse(SCRIPT).stripboard().to_html();
+
+    assert!(html.starts_with("<!doctype html>"));
+    assert!(html.contains("<title>Heist Stripboard</title>"));
+    assert_eq!(html.matches("<tr class=\"strip ").count(), 5);
+    assert!(html.contains(
+        "<tr class=\"strip blue\" style=\"background-color: #90caf9\" title=\"INT. SAFEHOUSE - NIGHT\"><td>3</td><td>INT</td><td>SAFEHOUSE</td><td>Night</td>"
+    ));
+    assert!(html.contains("<li value=\"4\">ALICE</li>"));
+    assert!(html.ends_with("</html>\n"));
+}
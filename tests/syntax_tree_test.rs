@@ -0,0 +1,174 @@
+use jumpcut::syntax::{LineChange, SyntaxKind, SyntaxTree};
+use jumpcut::{RenameOptions, parse};
+use pretty_assertions::assert_eq;
+use std::fs;
+use std::path::Path;
+
+fn fountain_fixtures(dir: &Path, found: &mut Vec<String>) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            fountain_fixtures(&path, found);
+        } else if path.extension().and_then(|ext| ext.to_str()) == Some("fountain") {
+            found.push(fs::read_to_string(&path).unwrap());
+        }
+    }
+}
+
+#[test]
+fn it_round_trips_every_fountain_fixture_byte_for_byte() {
+    let mut fixtures = Vec::new();
+    fountain_fixtures(Path::new("tests/fixtures"), &mut fixtures);
+    assert!(!fixtures.is_empty());
+
+    for text in fixtures {
+        let tree = SyntaxTree::parse(&text);
+        assert_eq!(tree.to_fountain(), text);
+        let covered = tree
+            .nodes()
+            .iter()
+            .map(|node| node.text(tree.source()))
+            .collect::<String>();
+        assert_eq!(covered, text);
+    }
+}
+
+#[test]
+fn it_keeps_trivia_as_nodes() {
+    let text = "Title: Example\n\nINT. HOUSE - DAY /* cut */\n\n/* gone */\n\n===\n\n!BANG\n";
+    let tree = SyntaxTree::parse(text);
+
+    let kinds = tree
+        .nodes()
+        .iter()
+        .map(|node| node.kind)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        vec![
+            SyntaxKind::Metadata,
+            SyntaxKind::Trivia,
+            SyntaxKind::SceneHeading,
+            SyntaxKind::Trivia,
+            SyntaxKind::Boneyard,
+            SyntaxKind::Trivia,
+            SyntaxKind::Action,
+            SyntaxKind::Trivia,
+        ]
+    );
+    assert_eq!(tree.nodes()[2].text(text), "INT. HOUSE - DAY /* cut */");
+    assert_eq!(tree.nodes()[4].text(text), "/* gone */");
+    assert_eq!(tree.nodes()[5].text(text), "\n\n===\n\n");
+    assert_eq!(tree.nodes()[6].text(text), "!BANG");
+}
+
+#[test]
+fn it_renames_character_cues_without_touching_anything_else() {
+    let text = "INT. HOUSE - DAY\n\n@McCLANE (V.O.)\nYippee.\n\nHANS\n  Mr. Mcclane.\n\nMCCLANE ^\n(beat)\nHi.  [[keep me]]\n";
+    let mut tree = SyntaxTree::parse(text);
+
+    assert_eq!(tree.rename_character("mcclane", "JOHN"), Ok(2));
+    assert_eq!(
+        tree.to_fountain(),
+        "INT. HOUSE - DAY\n\n@JOHN (V.O.)\nYippee.\n\nHANS\n  Mr. Mcclane.\n\nJOHN ^\n(beat)\nHi.  [[keep me]]\n"
+    );
+}
+
+#[test]
+fn it_writes_a_mixed_case_name_in_capitals_unless_the_cue_is_forced() {
+    let text = "INT. HOUSE - DAY\n\n@McCLANE (V.O.)\nYippee.\n\nMCCLANE ^\n(beat)\nHi.\n";
+    let mut tree = SyntaxTree::parse(text);
+
+    assert_eq!(tree.rename_character("mcclane", "John"), Ok(2));
+    let renamed = tree.to_fountain();
+    assert_eq!(
+        renamed,
+        "INT. HOUSE - DAY\n\n@John (V.O.)\nYippee.\n\nJOHN ^\n(beat)\nHi.\n"
+    );
+    let cues = parse(&renamed)
+        .elements
+        .iter()
+        .flat_map(|element| element.character_cues())
+        .map(|cue| cue.name)
+        .collect::<Vec<_>>();
+    assert_eq!(cues, ["John", "JOHN"]);
+}
+
+#[test]
+fn it_renames_characters_in_text_and_lists_the_changed_lines() {
+    let text = "INT. HOUSE - DAY\n\nMCCLANE, barefoot, and _McClane's_ wife. /* McClane */\n\nHANS\n(to McClane)\nMr. McClane. [[McClane?]]\n\nMCCLANE\nYippee.\n";
+    let mut tree = SyntaxTree::parse(text);
+
+    assert_eq!(
+        tree.rename_character_with_options("McClane", "JOHN", RenameOptions { in_text: true }),
+        Ok(5)
+    );
+    assert_eq!(
+        tree.to_fountain(),
+        "INT. HOUSE - DAY\n\nJOHN, barefoot, and _John's_ wife. /* McClane */\n\nHANS\n(to John)\nMr. John. [[McClane?]]\n\nJOHN\nYippee.\n"
+    );
+    assert_eq!(
+        tree.changed_lines(),
+        [
+            LineChange {
+                line: 3,
+                before: "MCCLANE, barefoot, and _McClane's_ wife. /* McClane */".to_string(),
+                after: "JOHN, barefoot, and _John's_ wife. /* McClane */".to_string(),
+            },
+            LineChange {
+                line: 6,
+                before: "(to McClane)".to_string(),
+                after: "(to John)".to_string(),
+            },
+            LineChange {
+                line: 7,
+                before: "Mr. McClane. [[McClane?]]".to_string(),
+                after: "Mr. John. [[McClane?]]".to_string(),
+            },
+            LineChange {
+                line: 9,
+                before: "MCCLANE".to_string(),
+                after: "JOHN".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn it_sets_and_clears_scene_numbers() {
+    let text = "INT. HOUSE - DAY\n\nBob sits.\n\n.FLASHBACK #4A#\n\nEXT. YARD - NIGHT [[rain]]\n";
+    let mut tree = SyntaxTree::parse(text);
+
+    tree.set_scene_number(0, Some("1")).unwrap();
+    tree.set_scene_number(1, None).unwrap();
+    tree.set_scene_number(2, Some("5")).unwrap();
+    let edited = tree.to_fountain();
+    assert_eq!(
+        edited,
+        "INT. HOUSE - DAY #1#\n\nBob sits.\n\n.FLASHBACK\n\nEXT. YARD - NIGHT [[rain]] #5#\n"
+    );
+
+    let numbers = parse(&edited)
+        .elements
+        .iter()
+        .filter_map(|element| match element {
+            jumpcut::Element::SceneHeading(_, attributes) => Some(attributes.scene_number.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        numbers,
+        vec![Some("1".to_string()), None, Some("5".to_string())]
+    );
+}
+
+#[test]
+fn it_rejects_overlapping_edits() {
+    let text = "INT. HOUSE - DAY #1#\n";
+    let mut tree = SyntaxTree::parse(text);
+
+    tree.set_scene_number(0, Some("2")).unwrap();
+    assert!(tree.set_scene_number(0, None).is_err());
+    assert!(tree.set_scene_number(3, Some("9")).is_err());
+    assert_eq!(tree.to_fountain(), "INT. HOUSE - DAY #2#\n");
+}
@@ -82,6 +82,12 @@ def build_steps(mode: str) -> list[tuple[str, list[str]]]:
     steps: list[tuple[str, list[str]]] = []
     if mode in {"full", "test"}:
         steps.append(("cargo-test", ["cargo", "test"]))
+        steps.append(
+            (
+                "cargo-build-no-default-features",
+                ["cargo", "build", "--lib", "--no-default-features"],
+            )
+        )
     if mode in {"full", "diagnostics"}:
         steps.append(
             (